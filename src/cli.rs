@@ -7,6 +7,7 @@ use crate::commands::config::ConfigCommands;
 use crate::commands::doctor::DoctorArgs;
 use crate::commands::gh::GhCommands;
 use crate::commands::name_branch::NameBranchArgs;
+use crate::commands::notify::NotifyCommands;
 use crate::commands::wm::WmCommands;
 
 #[derive(Parser)]
@@ -44,6 +45,10 @@ pub enum Commands {
     /// Generate a branch name from a description using AI
     NameBranch(NameBranchArgs),
 
+    /// Notification controls (focus mode, held notifications)
+    #[command(subcommand)]
+    Notify(NotifyCommands),
+
     /// Git worktree manager
     #[command(subcommand)]
     Wm(WmCommands),
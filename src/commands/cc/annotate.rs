@@ -0,0 +1,129 @@
+//! `a cc tag` / `a cc note` -- user metadata on a session besides its
+//! `label`: free-form tags (`waiting-on-ci`, `needs-review`, ...) and a
+//! multi-line note. Both are also editable from `cc watch` (`t` / `n`).
+
+use std::collections::BTreeSet;
+use std::io::Read;
+
+use anyhow::Result;
+use clap::Args;
+
+use super::error::CcError;
+use super::store;
+
+#[derive(Args, Clone, PartialEq, Eq)]
+pub struct TagArgs {
+    /// Session ID to tag
+    pub session_id: String,
+
+    /// Tags to add (space- or comma-separated; a leading `#` is dropped).
+    /// Prints the session's tags when no change is requested.
+    pub tags: Vec<String>,
+
+    /// Tags to remove
+    #[arg(long, short = 'r', value_name = "TAG")]
+    pub remove: Vec<String>,
+
+    /// Remove every tag before adding the given ones
+    #[arg(long)]
+    pub clear: bool,
+}
+
+#[derive(Args, Clone, PartialEq, Eq)]
+pub struct NoteArgs {
+    /// Session ID whose note to set
+    pub session_id: String,
+
+    /// Note text; `-` reads it from stdin. Prints the current note when
+    /// omitted.
+    pub text: Option<String>,
+
+    /// Remove the note
+    #[arg(long, conflicts_with = "text")]
+    pub clear: bool,
+}
+
+pub fn run_tag(args: &TagArgs) -> Result<()> {
+    let add = parse_tags(&args.tags.join(" "));
+    let remove = parse_tags(&args.remove.join(" "));
+    let tags = if add.is_empty() && remove.is_empty() && !args.clear {
+        store::load_session(&args.session_id)?.map(|session| session.tags)
+    } else {
+        store::update_session_tags(&args.session_id, |tags| {
+            if args.clear {
+                tags.clear();
+            }
+            tags.retain(|tag| !remove.contains(tag));
+            tags.extend(add);
+        })?
+    };
+    let tags = tags.ok_or_else(|| CcError::SessionNotFound(args.session_id.clone()))?;
+    for tag in tags {
+        println!("{tag}");
+    }
+    Ok(())
+}
+
+pub fn run_note(args: &NoteArgs) -> Result<()> {
+    let note = match args.text.as_deref() {
+        None if !args.clear => {
+            let session = store::load_session(&args.session_id)?
+                .ok_or_else(|| CcError::SessionNotFound(args.session_id.clone()))?;
+            if let Some(note) = session.note {
+                println!("{note}");
+            }
+            return Ok(());
+        }
+        None => None,
+        Some("-") => {
+            let mut text = String::new();
+            std::io::stdin().read_to_string(&mut text)?;
+            normalize_note(&text)
+        }
+        Some(text) => normalize_note(text),
+    };
+    if !store::update_session_note(&args.session_id, note)? {
+        return Err(CcError::SessionNotFound(args.session_id.clone()).into());
+    }
+    Ok(())
+}
+
+/// Splits user input into tags on whitespace and commas, dropping a
+/// leading `#` (tags are displayed as `#tag`) and empty pieces.
+pub(crate) fn parse_tags(input: &str) -> BTreeSet<String> {
+    input
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .map(|tag| tag.trim_start_matches('#'))
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Trims surrounding blank space off a note; a blank note is no note.
+pub(crate) fn normalize_note(text: &str) -> Option<String> {
+    let trimmed = text.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::spaces("waiting-on-ci needs-review", &["needs-review", "waiting-on-ci"])]
+    #[case::commas_and_hashes("#a,b, #c", &["a", "b", "c"])]
+    #[case::duplicates("a a", &["a"])]
+    #[case::blank("  , # ", &[])]
+    fn test_parse_tags(#[case] input: &str, #[case] expected: &[&str]) {
+        let expected: BTreeSet<String> = expected.iter().map(|s| s.to_string()).collect();
+        assert_eq!(parse_tags(input), expected);
+    }
+
+    #[rstest]
+    #[case::multi_line("\n line 1\nline 2 \n\n", Some("line 1\nline 2"))]
+    #[case::blank(" \n\t", None)]
+    fn test_normalize_note(#[case] input: &str, #[case] expected: Option<&str>) {
+        assert_eq!(normalize_note(input).as_deref(), expected);
+    }
+}
@@ -468,6 +468,13 @@ mod tests {
             pending_permission_agent_ids: std::collections::BTreeSet::new(),
             read_at: None,
             sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
         }
     }
 
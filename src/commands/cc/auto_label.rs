@@ -0,0 +1,362 @@
+//! Rule-based session labels (`cc.auto_label`): a session without a label
+//! is labeled from its branch, an issue number in its first prompt, or its
+//! PR's title, per the first matching rule.
+//!
+//! Labels are applied twice: by the hook when the session is first
+//! recorded, and by `cc watch` when a PR lookup finds the branch's PR. The
+//! second pass only replaces a label the first pass could have produced,
+//! so a label set by hand is never overwritten.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use regex::Regex;
+
+use super::claude_sessions;
+use super::pr_cache;
+use super::store;
+use super::types::Session;
+use super::window_status;
+use crate::infra::git::{GitRepo, github_owner_and_repo};
+use crate::infra::github::BranchPrQuery;
+use crate::shared::config::{AutoLabelRule, AutoLabelSource, Config};
+
+/// What the rules are matched against; absent inputs skip their rules.
+#[derive(Debug, Default)]
+pub(crate) struct LabelInputs<'a> {
+    pub branch: Option<&'a str>,
+    pub prompt: Option<&'a str>,
+    pub pr_title: Option<&'a str>,
+}
+
+/// The label of the first rule whose source is present and whose pattern
+/// matches. Rules with an invalid pattern, or expanding to a blank label,
+/// are skipped.
+pub(crate) fn derive_label(rules: &[AutoLabelRule], inputs: &LabelInputs) -> Option<String> {
+    rules.iter().find_map(|rule| {
+        let text = match rule.source {
+            AutoLabelSource::Branch => inputs.branch,
+            AutoLabelSource::Prompt => inputs.prompt,
+            AutoLabelSource::PrTitle => inputs.pr_title,
+        }?;
+        let Ok(pattern) = Regex::new(&rule.pattern) else {
+            tracing::warn!(
+                event = "cc.auto_label.invalid_pattern",
+                pattern = %rule.pattern,
+            );
+            return None;
+        };
+        let captures = pattern.captures(text)?;
+        let mut label = String::new();
+        captures.expand(&rule.label, &mut label);
+        let label = label.split_whitespace().collect::<Vec<_>>().join(" ");
+        (!label.is_empty()).then_some(label)
+    })
+}
+
+/// The GitHub `(owner, repo)` and branch checked out at `cwd`, each `None`
+/// when unknown (not a repository, no GitHub remote, detached HEAD).
+fn checkout(cwd: &Path) -> (Option<(String, String)>, Option<String>) {
+    let Ok(repo) = GitRepo::open_at(cwd) else {
+        return (None, None);
+    };
+    let branch = repo.current_branch().ok().filter(|b| b != "HEAD");
+    (github_owner_and_repo(&repo).ok(), branch)
+}
+
+/// Labels a just-recorded session from its branch, its first `prompt`, and
+/// its branch's PR title when the PR cache already knows it. No-op when
+/// auto-labeling is disabled for the repo or the session got a label in
+/// the meantime. Returns the applied label.
+pub(crate) fn label_new_session_in(
+    sessions_dir: &Path,
+    session: &Session,
+    prompt: Option<&str>,
+    config: &Config,
+) -> Result<Option<String>> {
+    if session.label.is_some() {
+        return Ok(None);
+    }
+    let (repo_id, branch) = checkout(&session.cwd);
+    let Some(rules) = config.resolve_auto_label_rules(
+        repo_id
+            .as_ref()
+            .map(|(owner, repo)| format!("{owner}/{repo}"))
+            .as_deref(),
+    ) else {
+        return Ok(None);
+    };
+    let pr_title = repo_id
+        .zip(branch.clone())
+        .and_then(|((owner, repo), branch)| {
+            pr_cache::last_known_pr(&BranchPrQuery {
+                owner,
+                repo,
+                branch,
+            })
+            .map(|pr| pr.title)
+            .filter(|title| !title.is_empty())
+        });
+    let Some(label) = derive_label(
+        rules,
+        &LabelInputs {
+            branch: branch.as_deref(),
+            prompt,
+            pr_title: pr_title.as_deref(),
+        },
+    ) else {
+        return Ok(None);
+    };
+    let applied = store::update_session_label_if_unchanged_in(
+        sessions_dir,
+        &session.session_id,
+        None,
+        Some(label.clone()),
+    )?;
+    Ok(applied.then_some(label))
+}
+
+/// Relabels `session` now that its branch's PR is known, in the repo
+/// `repo_id` (`owner/repo`). Applies only while the session's label is
+/// unset or still the one the rules derive without the PR. Returns the
+/// applied label.
+fn relabel_for_pr_in(
+    sessions_dir: &Path,
+    session: &Session,
+    repo_id: &str,
+    pr_title: &str,
+    config: &Config,
+) -> Result<Option<String>> {
+    let Some(rules) = config.resolve_auto_label_rules(Some(repo_id)) else {
+        return Ok(None);
+    };
+    let (_, branch) = checkout(&session.cwd);
+    let prompt = claude_sessions::get_first_user_message(&session.cwd, &session.session_id);
+    let mut inputs = LabelInputs {
+        branch: branch.as_deref(),
+        prompt: prompt.as_deref(),
+        pr_title: Some(pr_title),
+    };
+    let Some(label) = derive_label(rules, &inputs) else {
+        return Ok(None);
+    };
+    if session.label.as_deref() == Some(label.as_str()) {
+        return Ok(None);
+    }
+    inputs.pr_title = None;
+    if session.label.is_some() && session.label != derive_label(rules, &inputs) {
+        return Ok(None);
+    }
+    let applied = store::update_session_label_if_unchanged_in(
+        sessions_dir,
+        &session.session_id,
+        session.label.as_deref(),
+        Some(label.clone()),
+    )?;
+    Ok(applied.then_some(label))
+}
+
+/// Relabels every live session whose cwd has a PR in `prs` (cwd ->
+/// `(owner/repo, PR title)`), as looked up by `cc watch`. The watcher
+/// picks the new labels up from the session files. Failures are logged.
+pub(crate) fn relabel_sessions_for_prs(prs: &HashMap<PathBuf, (String, String)>) {
+    if prs.is_empty() {
+        return;
+    }
+    let Ok(config) = crate::shared::config::load_config() else {
+        return;
+    };
+    let Ok(sessions_dir) = store::sessions_dir() else {
+        return;
+    };
+    let Ok(sessions) = store::list_sessions() else {
+        return;
+    };
+    for session in sessions {
+        let Some((repo_id, title)) = prs.get(&session.cwd) else {
+            continue;
+        };
+        if title.is_empty() {
+            continue;
+        }
+        match relabel_for_pr_in(&sessions_dir, &session, repo_id, title, &config) {
+            Ok(Some(label)) => {
+                tracing::info!(
+                    event = "cc.auto_label.applied",
+                    session = %session.session_id,
+                    label = %label,
+                    trigger = "pr",
+                );
+                let session = Session {
+                    label: Some(label),
+                    ..session
+                };
+                window_status::sync_window_title_for_session(&session);
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!(
+                event = "cc.auto_label.failed",
+                session = %session.session_id,
+                error = %e,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::config::AutoLabelConfig;
+    use rstest::rstest;
+
+    fn default_rules() -> Vec<AutoLabelRule> {
+        AutoLabelConfig::default().rules
+    }
+
+    #[rstest]
+    #[case::pr_title_wins(
+        Some("feat/login"),
+        Some("fix #3"),
+        Some("Fix login redirect"),
+        Some("Fix login redirect")
+    )]
+    #[case::issue_branch(
+        Some("fix/123-login-redirect"),
+        None,
+        None,
+        Some("#123 login-redirect")
+    )]
+    #[case::gh_prefixed_issue_branch(Some("gh-42_flaky-test"), None, None, Some("#42 flaky-test"))]
+    #[case::issue_in_prompt(Some("feat/login"), Some("please fix #7 today"), None, Some("#7"))]
+    #[case::prefixed_branch(Some("feat/login"), Some("no issue here"), None, Some("login"))]
+    #[case::plain_branch(Some("main"), None, None, None)]
+    #[case::nothing(None, None, None, None)]
+    fn test_derive_label_default_rules(
+        #[case] branch: Option<&str>,
+        #[case] prompt: Option<&str>,
+        #[case] pr_title: Option<&str>,
+        #[case] expected: Option<&str>,
+    ) {
+        let inputs = LabelInputs {
+            branch,
+            prompt,
+            pr_title,
+        };
+        assert_eq!(derive_label(&default_rules(), &inputs).as_deref(), expected);
+    }
+
+    fn enabled_config() -> Config {
+        let mut config = Config::default();
+        config.cc.auto_label.enabled = true;
+        config
+    }
+
+    /// A saved session whose cwd is outside any repository, so only the
+    /// prompt and PR title rules can apply.
+    fn saved_session(sessions_dir: &Path, cwd: &Path, label: Option<&str>) -> Session {
+        let session = Session {
+            session_id: "s1".to_string(),
+            cwd: cwd.to_path_buf(),
+            transcript_path: None,
+            tty: None,
+            tmux_info: None,
+            status: crate::commands::cc::types::SessionStatus::Running,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            last_message: None,
+            current_tool: None,
+            label: label.map(str::to_string),
+            ancestor_session_ids: Vec::new(),
+            pending_bg_task_ids: Default::default(),
+            pending_agent_task_ids: Default::default(),
+            pending_permission_agent_ids: Default::default(),
+            read_at: None,
+            sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
+        };
+        store::save_session_to(sessions_dir, &session).expect("save should succeed");
+        session
+    }
+
+    fn saved_label(sessions_dir: &Path) -> Option<String> {
+        store::load_session_from(sessions_dir, "s1")
+            .expect("load should succeed")
+            .expect("session exists")
+            .label
+    }
+
+    #[rstest]
+    #[case::enabled(true, Some("#9"))]
+    #[case::disabled(false, None)]
+    fn test_label_new_session_in(#[case] enabled: bool, #[case] expected: Option<&str>) {
+        let temp_dir = tempfile::TempDir::new().expect("temp dir");
+        let sessions_dir = temp_dir.path().join("sessions");
+        let session = saved_session(&sessions_dir, temp_dir.path(), None);
+        let mut config = enabled_config();
+        config.cc.auto_label.enabled = enabled;
+
+        let applied = label_new_session_in(&sessions_dir, &session, Some("look at #9"), &config)
+            .expect("labeling should succeed");
+
+        assert_eq!(
+            (applied.as_deref(), saved_label(&sessions_dir).as_deref()),
+            (expected, expected)
+        );
+    }
+
+    #[rstest]
+    #[case::unlabeled(None, Some("Fix login redirect"))]
+    #[case::user_label_kept(Some("my label"), Some("my label"))]
+    fn test_relabel_for_pr_in(#[case] label: Option<&str>, #[case] expected: Option<&str>) {
+        let temp_dir = tempfile::TempDir::new().expect("temp dir");
+        let sessions_dir = temp_dir.path().join("sessions");
+        let session = saved_session(&sessions_dir, temp_dir.path(), label);
+
+        relabel_for_pr_in(
+            &sessions_dir,
+            &session,
+            "owner/repo",
+            "Fix login redirect",
+            &enabled_config(),
+        )
+        .expect("relabeling should succeed");
+
+        assert_eq!(saved_label(&sessions_dir).as_deref(), expected);
+    }
+
+    #[test]
+    fn test_derive_label_skips_invalid_and_blank_rules() {
+        let rules = vec![
+            AutoLabelRule {
+                source: AutoLabelSource::Branch,
+                pattern: "(".to_string(),
+                label: "broken".to_string(),
+            },
+            AutoLabelRule {
+                source: AutoLabelSource::Branch,
+                pattern: "^(?<ticket>\\d+)?".to_string(),
+                label: "${ticket}".to_string(),
+            },
+            AutoLabelRule {
+                source: AutoLabelSource::Branch,
+                pattern: "^(?<ticket>[A-Z]+-\\d+)-(?<rest>.+)$".to_string(),
+                label: "${ticket}: ${rest}".to_string(),
+            },
+        ];
+        let inputs = LabelInputs {
+            branch: Some("ENG-12-search"),
+            ..Default::default()
+        };
+        assert_eq!(
+            derive_label(&rules, &inputs).as_deref(),
+            Some("ENG-12: search")
+        );
+    }
+}
@@ -0,0 +1,314 @@
+//! `a cc changes` -- show which files each session modified, and which of
+//! those files other sessions modified too.
+//!
+//! Sessions sharing a repo usually run in separate worktrees, so the same
+//! repo file shows up under different absolute paths. Paths are compared by
+//! their `repo_file_key`, which folds `<repo>/<wm.worktrees_dir>/<name>/...`
+//! back onto `<repo>/...`.
+
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::Result;
+use clap::Args;
+use serde::Serialize;
+
+use super::error::CcError;
+use super::store;
+use super::types::Session;
+use crate::shared::config::load_config;
+
+#[derive(Args, Clone, PartialEq, Eq)]
+pub struct ChangesArgs {
+    /// Session ID whose changed files to list. When omitted, lists only the
+    /// files changed by more than one active session.
+    pub session_id: Option<String>,
+
+    /// Output as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// A session that also changed a file, as shown next to it.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+struct OtherEditor<'a> {
+    session_id: &'a str,
+    label: Option<&'a str>,
+    path: &'a Path,
+}
+
+impl<'a> OtherEditor<'a> {
+    fn new(session: &'a Session, path: &'a Path) -> Self {
+        Self {
+            session_id: &session.session_id,
+            label: session.label.as_deref(),
+            path,
+        }
+    }
+
+    fn display_name(&self) -> &str {
+        self.label.unwrap_or(self.session_id)
+    }
+}
+
+/// One file a session changed, with the other sessions that changed it too.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+struct ChangedFile<'a> {
+    path: &'a Path,
+    also_changed_by: Vec<OtherEditor<'a>>,
+}
+
+/// A repo file changed by several sessions.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+struct Overlap<'a> {
+    file: PathBuf,
+    sessions: Vec<OtherEditor<'a>>,
+}
+
+pub fn run(args: &ChangesArgs) -> Result<()> {
+    let worktrees_dir = load_config()?.wm.worktrees_dir;
+    let active = store::list_sessions()?;
+
+    match &args.session_id {
+        Some(session_id) => {
+            // Load directly rather than from `active` so an Ended session's
+            // changes stay inspectable.
+            let session = store::load_session(session_id)?
+                .ok_or_else(|| CcError::SessionNotFound(session_id.clone()))?;
+            let files = changed_files_with_overlaps(&session, &active, &worktrees_dir);
+            if args.json {
+                println!("{}", serde_json::to_string(&files)?);
+            } else {
+                print!("{}", render_changed_files(&files));
+            }
+        }
+        None => {
+            let overlaps = find_overlaps(&active, &worktrees_dir);
+            if args.json {
+                println!("{}", serde_json::to_string(&overlaps)?);
+            } else if overlaps.is_empty() {
+                println!("No file is changed by more than one active session.");
+            } else {
+                print!("{}", render_overlaps(&overlaps));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Maps a changed file to the path it would have in the repo's main
+/// checkout, so the same file edited from two worktrees compares equal:
+/// `/repo/.worktrees/feat/src/a.rs` -> `/repo/src/a.rs`. Paths outside any
+/// worktree directory are returned unchanged.
+pub(crate) fn repo_file_key(path: &Path, worktrees_dir: &str) -> PathBuf {
+    let worktrees_dir = Path::new(worktrees_dir);
+    let components: Vec<Component> = path.components().collect();
+    let marker: Vec<Component> = worktrees_dir.components().collect();
+    if marker.is_empty() {
+        return path.to_path_buf();
+    }
+
+    // Use the last match so a repo nested under another repo's worktree
+    // folds onto the innermost repo.
+    let found = (0..components.len().saturating_sub(marker.len()))
+        .rev()
+        .find(|&i| components[i..i + marker.len()] == marker[..]);
+    match found {
+        // Skip the worktrees dir and the worktree name right after it.
+        Some(i) if i + marker.len() < components.len() => components[..i]
+            .iter()
+            .chain(&components[i + marker.len() + 1..])
+            .collect(),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// `session`'s changed files, each paired with the other sessions in
+/// `others` that changed the same repo file.
+fn changed_files_with_overlaps<'a>(
+    session: &'a Session,
+    others: &'a [Session],
+    worktrees_dir: &str,
+) -> Vec<ChangedFile<'a>> {
+    session
+        .changed_files
+        .iter()
+        .map(|path| {
+            let key = repo_file_key(path, worktrees_dir);
+            let also_changed_by = others
+                .iter()
+                .filter(|other| other.session_id != session.session_id)
+                .filter_map(|other| {
+                    other
+                        .changed_files
+                        .iter()
+                        .find(|p| repo_file_key(p, worktrees_dir) == key)
+                        .map(|p| OtherEditor::new(other, p))
+                })
+                .collect();
+            ChangedFile {
+                path,
+                also_changed_by,
+            }
+        })
+        .collect()
+}
+
+/// The files `session` changed that some other session in `others` changed
+/// too, as shown on the `cc watch` detail line.
+pub(crate) fn shared_changed_files<'a>(
+    session: &'a Session,
+    others: &'a [Session],
+    worktrees_dir: &str,
+) -> Vec<&'a Path> {
+    changed_files_with_overlaps(session, others, worktrees_dir)
+        .into_iter()
+        .filter(|file| !file.also_changed_by.is_empty())
+        .map(|file| file.path)
+        .collect()
+}
+
+/// Repo files changed by more than one of `sessions`, sorted by file.
+fn find_overlaps<'a>(sessions: &'a [Session], worktrees_dir: &str) -> Vec<Overlap<'a>> {
+    let mut by_key: BTreeMap<PathBuf, Vec<OtherEditor<'a>>> = BTreeMap::new();
+    for session in sessions {
+        for path in &session.changed_files {
+            by_key
+                .entry(repo_file_key(path, worktrees_dir))
+                .or_default()
+                .push(OtherEditor::new(session, path));
+        }
+    }
+    by_key
+        .into_iter()
+        .filter(|(_, editors)| editors.len() > 1)
+        .map(|(file, sessions)| Overlap { file, sessions })
+        .collect()
+}
+
+fn render_changed_files(files: &[ChangedFile]) -> String {
+    let mut out = String::new();
+    for file in files {
+        out.push_str(&file.path.display().to_string());
+        if !file.also_changed_by.is_empty() {
+            let names: Vec<&str> = file
+                .also_changed_by
+                .iter()
+                .map(OtherEditor::display_name)
+                .collect();
+            out.push_str(&format!("  ⚠ also changed by: {}", names.join(", ")));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn render_overlaps(overlaps: &[Overlap]) -> String {
+    let mut out = String::new();
+    for overlap in overlaps {
+        out.push_str(&format!("{}\n", overlap.file.display()));
+        for editor in &overlap.sessions {
+            out.push_str(&format!(
+                "  {} ({})  {}\n",
+                editor.display_name(),
+                editor.session_id,
+                editor.path.display()
+            ));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::cc::types::SessionStatus;
+    use chrono::Utc;
+    use indoc::indoc;
+    use rstest::rstest;
+
+    fn session(id: &str, label: Option<&str>, files: &[&str]) -> Session {
+        Session {
+            session_id: id.to_string(),
+            cwd: PathBuf::from("/repo"),
+            transcript_path: None,
+            tty: None,
+            tmux_info: None,
+            status: SessionStatus::Running,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_message: None,
+            current_tool: None,
+            label: label.map(str::to_string),
+            ancestor_session_ids: Vec::new(),
+            pending_bg_task_ids: Default::default(),
+            pending_agent_task_ids: Default::default(),
+            pending_permission_agent_ids: Default::default(),
+            read_at: None,
+            sweep_signaled: false,
+            changed_files: files.iter().map(PathBuf::from).collect(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
+        }
+    }
+
+    #[rstest]
+    #[case::worktree_file("/repo/.worktrees/feat/src/a.rs", ".worktrees", "/repo/src/a.rs")]
+    #[case::main_checkout("/repo/src/a.rs", ".worktrees", "/repo/src/a.rs")]
+    #[case::custom_dir("/repo/wt/x/b.rs", "wt", "/repo/b.rs")]
+    #[case::multi_component_dir("/repo/.cache/wt/x/b.rs", ".cache/wt", "/repo/b.rs")]
+    #[case::worktree_root("/repo/.worktrees/feat", ".worktrees", "/repo")]
+    fn repo_file_key_cases(#[case] path: &str, #[case] dir: &str, #[case] expected: &str) {
+        assert_eq!(repo_file_key(Path::new(path), dir), PathBuf::from(expected));
+    }
+
+    #[test]
+    fn changed_files_flags_edits_from_other_worktrees() {
+        let me = session(
+            "me",
+            None,
+            &["/repo/.worktrees/a/src/x.rs", "/repo/.worktrees/a/src/y.rs"],
+        );
+        let others = vec![
+            session("other", Some("fix bug"), &["/repo/.worktrees/b/src/x.rs"]),
+            session("unrelated", None, &["/elsewhere/src/x.rs"]),
+        ];
+
+        let files = changed_files_with_overlaps(&me, &others, ".worktrees");
+
+        assert_eq!(
+            render_changed_files(&files),
+            indoc! {"
+                /repo/.worktrees/a/src/x.rs  ⚠ also changed by: fix bug
+                /repo/.worktrees/a/src/y.rs
+            "}
+        );
+    }
+
+    #[test]
+    fn find_overlaps_lists_only_shared_files() {
+        let sessions = vec![
+            session(
+                "s1",
+                Some("one"),
+                &["/repo/.worktrees/a/src/x.rs", "/repo/only.rs"],
+            ),
+            session("s2", None, &["/repo/src/x.rs"]),
+        ];
+
+        let overlaps = find_overlaps(&sessions, ".worktrees");
+
+        assert_eq!(
+            render_overlaps(&overlaps),
+            indoc! {"
+                /repo/src/x.rs
+                  one (s1)  /repo/.worktrees/a/src/x.rs
+                  s2 (s2)  /repo/src/x.rs
+            "}
+        );
+    }
+}
@@ -0,0 +1,640 @@
+//! Per-turn git checkpoints of a session's worktree: `a cc checkpoints`
+//! lists them and `a cc rollback` restores one.
+//!
+//! A checkpoint is a commit of the whole worktree (tracked and untracked,
+//! non-ignored files) stored at `refs/armyknife/checkpoints/<session>/<n>`.
+//! It is built by staging into a scratch copy of the index, so the real
+//! index, HEAD, and branches are never touched. Each checkpoint's parent is
+//! the commit HEAD pointed at when it was taken.
+
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, bail};
+use chrono::{DateTime, FixedOffset, Local};
+use clap::Args;
+use serde::Serialize;
+
+use super::error::CcError;
+use super::store;
+use super::types::SessionStatus;
+use crate::infra::git::GitRepo;
+use crate::infra::git::cmd::{run_git, run_git_optional, run_git_with_env};
+use crate::infra::process;
+
+const REF_NAMESPACE: &str = "refs/armyknife/checkpoints";
+
+/// Author and committer of checkpoint commits, so taking one does not depend
+/// on `user.name` / `user.email` being configured in the repo.
+const CHECKPOINT_IDENTITY: [(&str, &str); 4] = [
+    ("GIT_AUTHOR_NAME", "armyknife"),
+    ("GIT_AUTHOR_EMAIL", "armyknife@localhost"),
+    ("GIT_COMMITTER_NAME", "armyknife"),
+    ("GIT_COMMITTER_EMAIL", "armyknife@localhost"),
+];
+
+#[derive(Args, Clone, PartialEq, Eq)]
+pub struct CheckpointsArgs {
+    /// Session ID whose checkpoints to list
+    pub session_id: String,
+
+    /// Output as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Args, Clone, PartialEq, Eq)]
+pub struct RollbackArgs {
+    /// Session ID whose worktree to restore
+    pub session_id: String,
+
+    /// Checkpoint number (see `a cc checkpoints`)
+    pub number: u32,
+
+    /// Roll back even while the session is running or waiting for input
+    #[arg(long)]
+    pub force: bool,
+}
+
+#[derive(Args, Clone, PartialEq, Eq)]
+pub struct CheckpointDetachedArgs {
+    /// Session ID to take the checkpoint for
+    pub session_id: String,
+
+    /// Directory inside the worktree to snapshot
+    #[arg(long)]
+    pub cwd: PathBuf,
+
+    /// How many of the session's checkpoints to keep
+    #[arg(long)]
+    pub keep: usize,
+}
+
+/// One checkpoint ref.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Checkpoint {
+    number: u32,
+    commit: String,
+    /// HEAD when the checkpoint was taken; `None` on an unborn branch.
+    head: Option<String>,
+    created_at: DateTime<FixedOffset>,
+}
+
+/// Size of a diff as reported by `git diff --shortstat`.
+#[derive(Debug, Default, Clone, Copy, Serialize, PartialEq, Eq)]
+struct DiffStat {
+    files: u32,
+    insertions: u32,
+    deletions: u32,
+}
+
+/// A checkpoint as listed by `a cc checkpoints`.
+#[derive(Debug, Serialize)]
+struct CheckpointEntry {
+    number: u32,
+    commit: String,
+    head: Option<String>,
+    created_at: DateTime<FixedOffset>,
+    /// Changes since the previous checkpoint (for the oldest one: since the
+    /// commit it was taken on).
+    diffstat: DiffStat,
+}
+
+pub fn run_list(args: &CheckpointsArgs) -> Result<()> {
+    let session = store::load_session(&args.session_id)?
+        .ok_or_else(|| CcError::SessionNotFound(args.session_id.clone()))?;
+    let repo = GitRepo::open_at(&session.cwd).map_err(|_| CcError::NotInGitRepo)?;
+    let workdir = repo.workdir();
+
+    let checkpoints = list_checkpoints(workdir, &session.session_id)?;
+    let mut entries = Vec::with_capacity(checkpoints.len());
+    let mut previous: Option<&Checkpoint> = None;
+    for checkpoint in &checkpoints {
+        entries.push(CheckpointEntry {
+            number: checkpoint.number,
+            commit: checkpoint.commit.clone(),
+            head: checkpoint.head.clone(),
+            created_at: checkpoint.created_at,
+            diffstat: diffstat(
+                workdir,
+                previous.map(|p| p.commit.as_str()),
+                &checkpoint.commit,
+            )?,
+        });
+        previous = Some(checkpoint);
+    }
+
+    if args.json {
+        println!("{}", serde_json::to_string(&entries)?);
+    } else if entries.is_empty() {
+        println!("No checkpoints for session {}.", session.session_id);
+    } else {
+        print!("{}", render_entries(&entries));
+    }
+    Ok(())
+}
+
+pub fn run_rollback(args: &RollbackArgs) -> Result<()> {
+    let session = store::load_session(&args.session_id)?
+        .ok_or_else(|| CcError::SessionNotFound(args.session_id.clone()))?;
+    if !args.force
+        && matches!(
+            session.status,
+            SessionStatus::Running | SessionStatus::WaitingInput
+        )
+    {
+        bail!(
+            "Session {} is {}; rolling back would race its edits (stop it first, or pass --force)",
+            session.session_id,
+            session.status.display_name().to_lowercase()
+        );
+    }
+
+    let repo = GitRepo::open_at(&session.cwd).map_err(|_| CcError::NotInGitRepo)?;
+    let workdir = repo.workdir();
+    let target = list_checkpoints(workdir, &session.session_id)?
+        .into_iter()
+        .find(|c| c.number == args.number)
+        .ok_or_else(|| CcError::CheckpointNotFound {
+            session_id: session.session_id.clone(),
+            number: args.number,
+        })?;
+
+    // Save the current state first so the rollback itself can be undone.
+    // Nothing is pruned here: pruning could delete the target.
+    let current_tree = snapshot_tree(workdir)?;
+    let saved = save_checkpoint(workdir, &session.session_id, &current_tree, usize::MAX)?;
+
+    restore_worktree(workdir, &target.commit, &current_tree)?;
+
+    println!(
+        "Restored {} to checkpoint {}.",
+        workdir.display(),
+        target.number
+    );
+    match saved {
+        Some(number) => println!("The previous state is saved as checkpoint {number}."),
+        None => println!("The previous state was already the latest checkpoint."),
+    }
+    Ok(())
+}
+
+/// Spawns `a cc checkpoint-detached` so the Stop hook returns without
+/// waiting for the worktree to be staged. Failures are logged.
+pub(crate) fn spawn_take(cwd: &Path, session_id: &str, keep: usize) {
+    let keep = keep.to_string();
+    let result = std::env::current_exe().and_then(|exe| {
+        process::spawn_detached(
+            exe,
+            [
+                OsStr::new("cc"),
+                OsStr::new("checkpoint-detached"),
+                OsStr::new(session_id),
+                OsStr::new("--cwd"),
+                cwd.as_os_str(),
+                OsStr::new("--keep"),
+                OsStr::new(&keep),
+            ],
+            None,
+            &[],
+        )
+    });
+    if let Err(e) = result {
+        tracing::warn!(
+            event = "cc.checkpoint.spawn_failed",
+            session = %session_id,
+            error = %e,
+        );
+    }
+}
+
+/// Entry point for `a cc checkpoint-detached`. Never fails: it runs
+/// detached, so the outcome is only logged.
+pub fn run_detached(args: &CheckpointDetachedArgs) -> Result<()> {
+    match take(&args.cwd, &args.session_id, args.keep) {
+        Ok(Some(number)) => tracing::info!(
+            event = "cc.checkpoint.saved",
+            session = %args.session_id,
+            number,
+        ),
+        Ok(None) => {}
+        Err(e) => tracing::warn!(
+            event = "cc.checkpoint.failed",
+            session = %args.session_id,
+            error = %e,
+        ),
+    }
+    Ok(())
+}
+
+/// Takes a checkpoint of the worktree containing `cwd` for the Stop hook.
+/// Returns the new checkpoint's number, or `None` when nothing changed since
+/// the latest checkpoint or `cwd` is not inside a git repository.
+pub(crate) fn take(cwd: &Path, session_id: &str, keep: usize) -> Result<Option<u32>> {
+    // A session outside any git repository has nothing to checkpoint.
+    let Ok(repo) = GitRepo::open_at(cwd) else {
+        return Ok(None);
+    };
+    let tree = snapshot_tree(repo.workdir())?;
+    save_checkpoint(repo.workdir(), session_id, &tree, keep)
+}
+
+/// Deletes every checkpoint of a session whose file is being removed, so
+/// their refs (and the blobs they keep alive) don't outlive it. `cwd` may be
+/// gone already (a removed worktree): the repository is then looked up from
+/// its nearest existing ancestor, which finds the main checkout for
+/// worktrees under it. Returns how many checkpoints were deleted.
+pub(crate) fn delete_all(cwd: &Path, session_id: &str) -> Result<usize> {
+    let Some(repo) = cwd
+        .ancestors()
+        .filter(|dir| dir.is_dir())
+        .find_map(|dir| GitRepo::open_at(dir).ok())
+    else {
+        return Ok(0);
+    };
+    let workdir = repo.workdir();
+    let checkpoints = list_checkpoints(workdir, session_id)?;
+    for checkpoint in &checkpoints {
+        let ref_name = format!("{}{}", session_ref_prefix(session_id), checkpoint.number);
+        run_git(workdir, ["update-ref", "-d", ref_name.as_str()])?;
+    }
+    Ok(checkpoints.len())
+}
+
+fn session_ref_prefix(session_id: &str) -> String {
+    format!("{REF_NAMESPACE}/{session_id}/")
+}
+
+/// Writes the worktree's current content as a tree object and returns its
+/// id. Stages into a scratch copy of the index (copied rather than started
+/// empty so unchanged files hit git's stat cache instead of being rehashed).
+fn snapshot_tree(workdir: &Path) -> Result<String> {
+    let index = run_git(
+        workdir,
+        ["rev-parse", "--path-format=absolute", "--git-path", "index"],
+    )?;
+    let scratch = tempfile::tempdir()?;
+    let scratch_index = scratch.path().join("index");
+    if Path::new(&index).exists() {
+        std::fs::copy(&index, &scratch_index)?;
+    }
+
+    let env = [("GIT_INDEX_FILE", scratch_index.as_os_str())];
+    run_git_with_env(workdir, &env, ["add", "--all"])?;
+    run_git_with_env(workdir, &env, ["write-tree"])
+}
+
+/// Stores `tree` as the session's next checkpoint and deletes the oldest
+/// ones beyond `keep`. Returns `None` without storing anything when `tree`
+/// matches the latest checkpoint.
+fn save_checkpoint(
+    workdir: &Path,
+    session_id: &str,
+    tree: &str,
+    keep: usize,
+) -> Result<Option<u32>> {
+    let checkpoints = list_checkpoints(workdir, session_id)?;
+    if let Some(latest) = checkpoints.last()
+        && run_git(
+            workdir,
+            ["rev-parse", &format!("{}^{{tree}}", latest.commit)],
+        )? == tree
+    {
+        return Ok(None);
+    }
+
+    let number = checkpoints.last().map_or(1, |c| c.number + 1);
+    let message = format!("armyknife checkpoint {number} of session {session_id}");
+    let mut args = vec!["commit-tree", tree, "-m", &message];
+    let head = run_git_optional(workdir, ["rev-parse", "--verify", "--quiet", "HEAD"]);
+    if let Some(head) = &head {
+        args.extend(["-p", head.as_str()]);
+    }
+    let env = CHECKPOINT_IDENTITY.map(|(key, value)| (key, OsStr::new(value)));
+    let commit = run_git_with_env(workdir, &env, args)?;
+    let ref_name = format!("{}{number}", session_ref_prefix(session_id));
+    run_git(workdir, ["update-ref", ref_name.as_str(), commit.as_str()])?;
+
+    let excess = (checkpoints.len() + 1).saturating_sub(keep.max(1));
+    for old in checkpoints.iter().take(excess) {
+        let old_ref = format!("{}{}", session_ref_prefix(session_id), old.number);
+        run_git(workdir, ["update-ref", "-d", old_ref.as_str()])?;
+    }
+
+    Ok(Some(number))
+}
+
+/// The session's checkpoints, oldest first.
+fn list_checkpoints(workdir: &Path, session_id: &str) -> Result<Vec<Checkpoint>> {
+    let prefix = session_ref_prefix(session_id);
+    let output = run_git(
+        workdir,
+        [
+            "for-each-ref",
+            "--format=%(refname)%09%(objectname)%09%(parent)%09%(committerdate:iso-strict)",
+            prefix.as_str(),
+        ],
+    )?;
+    let mut checkpoints: Vec<Checkpoint> = output
+        .lines()
+        .filter_map(|line| parse_checkpoint_line(line, &prefix))
+        .collect();
+    checkpoints.sort_by_key(|c| c.number);
+    Ok(checkpoints)
+}
+
+/// Parses one `for-each-ref` line of `list_checkpoints`. Refs under the
+/// prefix whose last component is not a number are ignored.
+fn parse_checkpoint_line(line: &str, prefix: &str) -> Option<Checkpoint> {
+    let mut fields = line.split('\t');
+    let number = fields.next()?.strip_prefix(prefix)?.parse().ok()?;
+    let commit = fields.next()?.to_string();
+    let head = fields.next().filter(|s| !s.is_empty()).map(str::to_string);
+    let created_at = DateTime::parse_from_rfc3339(fields.next()?).ok()?;
+    Some(Checkpoint {
+        number,
+        commit,
+        head,
+        created_at,
+    })
+}
+
+/// Diffstat from `from` to `to`; without `from`, from `to`'s parent (or the
+/// empty tree when it has none).
+fn diffstat(workdir: &Path, from: Option<&str>, to: &str) -> Result<DiffStat> {
+    let output = match from {
+        Some(from) => run_git(workdir, ["diff-tree", "-r", "--shortstat", from, to])?,
+        None => run_git(
+            workdir,
+            [
+                "diff-tree",
+                "-r",
+                "--root",
+                "--no-commit-id",
+                "--shortstat",
+                to,
+            ],
+        )?,
+    };
+    Ok(parse_shortstat(&output))
+}
+
+/// Parses `git diff --shortstat` output such as
+/// ` 2 files changed, 10 insertions(+), 1 deletion(-)`. Empty output (no
+/// changes) parses to all zeros.
+fn parse_shortstat(output: &str) -> DiffStat {
+    let mut stat = DiffStat::default();
+    for part in output.trim().split(", ") {
+        let Some((count, what)) = part.split_once(' ') else {
+            continue;
+        };
+        let Ok(count) = count.parse() else {
+            continue;
+        };
+        if what.starts_with("file") {
+            stat.files = count;
+        } else if what.starts_with("insertion") {
+            stat.insertions = count;
+        } else if what.starts_with("deletion") {
+            stat.deletions = count;
+        }
+    }
+    stat
+}
+
+/// Makes the worktree match `target` without touching the index or HEAD:
+/// deletes files that exist now (`current_tree`) but not in `target`, then
+/// writes out every file of `target` through a scratch index.
+fn restore_worktree(workdir: &Path, target: &str, current_tree: &str) -> Result<()> {
+    let added = run_git(
+        workdir,
+        [
+            "diff-tree",
+            "-r",
+            "-z",
+            "--name-only",
+            "--no-renames",
+            "--diff-filter=A",
+            target,
+            current_tree,
+        ],
+    )?;
+    for path in added.split('\0').filter(|p| !p.is_empty()) {
+        match std::fs::remove_file(workdir.join(path)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+            _ => {}
+        }
+    }
+
+    let scratch = tempfile::tempdir()?;
+    let scratch_index = scratch.path().join("index");
+    let env = [("GIT_INDEX_FILE", scratch_index.as_os_str())];
+    run_git_with_env(workdir, &env, ["read-tree", target])?;
+    run_git_with_env(workdir, &env, ["checkout-index", "--all", "--force"])?;
+    Ok(())
+}
+
+fn render_entries(entries: &[CheckpointEntry]) -> String {
+    let mut out = String::from("   #  TAKEN             HEAD     CHANGES\n");
+    for entry in entries {
+        let head = entry.head.as_deref().map_or("-", |h| &h[..h.len().min(7)]);
+        out.push_str(&format!(
+            "{:>4}  {}  {:<7}  {}\n",
+            entry.number,
+            entry
+                .created_at
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M"),
+            head,
+            format_diffstat(entry.diffstat),
+        ));
+    }
+    out
+}
+
+fn format_diffstat(stat: DiffStat) -> String {
+    if stat.files == 0 {
+        return "no changes".to_string();
+    }
+    format!(
+        "{} file{}, +{} -{}",
+        stat.files,
+        if stat.files == 1 { "" } else { "s" },
+        stat.insertions,
+        stat.deletions
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::git::test_utils::TempRepo;
+    use rstest::rstest;
+    use std::fs;
+
+    fn read(repo: &TempRepo, path: &str) -> Option<String> {
+        fs::read_to_string(repo.path().join(path)).ok()
+    }
+
+    #[rstest]
+    #[case::full(
+        " 2 files changed, 10 insertions(+), 1 deletion(-)",
+        DiffStat { files: 2, insertions: 10, deletions: 1 }
+    )]
+    #[case::insertions_only(
+        " 1 file changed, 3 insertions(+)",
+        DiffStat { files: 1, insertions: 3, deletions: 0 }
+    )]
+    #[case::deletions_only(
+        " 1 file changed, 1 deletion(-)",
+        DiffStat { files: 1, insertions: 0, deletions: 1 }
+    )]
+    #[case::empty("", DiffStat::default())]
+    fn parse_shortstat_cases(#[case] output: &str, #[case] expected: DiffStat) {
+        assert_eq!(parse_shortstat(output), expected);
+    }
+
+    #[rstest]
+    #[case::numbered(
+        "refs/armyknife/checkpoints/s1/12\tabc\tdef\t2026-10-18T10:00:00+09:00",
+        Some((12, Some("def")))
+    )]
+    #[case::unborn_head(
+        "refs/armyknife/checkpoints/s1/1\tabc\t\t2026-10-18T10:00:00+09:00",
+        Some((1, None))
+    )]
+    #[case::non_numeric(
+        "refs/armyknife/checkpoints/s1/x\tabc\t\t2026-10-18T10:00:00+09:00",
+        None
+    )]
+    fn parse_checkpoint_line_cases(
+        #[case] line: &str,
+        #[case] expected: Option<(u32, Option<&str>)>,
+    ) {
+        let parsed = parse_checkpoint_line(line, &session_ref_prefix("s1"));
+        assert_eq!(
+            parsed.as_ref().map(|c| (c.number, c.head.as_deref())),
+            expected
+        );
+    }
+
+    #[test]
+    fn take_skips_unchanged_worktree_and_leaves_index_and_head_alone() {
+        let repo = TempRepo::new("owner", "repo", "main");
+        let head_before = run_git(&repo.path(), ["rev-parse", "HEAD"]).unwrap();
+        fs::write(repo.path().join("new.txt"), "untracked\n").unwrap();
+
+        let first = take(&repo.path(), "s1", 50).unwrap();
+        let unchanged = take(&repo.path(), "s1", 50).unwrap();
+
+        assert_eq!(
+            (
+                first,
+                unchanged,
+                run_git(&repo.path(), ["rev-parse", "HEAD"]).unwrap(),
+                run_git(&repo.path(), ["diff", "--cached", "--name-only"]).unwrap(),
+                run_git(
+                    &repo.path(),
+                    ["show", "refs/armyknife/checkpoints/s1/1:new.txt"]
+                )
+                .unwrap(),
+            ),
+            (
+                Some(1),
+                None,
+                head_before,
+                String::new(),
+                "untracked".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn take_prunes_oldest_beyond_keep() {
+        let repo = TempRepo::new("owner", "repo", "main");
+        for i in 0..3 {
+            fs::write(repo.path().join("f.txt"), format!("{i}\n")).unwrap();
+            take(&repo.path(), "s1", 2).unwrap();
+        }
+
+        let numbers: Vec<u32> = list_checkpoints(&repo.path(), "s1")
+            .unwrap()
+            .iter()
+            .map(|c| c.number)
+            .collect();
+
+        assert_eq!(numbers, vec![2, 3]);
+    }
+
+    #[test]
+    fn delete_all_removes_the_session_refs_even_after_its_worktree_is_gone() {
+        let repo = TempRepo::new("owner", "repo", "main");
+        fs::write(repo.path().join("f.txt"), "1\n").unwrap();
+        take(&repo.path(), "s1", 50).unwrap();
+        take(&repo.path(), "s2", 50).unwrap();
+        let removed_worktree = repo.path().join(".worktrees").join("gone");
+
+        let deleted = delete_all(&removed_worktree, "s1").unwrap();
+
+        assert_eq!(
+            (
+                deleted,
+                list_checkpoints(&repo.path(), "s1").unwrap().len(),
+                list_checkpoints(&repo.path(), "s2").unwrap().len(),
+            ),
+            (1, 0, 1)
+        );
+    }
+
+    #[test]
+    fn restore_worktree_brings_back_checkpoint_content() {
+        let repo = TempRepo::new("owner", "repo", "main");
+        fs::write(repo.path().join("keep.txt"), "v1\n").unwrap();
+        take(&repo.path(), "s1", 50).unwrap();
+        let target = list_checkpoints(&repo.path(), "s1").unwrap()[0].clone();
+
+        fs::write(repo.path().join("keep.txt"), "v2\n").unwrap();
+        fs::write(repo.path().join("later.txt"), "added later\n").unwrap();
+        let current_tree = snapshot_tree(&repo.path()).unwrap();
+        restore_worktree(&repo.path(), &target.commit, &current_tree).unwrap();
+
+        assert_eq!(
+            (read(&repo, "keep.txt"), read(&repo, "later.txt")),
+            (Some("v1\n".to_string()), None)
+        );
+    }
+
+    #[test]
+    fn diffstat_counts_changes_since_previous_checkpoint() {
+        let repo = TempRepo::new("owner", "repo", "main");
+        fs::write(repo.path().join("a.txt"), "one\n").unwrap();
+        take(&repo.path(), "s1", 50).unwrap();
+        fs::write(repo.path().join("a.txt"), "one\ntwo\nthree\n").unwrap();
+        take(&repo.path(), "s1", 50).unwrap();
+        let checkpoints = list_checkpoints(&repo.path(), "s1").unwrap();
+
+        assert_eq!(
+            (
+                diffstat(&repo.path(), None, &checkpoints[0].commit).unwrap(),
+                diffstat(
+                    &repo.path(),
+                    Some(&checkpoints[0].commit),
+                    &checkpoints[1].commit
+                )
+                .unwrap(),
+            ),
+            (
+                DiffStat {
+                    files: 1,
+                    insertions: 1,
+                    deletions: 0
+                },
+                DiffStat {
+                    files: 1,
+                    insertions: 2,
+                    deletions: 0
+                },
+            )
+        );
+    }
+}
@@ -5,10 +5,13 @@
 
 use lazy_regex::regex_replace_all;
 use serde::Deserialize;
+use std::collections::VecDeque;
 use std::fs::File;
 use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
 
+use super::types::FILE_WRITING_TOOLS;
+
 /// Initial buffer size for reading from end of file (8KB)
 const INITIAL_READ_SIZE: usize = 8 * 1024;
 
@@ -150,7 +153,7 @@ fn project_dir(project_path: &Path) -> Option<PathBuf> {
 /// Returns the path to a session's .jsonl file.
 ///
 /// Path format: ~/.claude/projects/{encoded-path}/{session_id}.jsonl
-fn session_jsonl_path(project_path: &Path, session_id: &str) -> Option<PathBuf> {
+pub fn session_jsonl_path(project_path: &Path, session_id: &str) -> Option<PathBuf> {
     Some(project_dir(project_path)?.join(format!("{session_id}.jsonl")))
 }
 
@@ -499,11 +502,17 @@ fn get_last_context_tokens_in_home(
         .join(&encoded)
         .join(format!("{session_id}.jsonl"));
 
+    get_last_context_tokens_from_transcript(&jsonl_path)
+}
+
+/// Same as `get_last_context_tokens`, for callers that already know the
+/// transcript path (e.g. from a statusLine payload).
+pub fn get_last_context_tokens_from_transcript(jsonl_path: &Path) -> Option<u64> {
     if !jsonl_path.exists() {
         return None;
     }
 
-    let file = File::open(&jsonl_path).ok()?;
+    let file = File::open(jsonl_path).ok()?;
 
     if let Some(tokens) = read_last_context_tokens_reverse(&file) {
         return Some(tokens);
@@ -751,6 +760,161 @@ fn read_last_assistant_message_forward(file: &File) -> Option<String> {
     last_text
 }
 
+/// Who said a `Turn`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TurnRole {
+    User,
+    Assistant,
+}
+
+/// One user prompt or assistant text reply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Turn {
+    pub role: TurnRole,
+    pub text: String,
+}
+
+/// What a context handoff (`a cc fork`) carries over from a transcript.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct TranscriptDigest {
+    /// The first user prompt, i.e. the task the session was started for.
+    pub first_prompt: Option<String>,
+    /// The last `recent_turns` turns, oldest first.
+    pub recent_turns: Vec<Turn>,
+    /// Files written by Edit / Write / NotebookEdit tool calls, in first-touch
+    /// order without duplicates.
+    pub touched_files: Vec<String>,
+}
+
+/// User entry for the digest scan. Unlike `JsonlEntry`, keeps `isMeta` so
+/// the caveat/command-output entries Claude Code injects as "user" turns
+/// are not mistaken for prompts.
+#[derive(Debug, Deserialize)]
+struct DigestUserEntry {
+    #[serde(rename = "type")]
+    entry_type: Option<String>,
+    #[serde(rename = "isMeta", default)]
+    is_meta: bool,
+    message: Option<JsonlMessage>,
+}
+
+/// Assistant entry for the digest scan, including tool_use inputs.
+#[derive(Debug, Deserialize)]
+struct DigestAssistantEntry {
+    #[serde(rename = "type")]
+    entry_type: Option<String>,
+    message: Option<DigestAssistantMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DigestAssistantMessage {
+    content: Option<Vec<DigestContent>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DigestContent {
+    #[serde(rename = "type")]
+    content_type: Option<String>,
+    text: Option<String>,
+    name: Option<String>,
+    input: Option<DigestToolInput>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DigestToolInput {
+    file_path: Option<String>,
+    notebook_path: Option<String>,
+}
+
+/// Scans a whole transcript for the pieces of a context handoff. Reads
+/// forward once: the first prompt sits at the top, and touched files can be
+/// anywhere. Returns `None` when the transcript cannot be opened.
+pub fn read_transcript_digest(
+    transcript_path: &Path,
+    recent_turns: usize,
+) -> Option<TranscriptDigest> {
+    let file = File::open(transcript_path).ok()?;
+    let reader = BufReader::new(file);
+
+    let mut digest = TranscriptDigest::default();
+    let mut recent: VecDeque<Turn> = VecDeque::with_capacity(recent_turns + 1);
+    let push_turn = |recent: &mut VecDeque<Turn>, turn: Turn| {
+        recent.push_back(turn);
+        if recent.len() > recent_turns {
+            recent.pop_front();
+        }
+    };
+
+    for line in reader.lines() {
+        let Ok(line) = line else {
+            continue;
+        };
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Ok(entry) = serde_json::from_str::<DigestUserEntry>(&line)
+            && entry.entry_type.as_deref() == Some("user")
+        {
+            if !entry.is_meta
+                && let Some(content) = entry.message.and_then(|m| m.content)
+                && !content.trim().is_empty()
+            {
+                let text = content.trim().to_string();
+                digest.first_prompt.get_or_insert_with(|| text.clone());
+                push_turn(
+                    &mut recent,
+                    Turn {
+                        role: TurnRole::User,
+                        text,
+                    },
+                );
+            }
+            continue;
+        }
+
+        if let Ok(entry) = serde_json::from_str::<DigestAssistantEntry>(&line)
+            && entry.entry_type.as_deref() == Some("assistant")
+            && let Some(contents) = entry.message.and_then(|m| m.content)
+        {
+            for content in contents {
+                match content.content_type.as_deref() {
+                    Some("text") => {
+                        if let Some(text) = content.text
+                            && !text.trim().is_empty()
+                        {
+                            push_turn(
+                                &mut recent,
+                                Turn {
+                                    role: TurnRole::Assistant,
+                                    text: text.trim().to_string(),
+                                },
+                            );
+                        }
+                    }
+                    Some("tool_use")
+                        if content
+                            .name
+                            .as_deref()
+                            .is_some_and(|n| FILE_WRITING_TOOLS.contains(&n)) =>
+                    {
+                        if let Some(path) =
+                            content.input.and_then(|i| i.file_path.or(i.notebook_path))
+                            && !digest.touched_files.contains(&path)
+                        {
+                            digest.touched_files.push(path);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    digest.recent_turns = recent.into();
+    Some(digest)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1351,4 +1515,60 @@ mod tests {
         // Last assistant has cache_read_input_tokens=499, input_tokens=1.
         assert_eq!(result, Some(500));
     }
+
+    // =========================================================================
+    // Tests for read_transcript_digest
+    // =========================================================================
+
+    #[test]
+    fn test_read_transcript_digest_collects_prompt_turns_and_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("session.jsonl");
+        std::fs::write(
+            &path,
+            indoc! {r#"
+                {"type":"user","message":{"content":"Fix the login bug"}}
+                {"type":"assistant","message":{"content":[{"type":"text","text":"Looking at it."},{"type":"tool_use","name":"Read","input":{"file_path":"/r/src/read_only.rs"}}]}}
+                {"type":"user","message":{"content":[{"type":"tool_result","content":"..."}]}}
+                {"type":"assistant","message":{"content":[{"type":"tool_use","name":"Edit","input":{"file_path":"/r/src/login.rs"}}]}}
+                {"type":"user","isMeta":true,"message":{"content":"Caveat: local command output"}}
+                {"type":"user","message":{"content":"Also update the docs"}}
+                {"type":"assistant","message":{"content":[{"type":"tool_use","name":"Write","input":{"file_path":"/r/docs/login.md"}},{"type":"tool_use","name":"Edit","input":{"file_path":"/r/src/login.rs"}},{"type":"text","text":"Done."}]}}
+            "#},
+        )
+        .unwrap();
+
+        let digest = read_transcript_digest(&path, 2).unwrap();
+
+        assert_eq!(
+            digest,
+            TranscriptDigest {
+                first_prompt: Some("Fix the login bug".to_string()),
+                recent_turns: vec![
+                    Turn {
+                        role: TurnRole::User,
+                        text: "Also update the docs".to_string(),
+                    },
+                    Turn {
+                        role: TurnRole::Assistant,
+                        text: "Done.".to_string(),
+                    },
+                ],
+                touched_files: vec![
+                    "/r/src/login.rs".to_string(),
+                    "/r/docs/login.md".to_string()
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn test_read_transcript_digest_handles_nonexistent_file() {
+        let temp_dir = TempDir::new().unwrap();
+
+        assert_eq!(
+            read_transcript_digest(&temp_dir.path().join("missing.jsonl"), 5),
+            None
+        );
+    }
 }
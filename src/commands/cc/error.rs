@@ -15,6 +15,14 @@ pub enum CcError {
     #[error("Cancelled: no prompt provided")]
     Cancelled,
 
+    #[error(
+        "Unknown session template '{0}' (define it under cc.templates in the config or as ~/.config/armyknife/templates/{0}.md)"
+    )]
+    UnknownTemplate(String),
+
+    #[error("Session template '{template}': {message}")]
+    InvalidTemplateVars { template: String, message: String },
+
     #[error("Unknown hook event: {0}")]
     UnknownHookEvent(String),
 
@@ -33,9 +41,27 @@ pub enum CcError {
     #[error("Session not found: {0}")]
     SessionNotFound(String),
 
+    #[error("No readable transcript for session '{0}'")]
+    TranscriptNotFound(String),
+
+    #[error("Session '{session_id}' has no checkpoint {number}")]
+    CheckpointNotFound { session_id: String, number: u32 },
+
     #[error("Session '{0}' has no tmux information (was not started in tmux)")]
     NoTmuxInfo(String),
 
+    #[error(
+        "Session '{session_id}' was written by a newer armyknife (schema version {version}, this build supports up to {supported}); refusing to overwrite it"
+    )]
+    SessionSchemaTooNew {
+        session_id: String,
+        version: u32,
+        supported: u32,
+    },
+
+    #[error("{0} session file(s) need migration; run `a cc store migrate`")]
+    SessionsNeedMigration(usize),
+
     #[error("Failed to acquire file lock within {0:?}")]
     LockTimeout(Duration),
 
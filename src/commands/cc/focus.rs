@@ -4,7 +4,7 @@ use clap::Args;
 use super::error::CcError;
 use super::store;
 use super::types::{Session, TmuxInfo};
-use crate::infra::tmux;
+use crate::infra::multiplexer::{self, Multiplexer};
 
 #[derive(Args, Clone, PartialEq, Eq)]
 pub struct FocusArgs {
@@ -13,13 +13,15 @@ pub struct FocusArgs {
 }
 
 /// Runs the focus command.
-/// Switches tmux focus to the pane associated with the specified session.
+/// Switches focus to the pane associated with the specified session.
 pub fn run(args: &FocusArgs) -> Result<()> {
     let session = store::load_session(&args.session_id)?;
-    let tmux_info = extract_tmux_info(&args.session_id, session)?;
-
-    tmux::focus_pane(&tmux_info.pane_id)?;
+    focus(multiplexer::current(), &args.session_id, session)
+}
 
+fn focus(mux: &dyn Multiplexer, session_id: &str, session: Option<Session>) -> Result<()> {
+    let tmux_info = extract_tmux_info(session_id, session)?;
+    mux.focus_pane(&tmux_info.pane_id)?;
     Ok(())
 }
 
@@ -59,6 +61,13 @@ mod tests {
             pending_permission_agent_ids: std::collections::BTreeSet::new(),
             read_at: None,
             sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
         }
     }
 
@@ -94,4 +103,20 @@ mod tests {
         assert_eq!(info.window_index, 0);
         assert_eq!(info.pane_id, "%0");
     }
+
+    #[test]
+    fn test_focus_switches_to_the_session_pane() {
+        let mux = multiplexer::FakeMultiplexer::new().with_pane("%3", "@1");
+        let mut session = create_test_session(None);
+        session.tmux_info = Some(TmuxInfo {
+            session_name: "main".to_string(),
+            window_name: "editor".to_string(),
+            window_index: 1,
+            pane_id: "%3".to_string(),
+        });
+
+        focus(&mux, "test-123", Some(session)).unwrap();
+
+        assert_eq!(mux.focused(), vec!["%3"]);
+    }
 }
@@ -0,0 +1,303 @@
+//! `a cc fork` -- branch a side task off a session into a new worktree
+//! session, carrying over a summary of the source conversation instead of
+//! the conversation itself.
+//!
+//! The handoff (first prompt, recent turns, files touched) is built from
+//! the source transcript and started through `a cc new --worktree --agent`,
+//! so the fork gets the usual `<delegated-task>` envelope and, with the
+//! source as its parent session, an `ancestor_session_ids` chain that puts
+//! it under the source in `cc watch` and `a cc tree`.
+
+use std::fmt::Write;
+
+use anyhow::{Context, Result};
+use clap::Args;
+use indoc::{formatdoc, indoc, writedoc};
+
+use super::claude_sessions::{self, TranscriptDigest, TurnRole};
+use super::error::CcError;
+use super::new::{CommonNewArgs, NewArgs};
+use super::store;
+use super::types::Session;
+use crate::commands::name_branch::generate_branch_name;
+use crate::infra::git::GitRepo;
+use crate::infra::llm::{self, LlmTask};
+
+/// Longest excerpt of a single turn kept in the handoff. The handoff is a
+/// summary, not a transcript copy; long tool-driven replies would otherwise
+/// dominate it.
+const MAX_TURN_CHARS: usize = 2000;
+
+#[derive(Args, Clone, PartialEq, Eq)]
+pub struct ForkArgs {
+    /// Session ID to fork
+    pub session_id: String,
+
+    /// Branch for the new worktree (default: generated from --prompt, or
+    /// from the handoff summary when --prompt is omitted)
+    #[arg(long)]
+    pub worktree: Option<String>,
+
+    /// What the forked session should do; appended after the handoff summary
+    #[arg(long)]
+    pub prompt: Option<String>,
+
+    /// Base branch for the new branch (default: origin/main or origin/master)
+    #[arg(long)]
+    pub from: Option<String>,
+
+    /// Label for the forked session (displayed in cc watch)
+    #[arg(long)]
+    pub label: Option<String>,
+
+    /// Model for the forked session. Passed through to `claude --model`.
+    #[arg(long)]
+    pub model: Option<String>,
+
+    /// Number of recent conversation turns to carry over
+    #[arg(long, default_value_t = 6)]
+    pub turns: usize,
+}
+
+pub fn run(args: &ForkArgs) -> Result<()> {
+    let source = store::load_session(&args.session_id)?
+        .ok_or_else(|| CcError::SessionNotFound(args.session_id.clone()))?;
+
+    let transcript_path = source
+        .transcript_path
+        .clone()
+        .or_else(|| claude_sessions::session_jsonl_path(&source.cwd, &source.session_id))
+        .ok_or_else(|| CcError::TranscriptNotFound(source.session_id.clone()))?;
+    let digest = claude_sessions::read_transcript_digest(&transcript_path, args.turns)
+        .ok_or_else(|| CcError::TranscriptNotFound(source.session_id.clone()))?;
+
+    let source_branch = GitRepo::open_at(&source.cwd)
+        .and_then(|repo| repo.current_branch())
+        .ok();
+    let handoff = build_handoff(&source, source_branch.as_deref(), &digest);
+    let prompt = match &args.prompt {
+        Some(task) => formatdoc! {"
+            {handoff}
+
+            {task}"},
+        None => handoff,
+    };
+
+    // Name the branch after the side task rather than the whole handoff,
+    // which mostly describes the source session's work.
+    let branch = match (&args.worktree, &args.prompt) {
+        (Some(branch), _) => Some(branch.clone()),
+        (None, Some(task)) => Some(
+            generate_branch_name(task, llm::backend_for(LlmTask::BranchName).as_ref())
+                .context("Failed to generate a branch name from --prompt")?,
+        ),
+        (None, None) => None,
+    };
+
+    super::new::run(&NewArgs {
+        worktree: Some(branch),
+        from: args.from.clone(),
+        force: false,
+        common: CommonNewArgs {
+            prompt: Some(prompt),
+            agent: true,
+            label: args.label.clone(),
+            model: args.model.clone(),
+            parent_session_id: Some(source.session_id.clone()),
+            repo: Some(source.cwd.clone()),
+            template: None,
+            vars: Vec::new(),
+        },
+        skip_hooks: false,
+    })
+}
+
+/// Renders the `<fork-handoff>` block the forked session starts from.
+fn build_handoff(source: &Session, branch: Option<&str>, digest: &TranscriptDigest) -> String {
+    let session_id = &source.session_id;
+    let label = source
+        .label
+        .as_deref()
+        .map(|label| format!(" (\"{label}\")"))
+        .unwrap_or_default();
+    let cwd = source.cwd.display();
+    let mut out = formatdoc! {"
+        <fork-handoff>
+        This session was forked from session {session_id}{label} to take over a side task. The source session keeps its own work; the summary below is all the context carried over from it.
+
+        - Source CWD: {cwd}
+    "};
+    if let Some(branch) = branch {
+        let _ = writeln!(out, "- Source branch: {branch}");
+    }
+
+    if let Some(first_prompt) = &digest.first_prompt {
+        let _ = writedoc!(
+            out,
+            "
+
+            ## Source session's original request
+
+            {}
+            ",
+            truncate_chars(first_prompt, MAX_TURN_CHARS)
+        );
+    }
+
+    if !digest.recent_turns.is_empty() {
+        out.push_str(indoc! {"
+
+            ## Recent conversation (oldest first)
+        "});
+        for turn in &digest.recent_turns {
+            let role = match turn.role {
+                TurnRole::User => "User",
+                TurnRole::Assistant => "Assistant",
+            };
+            let _ = writedoc!(
+                out,
+                "
+
+                ### {role}
+
+                {}
+                ",
+                truncate_chars(&turn.text, MAX_TURN_CHARS)
+            );
+        }
+    }
+
+    if !digest.touched_files.is_empty() {
+        out.push_str(indoc! {"
+
+            ## Files modified in the source session
+
+        "});
+        for path in &digest.touched_files {
+            let _ = writeln!(out, "- {path}");
+        }
+    }
+
+    out.push_str("</fork-handoff>");
+    out
+}
+
+/// Truncates `s` to at most `max` characters, marking the cut with `…`.
+fn truncate_chars(s: &str, max: usize) -> String {
+    match s.char_indices().nth(max) {
+        Some((byte_idx, _)) => format!("{}…", &s[..byte_idx]),
+        None => s.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::cc::claude_sessions::Turn;
+    use crate::commands::cc::types::SessionStatus;
+    use chrono::Utc;
+    use rstest::rstest;
+    use std::path::PathBuf;
+
+    fn source_session(label: Option<&str>) -> Session {
+        Session {
+            session_id: "src-1".to_string(),
+            cwd: PathBuf::from("/repo/.worktrees/feature"),
+            transcript_path: None,
+            tty: None,
+            tmux_info: None,
+            status: SessionStatus::Running,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_message: None,
+            current_tool: None,
+            label: label.map(str::to_string),
+            ancestor_session_ids: Vec::new(),
+            pending_bg_task_ids: Default::default(),
+            pending_agent_task_ids: Default::default(),
+            pending_permission_agent_ids: Default::default(),
+            read_at: None,
+            sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
+        }
+    }
+
+    #[test]
+    fn build_handoff_includes_all_sections() {
+        let digest = TranscriptDigest {
+            first_prompt: Some("Add OAuth login".to_string()),
+            recent_turns: vec![
+                Turn {
+                    role: TurnRole::User,
+                    text: "The token refresh is flaky too".to_string(),
+                },
+                Turn {
+                    role: TurnRole::Assistant,
+                    text: "That's a separate bug in refresh.rs.".to_string(),
+                },
+            ],
+            touched_files: vec!["/repo/src/auth.rs".to_string()],
+        };
+
+        let handoff = build_handoff(&source_session(Some("oauth")), Some("feature"), &digest);
+
+        assert_eq!(
+            handoff,
+            indoc! {r#"
+                <fork-handoff>
+                This session was forked from session src-1 ("oauth") to take over a side task. The source session keeps its own work; the summary below is all the context carried over from it.
+
+                - Source CWD: /repo/.worktrees/feature
+                - Source branch: feature
+
+                ## Source session's original request
+
+                Add OAuth login
+
+                ## Recent conversation (oldest first)
+
+                ### User
+
+                The token refresh is flaky too
+
+                ### Assistant
+
+                That's a separate bug in refresh.rs.
+
+                ## Files modified in the source session
+
+                - /repo/src/auth.rs
+                </fork-handoff>"#}
+        );
+    }
+
+    #[test]
+    fn build_handoff_omits_empty_sections() {
+        let handoff = build_handoff(&source_session(None), None, &TranscriptDigest::default());
+
+        assert_eq!(
+            handoff,
+            indoc! {"
+                <fork-handoff>
+                This session was forked from session src-1 to take over a side task. The source session keeps its own work; the summary below is all the context carried over from it.
+
+                - Source CWD: /repo/.worktrees/feature
+                </fork-handoff>"}
+        );
+    }
+
+    #[rstest]
+    #[case::short("abc", 5, "abc")]
+    #[case::exact("abcde", 5, "abcde")]
+    #[case::long("abcdef", 5, "abcde…")]
+    #[case::multibyte("あいうえおか", 3, "あいう…")]
+    fn truncate_chars_cases(#[case] input: &str, #[case] max: usize, #[case] expected: &str) {
+        assert_eq!(truncate_chars(input, max), expected);
+    }
+}
@@ -14,7 +14,7 @@ use anyhow::{Context, Result};
 use clap::Args;
 
 use crate::commands::cc::{store, window_status};
-use crate::commands::name_branch::{Backend, detect_backend};
+use crate::infra::llm::{self, Backend, LlmTask};
 
 /// Tracing target for events emitted by this subcommand.
 const EVENT_TARGET: &str = "armyknife::commands::cc::generate_title";
@@ -32,6 +32,10 @@ pub struct GenerateTitleDetachedArgs {
     /// was in flight. Absence means the label was unset at request time.
     #[arg(long, value_name = "LABEL")]
     pub previous_label: Option<String>,
+    /// Title applied instead when no LLM backend produces one, so titles
+    /// still work on machines without any backend installed.
+    #[arg(long, value_name = "TITLE")]
+    pub fallback_title: Option<String>,
 }
 
 pub fn run(args: &GenerateTitleDetachedArgs) -> Result<()> {
@@ -50,11 +54,12 @@ pub fn run(args: &GenerateTitleDetachedArgs) -> Result<()> {
 }
 
 fn run_inner(args: &GenerateTitleDetachedArgs) -> Result<()> {
-    let backend = detect_backend();
+    let backend = llm::backend_for(LlmTask::Title);
     generate_and_apply(args, backend.as_ref())
 }
 
-/// Reads the prompt file, calls `backend.generate`, and -- on success --
+/// Reads the prompt file, calls `backend.generate` (falling back to
+/// `args.fallback_title` when it fails), and -- on success --
 /// writes the session's `label` via a compare-and-swap keyed on
 /// `args.previous_label`, so a manual rename made while generation was
 /// running is never clobbered. Pulled out of `run_inner` so tests can
@@ -68,14 +73,15 @@ fn generate_and_apply(args: &GenerateTitleDetachedArgs, backend: &dyn Backend) -
     // doesn't linger in `/tmp` across repeated Ctrl+g presses.
     let _ = fs::remove_file(&args.prompt_file);
 
-    let title = backend.generate(&prompt).inspect_err(|e| {
-        tracing::warn!(
-            target: EVENT_TARGET,
-            event = "cc.generate_title.backend_err",
-            session_id = %args.session_id,
-            msg = format!("{e:#}"),
-        );
-    })?;
+    let title = llm::generate_with_fallback(backend, &prompt, || args.fallback_title.clone())
+        .inspect_err(|e| {
+            tracing::warn!(
+                target: EVENT_TARGET,
+                event = "cc.generate_title.backend_err",
+                session_id = %args.session_id,
+                msg = format!("{e:#}"),
+            );
+        })?;
 
     let applied = store::update_session_label_if_unchanged(
         &args.session_id,
@@ -141,6 +147,13 @@ mod tests {
             pending_permission_agent_ids: std::collections::BTreeSet::new(),
             read_at: None,
             sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
         }
     }
 
@@ -189,6 +202,7 @@ mod tests {
             session_id: "s1".to_string(),
             prompt_file: prompt_path.clone(),
             previous_label: None,
+            fallback_title: None,
         };
         let backend = StubBackend(Ok("Generated Title".to_string()));
 
@@ -217,6 +231,7 @@ mod tests {
             prompt_file: prompt_file(tmp.path()),
             // Snapshot taken before the manual rename above landed.
             previous_label: None,
+            fallback_title: None,
         };
         let backend = StubBackend(Ok("Generated Title".to_string()));
 
@@ -241,6 +256,7 @@ mod tests {
             session_id: "s1".to_string(),
             prompt_file: prompt_file(tmp.path()),
             previous_label: None,
+            fallback_title: None,
         };
         let backend = StubBackend(Err("claude exited with status 1".to_string()));
 
@@ -255,6 +271,31 @@ mod tests {
         assert_eq!((result.is_err(), reloaded.label), (true, None));
     }
 
+    #[rstest]
+    fn test_backend_failure_applies_the_fallback_title() {
+        let root = temp_cache_root();
+        let session = create_test_session("s1", None);
+        store::save_session_to(&root.sessions_dir, &session).expect("save should succeed");
+
+        let tmp = TempDir::new().expect("tempdir");
+        let args = GenerateTitleDetachedArgs {
+            session_id: "s1".to_string(),
+            prompt_file: prompt_file(tmp.path()),
+            previous_label: None,
+            fallback_title: Some("Fix the login bug".to_string()),
+        };
+        let backend = StubBackend(Err("no LLM backend is available".to_string()));
+
+        temp_env::with_vars([("XDG_CACHE_HOME", Some(root.cache_home.as_str()))], || {
+            generate_and_apply(&args, &backend).expect("generation should succeed");
+        });
+
+        let reloaded = store::load_session_from(&root.sessions_dir, "s1")
+            .expect("load should succeed")
+            .expect("session exists");
+        assert_eq!(reloaded.label, Some("Fix the login bug".to_string()));
+    }
+
     #[rstest]
     fn missing_prompt_file_returns_error() {
         let root = temp_cache_root();
@@ -265,6 +306,7 @@ mod tests {
             session_id: "s1".to_string(),
             prompt_file: PathBuf::from("/nonexistent/prompt.txt"),
             previous_label: None,
+            fallback_title: None,
         };
         let backend = StubBackend(Ok("Generated Title".to_string()));
 
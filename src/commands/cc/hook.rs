@@ -13,20 +13,23 @@ use indoc::formatdoc;
 use lazy_regex::regex_replace_all;
 
 use super::auto_compact;
+use super::auto_label;
+use super::checkpoint;
 use super::claude_sessions;
 use super::error::CcError;
 use super::store;
 use super::tmux_sync::{LiveTmuxStatusSyncer, TmuxStatusSyncer};
 use super::types::{
-    HookEvent, HookInput, MAIN_THREAD_AGENT_KEY, Session, SessionStatus, TMUX_SESSION_OPTION,
-    TmuxInfo,
+    HookEvent, HookInput, MAIN_THREAD_AGENT_KEY, Session, SessionStatus, Subagent,
+    TMUX_SESSION_OPTION, TmuxInfo,
 };
+use crate::infra::multiplexer;
 use crate::infra::notification::{Notification, NotificationAction};
-use crate::infra::tmux;
 use crate::shared::cache;
-use crate::shared::config::{self, Config, Terminal};
+use crate::shared::config::{self, Config};
 use crate::shared::env_var::EnvVars;
 use crate::shared::log::short_run_id;
+use crate::shared::notify;
 
 /// Delay between retries when waiting for transcript to be updated.
 const TRANSCRIPT_RETRY_DELAY: Duration = Duration::from_millis(100);
@@ -103,6 +106,12 @@ struct SideEffects {
     /// Spawn the detached `a cc auto-compact schedule` worker on Stop events.
     /// Off in tests (would fork a real process and survive past the test).
     auto_compact: bool,
+    /// Take a git checkpoint of the session's worktree on Stop events.
+    /// Off in tests (would write refs into whatever repo the cwd points at).
+    checkpoints: bool,
+    /// Label a newly recorded session per `cc.auto_label`. Off in tests
+    /// (would read the user's config and PR cache).
+    auto_label: bool,
     /// Test-only sink that records the group ids passed to
     /// `remove_notification_group`. Lets tests assert the call happened
     /// without invoking hammerspoon.
@@ -126,6 +135,8 @@ impl SideEffects {
             tmux: true,
             notifications: true,
             auto_compact: true,
+            checkpoints: true,
+            auto_label: true,
             #[cfg(test)]
             removed_notification_groups: None,
             #[cfg(test)]
@@ -139,6 +150,8 @@ impl SideEffects {
             tmux: false,
             notifications: false,
             auto_compact: false,
+            checkpoints: false,
+            auto_label: false,
             removed_notification_groups: None,
             tmux_sync_calls: None,
         }
@@ -192,29 +205,12 @@ fn evict_paused_sessions_on_pane_takeover(
     pane_id: &str,
     current_session_id: &str,
 ) {
-    let Ok(entries) = fs::read_dir(sessions_dir) else {
+    let Ok(sessions) = store::find_sessions_in(sessions_dir, &store::Lookup::Pane(pane_id)) else {
         return;
     };
     let now = Utc::now();
-    for entry in entries.flatten() {
-        let path = entry.path();
-        if path.extension().is_none_or(|ext| ext != "json") {
-            continue;
-        }
-        let Ok(content) = fs::read_to_string(&path) else {
-            continue;
-        };
-        let Ok(mut session) = serde_json::from_str::<Session>(&content) else {
-            continue;
-        };
-        let matches_pane = session
-            .tmux_info
-            .as_ref()
-            .is_some_and(|info| info.pane_id == pane_id);
-        if session.status != SessionStatus::Paused
-            || session.session_id == current_session_id
-            || !matches_pane
-        {
+    for mut session in sessions {
+        if session.status != SessionStatus::Paused || session.session_id == current_session_id {
             continue;
         }
         session.status = SessionStatus::Ended;
@@ -297,11 +293,14 @@ fn process_hook_event_impl(
         }
 
         if side_effects.tmux
-            && let Some(pane_info) = tmux::get_pane_info_by_pid(std::process::id())
+            && let Some(pane_info) = multiplexer::current().current_pane()
         {
             // Ignore errors; pane option is nice-to-have, not critical
-            let _ =
-                tmux::set_pane_option(&pane_info.pane_id, TMUX_SESSION_OPTION, &input.session_id);
+            let _ = multiplexer::current().set_pane_option(
+                &pane_info.pane_id,
+                TMUX_SESSION_OPTION,
+                &input.session_id,
+            );
             evict_paused_sessions_on_pane_takeover(
                 sessions_dir,
                 &pane_info.pane_id,
@@ -323,15 +322,19 @@ fn process_hook_event_impl(
         && !sessions_dir
             .join(format!("{}.json", input.session_id))
             .exists()
-        && let Some(pane_info) = tmux::get_pane_info_by_pid(std::process::id())
+        && let Some(pane_info) = multiplexer::current().current_pane()
     {
-        let _ = tmux::set_pane_option(&pane_info.pane_id, TMUX_SESSION_OPTION, &input.session_id);
+        let _ = multiplexer::current().set_pane_option(
+            &pane_info.pane_id,
+            TMUX_SESSION_OPTION,
+            &input.session_id,
+        );
         evict_paused_sessions_on_pane_takeover(sessions_dir, &pane_info.pane_id, &input.session_id);
     }
 
     // Get tmux info by finding the pane that contains this process
     let tmux_info = if side_effects.tmux {
-        tmux::get_pane_info_by_pid(std::process::id()).map(|info| TmuxInfo {
+        multiplexer::current().current_pane().map(|info| TmuxInfo {
             session_name: info.session_name,
             window_name: info.window_name,
             window_index: info.window_index,
@@ -355,7 +358,9 @@ fn process_hook_event_impl(
     // its update below.
     let session_lock = store::lock_session_for_update(sessions_dir, &input.session_id)?;
     let now = Utc::now();
-    let mut session = session_lock.load()?.unwrap_or_else(|| {
+    let existing = session_lock.load()?;
+    let is_new_session = existing.is_none();
+    let mut session = existing.unwrap_or_else(|| {
         // Read label and ancestor chain from environment variables (set by `a cc new`)
         let ancestor_session_ids = env
             .ancestor_session_ids
@@ -381,9 +386,28 @@ fn process_hook_event_impl(
             pending_permission_agent_ids: BTreeSet::new(),
             read_at: None,
             sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
         }
     });
 
+    // Subagent lifecycle and compaction events say nothing about the main
+    // loop (see `HookEvent::reports_main_loop`): keep the status the last
+    // main-loop event set. A `WaitingInput` only held by permission waits
+    // that this event resolves falls back to `Running`, via the clamp
+    // below re-adding `WaitingInput` when waits remain.
+    if !event.reports_main_loop() && !is_new_session {
+        status = match session.status {
+            SessionStatus::WaitingInput => SessionStatus::Running,
+            current => current,
+        };
+    }
+
     // Update session fields
     session.cwd.clone_from(&input.cwd);
     session.updated_at = now;
@@ -426,12 +450,13 @@ fn process_hook_event_impl(
     // Refresh in-flight background tasks (Bash bg shells and Task-tool
     // subagents) from Claude Code's own task registry (see
     // `HookInput::pending_bg_task_ids` / `pending_agent_task_ids` and the
-    // matching `Session` fields). armyknife only wires the `Stop` hook, so
-    // each Stop overwrites both sets wholesale with the registry's current
-    // view. The immediately-following Stop after a launch fires
-    // synthetically -- Claude moves on as soon as the task is spawned, not
-    // when it finishes -- so a non-empty set here means "the user is still
-    // mid-task even though Claude's main loop went idle":
+    // matching `Session` fields). Only `Stop` is read -- `SubagentStop`
+    // carries the registry too, but mid-turn -- so each Stop overwrites both
+    // sets wholesale with the registry's current view. The
+    // immediately-following Stop after a launch fires synthetically --
+    // Claude moves on as soon as the task is spawned, not when it finishes
+    // -- so a non-empty set here means "the user is still mid-task even
+    // though Claude's main loop went idle":
     //
     // - The status clamp directly below reports `Running` instead of
     //   `Stopped` while any id is pending, so `cc list` / `cc watch` / tmux
@@ -456,6 +481,19 @@ fn process_hook_event_impl(
             .retain(|key| key == MAIN_THREAD_AGENT_KEY || live_agent_ids.contains(key));
     }
 
+    record_subagent_lifecycle(&mut session, event, &input, now);
+
+    if event == HookEvent::PreCompact {
+        session.compaction_count += 1;
+        session.last_compacted_at = Some(now);
+        tracing::info!(
+            event = "cc.compaction",
+            session = %session.session_id,
+            trigger = input.trigger.as_deref().unwrap_or("unknown"),
+            count = session.compaction_count,
+        );
+    }
+
     // Claude Code resolves both `Stop` and `Notification(idle_prompt)` to
     // "the main loop / user went idle" independent of whether a launched
     // Bash bg shell or Task-tool subagent has actually finished -- Claude
@@ -496,7 +534,7 @@ fn process_hook_event_impl(
         session.status = status;
     }
 
-    if session.status == SessionStatus::Stopped {
+    if session.status == SessionStatus::Stopped && event.reports_main_loop() {
         session.read_at = None;
     }
 
@@ -514,6 +552,14 @@ fn process_hook_event_impl(
         _ => session.current_tool,
     };
 
+    // PostToolUse only fires for tool calls that succeeded, so a rejected
+    // or failed edit is never recorded.
+    if event == HookEvent::PostToolUse
+        && let Some(path) = input.written_file_path()
+    {
+        session.changed_files.insert(path);
+    }
+
     // Save the session, then release the lock before the two slow steps
     // below (transcript read, tmux sync). Both can block for up to 500ms,
     // and `sync_tmux` (via `window_status::sync_window_option`) re-reads
@@ -523,6 +569,35 @@ fn process_hook_event_impl(
     session_lock.save(&session)?;
     drop(session_lock);
 
+    // Label a session first seen without one (see `auto_label.rs`). Runs
+    // after the lock is released since it reads the repository, and
+    // compare-and-swaps against the unset label so a concurrent rename wins.
+    if side_effects.auto_label && is_new_session && session.label.is_none() {
+        let config = config::load_config().unwrap_or_default();
+        match auto_label::label_new_session_in(
+            sessions_dir,
+            &session,
+            input.prompt.as_deref(),
+            &config,
+        ) {
+            Ok(Some(label)) => {
+                tracing::info!(
+                    event = "cc.auto_label.applied",
+                    session = %session.session_id,
+                    label = %label,
+                    trigger = "new_session",
+                );
+                session.label = Some(label);
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!(
+                event = "cc.auto_label.failed",
+                session = %session.session_id,
+                error = %e,
+            ),
+        }
+    }
+
     // Update last_message from Claude Code's transcript.
     // For Stop events, retry if transcript hasn't been updated yet (race condition with
     // Claude Code's write). For other events, read once without retrying.
@@ -569,7 +644,13 @@ fn process_hook_event_impl(
     // SessionEnd never reaches here (early return above). Also skipped
     // while any agent still has a pending permission wait, so a sibling
     // agent's event doesn't erase a still-relevant permission notification.
-    if !matches!(event, HookEvent::Notification) && !session.has_pending_permission_requests() {
+    // Subagent lifecycle and compaction events are skipped too: a
+    // background subagent finishing must not clear the Stop notification of
+    // the session it belongs to.
+    if !matches!(event, HookEvent::Notification)
+        && event.reports_main_loop()
+        && !session.has_pending_permission_requests()
+    {
         side_effects.remove_notification_group(&input.session_id);
     }
 
@@ -622,9 +703,71 @@ fn process_hook_event_impl(
         }
     }
 
+    // Snapshot the worktree so `a cc rollback` can return to this turn.
+    // Staging a large worktree takes a while, so it runs detached like
+    // auto-compact; a missing checkpoint must never block the session.
+    if side_effects.checkpoints && event == HookEvent::Stop {
+        let config = config::load_config().unwrap_or_default();
+        if config.cc.checkpoints.enabled {
+            checkpoint::spawn_take(
+                &session.cwd,
+                &session.session_id,
+                config.cc.checkpoints.keep,
+            );
+        }
+    }
+
     Ok(ProcessResult::SessionSaved)
 }
 
+/// Keeps `Session::subagents` in step with the subagent lifecycle:
+///
+/// - Any event from a subagent (`agent_id` set) registers it as running,
+///   so builds without `SubagentStart` still get an entry.
+/// - `SubagentStop` marks it stopped.
+/// - A main-thread `Stop` marks every subagent missing from Claude Code's
+///   task registry stopped: foreground subagents always finish before the
+///   turn ends, and one that exits without `SubagentStop` (e.g. after a
+///   denied permission) would otherwise show as running forever.
+/// - A main-thread `UserPromptSubmit` starts a new turn and drops the
+///   stopped entries of the previous one.
+fn record_subagent_lifecycle(
+    session: &mut Session,
+    event: HookEvent,
+    input: &HookInput,
+    now: chrono::DateTime<Utc>,
+) {
+    match &input.agent_id {
+        Some(agent_id) => {
+            let subagent = session
+                .subagents
+                .entry(agent_id.clone())
+                .or_insert_with(|| Subagent {
+                    agent_type: None,
+                    started_at: now,
+                    stopped_at: None,
+                });
+            if input.agent_type.is_some() {
+                subagent.agent_type.clone_from(&input.agent_type);
+            }
+            subagent.stopped_at = (event == HookEvent::SubagentStop).then_some(now);
+        }
+        None if event == HookEvent::Stop => {
+            for (agent_id, subagent) in &mut session.subagents {
+                if subagent.is_running() && !session.pending_agent_task_ids.contains(agent_id) {
+                    subagent.stopped_at = Some(now);
+                }
+            }
+        }
+        None if event == HookEvent::UserPromptSubmit => {
+            session
+                .subagents
+                .retain(|_, subagent| subagent.is_running());
+        }
+        None => {}
+    }
+}
+
 /// Reads raw content from stdin.
 fn read_raw_stdin() -> Result<String> {
     let mut content = String::new();
@@ -909,16 +1052,22 @@ fn determine_status(event: HookEvent, input: &HookInput) -> SessionStatus {
         HookEvent::SessionStart if input.source.as_deref() == Some("resume") => {
             SessionStatus::Stopped
         }
+        // Subagent and compaction events only reach here for a session
+        // with no file yet; otherwise the main loop's status is kept (see
+        // `HookEvent::reports_main_loop`).
         HookEvent::SessionStart
         | HookEvent::UserPromptSubmit
         | HookEvent::PreToolUse
         | HookEvent::PostToolUse
-        | HookEvent::SessionEnd => SessionStatus::Running,
+        | HookEvent::SessionEnd
+        | HookEvent::SubagentStart
+        | HookEvent::SubagentStop
+        | HookEvent::PreCompact => SessionStatus::Running,
     }
 }
 
 /// Checks if notifications are enabled via environment variable.
-fn is_notification_enabled(config: &Config) -> bool {
+pub(super) fn is_notification_enabled(config: &Config) -> bool {
     // Environment variable takes precedence over config for backward compatibility
     match EnvVars::load().cc_notify {
         Some(val) => !matches!(val.to_lowercase().as_str(), "0" | "false"),
@@ -959,9 +1108,15 @@ fn is_notifiable_event(event: HookEvent, has_pending_bg_tasks: bool) -> bool {
 /// Errors are printed to stderr but don't fail the hook.
 fn send_notification(event: HookEvent, input: &HookInput, session: &Session, config: &Config) {
     let notification = build_notification(event, input, session, config);
+    let kind = match (event, session.status) {
+        (HookEvent::PermissionRequest, _) => notify::Kind::Permission,
+        (_, SessionStatus::WaitingInput) => notify::Kind::Waiting,
+        (_, SessionStatus::Stopped) => notify::Kind::Stopped,
+        _ => notify::Kind::Other,
+    };
 
     // Print notification errors to stderr without failing the hook
-    if let Err(e) = crate::infra::notification::send(&notification) {
+    if let Err(e) = notify::deliver(notification, kind, config) {
         eprintln!("[armyknife] warning: failed to send notification: {e}");
     }
 }
@@ -1044,7 +1199,7 @@ fn build_notification(
     if session.tmux_info.is_some() {
         let session_id = shlex::try_quote(&session.session_id)
             .unwrap_or_else(|_| session.session_id.clone().into());
-        let focus_cmd = build_focus_app_command(config);
+        let focus_cmd = notify::focus_app_command(config);
         let command = format!("a cc focus {session_id}; {focus_cmd}");
         notification = notification.with_action(NotificationAction::new(command));
     }
@@ -1052,33 +1207,9 @@ fn build_notification(
     notification
 }
 
-/// Ghostty's default window title. Used to identify the main terminal window
-/// when focusing via AppleScript, since Ghostty's AppleScript API does not
-/// expose tty information per window (https://github.com/ghostty-org/ghostty/issues/10756).
-const GHOSTTY_DEFAULT_TITLE: &str = "👻";
-
-/// Builds a shell command to focus the terminal application.
-/// For Ghostty on macOS, uses AppleScript to focus the main window by its default title.
-/// For other terminals, uses `open -a` which activates the most recent window.
-fn build_focus_app_command(config: &Config) -> String {
-    if cfg!(target_os = "macos")
-        && config.editor.terminal == Terminal::Ghostty
-        && config.editor.focus_app.is_none()
-    {
-        format!(
-            "osascript -e 'tell application \"Ghostty\"' -e 'activate (first window whose name is \"{GHOSTTY_DEFAULT_TITLE}\")' -e 'activate' -e 'end tell'"
-        )
-    } else {
-        let focus_app_str = config.editor.focus_app();
-        let focus_app =
-            shlex::try_quote(focus_app_str).unwrap_or_else(|_| focus_app_str.to_string().into());
-        format!("open -a {focus_app}")
-    }
-}
-
 /// Builds the subtitle for a notification.
 /// Format: "session:window | タイトル" or just "session:window" if no title.
-fn build_subtitle(session: &Session) -> Option<String> {
+pub(super) fn build_subtitle(session: &Session) -> Option<String> {
     let tmux_info = session.tmux_info.as_ref()?;
     let tmux_part = format!("{}:{}", tmux_info.session_name, tmux_info.window_name);
 
@@ -1244,6 +1375,18 @@ mod tests {
             HookEvent::from_str("session-end").expect("valid event"),
             HookEvent::SessionEnd
         );
+        assert_eq!(
+            HookEvent::from_str("subagent-start").expect("valid event"),
+            HookEvent::SubagentStart
+        );
+        assert_eq!(
+            HookEvent::from_str("subagent-stop").expect("valid event"),
+            HookEvent::SubagentStop
+        );
+        assert_eq!(
+            HookEvent::from_str("pre-compact").expect("valid event"),
+            HookEvent::PreCompact
+        );
 
         assert!(HookEvent::from_str("unknown").is_err());
     }
@@ -1580,6 +1723,13 @@ mod tests {
             pending_permission_agent_ids: BTreeSet::new(),
             read_at: None,
             sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
         };
         store::save_session_to(sessions_dir, &session).expect("save");
 
@@ -1597,6 +1747,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn resume_after_session_end_keeps_tags_and_note() {
+        // `claude -c` restores an ended session under its own session_id,
+        // so the user's tags and note must survive the SessionEnd ->
+        // SessionStart(resume) round trip.
+        let temp_dir = tempfile::TempDir::new().expect("temp dir");
+        let sessions_dir = temp_dir.path();
+
+        let mut session = create_test_session(None);
+        session.session_id = "tagged-sess".to_string();
+        session.tags = BTreeSet::from(["waiting-on-ci".to_string()]);
+        session.note = Some("rebase after #12 lands".to_string());
+        store::save_session_to(sessions_dir, &session).expect("save");
+
+        for (event, source) in [
+            (HookEvent::SessionEnd, None),
+            (HookEvent::SessionStart, Some("resume")),
+        ] {
+            let input = create_test_input_with_session_and_source("tagged-sess", None, source);
+            process_hook_event_impl(event, input, sessions_dir, &SideEffects::none())
+                .expect("hook should succeed");
+        }
+
+        let reloaded = store::load_session_from(sessions_dir, "tagged-sess")
+            .expect("load")
+            .expect("session exists");
+        assert_eq!(
+            (reloaded.status, reloaded.tags, reloaded.note),
+            (
+                SessionStatus::Stopped,
+                BTreeSet::from(["waiting-on-ci".to_string()]),
+                Some("rebase after #12 lands".to_string())
+            )
+        );
+    }
+
     #[test]
     fn stop_with_pending_bg_task_keeps_paused_session_paused() {
         // A session already confirmed Paused (its process is dead) can still
@@ -1688,6 +1874,13 @@ mod tests {
             pending_permission_agent_ids: BTreeSet::new(),
             read_at: None,
             sweep_signaled,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
         };
         store::save_session_to(sessions_dir, &session).expect("save");
 
@@ -1696,6 +1889,8 @@ mod tests {
             tmux: false,
             notifications: false,
             auto_compact: false,
+            checkpoints: false,
+            auto_label: false,
             removed_notification_groups: Some(removed.clone()),
             tmux_sync_calls: None,
         };
@@ -1749,6 +1944,8 @@ mod tests {
             tmux: false,
             notifications: false,
             auto_compact: false,
+            checkpoints: false,
+            auto_label: false,
             removed_notification_groups: None,
             tmux_sync_calls: Some(calls.clone()),
         };
@@ -1915,6 +2112,174 @@ mod tests {
         );
     }
 
+    #[rstest]
+    #[case::edit(HookEvent::PostToolUse, "Edit", r#"{"file_path":"src/a.rs"}"#, &["/tmp/test/src/a.rs"])]
+    #[case::absolute_write(HookEvent::PostToolUse, "Write", r#"{"file_path":"/abs/b.rs"}"#, &["/abs/b.rs"])]
+    #[case::notebook_edit(HookEvent::PostToolUse, "NotebookEdit", r#"{"notebook_path":"nb.ipynb"}"#, &["/tmp/test/nb.ipynb"])]
+    #[case::pre_tool_use_not_recorded(HookEvent::PreToolUse, "Edit", r#"{"file_path":"src/a.rs"}"#, &[])]
+    #[case::read_not_recorded(HookEvent::PostToolUse, "Read", r#"{"file_path":"src/a.rs"}"#, &[])]
+    fn post_tool_use_records_written_files(
+        #[case] event: HookEvent,
+        #[case] tool_name: &str,
+        #[case] tool_input: &str,
+        #[case] expected: &[&str],
+    ) {
+        let temp_dir = tempfile::TempDir::new().expect("temp dir");
+        let sessions_dir = temp_dir.path();
+
+        process_hook_event_impl(
+            event,
+            create_test_input_with_tool(tool_name, Some(tool_input)),
+            sessions_dir,
+            &SideEffects::none(),
+        )
+        .expect("hook should succeed");
+
+        let reloaded = store::load_session_from(sessions_dir, "test-123")
+            .expect("load")
+            .expect("session exists");
+        assert_eq!(
+            reloaded.changed_files,
+            expected.iter().map(PathBuf::from).collect()
+        );
+    }
+
+    fn run_hook(sessions_dir: &Path, event: HookEvent, json: &str) -> Session {
+        let input: HookInput = serde_json::from_str(json).expect("valid JSON");
+        let session_id = input.session_id.clone();
+        process_hook_event_impl(event, input, sessions_dir, &SideEffects::none())
+            .expect("hook should succeed");
+        store::load_session_from(sessions_dir, &session_id)
+            .expect("load")
+            .expect("session exists")
+    }
+
+    #[test]
+    fn subagent_lifecycle_is_tracked_per_agent() {
+        let temp_dir = tempfile::TempDir::new().expect("temp dir");
+        let sessions_dir = temp_dir.path();
+        save_test_session(sessions_dir, "sub-sess", |session| {
+            session.status = SessionStatus::Running;
+        });
+
+        run_hook(
+            sessions_dir,
+            HookEvent::SubagentStart,
+            r#"{"session_id":"sub-sess","cwd":"/tmp/test","agent_id":"a1","agent_type":"Explore"}"#,
+        );
+        // No SubagentStart for a2: its first own event registers it.
+        run_hook(
+            sessions_dir,
+            HookEvent::PreToolUse,
+            r#"{"session_id":"sub-sess","cwd":"/tmp/test","agent_id":"a2","tool_name":"Read"}"#,
+        );
+        let session = run_hook(
+            sessions_dir,
+            HookEvent::SubagentStop,
+            r#"{"session_id":"sub-sess","cwd":"/tmp/test","agent_id":"a1","agent_type":"Explore"}"#,
+        );
+
+        assert_eq!(
+            session.subagents["a1"].agent_type.as_deref(),
+            Some("Explore")
+        );
+        assert!(!session.subagents["a1"].is_running());
+        assert!(session.subagents["a2"].is_running());
+
+        // The turn ends with a2 gone from the task registry.
+        let session = run_hook(
+            sessions_dir,
+            HookEvent::Stop,
+            r#"{"session_id":"sub-sess","cwd":"/tmp/test"}"#,
+        );
+        assert!(session.subagents.values().all(|s| !s.is_running()));
+
+        // The next prompt starts a new turn and drops the finished ones.
+        let session = run_hook(
+            sessions_dir,
+            HookEvent::UserPromptSubmit,
+            r#"{"session_id":"sub-sess","cwd":"/tmp/test"}"#,
+        );
+        assert!(session.subagents.is_empty());
+    }
+
+    #[test]
+    fn stop_keeps_background_subagents_running() {
+        let temp_dir = tempfile::TempDir::new().expect("temp dir");
+        let sessions_dir = temp_dir.path();
+
+        run_hook(
+            sessions_dir,
+            HookEvent::SubagentStart,
+            r#"{"session_id":"bg-sub","cwd":"/tmp/test","agent_id":"a1"}"#,
+        );
+        let session = run_hook(
+            sessions_dir,
+            HookEvent::Stop,
+            r#"{"session_id":"bg-sub","cwd":"/tmp/test","background_tasks":[{"id":"a1","type":"subagent"}]}"#,
+        );
+
+        assert!(session.subagents["a1"].is_running());
+    }
+
+    #[rstest]
+    #[case::stopped(SessionStatus::Stopped, SessionStatus::Stopped)]
+    #[case::running(SessionStatus::Running, SessionStatus::Running)]
+    #[case::paused(SessionStatus::Paused, SessionStatus::Paused)]
+    #[case::waiting_without_pending_permission(SessionStatus::WaitingInput, SessionStatus::Running)]
+    fn subagent_and_compaction_events_keep_main_loop_status(
+        #[case] initial: SessionStatus,
+        #[case] expected: SessionStatus,
+        #[values(
+            HookEvent::SubagentStart,
+            HookEvent::SubagentStop,
+            HookEvent::PreCompact
+        )]
+        event: HookEvent,
+    ) {
+        let temp_dir = tempfile::TempDir::new().expect("temp dir");
+        let sessions_dir = temp_dir.path();
+        let read_at = Utc::now();
+        save_test_session(sessions_dir, "keep-sess", |session| {
+            session.status = initial;
+            session.read_at = Some(read_at);
+        });
+
+        let session = run_hook(
+            sessions_dir,
+            event,
+            r#"{"session_id":"keep-sess","cwd":"/tmp/test","agent_id":"a1"}"#,
+        );
+
+        assert_eq!(session.status, expected);
+        // A background subagent finishing must not re-mark a read session
+        // as unread.
+        assert_eq!(session.read_at, Some(read_at));
+    }
+
+    #[test]
+    fn pre_compact_counts_compactions() {
+        let temp_dir = tempfile::TempDir::new().expect("temp dir");
+        let sessions_dir = temp_dir.path();
+        save_test_session(sessions_dir, "compact-sess", |session| {
+            session.status = SessionStatus::Running;
+        });
+
+        for _ in 0..2 {
+            run_hook(
+                sessions_dir,
+                HookEvent::PreCompact,
+                r#"{"session_id":"compact-sess","cwd":"/tmp/test","trigger":"auto"}"#,
+            );
+        }
+        let session = store::load_session_from(sessions_dir, "compact-sess")
+            .expect("load")
+            .expect("session exists");
+
+        assert_eq!(session.compaction_count, 2);
+        assert!(session.last_compacted_at.is_some());
+    }
+
     #[test]
     fn permission_request_with_agent_id_sets_waiting_input_and_records_key() {
         let temp_dir = tempfile::TempDir::new().expect("temp dir");
@@ -2127,6 +2492,8 @@ mod tests {
             tmux: false,
             notifications: false,
             auto_compact: false,
+            checkpoints: false,
+            auto_label: false,
             removed_notification_groups: Some(removed.clone()),
             tmux_sync_calls: None,
         };
@@ -2219,6 +2586,13 @@ mod tests {
             pending_permission_agent_ids: BTreeSet::new(),
             read_at: None,
             sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
         }
     }
 
@@ -2534,6 +2908,13 @@ mod tests {
                 pending_permission_agent_ids: BTreeSet::new(),
                 read_at: None,
                 sweep_signaled: false,
+                changed_files: Default::default(),
+                subagents: Default::default(),
+                compaction_count: 0,
+                last_compacted_at: None,
+                report: None,
+                tags: Default::default(),
+                note: None,
             }
         }
 
@@ -6,7 +6,9 @@ use clap::Args;
 
 use super::claude_sessions;
 use super::store;
+use super::tmux_format;
 use super::types::{Session, SessionStatus, StatusColor};
+use crate::shared::config::{self, TmuxStatusConfig};
 use crate::shared::table::{color, pad_or_truncate};
 
 /// Column widths for fixed-width columns
@@ -26,6 +28,10 @@ pub struct ListArgs {
     /// Output short status for tmux status bar
     #[arg(long)]
     pub tmux: bool,
+
+    /// Output the sessions (including tags and note) as JSON
+    #[arg(long, conflicts_with = "tmux")]
+    pub json: bool,
 }
 
 /// Runs the list command.
@@ -38,8 +44,14 @@ pub fn run(args: &ListArgs) -> Result<()> {
     let sessions = store::list_sessions()?;
 
     if args.tmux {
+        let config = config::load_config()?;
         let mut stdout = io::stdout().lock();
-        render_tmux_status(&mut stdout, &sessions)?;
+        render_tmux_status(&mut stdout, &sessions, &config.cc.tmux)?;
+        return Ok(());
+    }
+
+    if args.json {
+        println!("{}", serde_json::to_string(&sessions)?);
         return Ok(());
     }
 
@@ -67,32 +79,19 @@ fn calculate_title_width(term_width: usize) -> usize {
     }
 }
 
-/// Renders a short status string for tmux status bar.
+/// Renders `cc.tmux.list` over every session for the tmux status bar.
 ///
-/// - If any session is WaitingInput: `#[fg=yellow]◐ N` (N = count of non-running sessions)
+/// With the default template:
+/// - If any session is WaitingInput: `#[fg=yellow]◐ N` (N = count of waiting and stopped sessions)
 /// - If only Stopped sessions exist: `○ N`
 /// - If all Running or no sessions: empty string (no output)
-fn render_tmux_status<W: Write>(writer: &mut W, sessions: &[Session]) -> Result<()> {
-    let (waiting_count, stopped_count) =
-        sessions
-            .iter()
-            .fold((0, 0), |(waiting, stopped), s| match s.status {
-                SessionStatus::WaitingInput => (waiting + 1, stopped),
-                SessionStatus::Stopped => (waiting, stopped + 1),
-                _ => (waiting, stopped),
-            });
-
-    let pending_count = waiting_count + stopped_count;
-    if pending_count == 0 {
-        return Ok(());
-    }
-
-    if waiting_count > 0 {
-        write!(writer, "#[fg=yellow]\u{25d0} {pending_count}#[default]")?;
-    } else {
-        write!(writer, "\u{25cb} {pending_count}")?;
-    }
-
+fn render_tmux_status<W: Write>(
+    writer: &mut W,
+    sessions: &[Session],
+    config: &TmuxStatusConfig,
+) -> Result<()> {
+    let rendered = tmux_format::render(&config.list, sessions, &config.styles, "list")?;
+    write!(writer, "{rendered}")?;
     Ok(())
 }
 
@@ -260,6 +259,13 @@ mod tests {
             pending_permission_agent_ids: std::collections::BTreeSet::new(),
             read_at: None,
             sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
         }
     }
 
@@ -406,6 +412,13 @@ mod tests {
                 pending_permission_agent_ids: std::collections::BTreeSet::new(),
                 read_at: None,
                 sweep_signaled: false,
+                changed_files: Default::default(),
+                subagents: Default::default(),
+                compaction_count: 0,
+                last_compacted_at: None,
+                report: None,
+                tags: Default::default(),
+                note: None,
             },
             Session {
                 session_id: "s2".to_string(),
@@ -425,6 +438,13 @@ mod tests {
                 pending_permission_agent_ids: std::collections::BTreeSet::new(),
                 read_at: None,
                 sweep_signaled: false,
+                changed_files: Default::default(),
+                subagents: Default::default(),
+                compaction_count: 0,
+                last_compacted_at: None,
+                report: None,
+                tags: Default::default(),
+                note: None,
             },
             Session {
                 session_id: "s3".to_string(),
@@ -444,6 +464,13 @@ mod tests {
                 pending_permission_agent_ids: std::collections::BTreeSet::new(),
                 read_at: None,
                 sweep_signaled: false,
+                changed_files: Default::default(),
+                subagents: Default::default(),
+                compaction_count: 0,
+                last_compacted_at: None,
+                report: None,
+                tags: Default::default(),
+                note: None,
             },
         ];
 
@@ -525,7 +552,8 @@ mod tests {
     #[test]
     fn test_render_tmux_status_empty() {
         let mut output = Vec::new();
-        render_tmux_status(&mut output, &[]).expect("render should succeed");
+        render_tmux_status(&mut output, &[], &TmuxStatusConfig::default())
+            .expect("render should succeed");
         assert_eq!(String::from_utf8(output).expect("valid utf8"), "");
     }
 
@@ -542,7 +570,8 @@ mod tests {
             },
         ];
         let mut output = Vec::new();
-        render_tmux_status(&mut output, &sessions).expect("render should succeed");
+        render_tmux_status(&mut output, &sessions, &TmuxStatusConfig::default())
+            .expect("render should succeed");
         assert_eq!(String::from_utf8(output).expect("valid utf8"), "");
     }
 
@@ -563,7 +592,8 @@ mod tests {
             },
         ];
         let mut output = Vec::new();
-        render_tmux_status(&mut output, &sessions).expect("render should succeed");
+        render_tmux_status(&mut output, &sessions, &TmuxStatusConfig::default())
+            .expect("render should succeed");
         assert_eq!(
             String::from_utf8(output).expect("valid utf8"),
             "#[fg=yellow]\u{25d0} 2#[default]"
@@ -583,7 +613,8 @@ mod tests {
             },
         ];
         let mut output = Vec::new();
-        render_tmux_status(&mut output, &sessions).expect("render should succeed");
+        render_tmux_status(&mut output, &sessions, &TmuxStatusConfig::default())
+            .expect("render should succeed");
         assert_eq!(String::from_utf8(output).expect("valid utf8"), "\u{25cb} 1");
     }
 
@@ -604,7 +635,8 @@ mod tests {
             },
         ];
         let mut output = Vec::new();
-        render_tmux_status(&mut output, &sessions).expect("render should succeed");
+        render_tmux_status(&mut output, &sessions, &TmuxStatusConfig::default())
+            .expect("render should succeed");
         // WaitingInput takes priority for the symbol, count includes both
         assert_eq!(
             String::from_utf8(output).expect("valid utf8"),
@@ -714,6 +746,13 @@ mod tests {
                 pending_permission_agent_ids: std::collections::BTreeSet::new(),
                 read_at: None,
                 sweep_signaled: false,
+                changed_files: Default::default(),
+                subagents: Default::default(),
+                compaction_count: 0,
+                last_compacted_at: None,
+                report: None,
+                tags: Default::default(),
+                note: None,
             },
             Session {
                 session_id: "s2".to_string(),
@@ -738,6 +777,13 @@ mod tests {
                 pending_permission_agent_ids: std::collections::BTreeSet::new(),
                 read_at: None,
                 sweep_signaled: false,
+                changed_files: Default::default(),
+                subagents: Default::default(),
+                compaction_count: 0,
+                last_compacted_at: None,
+                report: None,
+                tags: Default::default(),
+                note: None,
             },
             Session {
                 session_id: "s3".to_string(),
@@ -757,6 +803,13 @@ mod tests {
                 pending_permission_agent_ids: std::collections::BTreeSet::new(),
                 read_at: None,
                 sweep_signaled: false,
+                changed_files: Default::default(),
+                subagents: Default::default(),
+                compaction_count: 0,
+                last_compacted_at: None,
+                report: None,
+                tags: Default::default(),
+                note: None,
             },
         ];
 
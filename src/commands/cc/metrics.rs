@@ -0,0 +1,448 @@
+//! `a cc metrics` -- session state in the Prometheus text exposition format.
+//!
+//! One-shot by default: print to stdout, or with `--textfile` replace a
+//! `.prom` file for node_exporter's textfile collector (run it from cron or
+//! a timer). With `--http`, serve the same text on `/metrics` instead,
+//! collected fresh on every scrape.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs::{self, File};
+use std::io::Write;
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use anyhow::{Context, Result};
+use clap::Args;
+
+use super::claude_sessions;
+use super::serve::http::{is_loopback_host, parse_listen_addr, read_request, respond};
+use super::store;
+use super::sweep::stats::{self, SweepStats};
+use super::types::{Session, SessionStatus};
+use crate::commands::wm::worktree::{get_main_worktree_path, list_linked_worktrees};
+use crate::infra::git::GitRepo;
+
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+#[derive(Args, Clone, PartialEq, Eq)]
+pub struct MetricsArgs {
+    /// Write the metrics to this file (atomically) instead of stdout, for
+    /// node_exporter's textfile collector
+    #[arg(long, value_name = "PATH", conflicts_with = "http")]
+    pub textfile: Option<PathBuf>,
+
+    /// Serve the metrics on `/metrics` at this address (`PORT` or
+    /// `HOST:PORT`; a bare port binds to 127.0.0.1)
+    #[arg(long, value_name = "ADDR")]
+    pub http: Option<String>,
+
+    /// Allow `--http` to bind a non-loopback address. The endpoint has no
+    /// authentication.
+    #[arg(long, requires = "http")]
+    pub allow_remote: bool,
+}
+
+pub fn run(args: &MetricsArgs) -> Result<()> {
+    if let Some(addr) = &args.http {
+        let addr = parse_listen_addr(addr, args.allow_remote)?;
+        let listener = TcpListener::bind(addr).with_context(|| format!("failed to bind {addr}"))?;
+        eprintln!("Serving metrics on http://{addr}/metrics");
+        return serve(listener, addr.ip().is_loopback());
+    }
+
+    let text = collect()?.render();
+    match &args.textfile {
+        Some(path) => write_textfile(path, &text),
+        None => {
+            print!("{text}");
+            Ok(())
+        }
+    }
+}
+
+/// Everything the exposition is rendered from, gathered up front so
+/// `render` stays a pure function.
+#[derive(Debug, Default)]
+struct Snapshot {
+    sessions: Vec<Session>,
+    /// Context size of each non-ended session's latest turn, by session id.
+    context_tokens: BTreeMap<String, u64>,
+    sweep: SweepStats,
+    /// Worktree count (main + linked) of every repository that hosts a
+    /// non-ended session, keyed by the main worktree's path.
+    worktrees: BTreeMap<PathBuf, usize>,
+}
+
+fn collect() -> Result<Snapshot> {
+    let sessions = store::list_all_sessions()?;
+    let live = || sessions.iter().filter(|s| s.status != SessionStatus::Ended);
+
+    let context_tokens = live()
+        .filter_map(|s| {
+            let tokens = claude_sessions::get_last_context_tokens(&s.cwd, &s.session_id)?;
+            Some((s.session_id.clone(), tokens))
+        })
+        .collect();
+
+    let mut worktrees = BTreeMap::new();
+    for cwd in live().map(|s| s.cwd.as_path()) {
+        if let Some((main, count)) = count_worktrees(cwd)
+            && !worktrees.contains_key(&main)
+        {
+            worktrees.insert(main, count);
+        }
+    }
+
+    let sweep = stats::stats_path()
+        .map(|path| stats::load_from(&path))
+        .unwrap_or_default();
+
+    Ok(Snapshot {
+        sessions,
+        context_tokens,
+        sweep,
+        worktrees,
+    })
+}
+
+fn count_worktrees(cwd: &Path) -> Option<(PathBuf, usize)> {
+    let repo = GitRepo::open_at(cwd).ok()?;
+    let main = get_main_worktree_path(&repo).ok()?;
+    let linked = list_linked_worktrees(&repo).ok()?;
+    Some((main, linked.len() + 1))
+}
+
+impl Snapshot {
+    fn render(&self) -> String {
+        let mut out = String::new();
+
+        header(
+            &mut out,
+            "armyknife_cc_sessions",
+            "gauge",
+            "Claude Code sessions by status.",
+        );
+        for status in [
+            SessionStatus::Running,
+            SessionStatus::WaitingInput,
+            SessionStatus::Stopped,
+            SessionStatus::Paused,
+            SessionStatus::Ended,
+        ] {
+            let count = self.sessions.iter().filter(|s| s.status == status).count();
+            let _ = writeln!(
+                out,
+                "armyknife_cc_sessions{{status=\"{}\"}} {count}",
+                status_label(status)
+            );
+        }
+
+        header(
+            &mut out,
+            "armyknife_cc_unread_stopped_sessions",
+            "gauge",
+            "Stopped sessions not focused since they stopped.",
+        );
+        let unread = self
+            .sessions
+            .iter()
+            .filter(|s| s.is_unread_stopped())
+            .count();
+        let _ = writeln!(out, "armyknife_cc_unread_stopped_sessions {unread}");
+
+        header(
+            &mut out,
+            "armyknife_cc_pending_permission_requests",
+            "gauge",
+            "Agents blocked on a permission prompt, across all sessions.",
+        );
+        let pending: usize = self.sessions.iter().map(pending_permission_requests).sum();
+        let _ = writeln!(out, "armyknife_cc_pending_permission_requests {pending}");
+
+        header(
+            &mut out,
+            "armyknife_cc_session_context_tokens",
+            "gauge",
+            "Context tokens of each live session's latest turn.",
+        );
+        for session in &self.sessions {
+            let Some(tokens) = self.context_tokens.get(&session.session_id) else {
+                continue;
+            };
+            let _ = writeln!(
+                out,
+                "armyknife_cc_session_context_tokens{{session_id=\"{}\",cwd=\"{}\"}} {tokens}",
+                escape_label(&session.session_id),
+                escape_label(&session.cwd.display().to_string()),
+            );
+        }
+
+        for (name, help, value) in [
+            (
+                "armyknife_cc_sweep_runs_total",
+                "Completed sweep passes.",
+                self.sweep.runs,
+            ),
+            (
+                "armyknife_cc_sweep_paused_total",
+                "Sessions paused by sweep.",
+                self.sweep.paused,
+            ),
+            (
+                "armyknife_cc_sweep_signaled_total",
+                "SIGTERMs sent by sweep.",
+                self.sweep.signaled,
+            ),
+        ] {
+            header(&mut out, name, "counter", help);
+            let _ = writeln!(out, "{name} {value}");
+        }
+
+        header(
+            &mut out,
+            "armyknife_cc_worktrees",
+            "gauge",
+            "Worktrees (main + linked) of each repository hosting a live session.",
+        );
+        for (repo, count) in &self.worktrees {
+            let _ = writeln!(
+                out,
+                "armyknife_cc_worktrees{{repo=\"{}\"}} {count}",
+                escape_label(&repo.display().to_string())
+            );
+        }
+
+        out
+    }
+}
+
+/// Agents of `session` blocked on a permission prompt. A main-thread prompt
+/// seen only as `Notification(permission_prompt)` leaves the session
+/// `WaitingInput` without a pending key, and counts as one.
+fn pending_permission_requests(session: &Session) -> usize {
+    match session.pending_permission_agent_ids.len() {
+        0 if session.status == SessionStatus::WaitingInput => 1,
+        pending => pending,
+    }
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+fn status_label(status: SessionStatus) -> &'static str {
+    match status {
+        SessionStatus::Running => "running",
+        SessionStatus::WaitingInput => "waiting_input",
+        SessionStatus::Stopped => "stopped",
+        SessionStatus::Paused => "paused",
+        SessionStatus::Ended => "ended",
+    }
+}
+
+/// Escapes a label value per the exposition format: backslash, double
+/// quote, and newline.
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', r"\\")
+        .replace('"', "\\\"")
+        .replace('\n', r"\n")
+}
+
+/// Replaces `path` via a temp file in the same directory, so the textfile
+/// collector never reads a half-written file.
+fn write_textfile(path: &Path, text: &str) -> Result<()> {
+    let file_name = path
+        .file_name()
+        .with_context(|| format!("invalid --textfile path '{}'", path.display()))?;
+    let mut temp_name = file_name.to_os_string();
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+
+    let mut temp_file = File::create(&temp_path)
+        .with_context(|| format!("failed to write {}", temp_path.display()))?;
+    temp_file.write_all(text.as_bytes())?;
+    temp_file.sync_all()?;
+    fs::rename(&temp_path, path)?;
+    Ok(())
+}
+
+fn serve(listener: TcpListener, loopback: bool) -> Result<()> {
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        thread::spawn(move || {
+            if let Err(e) = handle_connection(stream, loopback) {
+                tracing::debug!(event = "cc.metrics.http_error", error = %e);
+            }
+        });
+    }
+    Ok(())
+}
+
+fn handle_connection(stream: TcpStream, loopback: bool) -> Result<()> {
+    let request = read_request(&stream)?;
+    let mut writer = stream;
+    // Same DNS-rebinding guard as the `a cc serve` dashboard.
+    if loopback && !request.host.as_deref().is_some_and(is_loopback_host) {
+        return respond(&mut writer, "403 Forbidden", "text/plain", b"forbidden\n");
+    }
+    if request.method != "GET" {
+        return respond(
+            &mut writer,
+            "405 Method Not Allowed",
+            "text/plain",
+            b"metrics are read-only\n",
+        );
+    }
+    match request.path.as_str() {
+        "/metrics" => match collect() {
+            Ok(snapshot) => respond(
+                &mut writer,
+                "200 OK",
+                CONTENT_TYPE,
+                snapshot.render().as_bytes(),
+            ),
+            Err(e) => respond(
+                &mut writer,
+                "500 Internal Server Error",
+                "text/plain",
+                format!("{e}\n").as_bytes(),
+            ),
+        },
+        _ => respond(&mut writer, "404 Not Found", "text/plain", b"not found\n"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use indoc::indoc;
+    use rstest::rstest;
+    use std::collections::BTreeSet;
+
+    fn session(id: &str, status: SessionStatus) -> Session {
+        Session {
+            session_id: id.to_string(),
+            cwd: PathBuf::from(format!("/work/{id}")),
+            transcript_path: None,
+            tty: None,
+            tmux_info: None,
+            status,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_message: None,
+            current_tool: None,
+            label: None,
+            ancestor_session_ids: Vec::new(),
+            pending_bg_task_ids: BTreeSet::new(),
+            pending_agent_task_ids: BTreeSet::new(),
+            pending_permission_agent_ids: BTreeSet::new(),
+            read_at: None,
+            sweep_signaled: false,
+            report: None,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            tags: Default::default(),
+            note: None,
+        }
+    }
+
+    #[test]
+    fn render_reports_every_metric() {
+        let mut waiting = session("w", SessionStatus::WaitingInput);
+        waiting.pending_permission_agent_ids =
+            BTreeSet::from(["main".to_string(), "agent-1".to_string()]);
+        let mut read = session("r", SessionStatus::Stopped);
+        read.read_at = Some(Utc::now());
+        let snapshot = Snapshot {
+            sessions: vec![
+                session("a", SessionStatus::Running),
+                waiting,
+                session("u", SessionStatus::Stopped),
+                read,
+                session("e", SessionStatus::Ended),
+            ],
+            context_tokens: BTreeMap::from([("a".to_string(), 12345)]),
+            sweep: SweepStats {
+                runs: 10,
+                paused: 3,
+                signaled: 4,
+            },
+            worktrees: BTreeMap::from([(PathBuf::from("/work/repo"), 3)]),
+        };
+
+        assert_eq!(
+            snapshot.render(),
+            indoc! {r#"
+                # HELP armyknife_cc_sessions Claude Code sessions by status.
+                # TYPE armyknife_cc_sessions gauge
+                armyknife_cc_sessions{status="running"} 1
+                armyknife_cc_sessions{status="waiting_input"} 1
+                armyknife_cc_sessions{status="stopped"} 2
+                armyknife_cc_sessions{status="paused"} 0
+                armyknife_cc_sessions{status="ended"} 1
+                # HELP armyknife_cc_unread_stopped_sessions Stopped sessions not focused since they stopped.
+                # TYPE armyknife_cc_unread_stopped_sessions gauge
+                armyknife_cc_unread_stopped_sessions 1
+                # HELP armyknife_cc_pending_permission_requests Agents blocked on a permission prompt, across all sessions.
+                # TYPE armyknife_cc_pending_permission_requests gauge
+                armyknife_cc_pending_permission_requests 2
+                # HELP armyknife_cc_session_context_tokens Context tokens of each live session's latest turn.
+                # TYPE armyknife_cc_session_context_tokens gauge
+                armyknife_cc_session_context_tokens{session_id="a",cwd="/work/a"} 12345
+                # HELP armyknife_cc_sweep_runs_total Completed sweep passes.
+                # TYPE armyknife_cc_sweep_runs_total counter
+                armyknife_cc_sweep_runs_total 10
+                # HELP armyknife_cc_sweep_paused_total Sessions paused by sweep.
+                # TYPE armyknife_cc_sweep_paused_total counter
+                armyknife_cc_sweep_paused_total 3
+                # HELP armyknife_cc_sweep_signaled_total SIGTERMs sent by sweep.
+                # TYPE armyknife_cc_sweep_signaled_total counter
+                armyknife_cc_sweep_signaled_total 4
+                # HELP armyknife_cc_worktrees Worktrees (main + linked) of each repository hosting a live session.
+                # TYPE armyknife_cc_worktrees gauge
+                armyknife_cc_worktrees{repo="/work/repo"} 3
+            "#}
+        );
+    }
+
+    #[rstest]
+    #[case::main_agent_prompt(SessionStatus::WaitingInput, &[], 1)]
+    #[case::main_and_subagent_prompts(SessionStatus::WaitingInput, &["__main__", "agent-1"], 2)]
+    #[case::subagent_prompt_while_running(SessionStatus::Running, &["agent-1"], 1)]
+    #[case::no_prompt(SessionStatus::Stopped, &[], 0)]
+    fn test_pending_permission_requests(
+        #[case] status: SessionStatus,
+        #[case] pending: &[&str],
+        #[case] expected: usize,
+    ) {
+        let mut s = session("s", status);
+        s.pending_permission_agent_ids = pending.iter().map(|id| id.to_string()).collect();
+        assert_eq!(pending_permission_requests(&s), expected);
+    }
+
+    #[test]
+    fn escape_label_escapes_quotes_backslashes_and_newlines() {
+        assert_eq!(escape_label("a\"b\\c\nd"), r#"a\"b\\c\nd"#);
+    }
+
+    #[test]
+    fn write_textfile_replaces_existing_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("armyknife.prom");
+        fs::write(&path, "old\n").unwrap();
+
+        write_textfile(&path, "new\n").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new\n");
+        assert!(!dir.path().join("armyknife.prom.tmp").exists());
+    }
+}
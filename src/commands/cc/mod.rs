@@ -1,24 +1,37 @@
+mod annotate;
 mod auto_compact;
+mod auto_label;
 pub(crate) mod auto_pause;
+pub(crate) mod changes;
+mod checkpoint;
 mod claude_messaging;
 mod claude_registry;
 mod claude_sessions;
 mod clean_detached;
 mod error;
 mod focus;
+mod fork;
 mod generate_title_detached;
 mod hook;
 mod list;
 mod mark_read;
+mod metrics;
 pub(crate) mod new;
 pub(crate) mod pane;
 pub(crate) mod peer;
+mod pr_cache;
 mod resume;
 mod resurrect;
+mod serve;
 mod signal;
+mod snapshot;
+mod statusline;
 pub(crate) mod store;
 mod sweep;
+mod template;
+mod tmux_format;
 pub(crate) mod tmux_sync;
+pub(crate) mod tree;
 mod tui;
 pub(crate) mod types;
 mod watch;
@@ -26,19 +39,29 @@ mod window_status;
 
 use clap::Subcommand;
 
+pub use annotate::{NoteArgs, TagArgs};
 pub use auto_compact::AutoCompactArgs;
+pub use changes::ChangesArgs;
+pub use checkpoint::{CheckpointDetachedArgs, CheckpointsArgs, RollbackArgs};
 pub use clean_detached::CleanDetachedArgs;
 pub use focus::FocusArgs;
+pub use fork::ForkArgs;
 pub use generate_title_detached::GenerateTitleDetachedArgs;
 pub use hook::HookArgs;
 pub use list::ListArgs;
 pub use mark_read::MarkReadArgs;
+pub use metrics::MetricsArgs;
 pub use new::NewArgs;
 pub use pane::status::HasPausedArgs;
 pub use peer::PeerCommands;
 pub use resume::ResumeArgs;
 pub use resurrect::ResurrectCommands;
+pub use serve::ServeArgs;
+pub use snapshot::SnapshotCommands;
+pub use statusline::StatuslineArgs;
+pub use store::StoreCommands;
 pub use sweep::SweepArgs;
+pub use tree::TreeArgs;
 pub use watch::WatchArgs;
 pub use window_status::WindowStatusArgs;
 
@@ -60,6 +83,16 @@ pub enum CcCommands {
     /// Focus on a Claude Code session's tmux pane
     Focus(FocusArgs),
 
+    /// Fork a session's side task into a new worktree session, with a
+    /// summary of the source conversation as its prompt
+    Fork(ForkArgs),
+
+    /// Add, remove, or print a session's tags
+    Tag(TagArgs),
+
+    /// Set, clear, or print a session's note
+    Note(NoteArgs),
+
     /// Mark the pane's Claude Code session as read (wire from tmux pane-focus-in)
     #[command(name = "mark-read")]
     MarkRead(MarkReadArgs),
@@ -72,10 +105,42 @@ pub enum CcCommands {
     #[command(subcommand)]
     Resurrect(ResurrectCommands),
 
+    /// Save/restore worktree windows' layouts and Claude Code sessions,
+    /// without tmux-resurrect
+    #[command(subcommand)]
+    Snapshot(SnapshotCommands),
+
     /// Resolve SendMessage target names for related Claude Code sessions
     #[command(subcommand)]
     Peer(PeerCommands),
 
+    /// List the files a session changed, or files changed by several sessions
+    Changes(ChangesArgs),
+
+    /// List the git checkpoints taken of a session's worktree on each Stop
+    Checkpoints(CheckpointsArgs),
+
+    /// Restore a session's worktree to one of its checkpoints
+    Rollback(RollbackArgs),
+
+    /// Render the delegation tree of Claude Code sessions (text, DOT,
+    /// Mermaid, or JSON)
+    Tree(TreeArgs),
+
+    /// Serve session state from an in-memory index over a Unix socket
+    Serve(ServeArgs),
+
+    /// Rebuild or query the on-disk session index
+    #[command(subcommand)]
+    Store(StoreCommands),
+
+    /// Export session metrics in the Prometheus text format (stdout, a
+    /// textfile-collector file, or an HTTP `/metrics` endpoint)
+    Metrics(MetricsArgs),
+
+    /// Render Claude Code's status line from its statusLine JSON on stdin
+    Statusline(StatuslineArgs),
+
     /// Pause long-stopped sessions by sending SIGTERM (run periodically)
     Sweep(SweepArgs),
 
@@ -99,6 +164,10 @@ pub enum CcCommands {
     /// Internal: non-interactive title generation for `cc watch`'s Ctrl+g.
     #[command(name = "generate-title-detached", hide = true)]
     GenerateTitleDetached(GenerateTitleDetachedArgs),
+
+    /// Internal: takes the Stop hook's worktree checkpoint in the background.
+    #[command(name = "checkpoint-detached", hide = true)]
+    CheckpointDetached(CheckpointDetachedArgs),
 }
 
 impl CcCommands {
@@ -109,16 +178,29 @@ impl CcCommands {
             Self::List(args) => list::run(args)?,
             Self::Watch(args) => watch::run(args)?,
             Self::Focus(args) => focus::run(args)?,
+            Self::Fork(args) => fork::run(args)?,
+            Self::Tag(args) => annotate::run_tag(args)?,
+            Self::Note(args) => annotate::run_note(args)?,
             Self::MarkRead(args) => mark_read::run(args)?,
             Self::Resume(args) => resume::run(args)?,
             Self::Resurrect(cmd) => resurrect::run(cmd)?,
+            Self::Snapshot(cmd) => snapshot::run(cmd)?,
             Self::Peer(cmd) => peer::run(cmd)?,
-            Self::Sweep(args) => sweep::run(args)?,
+            Self::Changes(args) => changes::run(args)?,
+            Self::Checkpoints(args) => checkpoint::run_list(args)?,
+            Self::Rollback(args) => checkpoint::run_rollback(args)?,
+            Self::Tree(args) => tree::run(args).await?,
+            Self::Serve(args) => serve::run(args)?,
+            Self::Store(cmd) => store::run(cmd)?,
+            Self::Metrics(args) => metrics::run(args)?,
+            Self::Statusline(args) => statusline::run(args).await?,
+            Self::Sweep(args) => sweep::run(args).await?,
             Self::AutoCompact(args) => auto_compact::run(args).await?,
             Self::WindowStatus(args) => window_status::run(args)?,
             Self::PaneHasPaused(args) => pane::status::run(args)?,
             Self::CleanDetached(args) => clean_detached::run(args).await?,
             Self::GenerateTitleDetached(args) => generate_title_detached::run(args)?,
+            Self::CheckpointDetached(args) => checkpoint::run_detached(args)?,
         }
         Ok(())
     }
@@ -13,11 +13,13 @@ use crate::shared::hooks;
 
 mod delegation;
 mod prompt;
+mod template;
 mod tmux;
 mod worktree;
 
 use delegation::{build_ancestor_chain, resolve_prompt};
 use prompt::{delete_prompt_cache, resolve_args, save_prompt_cache};
+use template::parse_template_var;
 use tmux::{TmuxSplitPaneSpec, TmuxWindowSpec, setup_split_pane, setup_tmux_window};
 use worktree::{
     BranchRollback, WorktreeAddMode, add_worktree_for_branch, git_worktree_add, repo_branch_exists,
@@ -58,6 +60,22 @@ pub struct CommonNewArgs {
     /// When specified, operates on the given repository instead of the current directory.
     #[arg(short = 'R', long)]
     pub repo: Option<PathBuf>,
+
+    /// Start from a named session template (`cc.templates` in the config or
+    /// ~/.config/armyknife/templates/<name>.md). Explicit --model, --label,
+    /// and --from override the template's values; --prompt is appended to
+    /// its prompt.
+    #[arg(long)]
+    pub template: Option<String>,
+
+    /// Template variable as NAME=VALUE (repeatable; requires --template)
+    #[arg(
+        long = "var",
+        value_name = "NAME=VALUE",
+        value_parser = parse_template_var,
+        requires = "template"
+    )]
+    pub vars: Vec<(String, String)>,
 }
 
 #[derive(Args, Clone, PartialEq, Eq)]
@@ -96,7 +114,8 @@ pub fn run(args: &NewArgs) -> Result<()> {
 }
 
 fn run_inner(args: &NewArgs) -> Result<()> {
-    let config = load_config()?;
+    let mut config = load_config()?;
+    let args = &template::apply(args, &mut config)?;
 
     let repo_root = match &args.common.repo {
         Some(path) => get_repo_root_in(path)?,
@@ -491,6 +510,7 @@ mod tests {
     #[case::from_without_worktree(&["a", "--from", "origin/master"])]
     #[case::force_without_worktree(&["a", "--force"])]
     #[case::skip_hooks_without_worktree(&["a", "--skip-hooks"])]
+    #[case::var_without_template(&["a", "--var", "pr=1"])]
     fn rejects_missing_or_misplaced_flags(#[case] argv: &[&str]) {
         assert!(TestCli::try_parse_from(argv).is_err());
     }
@@ -2,7 +2,8 @@ use anyhow::{Context, Result, bail};
 use std::path::{Path, PathBuf};
 
 use crate::commands::cc::error::CcError;
-use crate::commands::name_branch::{detect_backend, generate_branch_name};
+use crate::commands::name_branch::generate_branch_name;
+use crate::infra::llm::{self, Backend, LlmTask};
 use crate::shared::cache;
 use crate::shared::command;
 
@@ -97,7 +98,7 @@ pub(super) fn resolve_args(worktree: Option<&str>, prompt: Option<&str>) -> Resu
     resolve_args_with_deps(
         worktree,
         prompt,
-        || detect_backend(),
+        || llm::backend_for(LlmTask::BranchName),
         open_editor_for_prompt,
     )
 }
@@ -110,7 +111,7 @@ fn resolve_args_with_deps<F, E>(
     editor_fn: E,
 ) -> Result<ResolvedArgs>
 where
-    F: FnOnce() -> Box<dyn crate::commands::name_branch::Backend>,
+    F: FnOnce() -> Box<dyn Backend>,
     E: FnOnce() -> Result<Option<String>>,
 {
     match (worktree, prompt) {
@@ -142,7 +143,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::commands::name_branch::{Backend, Result as NameBranchResult};
+    use crate::commands::name_branch::Result as NameBranchResult;
     use rstest::rstest;
     use tempfile::TempDir;
 
@@ -0,0 +1,418 @@
+//! Named session templates for `a cc new --template <name>`.
+//!
+//! A template is either an entry under `cc.templates` in the config or a
+//! `~/.config/armyknife/templates/<name>.md` file whose YAML frontmatter
+//! carries the same fields and whose body is the prompt. Applying one
+//! fills in the `a cc new` args the caller left unset, so the rest of the
+//! flow (branch naming, `--agent` wrapping, prompt cache recovery) treats
+//! the expanded prompt exactly like a `--prompt` value.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use super::NewArgs;
+use crate::commands::cc::error::CcError;
+use crate::shared::config::{Config, SessionTemplate};
+use crate::shared::yaml_frontmatter::split_frontmatter;
+
+/// Parses a `--var name=value` argument.
+pub(super) fn parse_template_var(raw: &str) -> std::result::Result<(String, String), String> {
+    match raw.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), value.to_string()))
+        }
+        _ => Err(format!("expected NAME=VALUE, got '{raw}'")),
+    }
+}
+
+/// Returns `args` with the template named by `--template` (if any) applied:
+/// its expanded prompt is prepended to `--prompt`, and `model` / `label` /
+/// `base` fill in `--model` / `--label` / `--from` when those are unset.
+/// A template `layout` replaces `config.wm.layout` for this invocation.
+pub(super) fn apply(args: &NewArgs, config: &mut Config) -> Result<NewArgs> {
+    let mut args = args.clone();
+    let Some(name) = args.common.template.clone() else {
+        return Ok(args);
+    };
+
+    let template = load_template(templates_dir().as_deref(), &name, config)?;
+    let vars = resolve_vars(&name, &template, &args.common.vars)?;
+    let expand = |text: &str| expand_placeholders(&name, text, &vars);
+
+    let template_prompt = expand(&template.prompt)?;
+    args.common.prompt = match (template_prompt.trim(), args.common.prompt.take()) {
+        ("", extra) => extra,
+        (preamble, None) => Some(preamble.to_string()),
+        (preamble, Some(extra)) => Some(format!("{preamble}\n\n{extra}")),
+    };
+    if args.common.model.is_none() {
+        args.common.model = template.model;
+    }
+    if args.common.label.is_none() {
+        args.common.label = template.label.as_deref().map(expand).transpose()?;
+    }
+    // `--from` only exists in worktree mode; a session split into the
+    // current pane has no branch to base anything on.
+    if args.worktree.is_some() && args.from.is_none() {
+        args.from = template.base.as_deref().map(expand).transpose()?;
+    }
+    if let Some(layout) = template.layout {
+        config.wm.layout = layout;
+    }
+    Ok(args)
+}
+
+fn templates_dir() -> Option<PathBuf> {
+    crate::shared::dirs::config_dir().map(|dir| dir.join("armyknife").join("templates"))
+}
+
+/// Looks up `name`, preferring `<templates_dir>/<name>.md` over the
+/// `cc.templates` entry in `config`.
+fn load_template(
+    templates_dir: Option<&Path>,
+    name: &str,
+    config: &mut Config,
+) -> Result<SessionTemplate> {
+    // Template names become file names; reject anything that could escape
+    // the templates directory.
+    let is_plain_name = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !is_plain_name {
+        return Err(CcError::UnknownTemplate(name.to_string()).into());
+    }
+
+    if let Some(dir) = templates_dir {
+        let path = dir.join(format!("{name}.md"));
+        match std::fs::read_to_string(&path) {
+            Ok(content) => {
+                return parse_template_file(&content)
+                    .with_context(|| format!("Invalid template file {}", path.display()));
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read {}", path.display()));
+            }
+        }
+    }
+
+    config
+        .cc
+        .templates
+        .remove(name)
+        .ok_or_else(|| CcError::UnknownTemplate(name.to_string()).into())
+}
+
+/// Parses a template file: YAML frontmatter with the `SessionTemplate`
+/// fields, followed by the prompt as the Markdown body.
+fn parse_template_file(content: &str) -> Result<SessionTemplate> {
+    let (mut template, body) = match split_frontmatter(content) {
+        Some((_, yaml, body_offset)) => {
+            let template = if yaml.trim().is_empty() {
+                SessionTemplate::default()
+            } else {
+                serde_yaml::from_str(yaml)?
+            };
+            (template, &content[body_offset..])
+        }
+        None => (SessionTemplate::default(), content),
+    };
+    let body = body.trim();
+    if !body.is_empty() {
+        template.prompt = body.to_string();
+    }
+    Ok(template)
+}
+
+/// Merges `--var` values with the template's declared defaults. Rejects
+/// variables the template does not declare (most likely typos) and
+/// declared variables left without a value.
+fn resolve_vars(
+    name: &str,
+    template: &SessionTemplate,
+    given: &[(String, String)],
+) -> Result<HashMap<String, String>> {
+    let invalid = |message: String| CcError::InvalidTemplateVars {
+        template: name.to_string(),
+        message,
+    };
+
+    let mut vars = HashMap::new();
+    for (key, value) in given {
+        if !template.variables.contains_key(key) {
+            let mut accepted: Vec<&str> = template.variables.keys().map(String::as_str).collect();
+            accepted.sort_unstable();
+            return Err(invalid(format!(
+                "unknown variable '{key}' (accepted: {})",
+                if accepted.is_empty() {
+                    "none".to_string()
+                } else {
+                    accepted.join(", ")
+                }
+            ))
+            .into());
+        }
+        vars.insert(key.clone(), value.clone());
+    }
+
+    let mut missing = Vec::new();
+    for (key, var) in &template.variables {
+        if vars.contains_key(key) {
+            continue;
+        }
+        match (&var.default, &var.description) {
+            (Some(default), _) => {
+                vars.insert(key.clone(), default.clone());
+            }
+            (None, Some(description)) => missing.push(format!("{key} ({description})")),
+            (None, None) => missing.push(key.clone()),
+        }
+    }
+    if !missing.is_empty() {
+        missing.sort_unstable();
+        return Err(invalid(format!(
+            "missing required variable(s): {}; pass them with --var NAME=VALUE",
+            missing.join(", ")
+        ))
+        .into());
+    }
+    Ok(vars)
+}
+
+/// Replaces every `{{name}}` (surrounding whitespace allowed) in `text`.
+/// A placeholder for an undeclared variable is an error rather than being
+/// left in place, so a typo in the template can't reach the prompt.
+fn expand_placeholders(name: &str, text: &str, vars: &HashMap<String, String>) -> Result<String> {
+    let re = lazy_regex::regex!(r"\{\{\s*([A-Za-z0-9_-]+)\s*\}\}");
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for caps in re.captures_iter(text) {
+        let (Some(whole), Some(key)) = (caps.get(0), caps.get(1)) else {
+            continue;
+        };
+        let value = vars
+            .get(key.as_str())
+            .ok_or_else(|| CcError::InvalidTemplateVars {
+                template: name.to_string(),
+                message: format!(
+                    "placeholder '{{{{{}}}}}' refers to an undeclared variable",
+                    key.as_str()
+                ),
+            })?;
+        out.push_str(&text[last..whole.start()]);
+        out.push_str(value);
+        last = whole.end();
+    }
+    out.push_str(&text[last..]);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::config::TemplateVariable;
+    use clap::Parser;
+    use indoc::indoc;
+    use rstest::rstest;
+    use tempfile::TempDir;
+
+    #[derive(Parser)]
+    struct TestCli {
+        #[command(flatten)]
+        args: NewArgs,
+    }
+
+    fn review_template() -> SessionTemplate {
+        SessionTemplate {
+            prompt: "Review PR #{{pr}} in {{ repo }}.".to_string(),
+            model: Some("opus".to_string()),
+            label: Some("review #{{pr}}".to_string()),
+            base: Some("origin/{{branch}}".to_string()),
+            variables: HashMap::from([
+                (
+                    "pr".to_string(),
+                    TemplateVariable {
+                        description: Some("PR number".to_string()),
+                        default: None,
+                    },
+                ),
+                (
+                    "repo".to_string(),
+                    TemplateVariable {
+                        description: None,
+                        default: Some("armyknife".to_string()),
+                    },
+                ),
+                (
+                    "branch".to_string(),
+                    TemplateVariable {
+                        description: None,
+                        default: Some("main".to_string()),
+                    },
+                ),
+            ]),
+            ..Default::default()
+        }
+    }
+
+    fn config_with_review_template() -> Config {
+        let mut config = Config::default();
+        config
+            .cc
+            .templates
+            .insert("review-pr".to_string(), review_template());
+        config
+    }
+
+    #[rstest]
+    #[case::simple("pr=123", Some(("pr", "123")))]
+    #[case::value_with_equals("q=a=b", Some(("q", "a=b")))]
+    #[case::empty_value("pr=", Some(("pr", "")))]
+    #[case::missing_equals("pr", None)]
+    #[case::empty_name("=1", None)]
+    fn parse_template_var_cases(#[case] raw: &str, #[case] expected: Option<(&str, &str)>) {
+        let parsed = parse_template_var(raw).ok();
+
+        assert_eq!(
+            parsed.as_ref().map(|(k, v)| (k.as_str(), v.as_str())),
+            expected
+        );
+    }
+
+    #[test]
+    fn apply_fills_unset_args_from_template() {
+        let cli = TestCli::try_parse_from([
+            "a",
+            "--worktree",
+            "--template",
+            "review-pr",
+            "--var",
+            "pr=123",
+            "--prompt",
+            "Focus on error handling.",
+        ])
+        .unwrap();
+        let mut config = config_with_review_template();
+
+        let args = apply(&cli.args, &mut config).unwrap();
+
+        assert_eq!(
+            (
+                args.common.prompt.as_deref(),
+                args.common.model.as_deref(),
+                args.common.label.as_deref(),
+                args.from.as_deref(),
+            ),
+            (
+                Some("Review PR #123 in armyknife.\n\nFocus on error handling."),
+                Some("opus"),
+                Some("review #123"),
+                Some("origin/main"),
+            )
+        );
+    }
+
+    #[test]
+    fn apply_keeps_explicit_args_and_skips_base_without_worktree() {
+        let cli = TestCli::try_parse_from([
+            "a",
+            "--template",
+            "review-pr",
+            "--var",
+            "pr=1",
+            "--model",
+            "sonnet",
+            "--label",
+            "mine",
+        ])
+        .unwrap();
+        let mut config = config_with_review_template();
+
+        let args = apply(&cli.args, &mut config).unwrap();
+
+        assert_eq!(
+            (
+                args.common.model.as_deref(),
+                args.common.label.as_deref(),
+                args.from.as_deref(),
+            ),
+            (Some("sonnet"), Some("mine"), None)
+        );
+    }
+
+    #[rstest]
+    #[case::missing_required(
+        &[],
+        "missing required variable(s): pr (PR number); pass them with --var NAME=VALUE"
+    )]
+    #[case::unknown_variable(&[("pr", "1"), ("typo", "x")], "unknown variable 'typo' (accepted: branch, pr, repo)")]
+    fn resolve_vars_rejects(#[case] given: &[(&str, &str)], #[case] expected_message: &str) {
+        let given: Vec<(String, String)> = given
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect();
+
+        let err = resolve_vars("review-pr", &review_template(), &given).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            format!("Session template 'review-pr': {expected_message}")
+        );
+    }
+
+    #[test]
+    fn expand_placeholders_rejects_undeclared_variable() {
+        let err = expand_placeholders("t", "hello {{who}}", &HashMap::new()).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Session template 't': placeholder '{{who}}' refers to an undeclared variable"
+        );
+    }
+
+    #[test]
+    fn template_file_takes_precedence_over_config() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("review-pr.md"),
+            indoc! {"
+                ---
+                model: haiku
+                variables:
+                  pr: {}
+                ---
+
+                Review PR #{{pr}}.
+            "},
+        )
+        .unwrap();
+        let mut config = config_with_review_template();
+
+        let template = load_template(Some(dir.path()), "review-pr", &mut config).unwrap();
+
+        assert_eq!(
+            (template.prompt.as_str(), template.model.as_deref()),
+            ("Review PR #{{pr}}.", Some("haiku"))
+        );
+    }
+
+    #[rstest]
+    #[case::not_defined("nope")]
+    #[case::path_traversal("../secrets")]
+    fn load_template_unknown(#[case] name: &str) {
+        let dir = TempDir::new().unwrap();
+
+        let err = load_template(Some(dir.path()), name, &mut Config::default()).unwrap_err();
+
+        assert!(
+            matches!(
+                err.downcast_ref::<CcError>(),
+                Some(CcError::UnknownTemplate(n)) if n == name
+            ),
+            "unexpected error: {err}"
+        );
+    }
+}
@@ -1,10 +1,11 @@
 use anyhow::{Context, Result};
 
+use crate::infra::multiplexer::{self, WindowSpec};
 use crate::infra::tmux;
 use crate::shared::config::{Config, LayoutNode};
 
-/// Inputs for setting up a tmux window, grouped to keep `setup_tmux_window`'s
-/// argument count in check.
+/// Inputs for setting up a multiplexer window, grouped to keep
+/// `setup_tmux_window`'s argument count in check.
 pub(super) struct TmuxWindowSpec<'a> {
     pub repo_root: &'a str,
     pub cwd: &'a str,
@@ -22,33 +23,22 @@ pub(super) struct TmuxWindowSpec<'a> {
     pub restore_automatic_rename: bool,
 }
 
-/// Setup a tmux window with the given layout.
+/// Setup a multiplexer window with the given layout.
 pub(super) fn setup_tmux_window(spec: TmuxWindowSpec, config: &Config) -> Result<()> {
     let target_session = tmux::get_session_name(spec.repo_root, &config.wm.worktrees_dir);
 
-    tmux::ensure_session(&target_session, spec.repo_root)
-        .context("Failed to ensure tmux session")?;
-
-    tmux::layout::build_layout(tmux::layout::LayoutSpec {
-        common: tmux::layout::TmuxSessionSpec {
-            session: &target_session,
-            cwd: spec.cwd,
-            model: spec.model,
-            prompt: spec.prompt,
-            env_vars: spec.env_vars,
-            background: spec.background,
-        },
+    multiplexer::current().create_window(&WindowSpec {
+        session: &target_session,
+        session_cwd: spec.repo_root,
+        cwd: spec.cwd,
         window_name: spec.window_name,
         layout: spec.layout,
+        model: spec.model,
+        prompt: spec.prompt,
+        env_vars: spec.env_vars,
+        background: spec.background,
         restore_automatic_rename: spec.restore_automatic_rename,
     })
-    .context("Failed to create tmux layout")?;
-
-    if !spec.background {
-        tmux::switch_to_session(&target_session).context("Failed to switch to tmux session")?;
-    }
-
-    Ok(())
 }
 
 /// Inputs for `setup_split_pane`, grouped to keep its argument count in check.
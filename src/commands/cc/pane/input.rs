@@ -17,12 +17,12 @@
 //! `None` — callers must treat that as "no observation" rather than
 //! "input is empty".
 
-use crate::infra::tmux;
+use crate::infra::multiplexer;
 
 /// Returns the text the user has typed into the Claude Code TUI input
 /// box for `pane_id`, or `None` if no input box is currently rendered.
 pub fn get_pane_input_text(pane_id: &str) -> Option<String> {
-    let raw = tmux::capture_pane(pane_id)?;
+    let raw = multiplexer::current().capture_pane(pane_id)?;
     extract_input_text(&raw)
 }
 
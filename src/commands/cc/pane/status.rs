@@ -6,7 +6,7 @@ use clap::Args;
 
 use crate::commands::cc::store;
 use crate::commands::cc::types::{SessionStatus, TMUX_SESSION_OPTION};
-use crate::infra::tmux;
+use crate::infra::multiplexer;
 
 /// Filename prefix for the per-pane paused-flag file. The full path is
 /// `<flag_dir>/<PAUSED_FLAG_FILE_PREFIX><user>-<pane_id>` (e.g.
@@ -106,7 +106,7 @@ fn current_user() -> String {
 /// has no session option, the session file is gone, or the session is not
 /// Paused.
 fn render_for_pane(pane_id: &str, sessions_dir: &Path) -> Result<Option<&'static str>> {
-    let Some(session_id) = tmux::get_pane_option(pane_id, TMUX_SESSION_OPTION) else {
+    let Some(session_id) = multiplexer::current().pane_option(pane_id, TMUX_SESSION_OPTION) else {
         return Ok(None);
     };
     Ok(is_session_paused(sessions_dir, &session_id)?.then_some(PAUSED_FLAG_VALUE))
@@ -164,6 +164,13 @@ mod tests {
             pending_permission_agent_ids: std::collections::BTreeSet::new(),
             read_at: None,
             sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
         }
     }
 
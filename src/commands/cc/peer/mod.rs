@@ -16,12 +16,16 @@ use serde::Serialize;
 use super::claude_registry;
 use super::error::CcError;
 use super::store;
-use super::types::Session;
+use super::types::{Session, SessionStatus};
 use crate::shared::env_var::EnvVars;
 
 pub(crate) mod notify;
+pub(crate) mod report;
+mod wait_children;
 mod wake;
 use notify::NotifyArgs;
+use report::ReportArgs;
+use wait_children::WaitChildrenArgs;
 use wake::WakeArgs;
 
 #[derive(Subcommand, Clone, PartialEq, Eq)]
@@ -45,6 +49,18 @@ pub enum PeerCommands {
     /// Send a message directly to another session's SendMessage socket,
     /// without going through any session's own SendMessage tool call
     Notify(NotifyArgs),
+
+    /// File this session's result (done / blocked / failed) and deliver it
+    /// to the session that delegated to it
+    Report(ReportArgs),
+
+    /// List the latest report of every session this one delegated to (JSON)
+    Results,
+
+    /// Block until the sessions this one delegated to settle (stopped,
+    /// paused, ended, or reported), then print their final state (JSON)
+    #[command(name = "wait-children")]
+    WaitChildren(WaitChildrenArgs),
 }
 
 #[derive(Args, Clone, PartialEq, Eq)]
@@ -91,6 +107,9 @@ pub fn run(cmd: &PeerCommands) -> Result<()> {
         PeerCommands::List(args) => run_list(args),
         PeerCommands::Wake(args) => wake::run(args),
         PeerCommands::Notify(args) => notify::run(args),
+        PeerCommands::Report(args) => report::run_report(args),
+        PeerCommands::Results => report::run_results(),
+        PeerCommands::WaitChildren(args) => wait_children::run(args),
     }
 }
 
@@ -114,8 +133,9 @@ fn run_parent() -> Result<()> {
 fn run_children() -> Result<()> {
     let self_id = current_session_id()?;
     store::cleanup_stale_sessions()?;
-    let sessions = store::list_sessions()?;
-    print_peers(&filter_children(&sessions, &self_id))
+    let mut sessions = store::find_sessions(&store::Lookup::Parent(&self_id))?;
+    sessions.retain(|s| s.status != SessionStatus::Ended);
+    print_peers(&sessions.iter().collect::<Vec<_>>())
 }
 
 fn run_list(args: &PeerListArgs) -> Result<()> {
@@ -196,6 +216,13 @@ mod tests {
             pending_permission_agent_ids: Default::default(),
             read_at: None,
             sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
         }
     }
 
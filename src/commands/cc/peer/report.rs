@@ -0,0 +1,225 @@
+//! `a cc peer report` / `a cc peer results` -- structured handoff from a
+//! delegated session back to its delegator.
+//!
+//! The record is stored on the reporting (child) session's own file, not
+//! the parent's, so `results` still finds it when delivering the message
+//! to a gone or busy parent failed.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use clap::Args;
+use serde::Serialize;
+
+use super::current_session_id;
+use super::notify::notify as notify_peer_session;
+use crate::commands::cc::error::CcError;
+use crate::commands::cc::store;
+use crate::commands::cc::types::{ReportStatus, Session, SessionReport};
+use crate::shared::merge_notify::strip_angle_brackets;
+
+#[derive(Args, Clone, PartialEq, Eq)]
+pub struct ReportArgs {
+    /// Outcome of the delegated task
+    #[arg(long, value_enum)]
+    pub status: ReportStatus,
+
+    /// One-paragraph summary of what was done (or what is blocking)
+    #[arg(long)]
+    pub summary: String,
+
+    /// URL of the pull request produced by the delegated task (http or https)
+    #[arg(long, value_parser = parse_pr_url)]
+    pub pr: Option<String>,
+
+    /// File the delegator should look at (repeatable)
+    #[arg(long = "artifact")]
+    pub artifacts: Vec<PathBuf>,
+}
+
+/// Accepts `raw` only as an absolute http(s) URL. The PR is rendered as a
+/// link by `cc serve` and the tree exports, where a `javascript:` or other
+/// scheme must never end up.
+pub(crate) fn parse_pr_url(raw: &str) -> std::result::Result<String, String> {
+    match reqwest::Url::parse(raw.trim()) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") => Ok(raw.trim().to_string()),
+        Ok(url) => Err(format!(
+            "expected an http(s) URL, got scheme '{}'",
+            url.scheme()
+        )),
+        Err(e) => Err(format!("expected an http(s) URL: {e}")),
+    }
+}
+
+/// A child session's latest report, as printed by `a cc peer results`.
+/// `report` is `None` for children that have not reported yet, so the
+/// delegator can tell "still working" apart from "not delegated".
+#[derive(Debug, Serialize, PartialEq, Eq)]
+struct ChildResult<'a> {
+    session_id: &'a str,
+    label: Option<&'a str>,
+    cwd: String,
+    status: &'static str,
+    report: Option<&'a SessionReport>,
+}
+
+impl<'a> ChildResult<'a> {
+    fn from_session(session: &'a Session) -> Self {
+        Self {
+            session_id: &session.session_id,
+            label: session.label.as_deref(),
+            cwd: session.cwd.to_string_lossy().into_owned(),
+            status: session.status.display_name(),
+            report: session.report.as_ref(),
+        }
+    }
+}
+
+pub fn run_report(args: &ReportArgs) -> Result<()> {
+    let self_id = current_session_id()?;
+    let cwd = std::env::current_dir()?;
+    let report = SessionReport {
+        status: args.status,
+        summary: args.summary.clone(),
+        pr: args.pr.clone(),
+        artifacts: args
+            .artifacts
+            .iter()
+            .map(|p| std::path::absolute(cwd.join(p)))
+            .collect::<std::io::Result<_>>()?,
+        reported_at: Utc::now(),
+    };
+
+    let sessions_dir = store::sessions_dir()?;
+    if !store::update_session_report_in(&sessions_dir, &self_id, report.clone())? {
+        return Err(CcError::SessionNotFound(self_id).into());
+    }
+
+    let session = store::load_session_from(&sessions_dir, &self_id)?
+        .ok_or_else(|| CcError::SessionNotFound(self_id.clone()))?;
+    let Some(parent_id) = session.ancestor_session_ids.last() else {
+        eprintln!("[armyknife] report saved; no delegator session is tracked, nothing to deliver");
+        return Ok(());
+    };
+
+    let label = session.label.as_deref().unwrap_or(&session.session_id);
+    let message = build_report_message(label, &report);
+    notify_peer_session(parent_id, &message)
+        .with_context(|| format!("report saved, but delivery to session {parent_id} failed"))
+}
+
+pub fn run_results() -> Result<()> {
+    let self_id = current_session_id()?;
+    // Include Ended children: a delegate typically exits right after
+    // reporting, and its report must stay readable until the delegator
+    // gets to it.
+    let sessions = store::find_sessions(&store::Lookup::Parent(&self_id))?;
+    let results: Vec<ChildResult> = sessions.iter().map(ChildResult::from_session).collect();
+    println!("{}", serde_json::to_string(&results)?);
+    Ok(())
+}
+
+fn build_report_message(label: &str, report: &SessionReport) -> String {
+    let label = strip_angle_brackets(label);
+    let status = report.status.display_name();
+    let summary = strip_angle_brackets(&report.summary);
+    let mut details = String::new();
+    if let Some(pr) = &report.pr {
+        details.push_str(&format!("- PR: {}\n", strip_angle_brackets(pr)));
+    }
+    for artifact in &report.artifacts {
+        details.push_str(&format!(
+            "- Artifact: {}\n",
+            strip_angle_brackets(&artifact.to_string_lossy())
+        ));
+    }
+    indoc::formatdoc! {"
+        <delegation-report>
+        armyknife による自動送信です。委任先が `a cc peer report` で報告した結果です。
+
+        - 委任: {label}
+        - Status: {status}
+        {details}
+        {summary}
+
+        全委任先の最新の報告は `a cc peer results` で確認できます。
+        </delegation-report>"}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use indoc::indoc;
+    use rstest::rstest;
+
+    fn report(pr: Option<&str>, artifacts: &[&str]) -> SessionReport {
+        SessionReport {
+            status: ReportStatus::Blocked,
+            summary: "Need the <API> token".to_string(),
+            pr: pr.map(str::to_string),
+            artifacts: artifacts.iter().map(PathBuf::from).collect(),
+            reported_at: Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap(),
+        }
+    }
+
+    #[rstest]
+    #[case::https("https://github.com/o/r/pull/1", Some("https://github.com/o/r/pull/1"))]
+    #[case::http_trimmed(" http://git.example/pr/2 ", Some("http://git.example/pr/2"))]
+    #[case::javascript("javascript:alert(1)", None)]
+    #[case::file("file:///etc/passwd", None)]
+    #[case::not_a_url("#12", None)]
+    fn test_parse_pr_url(#[case] raw: &str, #[case] expected: Option<&str>) {
+        assert_eq!(parse_pr_url(raw).ok().as_deref(), expected);
+    }
+
+    #[test]
+    fn build_report_message_lists_pr_and_artifacts() {
+        let message = build_report_message(
+            "fix <login>",
+            &report(
+                Some("https://github.com/o/r/pull/1"),
+                &["/tmp/a.log", "/tmp/b.md"],
+            ),
+        );
+
+        assert_eq!(
+            message,
+            indoc! {"
+                <delegation-report>
+                armyknife による自動送信です。委任先が `a cc peer report` で報告した結果です。
+
+                - 委任: fix login
+                - Status: blocked
+                - PR: https://github.com/o/r/pull/1
+                - Artifact: /tmp/a.log
+                - Artifact: /tmp/b.md
+
+                Need the API token
+
+                全委任先の最新の報告は `a cc peer results` で確認できます。
+                </delegation-report>"}
+        );
+    }
+
+    #[test]
+    fn build_report_message_without_details() {
+        let message = build_report_message("task", &report(None, &[]));
+
+        assert_eq!(
+            message,
+            indoc! {"
+                <delegation-report>
+                armyknife による自動送信です。委任先が `a cc peer report` で報告した結果です。
+
+                - 委任: task
+                - Status: blocked
+
+                Need the API token
+
+                全委任先の最新の報告は `a cc peer results` で確認できます。
+                </delegation-report>"}
+        );
+    }
+}
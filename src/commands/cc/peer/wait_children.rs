@@ -0,0 +1,321 @@
+//! `a cc peer wait-children` -- block a delegator until the sessions it
+//! delegated to settle, instead of polling `a cc peer children`.
+//!
+//! A child counts as settled once it has filed a report (`a cc peer
+//! report`) since the wait started, or its main loop is no longer running:
+//! `Stopped` (idle, waiting for the next prompt), `Paused` (a `Stopped`
+//! session `a cc sweep` has since terminated), or `Ended`. A report from
+//! before the wait belongs to an earlier round, after which the child may
+//! have been resumed. Waiting is driven by a file watcher on the sessions
+//! directory, with a periodic re-scan as a fallback for dropped watcher
+//! events.
+
+use std::path::Path;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use clap::{Args, ValueEnum};
+use notify::{RecursiveMode, Watcher};
+use serde::Serialize;
+
+use super::{current_session_id, filter_children};
+use crate::commands::cc::auto_pause::parse_duration;
+use crate::commands::cc::store;
+use crate::commands::cc::types::{ReportStatus, Session, SessionStatus};
+use crate::infra::git::GitRepo;
+
+/// Re-scan interval used when no watcher event arrives, so a missed event
+/// (e.g. the watcher backend coalescing a rename) delays completion by at
+/// most this much rather than until the timeout.
+const RESCAN_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Args, Clone, PartialEq, Eq)]
+pub struct WaitChildrenArgs {
+    /// Only wait for these child session IDs (default: every session this
+    /// one delegated to, including ones delegated while waiting)
+    pub session_ids: Vec<String>,
+
+    /// Return once all (default) or any of the awaited children settle
+    #[arg(long, value_enum, default_value_t = WaitMode::All)]
+    pub mode: WaitMode,
+
+    /// Give up after this long (e.g. `30m`, `1h30m`); waits forever when
+    /// omitted
+    #[arg(long)]
+    pub timeout: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum WaitMode {
+    All,
+    Any,
+}
+
+/// Final state of one awaited child, as printed on exit.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+struct ChildSummary {
+    session_id: String,
+    label: Option<String>,
+    status: Option<&'static str>,
+    settled: bool,
+    branch: Option<String>,
+    pr: Option<String>,
+    report_status: Option<ReportStatus>,
+    last_message: Option<String>,
+}
+
+pub fn run(args: &WaitChildrenArgs) -> Result<()> {
+    let self_id = current_session_id()?;
+    let timeout = args
+        .timeout
+        .as_deref()
+        .map(parse_duration)
+        .transpose()
+        .context("invalid --timeout")?;
+    let sessions_dir = store::sessions_dir()?;
+    std::fs::create_dir_all(&sessions_dir)?;
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if res.is_ok() {
+            let _ = tx.send(());
+        }
+    })?;
+    watcher.watch(Path::new(&sessions_dir), RecursiveMode::NonRecursive)?;
+
+    let waiting_since = Utc::now();
+    let deadline = timeout.map(|t| Instant::now() + t);
+    let (sessions, timed_out) = loop {
+        let sessions = store::list_all_sessions()?;
+        let awaited = awaited_children(&sessions, &self_id, &args.session_ids);
+        if wait_satisfied(args.mode, &awaited, waiting_since) {
+            break (sessions, false);
+        }
+
+        let wait_for = match deadline {
+            Some(deadline) => {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    break (sessions, true);
+                }
+                remaining.min(RESCAN_INTERVAL)
+            }
+            None => RESCAN_INTERVAL,
+        };
+        match rx.recv_timeout(wait_for) {
+            // Drain the burst of events a single atomic save produces
+            // (tmp write + rename) so each burst costs one re-scan.
+            Ok(()) => while rx.try_recv().is_ok() {},
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => bail!("session watcher stopped"),
+        }
+    };
+
+    let summaries: Vec<ChildSummary> = awaited_children(&sessions, &self_id, &args.session_ids)
+        .into_iter()
+        .map(|(id, session)| summarize(id, session, waiting_since))
+        .collect();
+    println!("{}", serde_json::to_string(&summaries)?);
+
+    if timed_out {
+        bail!("timed out waiting for child sessions to settle");
+    }
+    Ok(())
+}
+
+/// The children being waited on, paired with their session if it is
+/// currently on disk. With explicit `session_ids`, a child whose session
+/// file does not exist yet (e.g. a just-spawned `a cc new` that has not
+/// fired its first hook) is kept as `None` -- unsettled -- rather than
+/// dropped, so it can't be skipped by winning the race against its own
+/// first hook.
+fn awaited_children<'a>(
+    sessions: &'a [Session],
+    self_id: &str,
+    session_ids: &'a [String],
+) -> Vec<(&'a str, Option<&'a Session>)> {
+    if session_ids.is_empty() {
+        return filter_children(sessions, self_id)
+            .into_iter()
+            .map(|s| (s.session_id.as_str(), Some(s)))
+            .collect();
+    }
+    session_ids
+        .iter()
+        .map(|id| (id.as_str(), sessions.iter().find(|s| &s.session_id == id)))
+        .collect()
+}
+
+fn is_settled(session: &Session, waiting_since: DateTime<Utc>) -> bool {
+    session
+        .report
+        .as_ref()
+        .is_some_and(|report| report.reported_at >= waiting_since)
+        || matches!(
+            session.status,
+            SessionStatus::Stopped | SessionStatus::Paused | SessionStatus::Ended
+        )
+}
+
+/// Whether the wait is over. An empty awaited set never satisfies the
+/// wait: a delegator that starts waiting before its first child fires a
+/// hook would otherwise return immediately.
+fn wait_satisfied(
+    mode: WaitMode,
+    awaited: &[(&str, Option<&Session>)],
+    waiting_since: DateTime<Utc>,
+) -> bool {
+    if awaited.is_empty() {
+        return false;
+    }
+    let mut settled = awaited
+        .iter()
+        .map(|(_, s)| s.is_some_and(|s| is_settled(s, waiting_since)));
+    match mode {
+        WaitMode::All => settled.all(|b| b),
+        WaitMode::Any => settled.any(|b| b),
+    }
+}
+
+fn summarize(
+    session_id: &str,
+    session: Option<&Session>,
+    waiting_since: DateTime<Utc>,
+) -> ChildSummary {
+    let Some(session) = session else {
+        return ChildSummary {
+            session_id: session_id.to_string(),
+            label: None,
+            status: None,
+            settled: false,
+            branch: None,
+            pr: None,
+            report_status: None,
+            last_message: None,
+        };
+    };
+    ChildSummary {
+        session_id: session_id.to_string(),
+        label: session.label.clone(),
+        status: Some(session.status.display_name()),
+        settled: is_settled(session, waiting_since),
+        branch: GitRepo::open_at(&session.cwd)
+            .and_then(|repo| repo.current_branch())
+            .ok(),
+        pr: session.report.as_ref().and_then(|r| r.pr.clone()),
+        report_status: session.report.as_ref().map(|r| r.status),
+        last_message: session.last_message.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::cc::types::SessionReport;
+    use chrono::{TimeDelta, TimeZone};
+    use rstest::rstest;
+    use std::path::PathBuf;
+
+    /// When the tests' wait started.
+    fn waiting_since() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap()
+    }
+
+    /// A child of `self`, which reported `reported` minutes after the wait
+    /// started (negative: before it) when set.
+    fn child(session_id: &str, status: SessionStatus, reported: Option<i64>) -> Session {
+        Session {
+            session_id: session_id.to_string(),
+            cwd: PathBuf::from("/repo"),
+            transcript_path: None,
+            tty: None,
+            tmux_info: None,
+            status,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_message: None,
+            current_tool: None,
+            label: None,
+            ancestor_session_ids: vec!["self".to_string()],
+            pending_bg_task_ids: Default::default(),
+            pending_agent_task_ids: Default::default(),
+            pending_permission_agent_ids: Default::default(),
+            read_at: None,
+            sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: reported.map(|minutes| SessionReport {
+                status: ReportStatus::Done,
+                summary: "done".to_string(),
+                pr: None,
+                artifacts: Vec::new(),
+                reported_at: waiting_since() + TimeDelta::minutes(minutes),
+            }),
+            tags: Default::default(),
+            note: None,
+        }
+    }
+
+    #[rstest]
+    #[case::running(SessionStatus::Running, None, false)]
+    #[case::waiting(SessionStatus::WaitingInput, None, false)]
+    #[case::stopped(SessionStatus::Stopped, None, true)]
+    #[case::paused(SessionStatus::Paused, None, true)]
+    #[case::ended(SessionStatus::Ended, None, true)]
+    #[case::running_but_reported(SessionStatus::Running, Some(5), true)]
+    #[case::running_with_stale_report(SessionStatus::Running, Some(-30), false)]
+    fn is_settled_cases(
+        #[case] status: SessionStatus,
+        #[case] reported: Option<i64>,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(
+            is_settled(&child("c", status, reported), waiting_since()),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case::all_requires_every_child(WaitMode::All, false)]
+    #[case::any_accepts_one_child(WaitMode::Any, true)]
+    fn wait_satisfied_modes(#[case] mode: WaitMode, #[case] expected: bool) {
+        let sessions = vec![
+            child("a", SessionStatus::Stopped, None),
+            child("b", SessionStatus::Running, None),
+        ];
+
+        let awaited = awaited_children(&sessions, "self", &[]);
+
+        assert_eq!(wait_satisfied(mode, &awaited, waiting_since()), expected);
+    }
+
+    #[rstest]
+    #[case::all(WaitMode::All)]
+    #[case::any(WaitMode::Any)]
+    fn wait_satisfied_is_false_without_children(#[case] mode: WaitMode) {
+        assert!(!wait_satisfied(mode, &[], waiting_since()));
+    }
+
+    #[test]
+    fn named_children_missing_from_disk_stay_unsettled() {
+        let sessions = vec![child("a", SessionStatus::Ended, None)];
+        let ids = vec!["a".to_string(), "not-yet-started".to_string()];
+
+        let awaited = awaited_children(&sessions, "self", &ids);
+
+        assert_eq!(
+            (
+                awaited
+                    .iter()
+                    .map(|(id, s)| (*id, s.is_some()))
+                    .collect::<Vec<_>>(),
+                wait_satisfied(WaitMode::All, &awaited, waiting_since()),
+            ),
+            (vec![("a", true), ("not-yet-started", false)], false)
+        );
+    }
+}
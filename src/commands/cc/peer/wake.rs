@@ -27,7 +27,7 @@ use crate::commands::cc::error::CcError;
 use crate::commands::cc::resume::{RespawnError, respawn_paused_session};
 use crate::commands::cc::store;
 use crate::commands::cc::types::{SessionStatus, TMUX_SESSION_OPTION};
-use crate::infra::tmux;
+use crate::infra::multiplexer;
 
 /// How often to poll Claude Code's session registry for the resumed
 /// process's name after respawning the pane.
@@ -69,7 +69,7 @@ pub(super) fn wake(session_id: &str) -> Result<String> {
         .tmux_info
         .as_ref()
         .ok_or_else(|| CcError::NoTmuxInfo(session_id.to_string()))?;
-    let recorded = tmux::get_pane_option(&tmux_info.pane_id, TMUX_SESSION_OPTION);
+    let recorded = multiplexer::current().pane_option(&tmux_info.pane_id, TMUX_SESSION_OPTION);
     check_pane_matches_target(recorded.as_deref(), session_id)?;
 
     // Serialize concurrent wakes of the same paused session -- e.g. several
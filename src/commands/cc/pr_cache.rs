@@ -0,0 +1,316 @@
+//! On-disk cache of branch -> PR lookups, for commands that show a PR's
+//! review and CI state on every render (`a cc statusline`) and so cannot
+//! afford a GitHub round trip each time.
+//!
+//! Entries older than the caller's TTL are re-fetched in one batched query.
+//! A failed or timed-out fetch keeps the stale values but still restarts
+//! their TTL, so being offline costs one slow render per TTL rather than
+//! one per render.
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::future::Future;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::error::CcError;
+use crate::infra::github::{BranchPrQuery, GitHubClient, PrInfo};
+use crate::shared::cache;
+
+/// Upper bound on one batched refresh, so a hung connection can't stall a
+/// status line.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// `(owner, repo, branch)`, the key `get_prs_for_branches_batch` uses.
+pub(crate) type PrKey = (String, String, String);
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    #[serde(default)]
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Entry {
+    owner: String,
+    repo: String,
+    branch: String,
+    fetched_at: DateTime<Utc>,
+    /// `None` when the branch has no PR.
+    pr: Option<PrInfo>,
+}
+
+impl Entry {
+    fn key(&self) -> PrKey {
+        (self.owner.clone(), self.repo.clone(), self.branch.clone())
+    }
+}
+
+/// Returns the cache file path.
+/// Path: ~/.cache/armyknife/cc/pr-cache.json
+fn cache_path() -> Result<PathBuf> {
+    cache::base_dir()
+        .map(|d| d.join("cc").join("pr-cache.json"))
+        .ok_or_else(|| CcError::CacheDirNotFound.into())
+}
+
+/// The PR of every queried branch, re-fetching only entries older than
+/// `ttl`. Branches whose lookup never succeeded are absent from the result.
+pub(crate) async fn cached_prs(
+    queries: &[BranchPrQuery],
+    ttl: Duration,
+) -> HashMap<PrKey, Option<PrInfo>> {
+    let Ok(path) = cache_path() else {
+        return HashMap::new();
+    };
+    cached_prs_in(&path, queries, ttl, Utc::now(), fetch).await
+}
+
+/// The last fetched PR of `query`'s branch whatever its age, without
+/// touching GitHub. For outputs rendered from hooks (the tmux window
+/// status), which must never wait on the network; the entry is kept fresh
+/// by whichever command last called `cached_prs`.
+pub(crate) fn last_known_pr(query: &BranchPrQuery) -> Option<PrInfo> {
+    last_known_pr_in(&cache_path().ok()?, query)
+}
+
+fn last_known_pr_in(path: &Path, query: &BranchPrQuery) -> Option<PrInfo> {
+    load(path)
+        .entries
+        .into_iter()
+        .find(|e| e.owner == query.owner && e.repo == query.repo && e.branch == query.branch)
+        .and_then(|e| e.pr)
+}
+
+async fn fetch(queries: Vec<BranchPrQuery>) -> Option<HashMap<PrKey, Option<PrInfo>>> {
+    let client = GitHubClient::get().ok()?;
+    match tokio::time::timeout(FETCH_TIMEOUT, client.get_prs_for_branches_batch(&queries)).await {
+        Ok(Ok(map)) => Some(map),
+        Ok(Err(e)) => {
+            tracing::debug!(event = "cc.pr_cache.fetch_failed", error = %e);
+            None
+        }
+        Err(_) => {
+            tracing::debug!(event = "cc.pr_cache.fetch_timed_out");
+            None
+        }
+    }
+}
+
+async fn cached_prs_in<F, Fut>(
+    path: &Path,
+    queries: &[BranchPrQuery],
+    ttl: Duration,
+    now: DateTime<Utc>,
+    fetch: F,
+) -> HashMap<PrKey, Option<PrInfo>>
+where
+    F: FnOnce(Vec<BranchPrQuery>) -> Fut,
+    Fut: Future<Output = Option<HashMap<PrKey, Option<PrInfo>>>>,
+{
+    let mut entries: HashMap<PrKey, Entry> = load(path)
+        .entries
+        .into_iter()
+        .map(|e| (e.key(), e))
+        .collect();
+
+    let ttl = chrono::Duration::from_std(ttl).unwrap_or(chrono::Duration::MAX);
+    let stale: Vec<BranchPrQuery> = queries
+        .iter()
+        .filter(|q| {
+            entries
+                .get(&(q.owner.clone(), q.repo.clone(), q.branch.clone()))
+                .is_none_or(|e| now - e.fetched_at >= ttl)
+        })
+        .cloned()
+        .collect();
+
+    if !stale.is_empty() {
+        let fetched = fetch(stale.clone()).await;
+        for q in stale {
+            let key = (q.owner, q.repo, q.branch);
+            match &fetched {
+                Some(map) => {
+                    let pr = map.get(&key).cloned().flatten();
+                    entries.insert(
+                        key.clone(),
+                        Entry {
+                            owner: key.0,
+                            repo: key.1,
+                            branch: key.2,
+                            fetched_at: now,
+                            pr,
+                        },
+                    );
+                }
+                None => {
+                    if let Some(entry) = entries.get_mut(&key) {
+                        entry.fetched_at = now;
+                    }
+                }
+            }
+        }
+        if let Err(e) = save(path, entries.values().cloned().collect()) {
+            tracing::debug!(event = "cc.pr_cache.save_failed", error = %e);
+        }
+    }
+
+    queries
+        .iter()
+        .filter_map(|q| {
+            let key = (q.owner.clone(), q.repo.clone(), q.branch.clone());
+            let pr = entries.get(&key)?.pr.clone();
+            Some((key, pr))
+        })
+        .collect()
+}
+
+fn load(path: &Path) -> CacheFile {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Several status lines may refresh at once; each writes its own temp file
+/// and the last rename wins, which only costs the loser's entries a
+/// re-fetch.
+fn save(path: &Path, entries: Vec<Entry>) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let temp_path = path.with_extension(format!("json.{}.tmp", std::process::id()));
+    let mut temp_file = File::create(&temp_path)?;
+    temp_file.write_all(serde_json::to_string(&CacheFile { entries })?.as_bytes())?;
+    temp_file.sync_all()?;
+    fs::rename(&temp_path, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::github::PrState;
+    use std::cell::Cell;
+    use tempfile::TempDir;
+
+    fn query(branch: &str) -> BranchPrQuery {
+        BranchPrQuery {
+            owner: "o".to_string(),
+            repo: "r".to_string(),
+            branch: branch.to_string(),
+        }
+    }
+
+    fn key(branch: &str) -> PrKey {
+        ("o".to_string(), "r".to_string(), branch.to_string())
+    }
+
+    fn pr(number: u64) -> PrInfo {
+        PrInfo {
+            number,
+            state: PrState::Open,
+            url: format!("https://github.com/o/r/pull/{number}"),
+            title: String::new(),
+            draft: false,
+            review: None,
+            checks: None,
+            unresolved_threads: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn fresh_entries_are_served_without_fetching() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("pr-cache.json");
+        let now = Utc::now();
+        let ttl = Duration::from_secs(60);
+
+        let first = cached_prs_in(&path, &[query("a")], ttl, now, |_| async {
+            Some(HashMap::from([(key("a"), Some(pr(1)))]))
+        })
+        .await;
+        assert_eq!(first[&key("a")].as_ref().map(|p| p.number), Some(1));
+
+        let fetched = Cell::new(false);
+        let second = cached_prs_in(
+            &path,
+            &[query("a")],
+            ttl,
+            now + chrono::Duration::seconds(30),
+            |_| {
+                fetched.set(true);
+                async { None }
+            },
+        )
+        .await;
+        assert!(!fetched.get());
+        assert_eq!(second[&key("a")].as_ref().map(|p| p.number), Some(1));
+    }
+
+    #[tokio::test]
+    async fn stale_entries_are_refetched_and_kept_on_failure() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("pr-cache.json");
+        let now = Utc::now();
+        let ttl = Duration::from_secs(60);
+        cached_prs_in(&path, &[query("a")], ttl, now, |_| async {
+            Some(HashMap::from([(key("a"), Some(pr(1)))]))
+        })
+        .await;
+
+        let later = now + chrono::Duration::seconds(120);
+        let failed = cached_prs_in(&path, &[query("a"), query("b")], ttl, later, |stale| {
+            assert_eq!(stale.len(), 2);
+            async { None }
+        })
+        .await;
+        assert_eq!(failed[&key("a")].as_ref().map(|p| p.number), Some(1));
+        assert!(!failed.contains_key(&key("b")));
+
+        // The failure restarted "a"'s TTL; only the never-fetched "b" is
+        // retried.
+        let retried = cached_prs_in(
+            &path,
+            &[query("a"), query("b")],
+            ttl,
+            later + chrono::Duration::seconds(1),
+            |stale| {
+                assert_eq!(stale.len(), 1);
+                assert_eq!(stale[0].branch, "b");
+                async { Some(HashMap::from([(key("b"), None)])) }
+            },
+        )
+        .await;
+        assert_eq!(retried[&key("b")].as_ref().map(|p| p.number), None);
+        assert!(retried.contains_key(&key("b")));
+    }
+
+    #[tokio::test]
+    async fn last_known_pr_ignores_ttl_and_never_fetches() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("pr-cache.json");
+        assert!(last_known_pr_in(&path, &query("a")).is_none());
+
+        let long_ago = Utc::now() - chrono::Duration::days(1);
+        cached_prs_in(
+            &path,
+            &[query("a")],
+            Duration::from_secs(60),
+            long_ago,
+            |_| async { Some(HashMap::from([(key("a"), Some(pr(7)))])) },
+        )
+        .await;
+
+        assert_eq!(
+            last_known_pr_in(&path, &query("a")).map(|p| p.number),
+            Some(7)
+        );
+        assert!(last_known_pr_in(&path, &query("b")).is_none());
+    }
+}
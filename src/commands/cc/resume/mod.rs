@@ -1,3 +1,7 @@
+mod picker;
+
+use std::io::IsTerminal;
+
 use anyhow::{Result, bail};
 use clap::Args;
 use thiserror::Error;
@@ -9,23 +13,34 @@ use crate::shared::command::find_command_path;
 #[derive(Args, Clone, PartialEq, Eq)]
 pub struct ResumeArgs {
     /// Claude Code session ID to resume. When omitted, the session ID is read from
-    /// the current tmux pane's `@armyknife-last-claude-code-session-id` user option.
+    /// the current tmux pane's `@armyknife-last-claude-code-session-id` user option,
+    /// and without one a picker of paused and ended sessions opens.
     pub session_id: Option<String>,
 }
 
 /// Runs the resume command.
 /// If a session ID argument is provided, resumes that session directly.
-/// Otherwise, reads the session ID from the current tmux pane's user option.
+/// Otherwise, reads the session ID from the current tmux pane's user option,
+/// falling back to the interactive picker on a terminal.
 pub fn run(args: &ResumeArgs) -> Result<()> {
     let session_id = match args.session_id.as_deref() {
         Some(id) if !id.is_empty() => id.to_string(),
-        _ => resolve_session_id_from_pane()?,
+        _ => match resolve_session_id_from_pane() {
+            Ok(id) => id,
+            Err(_) if std::io::stdout().is_terminal() => return picker::run(),
+            Err(e) => return Err(e),
+        },
     };
 
+    exec_claude_resume(&session_id)
+}
+
+/// Replaces this process with `claude --resume <session_id>`.
+fn exec_claude_resume(session_id: &str) -> Result<()> {
     let claude_path = find_command_path("claude")
         .ok_or_else(|| anyhow::anyhow!("Could not find 'claude' command in PATH"))?;
 
-    let err = process::exec_replace(&claude_path, ["--resume", &session_id]);
+    let err = process::exec_replace(&claude_path, ["--resume", session_id]);
     bail!("Failed to exec claude: {}", err)
 }
 
@@ -95,7 +110,7 @@ pub(crate) fn respawn_paused_session(session: &Session) -> Result<String, Respaw
     }
     check_idle_at_shell_prompt(tmux::get_pane_current_command(&tmux_info.pane_id).as_deref())?;
 
-    let wrapped = build_resume_command().ok_or(RespawnError::CommandBuildFailed)?;
+    let wrapped = build_resume_command(None).ok_or(RespawnError::CommandBuildFailed)?;
     tmux::respawn_pane(&tmux_info.pane_id, &wrapped)
         .map_err(|e| RespawnError::RespawnFailed(e.to_string()))?;
 
@@ -113,21 +128,25 @@ fn check_idle_at_shell_prompt(pane_current_command: Option<&str>) -> Result<(),
     }
 }
 
-/// Wraps `a cc resume` in the user's login shell so that when claude exits
-/// normally, control returns to a shell prompt instead of tmux closing the
-/// pane (`respawn-pane` replaces the pane's root process).
+/// Wraps `a cc resume [session_id]` in the user's login shell so that when
+/// claude exits normally, control returns to a shell prompt instead of tmux
+/// closing the pane (`respawn-pane` replaces the pane's root process).
+/// Without `session_id`, the resumed session is the pane's
+/// `TMUX_SESSION_OPTION`.
 ///
 /// `-i` is required on the outer shell: `a cc resume` looks up `claude` in
 /// $PATH via `find_command_path`, and many users only extend $PATH in their
 /// interactive rc file (e.g. `.zshrc`). Running without `-i` would inherit
 /// tmux's pre-rc $PATH and fail to locate `claude`.
-fn build_resume_command() -> Option<String> {
+fn build_resume_command(session_id: Option<&str>) -> Option<String> {
     let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
     let exe = std::env::current_exe()
         .ok()
         .and_then(|p| p.to_str().map(String::from))
         .unwrap_or_else(|| "a".to_string());
-    let inner = shlex::try_join([exe.as_str(), "cc", "resume"]).ok()?;
+    let mut inner_args = vec![exe.as_str(), "cc", "resume"];
+    inner_args.extend(session_id);
+    let inner = shlex::try_join(inner_args).ok()?;
     let exec_shell = shlex::try_join([shell.as_str(), "-i"]).ok()?;
     let script = format!("{inner}; exec {exec_shell}");
     shlex::try_join([shell.as_str(), "-i", "-c", &script]).ok()
@@ -200,6 +219,13 @@ mod tests {
                 pending_permission_agent_ids: Default::default(),
                 read_at: None,
                 sweep_signaled: false,
+                changed_files: Default::default(),
+                subagents: Default::default(),
+                compaction_count: 0,
+                last_compacted_at: None,
+                report: None,
+                tags: Default::default(),
+                note: None,
             }
         }
 
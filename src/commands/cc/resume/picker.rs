@@ -0,0 +1,567 @@
+//! Interactive picker for `a cc resume` run with neither a session ID nor a
+//! pane option: fuzzy-search the paused and recently ended sessions, preview
+//! each one's last answer, and resume the chosen one where it used to run.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::{Result, bail};
+use chrono::{DateTime, Utc};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::{
+    DefaultTerminal, Frame,
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+use super::{build_resume_command, check_idle_at_shell_prompt, exec_claude_resume};
+use crate::commands::cc::claude_sessions;
+use crate::commands::cc::store;
+use crate::commands::cc::types::{Session, SessionStatus};
+use crate::infra::git::open_repo_at;
+use crate::infra::tmux;
+use crate::shared::table::pad_or_truncate;
+
+const DIM_FG: Color = Color::Indexed(245);
+const TITLE_WIDTH: usize = 32;
+const LOCATION_WIDTH: usize = 32;
+const AGE_WIDTH: usize = 4;
+
+/// One resumable session with what the picker shows and searches.
+struct Candidate {
+    session: Session,
+    /// `repo · branch`, or the cwd's name outside a repository.
+    location: String,
+    first_prompt: String,
+    /// Lowercased label, location, and first prompt.
+    search_text: String,
+}
+
+impl Candidate {
+    fn new(session: Session, location: String, first_prompt: String) -> Self {
+        let search_text = format!(
+            "{} {location} {first_prompt}",
+            session.label.as_deref().unwrap_or("")
+        )
+        .to_lowercase();
+        Self {
+            session,
+            location,
+            first_prompt,
+            search_text,
+        }
+    }
+
+    fn title(&self) -> &str {
+        self.session
+            .label
+            .as_deref()
+            .unwrap_or(self.first_prompt.as_str())
+    }
+}
+
+pub(super) fn run() -> Result<()> {
+    let candidates: Vec<Candidate> = resumable_sessions(store::list_all_sessions()?)
+        .into_iter()
+        .map(|session| {
+            let location = location(&session.cwd);
+            let first_prompt =
+                claude_sessions::get_first_user_message(&session.cwd, &session.session_id)
+                    .unwrap_or_default();
+            Candidate::new(session, location, first_prompt)
+        })
+        .collect();
+    if candidates.is_empty() {
+        bail!("No paused or ended Claude Code sessions to resume");
+    }
+
+    let mut terminal = ratatui::init();
+    let chosen = pick(&mut terminal, Picker::new(candidates), Utc::now());
+    ratatui::restore();
+
+    match chosen? {
+        Some(session) => resume_session(&session),
+        None => Ok(()),
+    }
+}
+
+/// Paused and ended sessions, most recently updated first. Ended sessions
+/// are only on disk for `store`'s retention period, which is what makes
+/// them "recent".
+fn resumable_sessions(mut sessions: Vec<Session>) -> Vec<Session> {
+    sessions.retain(|s| matches!(s.status, SessionStatus::Paused | SessionStatus::Ended));
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.updated_at));
+    sessions
+}
+
+/// `repo · branch` for `cwd`, read from the repository when it still exists.
+fn location(cwd: &Path) -> String {
+    let basename = cwd
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| cwd.display().to_string());
+    let Ok(repo) = open_repo_at(cwd) else {
+        return basename;
+    };
+    let repo_name = repo
+        .main_workdir()
+        .ok()
+        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+        .unwrap_or(basename);
+    match repo.current_branch().ok().filter(|b| b != "HEAD") {
+        Some(branch) => format!("{repo_name} \u{b7} {branch}"),
+        None => repo_name,
+    }
+}
+
+/// Whether every whitespace-separated word of `query` occurs in `text` as a
+/// case-insensitive subsequence (`flb` matches "fix login bug"). `text` must
+/// already be lowercase.
+fn fuzzy_matches(query: &str, text: &str) -> bool {
+    query.split_whitespace().all(|word| {
+        let mut chars = text.chars();
+        word.to_lowercase().chars().all(|c| chars.any(|t| t == c))
+    })
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Outcome {
+    Continue,
+    Cancel,
+    Select,
+}
+
+struct Picker {
+    candidates: Vec<Candidate>,
+    query: String,
+    /// Indices into `candidates` matching `query`, in display order.
+    filtered: Vec<usize>,
+    list_state: ListState,
+    /// Last assistant message per session ID, read on first selection.
+    previews: HashMap<String, Option<String>>,
+}
+
+impl Picker {
+    fn new(candidates: Vec<Candidate>) -> Self {
+        let mut picker = Self {
+            candidates,
+            query: String::new(),
+            filtered: Vec::new(),
+            list_state: ListState::default(),
+            previews: HashMap::new(),
+        };
+        picker.refilter();
+        picker
+    }
+
+    fn refilter(&mut self) {
+        self.filtered = self
+            .candidates
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| fuzzy_matches(&self.query, &c.search_text))
+            .map(|(i, _)| i)
+            .collect();
+        self.list_state
+            .select((!self.filtered.is_empty()).then_some(0));
+    }
+
+    fn selected(&self) -> Option<&Candidate> {
+        let index = *self.filtered.get(self.list_state.selected()?)?;
+        self.candidates.get(index)
+    }
+
+    fn handle_key(&mut self, code: KeyCode, modifiers: KeyModifiers) -> Outcome {
+        let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+        match code {
+            KeyCode::Esc => return Outcome::Cancel,
+            KeyCode::Char('c') if ctrl => return Outcome::Cancel,
+            KeyCode::Enter if self.selected().is_some() => return Outcome::Select,
+            KeyCode::Up => self.list_state.select_previous(),
+            KeyCode::Char('p') if ctrl => self.list_state.select_previous(),
+            KeyCode::Down => self.move_down(),
+            KeyCode::Char('n') if ctrl => self.move_down(),
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.refilter();
+            }
+            KeyCode::Char(c) if !ctrl => {
+                self.query.push(c);
+                self.refilter();
+            }
+            _ => {}
+        }
+        Outcome::Continue
+    }
+
+    /// `ListState::select_next` runs past the end until the next render
+    /// clamps it; clamp here so `selected` is right between renders too.
+    fn move_down(&mut self) {
+        if let Some(i) = self.list_state.selected()
+            && i + 1 < self.filtered.len()
+        {
+            self.list_state.select(Some(i + 1));
+        }
+    }
+
+    fn preview(&mut self) -> Option<String> {
+        let session = &self.selected()?.session;
+        let id = session.session_id.clone();
+        let cwd = session.cwd.clone();
+        self.previews
+            .entry(id.clone())
+            .or_insert_with(|| claude_sessions::get_last_assistant_message(&cwd, &id))
+            .clone()
+    }
+}
+
+/// Runs the picker until the user selects a session (returned) or cancels.
+fn pick(
+    terminal: &mut DefaultTerminal,
+    mut picker: Picker,
+    now: DateTime<Utc>,
+) -> Result<Option<Session>> {
+    loop {
+        terminal.draw(|frame| render(frame, &mut picker, now))?;
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match picker.handle_key(key.code, key.modifiers) {
+            Outcome::Continue => {}
+            Outcome::Cancel => return Ok(None),
+            Outcome::Select => return Ok(picker.selected().map(|c| c.session.clone())),
+        }
+    }
+}
+
+fn render(frame: &mut Frame, picker: &mut Picker, now: DateTime<Utc>) {
+    let [prompt_area, list_area, preview_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Percentage(60),
+        Constraint::Min(3),
+    ])
+    .areas(frame.area());
+
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled("resume> ", Style::default().fg(Color::Cyan)),
+            Span::raw(picker.query.clone()),
+            Span::styled(
+                format!("  {}/{}", picker.filtered.len(), picker.candidates.len()),
+                Style::default().fg(DIM_FG),
+            ),
+        ])),
+        prompt_area,
+    );
+
+    let items: Vec<ListItem> = picker
+        .filtered
+        .iter()
+        .map(|&i| candidate_row(&picker.candidates[i], now))
+        .collect();
+    let list = List::new(items)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol(">");
+    frame.render_stateful_widget(list, list_area, &mut picker.list_state);
+
+    let preview = picker.preview().unwrap_or_default();
+    frame.render_widget(
+        Paragraph::new(preview)
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::TOP).title("Last answer")),
+        preview_area,
+    );
+}
+
+fn candidate_row(candidate: &Candidate, now: DateTime<Utc>) -> ListItem<'static> {
+    let session = &candidate.session;
+    ListItem::new(Line::from(vec![
+        Span::raw(format!("{} ", session.display_symbol())),
+        Span::raw(pad_or_truncate(candidate.title(), TITLE_WIDTH)),
+        Span::raw(" "),
+        Span::styled(
+            pad_or_truncate(&candidate.location, LOCATION_WIDTH),
+            Style::default().fg(DIM_FG),
+        ),
+        Span::raw(" "),
+        Span::raw(format!(
+            "{:>AGE_WIDTH$}",
+            format_age(session.updated_at, now)
+        )),
+        Span::raw("  "),
+        Span::styled(candidate.first_prompt.clone(), Style::default().fg(DIM_FG)),
+    ]))
+}
+
+/// `now`, `12m`, `3h`, `2d`.
+fn format_age(dt: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let minutes = (now - dt).num_minutes().max(0);
+    match minutes {
+        0 => "now".to_string(),
+        1..60 => format!("{minutes}m"),
+        60..1440 => format!("{}h", minutes / 60),
+        _ => format!("{}d", minutes / 1440),
+    }
+}
+
+/// Where the chosen session is resumed.
+#[derive(Debug, PartialEq, Eq)]
+enum Target {
+    /// In this process: outside tmux, or from the session's own pane.
+    Here,
+    /// Respawned in its original pane, which is alive and at a shell prompt.
+    OriginalPane(String),
+    /// In a new window of its tmux session, in its working directory.
+    NewWindow,
+}
+
+/// `original_pane` is the session's pane if it is still alive, with the
+/// pane's foreground command.
+fn choose_target(
+    in_tmux: bool,
+    current_pane: Option<&str>,
+    original_pane: Option<(&str, Option<&str>)>,
+) -> Target {
+    if !in_tmux {
+        return Target::Here;
+    }
+    match original_pane {
+        Some((pane, _)) if Some(pane) == current_pane => Target::Here,
+        Some((pane, command)) if check_idle_at_shell_prompt(command).is_ok() => {
+            Target::OriginalPane(pane.to_string())
+        }
+        _ => Target::NewWindow,
+    }
+}
+
+fn resume_session(session: &Session) -> Result<()> {
+    let original_pane = session
+        .tmux_info
+        .as_ref()
+        .map(|info| info.pane_id.as_str())
+        .filter(|pane| tmux::is_pane_alive(pane))
+        .map(|pane| (pane, tmux::get_pane_current_command(pane)));
+    let target = choose_target(
+        tmux::in_tmux(),
+        tmux::current_pane_id_from_env().as_deref(),
+        original_pane.as_ref().map(|(p, c)| (*p, c.as_deref())),
+    );
+    tracing::info!(event = "cc.resume.picked", session_id = %session.session_id, target = ?target);
+
+    if !session.cwd.is_dir() {
+        bail!(
+            "{} no longer exists; cannot resume session {} there",
+            session.cwd.display(),
+            session.session_id
+        );
+    }
+    let Some(command) = build_resume_command(Some(&session.session_id)) else {
+        bail!("Failed to build resume command");
+    };
+
+    match target {
+        Target::Here => {
+            std::env::set_current_dir(&session.cwd)?;
+            exec_claude_resume(&session.session_id)
+        }
+        Target::OriginalPane(pane) => {
+            tmux::respawn_pane(&pane, &command)?;
+            tmux::focus_pane(&pane)?;
+            Ok(())
+        }
+        Target::NewWindow => {
+            let tmux_session = session
+                .tmux_info
+                .as_ref()
+                .map(|info| info.session_name.clone())
+                .filter(|name| tmux::session_exists(name))
+                .or_else(tmux::current_session);
+            let Some(tmux_session) = tmux_session else {
+                bail!("No tmux session to open the window in");
+            };
+            let window_name = session.label.clone().unwrap_or_else(|| {
+                session
+                    .cwd
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_default()
+            });
+            let pane = tmux::new_window_with_command(
+                &tmux_session,
+                &session.cwd.to_string_lossy(),
+                &window_name,
+                &command,
+            )?;
+            tmux::focus_pane(&pane)?;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::path::PathBuf;
+
+    fn session(id: &str, status: SessionStatus, minutes_ago: i64) -> Session {
+        let updated_at = Utc::now() - chrono::Duration::minutes(minutes_ago);
+        Session {
+            session_id: id.to_string(),
+            cwd: PathBuf::from("/tmp/test"),
+            transcript_path: None,
+            tty: None,
+            tmux_info: None,
+            status,
+            created_at: updated_at,
+            updated_at,
+            last_message: None,
+            current_tool: None,
+            label: None,
+            ancestor_session_ids: Vec::new(),
+            pending_bg_task_ids: Default::default(),
+            pending_agent_task_ids: Default::default(),
+            pending_permission_agent_ids: Default::default(),
+            read_at: None,
+            sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
+        }
+    }
+
+    fn candidate(id: &str, label: Option<&str>, location: &str, prompt: &str) -> Candidate {
+        let mut s = session(id, SessionStatus::Paused, 0);
+        s.label = label.map(str::to_string);
+        Candidate::new(s, location.to_string(), prompt.to_string())
+    }
+
+    #[test]
+    fn resumable_sessions_keeps_paused_and_ended_newest_first() {
+        let sessions = vec![
+            session("old-ended", SessionStatus::Ended, 60),
+            session("running", SessionStatus::Running, 1),
+            session("paused", SessionStatus::Paused, 5),
+            session("stopped", SessionStatus::Stopped, 2),
+        ];
+        let ids: Vec<String> = resumable_sessions(sessions)
+            .into_iter()
+            .map(|s| s.session_id)
+            .collect();
+        assert_eq!(ids, vec!["paused", "old-ended"]);
+    }
+
+    #[rstest]
+    #[case::empty("", true)]
+    #[case::substring("login", true)]
+    #[case::subsequence("flb", true)]
+    #[case::case_insensitive("FLB", true)]
+    #[case::words_and("fix armyknife", true)]
+    #[case::out_of_order("blf", false)]
+    #[case::one_word_missing("fix zzz", false)]
+    fn fuzzy_matches_cases(#[case] query: &str, #[case] expected: bool) {
+        assert_eq!(
+            fuzzy_matches(query, "fix login bug armyknife \u{b7} main"),
+            expected
+        );
+    }
+
+    #[test]
+    fn typing_filters_and_enter_selects_the_highlighted_match() {
+        let mut picker = Picker::new(vec![
+            candidate(
+                "a",
+                Some("Fix login"),
+                "armyknife \u{b7} fix-login",
+                "login fails",
+            ),
+            candidate("b", None, "dotfiles \u{b7} main", "update zsh config"),
+        ]);
+        assert_eq!(picker.filtered, vec![0, 1]);
+
+        for c in "zsh".chars() {
+            assert_eq!(
+                picker.handle_key(KeyCode::Char(c), KeyModifiers::NONE),
+                Outcome::Continue
+            );
+        }
+        assert_eq!(picker.filtered, vec![1]);
+        assert_eq!(
+            picker.handle_key(KeyCode::Enter, KeyModifiers::NONE),
+            Outcome::Select
+        );
+        assert_eq!(picker.selected().unwrap().session.session_id, "b");
+        assert_eq!(picker.selected().unwrap().title(), "update zsh config");
+    }
+
+    #[test]
+    fn navigation_stays_in_bounds_and_enter_needs_a_match() {
+        let mut picker = Picker::new(vec![
+            candidate("a", None, "r", "one"),
+            candidate("b", None, "r", "two"),
+        ]);
+        picker.handle_key(KeyCode::Down, KeyModifiers::NONE);
+        picker.handle_key(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        assert_eq!(picker.selected().unwrap().session.session_id, "b");
+        picker.handle_key(KeyCode::Up, KeyModifiers::NONE);
+        assert_eq!(picker.selected().unwrap().session.session_id, "a");
+
+        picker.handle_key(KeyCode::Char('x'), KeyModifiers::NONE);
+        assert!(picker.selected().is_none());
+        assert_eq!(
+            picker.handle_key(KeyCode::Enter, KeyModifiers::NONE),
+            Outcome::Continue
+        );
+        picker.handle_key(KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!(picker.filtered.len(), 2);
+        assert_eq!(
+            picker.handle_key(KeyCode::Esc, KeyModifiers::NONE),
+            Outcome::Cancel
+        );
+    }
+
+    #[rstest]
+    #[case::outside_tmux(false, Some("%1"), Some(("%2", Some("zsh"))), Target::Here)]
+    #[case::own_pane(true, Some("%2"), Some(("%2", Some("a"))), Target::Here)]
+    #[case::idle_original_pane(
+        true,
+        Some("%1"),
+        Some(("%2", Some("zsh"))),
+        Target::OriginalPane("%2".to_string())
+    )]
+    #[case::busy_original_pane(true, Some("%1"), Some(("%2", Some("nvim"))), Target::NewWindow)]
+    #[case::pane_gone(true, Some("%1"), None, Target::NewWindow)]
+    fn choose_target_cases(
+        #[case] in_tmux: bool,
+        #[case] current_pane: Option<&str>,
+        #[case] original_pane: Option<(&str, Option<&str>)>,
+        #[case] expected: Target,
+    ) {
+        assert_eq!(
+            choose_target(in_tmux, current_pane, original_pane),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case(0, "now")]
+    #[case(12, "12m")]
+    #[case(180, "3h")]
+    #[case(2 * 1440, "2d")]
+    fn format_age_cases(#[case] minutes_ago: i64, #[case] expected: &str) {
+        let now = Utc::now();
+        assert_eq!(
+            format_age(now - chrono::Duration::minutes(minutes_ago), now),
+            expected
+        );
+    }
+}
@@ -273,7 +273,7 @@ fn run_restore(_args: &RestoreArgs) -> Result<()> {
 /// the pane's input -- so restoring the option is always safe, but typing
 /// text into that pane is not: it would land in the middle of whatever the
 /// user or Claude Code is doing.
-fn resume_command_for(session_id: &str, pane_has_claude: bool) -> Option<String> {
+pub(super) fn resume_command_for(session_id: &str, pane_has_claude: bool) -> Option<String> {
     if pane_has_claude {
         return None;
     }
@@ -0,0 +1,39 @@
+//! Client side of the `a cc serve` socket, used by `store` to read sessions
+//! from the daemon's index instead of parsing every session file.
+
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::time::Duration;
+
+use super::protocol::{Request, Response};
+use super::socket_path;
+use crate::commands::cc::types::Session;
+
+/// How long to wait on a connected daemon before falling back to the files.
+/// Connecting itself fails immediately when no daemon is listening.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Every session (including ended ones) from a running `a cc serve`, or
+/// `None` when no daemon answers in time -- callers then read the session
+/// files directly.
+pub(crate) fn list_sessions() -> Option<Vec<Session>> {
+    let stream = UnixStream::connect(socket_path().ok()?).ok()?;
+    stream.set_read_timeout(Some(CLIENT_TIMEOUT)).ok()?;
+    stream.set_write_timeout(Some(CLIENT_TIMEOUT)).ok()?;
+
+    let request = serde_json::to_string(&Request::List).ok()?;
+    writeln!(&stream, "{request}").ok()?;
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line).ok()?;
+
+    match serde_json::from_str(&line) {
+        Ok(Response::Sessions { sessions }) => Some(sessions),
+        _ => {
+            tracing::debug!(
+                event = "cc.serve.client_fallback",
+                response = %line.trim_end(),
+            );
+            None
+        }
+    }
+}
@@ -0,0 +1,314 @@
+//! `a cc serve --http` -- a read-only HTML dashboard of the session tree,
+//! live-updated over server-sent events.
+//!
+//! A deliberately small HTTP/1.1 server on std's `TcpListener`: three GET
+//! routes, one thread per connection, no keep-alive. The page itself is a
+//! static, self-contained file; it fetches `/api/tree` (the same forest
+//! `a cc tree --format json` prints) and re-fetches whenever `/events`
+//! reports a change.
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{IpAddr, SocketAddr, TcpListener, TcpStream};
+use std::path::Path;
+use std::sync::{Arc, Mutex, mpsc};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+
+use super::index::SessionIndex;
+use super::{Subscribers, lock, refresh};
+use crate::commands::cc::tree::{build_forest, fetch_pr_urls};
+use crate::commands::cc::types::Session;
+use crate::infra::git::GitRepo;
+
+const DASHBOARD_HTML: &str = include_str!("dashboard.html");
+
+/// How often PR links are re-resolved from GitHub. PRs change far less
+/// often than sessions, and each refresh is a GitHub API call.
+const PR_REFRESH_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Interval of SSE comment lines on an idle event stream, so a closed
+/// browser tab is noticed (the write fails) and its subscriber dropped.
+const SSE_KEEPALIVE: Duration = Duration::from_secs(30);
+
+/// Parses `--http`: a bare port binds to 127.0.0.1. Anything but a loopback
+/// address is refused unless `allow_remote` is set -- neither the dashboard
+/// nor `a cc metrics` has authentication.
+pub(crate) fn parse_listen_addr(value: &str, allow_remote: bool) -> Result<SocketAddr> {
+    let addr = match value.parse::<u16>() {
+        Ok(port) => SocketAddr::from(([127, 0, 0, 1], port)),
+        Err(_) => value.parse().with_context(|| {
+            format!("invalid --http address '{value}' (expected PORT or HOST:PORT)")
+        })?,
+    };
+    if !addr.ip().is_loopback() && !allow_remote {
+        bail!("refusing to serve on non-loopback address {addr} without --allow-remote");
+    }
+    Ok(addr)
+}
+
+/// A session's branch, with the `updated_at` it was resolved at.
+type CachedBranch = (DateTime<Utc>, Option<String>);
+
+/// State shared by the dashboard's connection threads.
+pub(super) struct Dashboard {
+    index: Arc<Mutex<SessionIndex>>,
+    subscribers: Subscribers,
+    /// Branch of each session's cwd, keyed by session id and re-resolved
+    /// only when the session's `updated_at` moves.
+    branches: Mutex<HashMap<String, CachedBranch>>,
+    /// PR URL of each session, refreshed every `PR_REFRESH_INTERVAL`.
+    prs: Mutex<HashMap<String, String>>,
+    /// Whether the server is bound to a loopback address, in which case
+    /// requests must also name a loopback host (see `is_loopback_host`).
+    loopback: bool,
+}
+
+impl Dashboard {
+    pub(super) fn new(
+        index: Arc<Mutex<SessionIndex>>,
+        subscribers: Subscribers,
+        loopback: bool,
+    ) -> Self {
+        Self {
+            index,
+            subscribers,
+            branches: Mutex::new(HashMap::new()),
+            prs: Mutex::new(HashMap::new()),
+            loopback,
+        }
+    }
+
+    fn tree_json(&self) -> Result<String> {
+        refresh(&self.index, &self.subscribers)?;
+        let sessions = lock(&self.index).sessions();
+        let branches = self.branches_for(&sessions);
+        let prs = lock(&self.prs).clone();
+        Ok(serde_json::to_string(&build_forest(
+            &sessions, &branches, &prs,
+        ))?)
+    }
+
+    fn branches_for(&self, sessions: &[Session]) -> HashMap<String, String> {
+        let mut cache = lock(&self.branches);
+        cache.retain(|id, _| sessions.iter().any(|s| &s.session_id == id));
+        for session in sessions {
+            let stale = cache
+                .get(&session.session_id)
+                .is_none_or(|(seen_at, _)| *seen_at != session.updated_at);
+            if stale {
+                cache.insert(
+                    session.session_id.clone(),
+                    (session.updated_at, current_branch(&session.cwd)),
+                );
+            }
+        }
+        cache
+            .iter()
+            .filter_map(|(id, (_, branch))| Some((id.clone(), branch.clone()?)))
+            .collect()
+    }
+}
+
+fn current_branch(cwd: &Path) -> Option<String> {
+    GitRepo::open_at(cwd).ok()?.current_branch().ok()
+}
+
+/// Accepts dashboard connections until the listener fails.
+pub(super) fn serve(listener: TcpListener, dashboard: Arc<Dashboard>) {
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let dashboard = Arc::clone(&dashboard);
+        thread::spawn(move || {
+            if let Err(e) = handle_connection(stream, &dashboard) {
+                tracing::debug!(event = "cc.serve.http_error", error = %e);
+            }
+        });
+    }
+}
+
+/// Re-resolves PR links forever. Runs on its own thread and drives the
+/// GitHub client's futures through `runtime`.
+pub(super) fn refresh_prs(dashboard: &Dashboard, runtime: &tokio::runtime::Handle) {
+    loop {
+        let sessions = lock(&dashboard.index).sessions();
+        let branches = dashboard.branches_for(&sessions);
+        let prs = runtime.block_on(fetch_pr_urls(&sessions, &branches));
+        *lock(&dashboard.prs) = prs;
+        thread::sleep(PR_REFRESH_INTERVAL);
+    }
+}
+
+/// The parts of an HTTP request the servers here route on.
+pub(crate) struct Request {
+    pub method: String,
+    pub path: String,
+    pub host: Option<String>,
+}
+
+/// Reads the request line and headers (bodies are never read -- every route
+/// is a GET).
+pub(crate) fn read_request(stream: &TcpStream) -> Result<Request> {
+    stream.set_read_timeout(Some(Duration::from_secs(10)))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    let mut host = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.eq_ignore_ascii_case("host")
+        {
+            host = Some(value.trim().to_string());
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts
+        .next()
+        .unwrap_or_default()
+        .split('?')
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    Ok(Request { method, path, host })
+}
+
+fn handle_connection(stream: TcpStream, dashboard: &Dashboard) -> Result<()> {
+    let request = read_request(&stream)?;
+    let mut writer = stream;
+
+    // A page on another origin can point a hostname at 127.0.0.1 (DNS
+    // rebinding) and read the responses as same-origin; checking Host
+    // closes that hole for a server that is otherwise unauthenticated.
+    if dashboard.loopback && !request.host.as_deref().is_some_and(is_loopback_host) {
+        return respond(&mut writer, "403 Forbidden", "text/plain", b"forbidden\n");
+    }
+    if request.method != "GET" {
+        return respond(
+            &mut writer,
+            "405 Method Not Allowed",
+            "text/plain",
+            b"the dashboard is read-only\n",
+        );
+    }
+
+    match request.path.as_str() {
+        "/" => respond(
+            &mut writer,
+            "200 OK",
+            "text/html; charset=utf-8",
+            DASHBOARD_HTML.as_bytes(),
+        ),
+        "/api/tree" => match dashboard.tree_json() {
+            Ok(json) => respond(&mut writer, "200 OK", "application/json", json.as_bytes()),
+            Err(e) => respond(
+                &mut writer,
+                "500 Internal Server Error",
+                "text/plain",
+                format!("{e}\n").as_bytes(),
+            ),
+        },
+        "/events" => stream_events(writer, &dashboard.subscribers),
+        _ => respond(&mut writer, "404 Not Found", "text/plain", b"not found\n"),
+    }
+}
+
+pub(crate) fn respond(
+    writer: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &[u8],
+) -> Result<()> {
+    write!(
+        writer,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+        body.len()
+    )?;
+    writer.write_all(body)?;
+    Ok(())
+}
+
+/// Streams one SSE message per session change until the browser goes away.
+fn stream_events(mut writer: TcpStream, subscribers: &Subscribers) -> Result<()> {
+    write!(
+        writer,
+        "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-store\r\nConnection: keep-alive\r\n\r\n"
+    )?;
+    writer.flush()?;
+
+    let (tx, rx) = mpsc::channel();
+    lock(subscribers).push(tx);
+    loop {
+        match rx.recv_timeout(SSE_KEEPALIVE) {
+            // Change lines are single-line JSON, so one `data:` field holds
+            // each as-is.
+            Ok(line) => write!(writer, "data: {line}\n\n")?,
+            Err(mpsc::RecvTimeoutError::Timeout) => write!(writer, ": keepalive\n\n")?,
+            Err(mpsc::RecvTimeoutError::Disconnected) => return Ok(()),
+        }
+        writer.flush()?;
+    }
+}
+
+/// Whether a `Host` header value names this machine: `localhost` or a
+/// loopback IP, with or without a port.
+pub(crate) fn is_loopback_host(host: &str) -> bool {
+    let name = match host.strip_prefix('[') {
+        Some(rest) => rest.split(']').next().unwrap_or_default(),
+        None => host.rsplit_once(':').map_or(host, |(name, _)| name),
+    };
+    name.eq_ignore_ascii_case("localhost")
+        || name.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::bare_port("7878", false, Some("127.0.0.1:7878"))]
+    #[case::loopback_v4("127.0.0.1:8080", false, Some("127.0.0.1:8080"))]
+    #[case::loopback_v6("[::1]:8080", false, Some("[::1]:8080"))]
+    #[case::remote_refused("0.0.0.0:8080", false, None)]
+    #[case::remote_allowed("0.0.0.0:8080", true, Some("0.0.0.0:8080"))]
+    #[case::invalid("localhost:port", false, None)]
+    fn parse_listen_addr_cases(
+        #[case] value: &str,
+        #[case] allow_remote: bool,
+        #[case] expected: Option<&str>,
+    ) {
+        assert_eq!(
+            parse_listen_addr(value, allow_remote)
+                .ok()
+                .map(|a| a.to_string()),
+            expected.map(str::to_string)
+        );
+    }
+
+    #[rstest]
+    #[case::localhost_with_port("localhost:7878", true)]
+    #[case::localhost("LOCALHOST", true)]
+    #[case::ipv4("127.0.0.1:7878", true)]
+    #[case::ipv6("[::1]:7878", true)]
+    #[case::rebinding_name("evil.example:7878", false)]
+    #[case::lan_ip("192.168.1.5:7878", false)]
+    fn is_loopback_host_cases(#[case] host: &str, #[case] expected: bool) {
+        assert_eq!(is_loopback_host(host), expected);
+    }
+}
@@ -0,0 +1,240 @@
+//! In-memory index of the session files, kept current by cheap re-scans.
+//!
+//! A re-scan only stats the directory's files and re-parses the ones whose
+//! stamp (mtime, size, inode) changed, so it is safe to run before every
+//! query: a client that just wrote a session file (e.g. the hook, followed
+//! by its own tmux status refresh) never reads a stale entry, no matter how
+//! far behind the file watcher is.
+
+use std::collections::HashMap;
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::commands::cc::store;
+use crate::commands::cc::types::Session;
+
+/// What identifies one version of a session file on disk. Session saves
+/// are atomic renames, so the inode alone changes on every save; mtime and
+/// size cover editors that write in place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    modified: SystemTime,
+    len: u64,
+    ino: u64,
+}
+
+impl FileStamp {
+    fn of(metadata: &fs::Metadata) -> Option<Self> {
+        Some(Self {
+            modified: metadata.modified().ok()?,
+            len: metadata.len(),
+            ino: metadata.ino(),
+        })
+    }
+}
+
+/// A change found by [`SessionIndex::refresh`], as streamed to subscribers.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum IndexChange {
+    Upserted { session: Box<Session> },
+    Removed { session_id: String },
+}
+
+pub struct SessionIndex {
+    dir: PathBuf,
+    entries: HashMap<String, (FileStamp, Session)>,
+}
+
+impl SessionIndex {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Brings the index in line with the directory and returns what changed.
+    /// Files that fail to parse (e.g. caught mid-write by an editor) keep
+    /// their previous entry until a later scan reads them cleanly.
+    pub fn refresh(&mut self) -> Result<Vec<IndexChange>> {
+        let mut seen = HashMap::new();
+        if self.dir.exists() {
+            for entry in fs::read_dir(&self.dir)? {
+                let path = entry?.path();
+                if let Some((id, stamp)) = session_file_stamp(&path) {
+                    seen.insert(id, (path, stamp));
+                }
+            }
+        }
+
+        let mut changes = Vec::new();
+        self.entries.retain(|id, _| {
+            let keep = seen.contains_key(id);
+            if !keep {
+                changes.push(IndexChange::Removed {
+                    session_id: id.clone(),
+                });
+            }
+            keep
+        });
+
+        for (id, (path, stamp)) in seen {
+            if self
+                .entries
+                .get(&id)
+                .is_some_and(|(known, _)| *known == stamp)
+            {
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            let Some(session) = store::parse_session(&content) else {
+                continue;
+            };
+            changes.push(IndexChange::Upserted {
+                session: Box::new(session.clone()),
+            });
+            self.entries.insert(id, (stamp, session));
+        }
+
+        Ok(changes)
+    }
+
+    /// Every indexed session, in `store::sort_sessions` order.
+    pub fn sessions(&self) -> Vec<Session> {
+        let mut sessions: Vec<Session> = self
+            .entries
+            .values()
+            .map(|(_, session)| session.clone())
+            .collect();
+        store::sort_sessions(&mut sessions);
+        sessions
+    }
+
+    pub fn get(&self, session_id: &str) -> Option<Session> {
+        self.entries
+            .get(session_id)
+            .map(|(_, session)| session.clone())
+    }
+}
+
+/// The session id and stamp of `path` when it is a session file (`<id>.json`,
+/// not a `.json.lock` or `.json.tmp` sibling).
+fn session_file_stamp(path: &Path) -> Option<(String, FileStamp)> {
+    if path.extension()? != "json" {
+        return None;
+    }
+    let id = path.file_stem()?.to_str()?.to_string();
+    let stamp = FileStamp::of(&fs::metadata(path).ok()?)?;
+    Some((id, stamp))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::cc::types::SessionStatus;
+    use chrono::Utc;
+
+    fn session(id: &str, status: SessionStatus) -> Session {
+        Session {
+            session_id: id.to_string(),
+            cwd: PathBuf::from("/repo"),
+            transcript_path: None,
+            tty: None,
+            tmux_info: None,
+            status,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_message: None,
+            current_tool: None,
+            label: None,
+            ancestor_session_ids: Vec::new(),
+            pending_bg_task_ids: Default::default(),
+            pending_agent_task_ids: Default::default(),
+            pending_permission_agent_ids: Default::default(),
+            read_at: None,
+            sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
+        }
+    }
+
+    fn summarize(changes: &[IndexChange]) -> Vec<(String, Option<SessionStatus>)> {
+        let mut summary: Vec<_> = changes
+            .iter()
+            .map(|change| match change {
+                IndexChange::Upserted { session } => {
+                    (session.session_id.clone(), Some(session.status))
+                }
+                IndexChange::Removed { session_id } => (session_id.clone(), None),
+            })
+            .collect();
+        summary.sort_by(|a, b| a.0.cmp(&b.0));
+        summary
+    }
+
+    #[test]
+    fn refresh_reports_added_modified_and_removed_sessions() {
+        let dir = tempfile::TempDir::new().expect("temp dir");
+        store::save_session_to(dir.path(), &session("a", SessionStatus::Running)).unwrap();
+        store::save_session_to(dir.path(), &session("b", SessionStatus::Running)).unwrap();
+        let mut index = SessionIndex::new(dir.path().to_path_buf());
+        let initial = index.refresh().unwrap();
+
+        store::save_session_to(dir.path(), &session("a", SessionStatus::Stopped)).unwrap();
+        store::delete_session_from(dir.path(), "b", false).unwrap();
+        let updated = index.refresh().unwrap();
+        let unchanged = index.refresh().unwrap();
+
+        assert_eq!(
+            (
+                summarize(&initial),
+                summarize(&updated),
+                summarize(&unchanged),
+                index.get("a").map(|s| s.status),
+                index.get("b").map(|s| s.status),
+            ),
+            (
+                vec![
+                    ("a".to_string(), Some(SessionStatus::Running)),
+                    ("b".to_string(), Some(SessionStatus::Running)),
+                ],
+                vec![
+                    ("a".to_string(), Some(SessionStatus::Stopped)),
+                    ("b".to_string(), None),
+                ],
+                vec![],
+                Some(SessionStatus::Stopped),
+                None,
+            )
+        );
+    }
+
+    #[test]
+    fn refresh_keeps_previous_entry_for_unparsable_file() {
+        let dir = tempfile::TempDir::new().expect("temp dir");
+        store::save_session_to(dir.path(), &session("a", SessionStatus::Running)).unwrap();
+        let mut index = SessionIndex::new(dir.path().to_path_buf());
+        index.refresh().unwrap();
+
+        fs::write(dir.path().join("a.json"), "{ half-written").unwrap();
+        let changes = index.refresh().unwrap();
+
+        assert_eq!(
+            (summarize(&changes), index.get("a").map(|s| s.status)),
+            (vec![], Some(SessionStatus::Running))
+        );
+    }
+}
@@ -0,0 +1,232 @@
+//! `a cc serve` -- an optional daemon that keeps the session files indexed
+//! in memory and answers queries over a Unix socket, so `cc list`, `cc
+//! watch`, and the other listing commands don't each re-read and re-parse
+//! every session file.
+//!
+//! With `--http`, it also serves a read-only HTML dashboard (see `http`).
+//!
+//! The daemon is purely a read cache: hooks keep writing the session files
+//! as before, and every client falls back to reading the files directly
+//! when no daemon is listening (see `client`).
+
+pub(crate) mod client;
+pub(crate) mod http;
+mod index;
+mod protocol;
+
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, mpsc};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use notify::{RecursiveMode, Watcher};
+
+use super::error::CcError;
+use super::store;
+use crate::shared::cache;
+use index::{IndexChange, SessionIndex};
+use protocol::{Request, Response};
+
+/// Re-scan interval used when no watcher event arrives, so subscribers see
+/// a change the watcher missed within this long.
+const RESCAN_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Args, Clone, PartialEq, Eq)]
+pub struct ServeArgs {
+    /// Also serve a read-only HTML dashboard on this address (`PORT` or
+    /// `HOST:PORT`; a bare port binds to 127.0.0.1)
+    #[arg(long, value_name = "ADDR")]
+    pub http: Option<String>,
+
+    /// Allow `--http` to bind a non-loopback address. The dashboard has no
+    /// authentication.
+    #[arg(long, requires = "http")]
+    pub allow_remote: bool,
+
+    /// Skip the periodic GitHub lookup for the dashboard's PR links; only
+    /// PRs recorded via `a cc peer report` are shown
+    #[arg(long, requires = "http")]
+    pub offline: bool,
+}
+
+/// Senders of the connections streaming changes (`subscribe`). A sender is
+/// dropped once its connection goes away.
+type Subscribers = Arc<Mutex<Vec<mpsc::Sender<String>>>>;
+
+/// Returns the daemon's socket path.
+/// Path: ~/.cache/armyknife/cc/serve.sock
+pub fn socket_path() -> Result<PathBuf> {
+    cache::base_dir()
+        .map(|d| d.join("cc").join("serve.sock"))
+        .ok_or_else(|| CcError::CacheDirNotFound.into())
+}
+
+pub fn run(args: &ServeArgs) -> Result<()> {
+    let http_addr = args
+        .http
+        .as_deref()
+        .map(|addr| http::parse_listen_addr(addr, args.allow_remote))
+        .transpose()?;
+    let sessions_dir = store::sessions_dir()?;
+    fs::create_dir_all(&sessions_dir)?;
+    let socket = socket_path()?;
+    let listener = bind(&socket)?;
+
+    let index = Arc::new(Mutex::new(SessionIndex::new(sessions_dir.clone())));
+    lock(&index).refresh()?;
+    let subscribers = Subscribers::default();
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if res.is_ok() {
+            let _ = tx.send(());
+        }
+    })?;
+    watcher.watch(&sessions_dir, RecursiveMode::NonRecursive)?;
+    {
+        let index = Arc::clone(&index);
+        let subscribers = Arc::clone(&subscribers);
+        thread::spawn(move || watch_loop(&rx, &index, &subscribers));
+    }
+
+    if let Some(addr) = http_addr {
+        let http_listener =
+            TcpListener::bind(addr).with_context(|| format!("failed to bind {addr}"))?;
+        let dashboard = Arc::new(http::Dashboard::new(
+            Arc::clone(&index),
+            Arc::clone(&subscribers),
+            addr.ip().is_loopback(),
+        ));
+        if !args.offline
+            && let Ok(runtime) = tokio::runtime::Handle::try_current()
+        {
+            let dashboard = Arc::clone(&dashboard);
+            thread::spawn(move || http::refresh_prs(&dashboard, &runtime));
+        }
+        thread::spawn(move || http::serve(http_listener, dashboard));
+        eprintln!("Serving the dashboard on http://{addr}/");
+    }
+
+    eprintln!("Serving Claude Code session state on {}", socket.display());
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let index = Arc::clone(&index);
+        let subscribers = Arc::clone(&subscribers);
+        thread::spawn(move || {
+            if let Err(e) = handle_client(stream, &index, &subscribers) {
+                tracing::debug!(event = "cc.serve.client_error", error = %e);
+            }
+        });
+    }
+    Ok(())
+}
+
+/// Binds the socket, replacing a socket file left behind by a daemon that
+/// did not shut down cleanly. Refuses to start when another daemon answers.
+fn bind(path: &Path) -> Result<UnixListener> {
+    if UnixStream::connect(path).is_ok() {
+        bail!("`a cc serve` is already running on {}", path.display());
+    }
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.into()),
+        _ => {}
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    Ok(UnixListener::bind(path)?)
+}
+
+/// A panicked client thread must not take the daemon down with it; the
+/// index is only ever replaced entry by entry, so it stays usable.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn watch_loop(rx: &mpsc::Receiver<()>, index: &Mutex<SessionIndex>, subscribers: &Subscribers) {
+    loop {
+        match rx.recv_timeout(RESCAN_INTERVAL) {
+            // Drain the burst of events a single atomic save produces
+            // (tmp write + rename) so each burst costs one re-scan.
+            Ok(()) => while rx.try_recv().is_ok() {},
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => return,
+        }
+        if let Err(e) = refresh(index, subscribers) {
+            tracing::warn!(event = "cc.serve.refresh_failed", error = %e);
+        }
+    }
+}
+
+/// Re-scans the sessions directory and streams what changed. Runs before
+/// every query as well as on watcher events, so whichever finds a change
+/// first is the one that broadcasts it.
+fn refresh(index: &Mutex<SessionIndex>, subscribers: &Subscribers) -> Result<()> {
+    let changes = lock(index).refresh()?;
+    broadcast(subscribers, &changes);
+    Ok(())
+}
+
+fn broadcast(subscribers: &Subscribers, changes: &[IndexChange]) {
+    if changes.is_empty() {
+        return;
+    }
+    let lines: Vec<String> = changes
+        .iter()
+        .filter_map(|change| serde_json::to_string(change).ok())
+        .collect();
+    lock(subscribers).retain(|tx| lines.iter().all(|line| tx.send(line.clone()).is_ok()));
+}
+
+fn handle_client(
+    stream: UnixStream,
+    index: &Mutex<SessionIndex>,
+    subscribers: &Subscribers,
+) -> Result<()> {
+    let reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Request>(&line) {
+            Ok(Request::Subscribe) => return stream_changes(writer, subscribers),
+            Ok(Request::List) => {
+                refresh(index, subscribers)?;
+                Response::Sessions {
+                    sessions: lock(index).sessions(),
+                }
+            }
+            Ok(Request::Get { session_id }) => {
+                refresh(index, subscribers)?;
+                Response::Session {
+                    session: lock(index).get(&session_id).map(Box::new),
+                }
+            }
+            Err(e) => Response::Error {
+                message: format!("invalid request: {e}"),
+            },
+        };
+        writeln!(writer, "{}", serde_json::to_string(&response)?)?;
+    }
+    Ok(())
+}
+
+/// Forwards every broadcast change to `writer` until the client hangs up.
+fn stream_changes(mut writer: UnixStream, subscribers: &Subscribers) -> Result<()> {
+    let (tx, rx) = mpsc::channel();
+    lock(subscribers).push(tx);
+    for line in rx {
+        writeln!(writer, "{line}")?;
+    }
+    Ok(())
+}
@@ -0,0 +1,44 @@
+//! Wire format of the `a cc serve` socket: one JSON object per line in both
+//! directions. A connection may send any number of `list` / `get` requests,
+//! each answered by one response line; `subscribe` turns the connection
+//! into a one-way stream of `IndexChange` lines.
+
+use serde::{Deserialize, Serialize};
+
+use crate::commands::cc::types::Session;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Request {
+    /// Every session, including ended ones, in `store::sort_sessions` order.
+    List,
+    /// One session by id.
+    Get { session_id: String },
+    /// Stream a line per session change until the client disconnects.
+    Subscribe,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Response {
+    Sessions { sessions: Vec<Session> },
+    Session { session: Option<Box<Session>> },
+    Error { message: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::list(r#"{"op":"list"}"#, Request::List)]
+    #[case::get(
+        r#"{"op":"get","session_id":"abc"}"#,
+        Request::Get { session_id: "abc".to_string() }
+    )]
+    #[case::subscribe(r#"{"op":"subscribe"}"#, Request::Subscribe)]
+    fn request_wire_format(#[case] line: &str, #[case] expected: Request) {
+        assert_eq!(serde_json::from_str::<Request>(line).unwrap(), expected);
+    }
+}
@@ -0,0 +1,440 @@
+//! Standalone snapshot/restore of the tmux windows Claude Code works in.
+//!
+//! Unlike `cc resurrect`, which only re-attaches session IDs to panes that
+//! tmux-resurrect already rebuilt, `a cc snapshot save` records each
+//! worktree window's layout, its panes' working directories and commands,
+//! and their Claude Code session IDs, and `a cc snapshot restore` rebuilds
+//! the windows from that alone (after a reboot, with no tmux plugin),
+//! resuming each Claude Code session in its original pane.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use clap::{Args, Subcommand};
+use serde::{Deserialize, Serialize};
+
+use super::error::CcError;
+use super::resurrect::resume_command_for;
+use super::store;
+use super::types::{SessionStatus, TMUX_SESSION_OPTION};
+use crate::infra::tmux::{self, PaneLayoutInfo, layout};
+use crate::shared::cache;
+use crate::shared::config;
+
+#[derive(Subcommand, Clone, PartialEq, Eq)]
+pub enum SnapshotCommands {
+    /// Record the layout, panes, and Claude Code sessions of every worktree window
+    Save(SaveArgs),
+
+    /// Rebuild the recorded windows that don't exist, resuming their sessions
+    Restore(RestoreArgs),
+}
+
+#[derive(Args, Clone, PartialEq, Eq)]
+pub struct SaveArgs {
+    /// Snapshot file (default: ~/.cache/armyknife/cc/snapshot.json)
+    #[arg(long)]
+    pub file: Option<PathBuf>,
+}
+
+#[derive(Args, Clone, PartialEq, Eq)]
+pub struct RestoreArgs {
+    /// Snapshot file (default: ~/.cache/armyknife/cc/snapshot.json)
+    #[arg(long)]
+    pub file: Option<PathBuf>,
+
+    /// Print what would be restored without touching tmux
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Snapshot {
+    saved_at: DateTime<Utc>,
+    windows: Vec<WindowSnapshot>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct WindowSnapshot {
+    session_name: String,
+    window_name: String,
+    /// tmux's `#{window_layout}` string.
+    layout: String,
+    panes: Vec<PaneSnapshot>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct PaneSnapshot {
+    cwd: String,
+    /// Command to re-run, `None` for a pane that was at its shell prompt.
+    #[serde(default)]
+    command: Option<String>,
+    /// The Claude Code session to resume in this pane.
+    #[serde(default)]
+    session_id: Option<String>,
+    #[serde(default)]
+    active: bool,
+}
+
+impl PaneSnapshot {
+    /// What to type into the rebuilt pane: resuming its session wins over
+    /// re-running the command it had in the foreground.
+    fn restore_command(&self) -> Option<String> {
+        match &self.session_id {
+            Some(id) => resume_command_for(id, false),
+            None => self.command.clone(),
+        }
+    }
+}
+
+pub fn run(cmd: &SnapshotCommands) -> Result<()> {
+    match cmd {
+        SnapshotCommands::Save(args) => run_save(args),
+        SnapshotCommands::Restore(args) => run_restore(args),
+    }
+}
+
+/// Returns the default snapshot file path.
+/// Path: ~/.cache/armyknife/cc/snapshot.json
+fn default_path() -> Result<PathBuf> {
+    cache::base_dir()
+        .map(|d| d.join("cc").join("snapshot.json"))
+        .ok_or_else(|| CcError::CacheDirNotFound.into())
+}
+
+fn resolve_path(file: Option<&PathBuf>) -> Result<PathBuf> {
+    file.cloned().map_or_else(default_path, Ok)
+}
+
+fn run_save(args: &SaveArgs) -> Result<()> {
+    let path = resolve_path(args.file.as_ref())?;
+    let config = config::load_config()?;
+    let panes = tmux::list_all_pane_layouts(TMUX_SESSION_OPTION)?;
+
+    let windows = collect_windows(panes, &config.wm.worktrees_dir, |session_id| {
+        store::load_session(session_id)
+            .ok()
+            .flatten()
+            .is_some_and(|s| s.status != SessionStatus::Ended)
+    });
+
+    // Like `cc resurrect save`, keep the previous snapshot rather than
+    // overwrite it with nothing: a save that runs right after a reboot,
+    // before the restore, must not destroy what the restore needs.
+    if windows.is_empty() {
+        println!(
+            "No worktree windows to snapshot; keeping {}",
+            path.display()
+        );
+        return Ok(());
+    }
+
+    let session_count: usize = windows
+        .iter()
+        .map(|w| w.panes.iter().filter(|p| p.session_id.is_some()).count())
+        .sum();
+    let window_count = windows.len();
+    write_snapshot(
+        &path,
+        &Snapshot {
+            saved_at: Utc::now(),
+            windows,
+        },
+    )?;
+    tracing::info!(
+        event = "cc.snapshot.saved",
+        windows = window_count,
+        sessions = session_count,
+    );
+    println!(
+        "Saved {window_count} window(s) with {session_count} Claude Code session(s) to {}",
+        path.display()
+    );
+    Ok(())
+}
+
+fn run_restore(args: &RestoreArgs) -> Result<()> {
+    let path = resolve_path(args.file.as_ref())?;
+    let snapshot = read_snapshot(&path)?;
+
+    let mut restored = 0;
+    for window in &snapshot.windows {
+        let target = format!("{}:{}", window.session_name, window.window_name);
+        if tmux::list_window_names(&window.session_name).contains(&window.window_name) {
+            println!("Skipping {target}: window already exists");
+            continue;
+        }
+        let sessions = window
+            .panes
+            .iter()
+            .filter(|p| p.session_id.is_some())
+            .count();
+        if args.dry_run {
+            println!(
+                "Would restore {target} ({} pane(s), {sessions} Claude Code session(s))",
+                window.panes.len()
+            );
+            continue;
+        }
+
+        let commands: Vec<Option<String>> =
+            window.panes.iter().map(|p| p.restore_command()).collect();
+        let panes: Vec<layout::RestorePane> = window
+            .panes
+            .iter()
+            .zip(&commands)
+            .map(|(pane, command)| layout::RestorePane {
+                cwd: &pane.cwd,
+                command: command.as_deref(),
+                option: pane
+                    .session_id
+                    .as_deref()
+                    .map(|id| (TMUX_SESSION_OPTION, id)),
+                active: pane.active,
+            })
+            .collect();
+        let result = layout::restore_window(&layout::RestoreWindowSpec {
+            session: &window.session_name,
+            window_name: &window.window_name,
+            layout: &window.layout,
+            panes: &panes,
+        });
+        match result {
+            Ok(()) => {
+                restored += 1;
+                println!(
+                    "Restored {target} ({} pane(s), {sessions} Claude Code session(s))",
+                    window.panes.len()
+                );
+            }
+            Err(e) => {
+                tracing::warn!(event = "cc.snapshot.restore_failed", window = %target, error = %e);
+                println!("Failed to restore {target}: {e}");
+            }
+        }
+    }
+    tracing::info!(
+        event = "cc.snapshot.restored",
+        restored,
+        total = snapshot.windows.len(),
+    );
+    Ok(())
+}
+
+/// Groups panes into the windows worth snapshotting: those with a pane in a
+/// worktree (a path under a `worktrees_dir` directory) or resuming a Claude
+/// Code session. A pane's session ID is kept only when `is_resumable`
+/// says the session is still alive; a pane left at its shell prompt gets
+/// no command.
+fn collect_windows(
+    panes: Vec<PaneLayoutInfo>,
+    worktrees_dir: &str,
+    is_resumable: impl Fn(&str) -> bool,
+) -> Vec<WindowSnapshot> {
+    let marker = format!("/{}/", worktrees_dir.trim_matches('/'));
+    let in_worktree = |path: &str| format!("{path}/").contains(&marker);
+
+    let mut windows: BTreeMap<(String, u32), WindowSnapshot> = BTreeMap::new();
+    for pane in panes {
+        let window = windows
+            .entry((pane.session_name.clone(), pane.window_index))
+            .or_insert_with(|| WindowSnapshot {
+                session_name: pane.session_name.clone(),
+                window_name: pane.window_name.clone(),
+                layout: pane.window_layout.clone(),
+                panes: Vec::new(),
+            });
+        let session_id = pane.option_value.filter(|id| is_resumable(id));
+        let command = Some(pane.current_command).filter(|c| !c.is_empty() && !is_shell(c));
+        window.panes.push(PaneSnapshot {
+            cwd: pane.current_path,
+            command,
+            session_id,
+            active: pane.pane_active,
+        });
+    }
+
+    windows
+        .into_values()
+        .filter(|w| {
+            w.panes
+                .iter()
+                .any(|p| p.session_id.is_some() || in_worktree(&p.cwd))
+        })
+        .collect()
+}
+
+/// Whether `command` (tmux's `#{pane_current_command}`) is an interactive
+/// shell, i.e. the pane was idle at its prompt.
+fn is_shell(command: &str) -> bool {
+    const SHELLS: &[&str] = &["bash", "zsh", "fish", "sh", "dash", "ksh", "tcsh", "nu"];
+    let command = command.trim_start_matches('-');
+    SHELLS.contains(&command)
+        || std::env::var("SHELL").is_ok_and(|shell| {
+            Path::new(&shell)
+                .file_name()
+                .is_some_and(|name| name == command)
+        })
+}
+
+fn read_snapshot(path: &Path) -> Result<Snapshot> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("no snapshot at {}", path.display()))?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("invalid snapshot file {}", path.display()))
+}
+
+fn write_snapshot(path: &Path, snapshot: &Snapshot) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let temp_path = path.with_extension("json.tmp");
+    let mut temp_file = File::create(&temp_path)?;
+    temp_file.write_all(serde_json::to_string_pretty(snapshot)?.as_bytes())?;
+    temp_file.sync_all()?;
+    fs::rename(&temp_path, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn pane(
+        window_index: u32,
+        pane_index: u32,
+        path: &str,
+        command: &str,
+        session_id: Option<&str>,
+    ) -> PaneLayoutInfo {
+        PaneLayoutInfo {
+            session_name: "main".to_string(),
+            window_index,
+            window_name: format!("w{window_index}"),
+            window_layout: format!("layout-{window_index}"),
+            pane_index,
+            pane_active: pane_index == 1,
+            current_path: path.to_string(),
+            current_command: command.to_string(),
+            option_value: session_id.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn collect_windows_keeps_worktree_and_claude_windows() {
+        let panes = vec![
+            // A worktree window: Claude Code next to an editor.
+            pane(1, 1, "/repo/.worktrees/fix", "claude", Some("live")),
+            pane(1, 2, "/repo/.worktrees/fix/src", "nvim", None),
+            // Not a worktree, no session: dropped.
+            pane(2, 1, "/home/me", "zsh", None),
+            // Outside a worktree but with a live session: kept.
+            pane(3, 1, "/tmp/scratch", "claude", Some("live")),
+            // An ended session's leftover option is not resumed.
+            pane(4, 1, "/repo/.worktrees/old", "zsh", Some("ended")),
+        ];
+
+        let windows = collect_windows(panes, ".worktrees", |id| id == "live");
+
+        assert_eq!(
+            windows,
+            vec![
+                WindowSnapshot {
+                    session_name: "main".to_string(),
+                    window_name: "w1".to_string(),
+                    layout: "layout-1".to_string(),
+                    panes: vec![
+                        PaneSnapshot {
+                            cwd: "/repo/.worktrees/fix".to_string(),
+                            command: Some("claude".to_string()),
+                            session_id: Some("live".to_string()),
+                            active: true,
+                        },
+                        PaneSnapshot {
+                            cwd: "/repo/.worktrees/fix/src".to_string(),
+                            command: Some("nvim".to_string()),
+                            session_id: None,
+                            active: false,
+                        },
+                    ],
+                },
+                WindowSnapshot {
+                    session_name: "main".to_string(),
+                    window_name: "w3".to_string(),
+                    layout: "layout-3".to_string(),
+                    panes: vec![PaneSnapshot {
+                        cwd: "/tmp/scratch".to_string(),
+                        command: Some("claude".to_string()),
+                        session_id: Some("live".to_string()),
+                        active: true,
+                    }],
+                },
+                WindowSnapshot {
+                    session_name: "main".to_string(),
+                    window_name: "w4".to_string(),
+                    layout: "layout-4".to_string(),
+                    panes: vec![PaneSnapshot {
+                        cwd: "/repo/.worktrees/old".to_string(),
+                        command: None,
+                        session_id: None,
+                        active: true,
+                    }],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn restore_command_prefers_resuming_the_session() {
+        let with_session = PaneSnapshot {
+            cwd: "/w".to_string(),
+            command: Some("claude".to_string()),
+            session_id: Some("abc".to_string()),
+            active: false,
+        };
+        assert_eq!(
+            with_session.restore_command().as_deref(),
+            Some("a cc resume abc")
+        );
+
+        let editor = PaneSnapshot {
+            session_id: None,
+            command: Some("nvim".to_string()),
+            ..with_session
+        };
+        assert_eq!(editor.restore_command().as_deref(), Some("nvim"));
+    }
+
+    #[test]
+    fn snapshot_round_trips_through_the_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("cc").join("snapshot.json");
+        let snapshot = Snapshot {
+            saved_at: Utc::now(),
+            windows: collect_windows(
+                vec![pane(1, 1, "/repo/.worktrees/a", "zsh", Some("s1"))],
+                ".worktrees",
+                |_| true,
+            ),
+        };
+
+        write_snapshot(&path, &snapshot).unwrap();
+        assert_eq!(read_snapshot(&path).unwrap(), snapshot);
+    }
+
+    #[test]
+    fn read_snapshot_reports_a_missing_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("snapshot.json");
+        let err = read_snapshot(&path).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("no snapshot at {}", path.display())
+        );
+    }
+}
@@ -0,0 +1,425 @@
+//! `a cc statusline` -- Claude Code's statusLine command.
+//!
+//! Claude Code pipes a JSON description of the session to the command on
+//! every status line refresh and shows the first line it prints. This
+//! combines that payload with what armyknife tracks about the session
+//! (label, pending background tasks, auto-pause timing) and the worktree's
+//! branch and PR, and renders `cc.statusline.template`.
+
+use std::collections::HashMap;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use clap::Args;
+use serde::Deserialize;
+
+use super::auto_pause;
+use super::claude_sessions;
+use super::pr_cache;
+use super::store;
+use super::template;
+use super::types::{Session, SessionStatus};
+use crate::infra::git::{GitRepo, github_owner_and_repo};
+use crate::infra::github::{BranchPrQuery, CheckState, PrInfo, PrState, ReviewDecision};
+use crate::shared::config::{self, Config};
+
+#[derive(Args, Clone, PartialEq, Eq)]
+pub struct StatuslineArgs {
+    /// Template to render instead of `cc.statusline.template`
+    #[arg(long)]
+    pub template: Option<String>,
+}
+
+/// The parts of Claude Code's statusLine payload used here.
+#[derive(Debug, Default, Deserialize)]
+struct StatusLineInput {
+    #[serde(default)]
+    session_id: String,
+    #[serde(default)]
+    transcript_path: Option<PathBuf>,
+    #[serde(default)]
+    cwd: Option<PathBuf>,
+    #[serde(default)]
+    model: Option<ModelInfo>,
+    #[serde(default)]
+    workspace: Option<WorkspaceInfo>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ModelInfo {
+    #[serde(default)]
+    display_name: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct WorkspaceInfo {
+    #[serde(default)]
+    current_dir: Option<PathBuf>,
+}
+
+/// Variables whose values need a GitHub lookup.
+const PR_VARIABLES: &[&str] = &["pr", "pr_state", "review", "ci"];
+
+/// Variables whose values need the transcript's token usage.
+const CONTEXT_VARIABLES: &[&str] = &["context", "context_tokens", "context_percent"];
+
+pub async fn run(args: &StatuslineArgs) -> Result<()> {
+    let mut raw = String::new();
+    io::stdin().read_to_string(&mut raw)?;
+    let input: StatusLineInput =
+        serde_json::from_str(&raw).context("invalid statusLine JSON on stdin")?;
+
+    let config = config::load_config().unwrap_or_default();
+    let template = args
+        .template
+        .clone()
+        .unwrap_or_else(|| config.cc.statusline.template.clone());
+    let uses = |names: &[&str]| names.iter().any(|name| template::uses(&template, name));
+
+    let session = if input.session_id.is_empty() {
+        None
+    } else {
+        store::load_session(&input.session_id).ok().flatten()
+    };
+    let cwd = input
+        .workspace
+        .as_ref()
+        .and_then(|w| w.current_dir.clone())
+        .or_else(|| input.cwd.clone())
+        .or_else(|| session.as_ref().map(|s| s.cwd.clone()))
+        .unwrap_or_default();
+    let repo = GitRepo::open_at(&cwd).ok();
+    let branch = repo
+        .as_ref()
+        .and_then(|r| r.current_branch().ok())
+        .filter(|b| b != "HEAD");
+
+    let pr = match (&repo, &branch) {
+        (Some(repo), Some(branch)) if uses(PR_VARIABLES) => {
+            lookup_pr(repo, branch, &config.cc.statusline.pr_ttl).await
+        }
+        _ => None,
+    };
+    let context_tokens = if uses(CONTEXT_VARIABLES) {
+        input
+            .transcript_path
+            .as_deref()
+            .and_then(claude_sessions::get_last_context_tokens_from_transcript)
+    } else {
+        None
+    };
+
+    let resolved = Resolved {
+        model: input.model.and_then(|m| m.display_name),
+        cwd,
+        session,
+        branch,
+        pr,
+        context_tokens,
+    };
+    println!(
+        "{}",
+        template::render(
+            &template,
+            &variables(&resolved, &config, Utc::now()),
+            "statusline"
+        )?
+    );
+    Ok(())
+}
+
+async fn lookup_pr(repo: &GitRepo, branch: &str, ttl: &str) -> Option<PrInfo> {
+    let (owner, name) = github_owner_and_repo(repo).ok()?;
+    let ttl = auto_pause::parse_duration(ttl).unwrap_or(Duration::from_secs(60));
+    let query = BranchPrQuery {
+        owner,
+        repo: name,
+        branch: branch.to_string(),
+    };
+    let key = (
+        query.owner.clone(),
+        query.repo.clone(),
+        query.branch.clone(),
+    );
+    pr_cache::cached_prs(&[query], ttl)
+        .await
+        .remove(&key)
+        .flatten()
+}
+
+/// Everything the variables are computed from.
+#[derive(Debug, Default)]
+struct Resolved {
+    model: Option<String>,
+    cwd: PathBuf,
+    session: Option<Session>,
+    branch: Option<String>,
+    pr: Option<PrInfo>,
+    context_tokens: Option<u64>,
+}
+
+/// Every template variable, empty when unknown or not applicable.
+fn variables(
+    resolved: &Resolved,
+    config: &Config,
+    now: DateTime<Utc>,
+) -> HashMap<&'static str, String> {
+    let session = resolved.session.as_ref();
+    let pr = resolved.pr.as_ref();
+    let threshold = config.cc.auto_compact.min_context_tokens;
+
+    let mut vars = HashMap::new();
+    vars.insert("model", resolved.model.clone().unwrap_or_default());
+    vars.insert("dir", dir_name(&resolved.cwd));
+    vars.insert(
+        "status",
+        session
+            .map(|s| s.display_symbol().to_string())
+            .unwrap_or_default(),
+    );
+    vars.insert(
+        "label",
+        session.and_then(|s| s.label.clone()).unwrap_or_default(),
+    );
+    vars.insert("branch", resolved.branch.clone().unwrap_or_default());
+    vars.insert(
+        "pr",
+        pr.map(|p| format!("#{}", p.number)).unwrap_or_default(),
+    );
+    vars.insert(
+        "pr_state",
+        pr.map(|p| pr_state_name(&p.state).to_string())
+            .unwrap_or_default(),
+    );
+    vars.insert(
+        "review",
+        pr.and_then(|p| p.review)
+            .map(|r| review_name(r).to_string())
+            .unwrap_or_default(),
+    );
+    vars.insert(
+        "ci",
+        pr.and_then(|p| p.checks)
+            .map(|c| checks_name(c).to_string())
+            .unwrap_or_default(),
+    );
+    vars.insert(
+        "context",
+        resolved
+            .context_tokens
+            .map(|t| format!("{}/{}", format_tokens(t), format_tokens(threshold)))
+            .unwrap_or_default(),
+    );
+    vars.insert(
+        "context_tokens",
+        resolved
+            .context_tokens
+            .map(|t| t.to_string())
+            .unwrap_or_default(),
+    );
+    vars.insert(
+        "context_percent",
+        resolved
+            .context_tokens
+            .filter(|_| threshold > 0)
+            .map(|t| (t * 100 / threshold).to_string())
+            .unwrap_or_default(),
+    );
+    vars.insert(
+        "pause_in",
+        session
+            .and_then(|s| pause_in(s, config, now))
+            .map(format_remaining)
+            .unwrap_or_default(),
+    );
+    let bg_tasks = session.map_or(0, |s| {
+        s.pending_bg_task_ids.len() + s.pending_agent_task_ids.len()
+    });
+    vars.insert(
+        "bg_tasks",
+        if bg_tasks > 0 {
+            bg_tasks.to_string()
+        } else {
+            String::new()
+        },
+    );
+    vars
+}
+
+fn dir_name(cwd: &Path) -> String {
+    cwd.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Time left before `a cc sweep` may pause a Stopped session. `None` for
+/// any other status (only Stopped sessions are paused), while background
+/// tasks keep it from pausing, or with auto-pause disabled.
+fn pause_in(session: &Session, config: &Config, now: DateTime<Utc>) -> Option<Duration> {
+    let auto_pause = &config.cc.auto_pause;
+    if !auto_pause.enabled
+        || session.status != SessionStatus::Stopped
+        || session.has_pending_bg_tasks()
+    {
+        return None;
+    }
+    let timeout = auto_pause::parse_duration(&auto_pause.timeout).ok()?;
+    let idle = (now - session.updated_at).to_std().unwrap_or_default();
+    Some(timeout.saturating_sub(idle))
+}
+
+pub(super) fn pr_state_name(state: &PrState) -> &'static str {
+    match state {
+        PrState::Open => "open",
+        PrState::Closed => "closed",
+        PrState::Merged => "merged",
+    }
+}
+
+pub(super) fn review_name(review: ReviewDecision) -> &'static str {
+    match review {
+        ReviewDecision::Approved => "approved",
+        ReviewDecision::ChangesRequested => "changes requested",
+        ReviewDecision::ReviewRequired => "review required",
+    }
+}
+
+pub(super) fn checks_name(checks: CheckState) -> &'static str {
+    match checks {
+        CheckState::Success => "passing",
+        CheckState::Failure | CheckState::Error => "failing",
+        CheckState::Pending | CheckState::Expected => "pending",
+    }
+}
+
+/// `950`, `12k`, `1.2M`.
+fn format_tokens(tokens: u64) -> String {
+    match tokens {
+        0..1_000 => tokens.to_string(),
+        1_000..1_000_000 => format!("{}k", tokens / 1_000),
+        _ => format!("{:.1}M", tokens as f64 / 1_000_000.0),
+    }
+}
+
+/// `45s`, `12m`, `1h05m`.
+fn format_remaining(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3_600 => format!("{}m", secs / 60),
+        _ => format!("{}h{:02}m", secs / 3_600, secs % 3_600 / 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use rstest::rstest;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn default_template_renders_with_every_variable_empty() {
+        let config = Config::default();
+        let vars = variables(&Resolved::default(), &config, Utc::now());
+        assert_eq!(
+            template::render(&config.cc.statusline.template, &vars, "statusline").unwrap(),
+            ""
+        );
+    }
+
+    fn stopped_session(updated_at: DateTime<Utc>) -> Session {
+        Session {
+            session_id: "s1".to_string(),
+            cwd: PathBuf::from("/work/repo"),
+            transcript_path: None,
+            tty: None,
+            tmux_info: None,
+            status: SessionStatus::Stopped,
+            created_at: updated_at,
+            updated_at,
+            last_message: None,
+            current_tool: None,
+            label: Some("fix login".to_string()),
+            ancestor_session_ids: Vec::new(),
+            pending_bg_task_ids: BTreeSet::new(),
+            pending_agent_task_ids: BTreeSet::new(),
+            pending_permission_agent_ids: BTreeSet::new(),
+            read_at: None,
+            sweep_signaled: false,
+            report: None,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            tags: Default::default(),
+            note: None,
+        }
+    }
+
+    #[test]
+    fn default_template_renders_full_session() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap();
+        let resolved = Resolved {
+            model: Some("Opus".to_string()),
+            cwd: PathBuf::from("/work/repo"),
+            session: Some(stopped_session(now - chrono::Duration::minutes(10))),
+            branch: Some("fix-login".to_string()),
+            pr: Some(PrInfo {
+                number: 42,
+                state: PrState::Open,
+                url: "https://github.com/o/r/pull/42".to_string(),
+                title: String::new(),
+                draft: false,
+                review: Some(ReviewDecision::Approved),
+                checks: Some(CheckState::Pending),
+                unresolved_threads: None,
+            }),
+            context_tokens: Some(95_500),
+        };
+        let config = Config::default();
+        let vars = variables(&resolved, &config, now);
+
+        assert_eq!(
+            template::render(&config.cc.statusline.template, &vars, "statusline").unwrap(),
+            "fix login │ fix-login #42 approved ci:pending │ ctx 95k/180k"
+        );
+        assert_eq!(vars["pause_in"], "20m");
+        assert_eq!(vars["context_percent"], "53");
+        assert_eq!(vars["dir"], "repo");
+        assert_eq!(vars["status"], "✱");
+    }
+
+    #[test]
+    fn pause_in_is_empty_while_background_tasks_run() {
+        let now = Utc::now();
+        let mut session = stopped_session(now);
+        session.pending_bg_task_ids.insert("bg-1".to_string());
+        let resolved = Resolved {
+            session: Some(session),
+            ..Default::default()
+        };
+        let vars = variables(&resolved, &Config::default(), now);
+        assert_eq!(vars["pause_in"], "");
+        assert_eq!(vars["bg_tasks"], "1");
+    }
+
+    #[rstest]
+    #[case(950, "950")]
+    #[case(12_345, "12k")]
+    #[case(1_234_567, "1.2M")]
+    fn format_tokens_cases(#[case] tokens: u64, #[case] expected: &str) {
+        assert_eq!(format_tokens(tokens), expected);
+    }
+
+    #[rstest]
+    #[case(45, "45s")]
+    #[case(720, "12m")]
+    #[case(3_900, "1h05m")]
+    fn format_remaining_cases(#[case] secs: u64, #[case] expected: &str) {
+        assert_eq!(format_remaining(Duration::from_secs(secs)), expected);
+    }
+}
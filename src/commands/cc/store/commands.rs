@@ -0,0 +1,110 @@
+//! `a cc store` -- maintenance and queries for the session store itself.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+use super::index::{self, Lookup};
+use super::schema::CURRENT_VERSION;
+use super::{migrate_sessions_in, sessions_dir};
+use crate::commands::cc::error::CcError;
+
+#[derive(Subcommand, Clone, PartialEq, Eq)]
+pub enum StoreCommands {
+    /// Rebuild the session index from the session files (recovery)
+    #[command(name = "rebuild-index")]
+    RebuildIndex,
+
+    /// Print the IDs of the sessions matching a key, via the index
+    Lookup(LookupArgs),
+
+    /// Upgrade session files written by older armyknife versions to the
+    /// current schema
+    Migrate(MigrateArgs),
+}
+
+#[derive(Args, Clone, PartialEq, Eq)]
+pub struct MigrateArgs {
+    /// Only report outdated session files; exit non-zero if there are any
+    #[arg(long)]
+    pub check: bool,
+}
+
+#[derive(Args, Clone, PartialEq, Eq)]
+#[group(required = true, multiple = false)]
+pub struct LookupArgs {
+    /// Pane ID (e.g. `%3`)
+    #[arg(long)]
+    pub pane: Option<String>,
+
+    /// Window as `<session>:<index>` (e.g. `main:2`)
+    #[arg(long)]
+    pub window: Option<String>,
+
+    /// Directory; matches sessions in it or below it
+    #[arg(long)]
+    pub cwd: Option<PathBuf>,
+
+    /// Session ID of the parent (delegating) session
+    #[arg(long)]
+    pub parent: Option<String>,
+}
+
+impl LookupArgs {
+    fn lookup(&self) -> Option<Lookup<'_>> {
+        if let Some(pane) = &self.pane {
+            return Some(Lookup::Pane(pane));
+        }
+        if let Some(window) = &self.window {
+            return Some(Lookup::Window(window));
+        }
+        if let Some(cwd) = &self.cwd {
+            return Some(Lookup::Cwd(cwd));
+        }
+        self.parent.as_deref().map(Lookup::Parent)
+    }
+}
+
+pub(crate) fn run(cmd: &StoreCommands) -> Result<()> {
+    let dir = sessions_dir()?;
+    match cmd {
+        StoreCommands::RebuildIndex => {
+            let sessions = index::rebuild(&dir)?;
+            println!("Indexed {} sessions", sessions.len());
+        }
+        StoreCommands::Lookup(args) => {
+            // clap's required group guarantees one key is set
+            let Some(lookup) = args.lookup() else {
+                return Ok(());
+            };
+            for session_id in index::lookup(&dir, &lookup)? {
+                println!("{session_id}");
+            }
+        }
+        StoreCommands::Migrate(args) => run_migrate(&dir, args.check)?,
+    }
+    Ok(())
+}
+
+fn run_migrate(dir: &Path, check: bool) -> Result<()> {
+    let report = migrate_sessions_in(dir, check)?;
+
+    let verb = if check { "needs migration" } else { "migrated" };
+    for (session_id, version) in &report.outdated {
+        println!("{session_id}: {verb} (v{version} -> v{CURRENT_VERSION})");
+    }
+    for (session_id, version) in &report.too_new {
+        eprintln!(
+            "[armyknife] warning: {session_id} has schema v{version}, newer than this armyknife (v{CURRENT_VERSION}); left untouched"
+        );
+    }
+
+    if check && !report.outdated.is_empty() {
+        return Err(CcError::SessionsNeedMigration(report.outdated.len()).into());
+    }
+    if !check {
+        println!("Migrated {} sessions", report.outdated.len());
+    }
+    Ok(())
+}
@@ -0,0 +1,367 @@
+//! Compact on-disk index of the session files.
+//!
+//! `sessions/.index/sessions.json` maps each session ID to the handful of
+//! fields that listing, cleanup, and lookups need (status, `updated_at`,
+//! pane, window, cwd, parent), so `list_sessions` skips Ended files and a
+//! lookup by pane, window, cwd, or parent never parses every session. Each
+//! store write upserts its session's entry while still holding that
+//! session's lock; the index file itself is guarded by its own lock file.
+//!
+//! The index also records the sessions directory's mtime as of its last
+//! update (session lock files live in `.locks/`, so taking a lock does not
+//! move it). A write that bypasses it -- an older armyknife, a hand edit, an
+//! index update that timed out -- moves that mtime, so readers see the
+//! mismatch and rebuild from a full scan instead of trusting stale entries.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::acquire_lock;
+use crate::commands::cc::types::{Session, SessionStatus};
+
+/// Directory inside the sessions directory that holds the index. A dotted
+/// name has no extension, so the `*.json` scans and the non-recursive file
+/// watchers on the sessions directory never see it.
+const INDEX_DIR: &str = ".index";
+
+const INDEX_FILE: &str = "sessions.json";
+
+/// What the index keeps about one session.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct IndexEntry {
+    pub status: SessionStatus,
+    pub updated_at: DateTime<Utc>,
+    pub cwd: PathBuf,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pane_id: Option<String>,
+    /// The pane's window as a tmux target, `<session_name>:<window_index>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window: Option<String>,
+    /// The nearest entry of `ancestor_session_ids`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+}
+
+impl IndexEntry {
+    pub(crate) fn of(session: &Session) -> Self {
+        let tmux_info = session.tmux_info.as_ref();
+        Self {
+            status: session.status,
+            updated_at: session.updated_at,
+            cwd: session.cwd.clone(),
+            pane_id: tmux_info.map(|info| info.pane_id.clone()),
+            window: tmux_info.map(|info| format!("{}:{}", info.session_name, info.window_index)),
+            parent_id: session.ancestor_session_ids.last().cloned(),
+        }
+    }
+}
+
+/// A key to find sessions by without a full scan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lookup<'a> {
+    /// The pane ID recorded in `tmux_info` (e.g. `%3`).
+    Pane(&'a str),
+    /// A window as `<session_name>:<window_index>`.
+    Window(&'a str),
+    /// Sessions whose cwd is this directory or below it.
+    Cwd(&'a Path),
+    /// Sessions whose immediate parent is this session ID.
+    Parent(&'a str),
+}
+
+impl Lookup<'_> {
+    fn matches(&self, entry: &IndexEntry) -> bool {
+        match self {
+            Self::Pane(pane_id) => entry.pane_id.as_deref() == Some(pane_id),
+            Self::Window(window) => entry.window.as_deref() == Some(window),
+            Self::Cwd(dir) => entry.cwd.starts_with(dir),
+            Self::Parent(parent_id) => entry.parent_id.as_deref() == Some(parent_id),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct IndexFile {
+    /// The sessions directory's mtime once every entry below was written.
+    dir_modified: Option<SystemTime>,
+    entries: BTreeMap<String, IndexEntry>,
+}
+
+fn index_file(sessions_dir: &Path) -> PathBuf {
+    sessions_dir.join(INDEX_DIR).join(INDEX_FILE)
+}
+
+/// The sessions directory's mtime. Any file created, renamed over, or
+/// removed in it moves this forward.
+pub(super) fn dir_modified(sessions_dir: &Path) -> Option<SystemTime> {
+    fs::metadata(sessions_dir).ok()?.modified().ok()
+}
+
+fn read_index(sessions_dir: &Path) -> Option<IndexFile> {
+    let content = fs::read_to_string(index_file(sessions_dir)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Writes the index atomically. The caller holds the index lock.
+fn write_index(sessions_dir: &Path, index: &IndexFile) -> Result<()> {
+    let path = index_file(sessions_dir);
+    let temp_path = path.with_extension("json.tmp");
+    let mut temp_file = File::create(&temp_path)?;
+    temp_file.write_all(serde_json::to_string(index)?.as_bytes())?;
+    temp_file.sync_all()?;
+    fs::rename(&temp_path, &path)?;
+    Ok(())
+}
+
+/// Opens and exclusively locks the index lock file, creating the index
+/// directory on first use.
+fn lock_index(sessions_dir: &Path) -> Result<File> {
+    let dir = sessions_dir.join(INDEX_DIR);
+    fs::create_dir_all(&dir)?;
+    let lock_file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(dir.join(format!("{INDEX_FILE}.lock")))?;
+    acquire_lock(&lock_file)?;
+    Ok(lock_file)
+}
+
+/// Upserts (`Some`) or removes (`None`) `session_id`'s entry after its
+/// session file was written or deleted. `dir_modified_before` is the
+/// directory's mtime sampled just before that file operation.
+///
+/// Best-effort: when the index was already behind the directory before
+/// this write (or the update itself fails), the index is dropped so the
+/// next reader rebuilds it, rather than recording a fresh mtime over
+/// entries that are known to be incomplete.
+pub(super) fn record(
+    sessions_dir: &Path,
+    session_id: &str,
+    entry: Option<IndexEntry>,
+    dir_modified_before: Option<SystemTime>,
+) {
+    if try_record(sessions_dir, session_id, entry, dir_modified_before).is_err() {
+        let _ = fs::remove_file(index_file(sessions_dir));
+    }
+}
+
+fn try_record(
+    sessions_dir: &Path,
+    session_id: &str,
+    entry: Option<IndexEntry>,
+    dir_modified_before: Option<SystemTime>,
+) -> Result<()> {
+    let _lock = lock_index(sessions_dir)?;
+    let Some(mut index) = read_index(sessions_dir) else {
+        return Ok(());
+    };
+    if dir_modified_before.is_none() || index.dir_modified != dir_modified_before {
+        fs::remove_file(index_file(sessions_dir))?;
+        return Ok(());
+    }
+
+    match entry {
+        Some(entry) => {
+            index.entries.insert(session_id.to_string(), entry);
+        }
+        None => {
+            index.entries.remove(session_id);
+        }
+    }
+    index.dir_modified = dir_modified(sessions_dir);
+    write_index(sessions_dir, &index)
+}
+
+/// Rescans every session file and rewrites the index from it. Returns the
+/// sessions read so a caller that needed a full scan anyway does not read
+/// them twice.
+///
+/// The recorded mtime is sampled before the scan, so a write racing with
+/// it leaves the new index stale and the next reader rebuilds again.
+pub(crate) fn rebuild(sessions_dir: &Path) -> Result<Vec<Session>> {
+    fs::create_dir_all(sessions_dir.join(INDEX_DIR))?;
+    let before = dir_modified(sessions_dir);
+    let sessions = super::scan_sessions(sessions_dir)?;
+    let index = IndexFile {
+        dir_modified: before,
+        entries: sessions
+            .iter()
+            .map(|session| (session.session_id.clone(), IndexEntry::of(session)))
+            .collect(),
+    };
+
+    let _lock = lock_index(sessions_dir)?;
+    write_index(sessions_dir, &index)?;
+    Ok(sessions)
+}
+
+/// The index's entries when it is current, `None` when it is missing,
+/// corrupted, or behind the sessions directory.
+fn fresh_entries(sessions_dir: &Path) -> Option<BTreeMap<String, IndexEntry>> {
+    let index = read_index(sessions_dir)?;
+    let current = dir_modified(sessions_dir)?;
+    (index.dir_modified == Some(current)).then_some(index.entries)
+}
+
+/// Every indexed session's entry, rebuilding the index first when it is
+/// not current. A rebuild that cannot write the index (e.g. lock timeout)
+/// still answers from its scan.
+pub(crate) fn entries(sessions_dir: &Path) -> Result<BTreeMap<String, IndexEntry>> {
+    if !sessions_dir.exists() {
+        return Ok(BTreeMap::new());
+    }
+    if let Some(entries) = fresh_entries(sessions_dir) {
+        return Ok(entries);
+    }
+    let sessions = match rebuild(sessions_dir) {
+        Ok(sessions) => sessions,
+        Err(_) => super::scan_sessions(sessions_dir)?,
+    };
+    Ok(sessions
+        .iter()
+        .map(|session| (session.session_id.clone(), IndexEntry::of(session)))
+        .collect())
+}
+
+/// IDs of the sessions matching `lookup`, in ID order.
+pub(crate) fn lookup(sessions_dir: &Path, lookup: &Lookup) -> Result<Vec<String>> {
+    Ok(entries(sessions_dir)?
+        .into_iter()
+        .filter(|(_, entry)| lookup.matches(entry))
+        .map(|(id, _)| id)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::cc::store::{
+        LOCKS_DIR, delete_session_from, load_session_from, save_session_to,
+    };
+    use crate::commands::cc::types::TmuxInfo;
+    use rstest::rstest;
+    use tempfile::TempDir;
+
+    fn session(id: &str, cwd: &str, pane_id: Option<&str>, parent: Option<&str>) -> Session {
+        Session {
+            session_id: id.to_string(),
+            cwd: PathBuf::from(cwd),
+            transcript_path: None,
+            tty: None,
+            tmux_info: pane_id.map(|pane_id| TmuxInfo {
+                session_name: "main".to_string(),
+                window_name: "w".to_string(),
+                window_index: 2,
+                pane_id: pane_id.to_string(),
+            }),
+            status: SessionStatus::Running,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_message: None,
+            current_tool: None,
+            label: None,
+            ancestor_session_ids: parent.map(str::to_string).into_iter().collect(),
+            pending_bg_task_ids: Default::default(),
+            pending_agent_task_ids: Default::default(),
+            pending_permission_agent_ids: Default::default(),
+            read_at: None,
+            sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
+        }
+    }
+
+    fn populated_dir() -> TempDir {
+        let dir = TempDir::new().expect("temp dir");
+        for s in [
+            session("a", "/w/repo", Some("%1"), None),
+            session("b", "/w/repo/sub", Some("%2"), Some("a")),
+            session("c", "/w/other", None, Some("a")),
+        ] {
+            save_session_to(dir.path(), &s).expect("save");
+        }
+        rebuild(dir.path()).expect("rebuild");
+        dir
+    }
+
+    #[rstest]
+    #[case::pane(Lookup::Pane("%2"), &["b"])]
+    #[case::window(Lookup::Window("main:2"), &["a", "b"])]
+    #[case::cwd_includes_subdirectories(Lookup::Cwd(Path::new("/w/repo")), &["a", "b"])]
+    #[case::parent(Lookup::Parent("a"), &["b", "c"])]
+    #[case::no_match(Lookup::Pane("%9"), &[])]
+    fn lookup_cases(#[case] key: Lookup, #[case] expected: &[&str]) {
+        let dir = populated_dir();
+        assert_eq!(lookup(dir.path(), &key).expect("lookup"), expected);
+    }
+
+    #[test]
+    fn store_writes_keep_the_index_current() {
+        let dir = populated_dir();
+        let mut b = session("b", "/w/repo/sub", Some("%2"), Some("a"));
+        b.status = SessionStatus::Ended;
+        save_session_to(dir.path(), &b).expect("save");
+        delete_session_from(dir.path(), "c", false).expect("delete");
+
+        let entries = fresh_entries(dir.path()).expect("index should be current");
+        assert_eq!(
+            entries
+                .iter()
+                .map(|(id, entry)| (id.as_str(), entry.status))
+                .collect::<Vec<_>>(),
+            vec![("a", SessionStatus::Running), ("b", SessionStatus::Ended)]
+        );
+    }
+
+    #[test]
+    fn reading_a_session_keeps_the_index_current() {
+        let dir = populated_dir();
+        // A reader that gets to a session first creates its lock file
+        fs::remove_file(dir.path().join(LOCKS_DIR).join("a.json.lock")).expect("remove lock");
+
+        load_session_from(dir.path(), "a").expect("load");
+
+        assert!(fresh_entries(dir.path()).is_some());
+    }
+
+    #[test]
+    fn write_bypassing_the_index_triggers_a_rebuild() {
+        let dir = populated_dir();
+        assert!(fresh_entries(dir.path()).is_some());
+
+        let content = serde_json::to_string(&session("d", "/w/d", Some("%1"), None)).unwrap();
+        fs::write(dir.path().join("d.json"), content).expect("write");
+
+        assert!(fresh_entries(dir.path()).is_none());
+        assert_eq!(
+            lookup(dir.path(), &Lookup::Pane("%1")).expect("lookup"),
+            vec!["a", "d"]
+        );
+        assert!(fresh_entries(dir.path()).is_some());
+    }
+
+    #[test]
+    fn corrupted_index_is_rebuilt() {
+        let dir = populated_dir();
+        fs::write(index_file(dir.path()), "{ not json").expect("write");
+
+        assert_eq!(
+            lookup(dir.path(), &Lookup::Parent("a")).expect("lookup"),
+            vec!["b", "c"]
+        );
+        assert!(fresh_entries(dir.path()).is_some());
+    }
+}
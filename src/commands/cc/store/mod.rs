@@ -1,3 +1,8 @@
+mod commands;
+mod index;
+mod schema;
+
+use std::collections::BTreeSet;
 use std::fs::{self, File, OpenOptions, TryLockError};
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
@@ -6,10 +11,18 @@ use std::time::Duration;
 use anyhow::Result;
 use chrono::{DateTime, TimeDelta, Utc};
 
+use super::checkpoint;
 use super::error::CcError;
-use super::types::{Session, SessionStatus};
+use super::serve;
+use super::types::{Session, SessionReport, SessionStatus};
 use crate::infra::tmux;
 use crate::shared::cache;
+use crate::shared::config;
+
+pub use commands::StoreCommands;
+pub(crate) use commands::run;
+use index::IndexEntry;
+pub use index::Lookup;
 
 /// Threshold in seconds for sort stability.
 /// Sessions updated within this window are sorted by created_at instead,
@@ -97,6 +110,19 @@ fn session_file_in(sessions_dir: &Path, session_id: &str) -> Result<PathBuf> {
     Ok(sessions_dir.join(format!("{session_id}.json")))
 }
 
+/// Directory inside the sessions directory that holds the per-session lock
+/// files. Creating a lock file there leaves the sessions directory's mtime
+/// alone, which the index uses to detect writes that bypassed it.
+pub(super) const LOCKS_DIR: &str = ".locks";
+
+/// Returns the lock file guarding a session's file, creating the lock
+/// directory on first use.
+fn lock_file_in(sessions_dir: &Path, session_id: &str) -> Result<PathBuf> {
+    let dir = sessions_dir.join(LOCKS_DIR);
+    fs::create_dir_all(&dir)?;
+    Ok(dir.join(format!("{session_id}.json.lock")))
+}
+
 /// Returns the file path for a specific session.
 /// Path: ~/.cache/armyknife/cc/sessions/<session_id>.json
 ///
@@ -148,7 +174,7 @@ pub(crate) fn load_session_from(sessions_dir: &Path, session_id: &str) -> Result
     }
 
     // Use the same lock file as save_session to coordinate readers and writers
-    let lock_path = path.with_extension("json.lock");
+    let lock_path = lock_file_in(sessions_dir, session_id)?;
 
     // Open or create lock file for shared lock
     let lock_file = match OpenOptions::new()
@@ -174,8 +200,8 @@ pub(crate) fn load_session_from(sessions_dir: &Path, session_id: &str) -> Result
     };
 
     // Parse JSON (lock is held until lock_file is dropped)
-    match serde_json::from_str::<Session>(&content) {
-        Ok(session) => Ok(Some(session)),
+    match schema::decode(&content) {
+        Ok(decoded) => Ok(Some(decoded.session)),
         Err(_) => {
             // File is corrupted; return None and let save_session overwrite it
             eprintln!(
@@ -204,6 +230,34 @@ fn acquire_shared_lock(file: &File) -> Result<()> {
     Err(CcError::LockTimeout(LOCK_TIMEOUT).into())
 }
 
+/// Parses a session file's content, migrating it from an older schema
+/// version. `None` when the content is not a session.
+pub(crate) fn parse_session(content: &str) -> Option<Session> {
+    schema::decode(content).ok().map(|decoded| decoded.session)
+}
+
+/// Refuses to overwrite a session file written by a newer armyknife, whose
+/// fields this build would drop. The caller holds the session lock.
+fn ensure_writable(path: &Path, session_id: &str) -> Result<()> {
+    match fs::read_to_string(path) {
+        Ok(content) => ensure_writable_content(&content, session_id),
+        Err(_) => Ok(()),
+    }
+}
+
+fn ensure_writable_content(content: &str, session_id: &str) -> Result<()> {
+    let version = schema::version_of(content);
+    if version > schema::CURRENT_VERSION {
+        return Err(CcError::SessionSchemaTooNew {
+            session_id: session_id.to_string(),
+            version,
+            supported: schema::CURRENT_VERSION,
+        }
+        .into());
+    }
+    Ok(())
+}
+
 /// Saves a session to a specific directory.
 /// Allows testing with temporary directories.
 pub(crate) fn save_session_to(sessions_dir: &Path, session: &Session) -> Result<()> {
@@ -213,8 +267,7 @@ pub(crate) fn save_session_to(sessions_dir: &Path, session: &Session) -> Result<
         fs::create_dir_all(parent)?;
     }
 
-    // Create lock file path (use .lock suffix to avoid conflicts)
-    let lock_path = path.with_extension("json.lock");
+    let lock_path = lock_file_in(sessions_dir, &session.session_id)?;
 
     // Open or create lock file
     let lock_file = OpenOptions::new()
@@ -225,9 +278,11 @@ pub(crate) fn save_session_to(sessions_dir: &Path, session: &Session) -> Result<
 
     // Acquire exclusive lock
     acquire_lock(&lock_file)?;
+    ensure_writable(&path, &session.session_id)?;
+    let dir_modified_before = index::dir_modified(sessions_dir);
 
     // Serialize content
-    let content = serde_json::to_string_pretty(session)?;
+    let content = schema::encode(session)?;
 
     // Write to temporary file first for atomic operation
     let temp_path = path.with_extension("json.tmp");
@@ -238,6 +293,15 @@ pub(crate) fn save_session_to(sessions_dir: &Path, session: &Session) -> Result<
     // Rename temporary file to target (atomic on Unix)
     fs::rename(&temp_path, &path)?;
 
+    // Still under the session lock, so index updates for one session
+    // land in the same order as its file writes
+    index::record(
+        sessions_dir,
+        &session.session_id,
+        Some(IndexEntry::of(session)),
+        dir_modified_before,
+    );
+
     // Lock is automatically released when lock_file is dropped
     Ok(())
 }
@@ -271,7 +335,7 @@ pub(crate) fn lock_session_for_update(
         fs::create_dir_all(parent)?;
     }
 
-    let lock_path = path.with_extension("json.lock");
+    let lock_path = lock_file_in(sessions_dir, session_id)?;
     let lock_file = OpenOptions::new()
         .write(true)
         .create(true)
@@ -295,8 +359,8 @@ impl SessionLock {
             Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
             Err(e) => return Err(e.into()),
         };
-        match serde_json::from_str::<Session>(&content) {
-            Ok(session) => Ok(Some(session)),
+        match schema::decode(&content) {
+            Ok(decoded) => Ok(Some(decoded.session)),
             Err(_) => {
                 eprintln!(
                     "[armyknife] warning: session file corrupted: {}",
@@ -310,12 +374,21 @@ impl SessionLock {
     /// Writes the session atomically under the lock already held by this
     /// guard, matching `save_session_to`.
     pub(crate) fn save(&self, session: &Session) -> Result<()> {
-        let content = serde_json::to_string_pretty(session)?;
+        let sessions_dir = self.path.parent().unwrap_or(Path::new("."));
+        ensure_writable(&self.path, &session.session_id)?;
+        let dir_modified_before = index::dir_modified(sessions_dir);
+        let content = schema::encode(session)?;
         let temp_path = self.path.with_extension("json.tmp");
         let mut temp_file = File::create(&temp_path)?;
         temp_file.write_all(content.as_bytes())?;
         temp_file.sync_all()?;
         fs::rename(&temp_path, &self.path)?;
+        index::record(
+            sessions_dir,
+            &session.session_id,
+            Some(IndexEntry::of(session)),
+            dir_modified_before,
+        );
         Ok(())
     }
 }
@@ -339,7 +412,7 @@ fn update_session_field_in(
         return Ok(());
     }
 
-    let lock_path = path.with_extension("json.lock");
+    let lock_path = lock_file_in(sessions_dir, session_id)?;
     let lock_file = OpenOptions::new()
         .write(true)
         .create(true)
@@ -352,19 +425,27 @@ fn update_session_field_in(
         Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
         Err(e) => return Err(e.into()),
     };
-    let Ok(mut session) = serde_json::from_str::<Session>(&content) else {
+    ensure_writable_content(&content, session_id)?;
+    let Ok(schema::Decoded { mut session, .. }) = schema::decode(&content) else {
         return Ok(());
     };
     if !mutate(&mut session) {
         return Ok(());
     }
 
-    let new_content = serde_json::to_string_pretty(&session)?;
+    let dir_modified_before = index::dir_modified(sessions_dir);
+    let new_content = schema::encode(&session)?;
     let temp_path = path.with_extension("json.tmp");
     let mut temp_file = File::create(&temp_path)?;
     temp_file.write_all(new_content.as_bytes())?;
     temp_file.sync_all()?;
     fs::rename(&temp_path, &path)?;
+    index::record(
+        sessions_dir,
+        session_id,
+        Some(IndexEntry::of(&session)),
+        dir_modified_before,
+    );
     Ok(())
 }
 
@@ -466,24 +547,126 @@ pub(crate) fn update_session_label_if_unchanged_in(
     Ok(applied)
 }
 
+/// Edits a session's `tags` under the session lock. Resolves the default
+/// sessions directory -- see `update_session_tags_in`.
+pub fn update_session_tags(
+    session_id: &str,
+    edit: impl FnOnce(&mut BTreeSet<String>),
+) -> Result<Option<BTreeSet<String>>> {
+    update_session_tags_in(&sessions_dir()?, session_id, edit)
+}
+
+/// Applies `edit` to a session's `tags` within one locked
+/// load-mutate-save, so concurrent `a cc tag` invocations adding different
+/// tags cannot drop each other's. Returns the resulting tags, or `None`
+/// when the session file does not exist.
+pub(crate) fn update_session_tags_in(
+    sessions_dir: &Path,
+    session_id: &str,
+    edit: impl FnOnce(&mut BTreeSet<String>),
+) -> Result<Option<BTreeSet<String>>> {
+    let mut updated = None;
+    update_session_field_in(sessions_dir, session_id, |session| {
+        edit(&mut session.tags);
+        updated = Some(session.tags.clone());
+        true
+    })?;
+    Ok(updated)
+}
+
+/// Overwrites a session's `note`. Resolves the default sessions directory
+/// -- see `update_session_note_in`.
+pub fn update_session_note(session_id: &str, note: Option<String>) -> Result<bool> {
+    update_session_note_in(&sessions_dir()?, session_id, note)
+}
+
+/// Atomically overwrites a session's `note` field. Returns whether the
+/// session existed.
+pub(crate) fn update_session_note_in(
+    sessions_dir: &Path,
+    session_id: &str,
+    note: Option<String>,
+) -> Result<bool> {
+    let mut applied = false;
+    update_session_field_in(sessions_dir, session_id, |session| {
+        session.note = note;
+        applied = true;
+        true
+    })?;
+    Ok(applied)
+}
+
+/// Atomically overwrites a session's `report` field. Returns whether the
+/// session existed -- unlike the label setters, a report filed against a
+/// missing session file is a caller error (`a cc peer report` surfaces it)
+/// rather than a silent no-op.
+pub(crate) fn update_session_report_in(
+    sessions_dir: &Path,
+    session_id: &str,
+    report: SessionReport,
+) -> Result<bool> {
+    let mut applied = false;
+    update_session_field_in(sessions_dir, session_id, |session| {
+        session.report = Some(report);
+        applied = true;
+        true
+    })?;
+    Ok(applied)
+}
+
 /// Deletes a session from disk.
 /// Returns Ok(()) even if the session file doesn't exist.
 pub fn delete_session(session_id: &str) -> Result<()> {
-    delete_session_from(&sessions_dir()?, session_id)
+    delete_session_from(&sessions_dir()?, session_id, checkpoints_enabled())
 }
 
-/// Deletes a session from a specific directory.
+/// Deletes a session from a specific directory, along with its checkpoint
+/// refs when `checkpoints` is set.
 /// Allows testing with temporary directories.
-pub(crate) fn delete_session_from(sessions_dir: &Path, session_id: &str) -> Result<()> {
+pub(crate) fn delete_session_from(
+    sessions_dir: &Path,
+    session_id: &str,
+    checkpoints: bool,
+) -> Result<()> {
     let path = session_file_in(sessions_dir, session_id)?;
 
     if path.exists() {
+        let cwd = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| parse_session(&content))
+            .map(|session| session.cwd);
+        let dir_modified_before = index::dir_modified(sessions_dir);
         fs::remove_file(&path)?;
+        index::record(sessions_dir, session_id, None, dir_modified_before);
+        if checkpoints && let Some(cwd) = cwd {
+            delete_checkpoints(&cwd, session_id);
+        }
     }
 
     Ok(())
 }
 
+/// Whether `cc.checkpoints` is enabled, so deleted sessions may have
+/// checkpoint refs to drop. Without it, deleting a session runs no git.
+fn checkpoints_enabled() -> bool {
+    config::load_config()
+        .map(|c| c.cc.checkpoints.enabled)
+        .unwrap_or_default()
+}
+
+/// Drops the checkpoint refs of a deleted session (see
+/// `checkpoint::delete_all`). Best-effort: a failure only leaves refs
+/// behind, so it is logged rather than failing the deletion.
+fn delete_checkpoints(cwd: &Path, session_id: &str) {
+    if let Err(e) = checkpoint::delete_all(cwd, session_id) {
+        tracing::warn!(
+            event = "cc.checkpoint.delete_failed",
+            session = %session_id,
+            error = %e,
+        );
+    }
+}
+
 /// Sorts sessions by updated_at descending with stability threshold.
 ///
 /// Sessions updated within [`SORT_STABILITY_THRESHOLD_SECS`] of each other
@@ -499,23 +682,57 @@ pub fn sort_sessions(sessions: &mut [Session]) {
     });
 }
 
-/// Reads every session file from disk, regardless of status.
-fn read_all_sessions() -> Result<Vec<Session>> {
+/// Reads the sessions in `dir`, from the `a cc serve` daemon's index when
+/// one is running and from disk otherwise. Without `include_ended`, the
+/// on-disk index picks out the non-Ended files so Ended sessions are never
+/// opened.
+fn read_sessions(include_ended: bool) -> Result<Vec<Session>> {
+    if let Some(mut sessions) = serve::client::list_sessions() {
+        if !include_ended {
+            sessions.retain(|s| s.status != SessionStatus::Ended);
+        }
+        return Ok(sessions);
+    }
+
     let dir = sessions_dir()?;
+    if include_ended {
+        return scan_sessions(&dir);
+    }
+
+    let mut sessions = Vec::new();
+    for (session_id, entry) in index::entries(&dir)? {
+        if entry.status != SessionStatus::Ended
+            && let Some(session) = read_session_file(&dir, &session_id)
+            && session.status != SessionStatus::Ended
+        {
+            sessions.push(session);
+        }
+    }
+    Ok(sessions)
+}
+
+/// Parses one session file without taking its lock, like the directory
+/// scan. `None` when the file is missing or corrupted.
+fn read_session_file(dir: &Path, session_id: &str) -> Option<Session> {
+    let content = fs::read_to_string(session_file_in(dir, session_id).ok()?).ok()?;
+    parse_session(&content)
+}
 
+/// Parses every session file in `dir`, skipping unreadable ones.
+fn scan_sessions(dir: &Path) -> Result<Vec<Session>> {
     if !dir.exists() {
         return Ok(Vec::new());
     }
 
     let mut sessions = Vec::new();
 
-    for entry in fs::read_dir(&dir)? {
+    for entry in fs::read_dir(dir)? {
         let entry = entry?;
         let path = entry.path();
 
         if path.extension().is_some_and(|ext| ext == "json")
             && let Ok(content) = fs::read_to_string(&path)
-            && let Ok(session) = serde_json::from_str::<Session>(&content)
+            && let Some(session) = parse_session(&content)
         {
             sessions.push(session);
         }
@@ -526,8 +743,7 @@ fn read_all_sessions() -> Result<Vec<Session>> {
 
 /// Lists all active sessions from disk, excluding ended sessions.
 pub fn list_sessions() -> Result<Vec<Session>> {
-    let mut sessions = read_all_sessions()?;
-    sessions.retain(|s| s.status != SessionStatus::Ended);
+    let mut sessions = read_sessions(false)?;
     sort_sessions(&mut sessions);
     Ok(sessions)
 }
@@ -537,11 +753,82 @@ pub fn list_sessions() -> Result<Vec<Session>> {
 /// after it ended -- e.g. reading `ancestor_session_ids` off a delegated
 /// session that already exited by the time its PR merged.
 pub fn list_all_sessions() -> Result<Vec<Session>> {
-    let mut sessions = read_all_sessions()?;
+    let mut sessions = read_sessions(true)?;
     sort_sessions(&mut sessions);
     Ok(sessions)
 }
 
+/// Sessions matching `lookup`, Ended ones included, resolved through the
+/// on-disk index instead of a full scan.
+pub fn find_sessions(lookup: &Lookup) -> Result<Vec<Session>> {
+    find_sessions_in(&sessions_dir()?, lookup)
+}
+
+pub(crate) fn find_sessions_in(sessions_dir: &Path, lookup: &Lookup) -> Result<Vec<Session>> {
+    let mut sessions = Vec::new();
+    for session_id in index::lookup(sessions_dir, lookup)? {
+        if let Some(session) = load_session_from(sessions_dir, &session_id)? {
+            sessions.push(session);
+        }
+    }
+    sort_sessions(&mut sessions);
+    Ok(sessions)
+}
+
+/// What `migrate_sessions_in` found among the session files.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct MigrationReport {
+    /// Sessions below the current schema version, with their version.
+    pub outdated: Vec<(String, u32)>,
+    /// Sessions written by a newer armyknife, with their version.
+    pub too_new: Vec<(String, u32)>,
+}
+
+/// Rewrites every session file below the current schema version in the
+/// current format, or only reports them when `check` is set. Files from a
+/// newer armyknife are reported and left untouched.
+pub(crate) fn migrate_sessions_in(dir: &Path, check: bool) -> Result<MigrationReport> {
+    let mut report = MigrationReport::default();
+    if !dir.exists() {
+        return Ok(report);
+    }
+
+    let mut session_ids: Vec<String> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+        .collect();
+    session_ids.sort();
+
+    for session_id in session_ids {
+        let Ok(content) = fs::read_to_string(session_file_in(dir, &session_id)?) else {
+            continue;
+        };
+        let Ok(decoded) = schema::decode(&content) else {
+            continue;
+        };
+        if decoded.version > schema::CURRENT_VERSION {
+            report.too_new.push((session_id, decoded.version));
+            continue;
+        }
+        if decoded.version == schema::CURRENT_VERSION {
+            continue;
+        }
+
+        if !check {
+            // Re-read under the lock so a hook write since the read above
+            // is migrated rather than overwritten
+            let lock = lock_session_for_update(dir, &session_id)?;
+            if let Some(session) = lock.load()? {
+                lock.save(&session)?;
+            }
+        }
+        report.outdated.push((session_id, decoded.version));
+    }
+
+    Ok(report)
+}
+
 /// Removes stale sessions from disk.
 ///
 /// A session is considered stale and removed if its tmux pane no longer exists.
@@ -575,11 +862,22 @@ where
     F: Fn(&str) -> bool,
     G: Fn(&Path) -> bool,
 {
-    cleanup_stale_sessions_in(&sessions_dir()?, is_pane_alive, cwd_exists)
+    cleanup_stale_sessions_in(
+        &sessions_dir()?,
+        is_pane_alive,
+        cwd_exists,
+        checkpoints_enabled(),
+    )
 }
 
-/// Returns whether at least one session file was removed.
-fn cleanup_stale_sessions_in<F, G>(dir: &Path, is_pane_alive: F, cwd_exists: G) -> Result<bool>
+/// Returns whether at least one session file was removed. Checkpoint refs
+/// of removed sessions are dropped too when `checkpoints` is set.
+fn cleanup_stale_sessions_in<F, G>(
+    dir: &Path,
+    is_pane_alive: F,
+    cwd_exists: G,
+    checkpoints: bool,
+) -> Result<bool>
 where
     F: Fn(&str) -> bool,
     G: Fn(&Path) -> bool,
@@ -592,31 +890,18 @@ where
     let retention = TimeDelta::days(ENDED_SESSION_RETENTION_DAYS);
     let mut removed_any = false;
 
-    for entry in fs::read_dir(dir)? {
-        let entry = entry?;
-        let path = entry.path();
-
-        if path.extension().is_none_or(|ext| ext != "json") {
-            continue;
-        }
-
-        let Ok(content) = fs::read_to_string(&path) else {
-            continue;
-        };
-
-        let Ok(session) = serde_json::from_str::<Session>(&content) else {
-            continue;
-        };
-
+    // Decided from the index alone, so a cleanup pass over hundreds of
+    // sessions parses none of their files
+    for (session_id, entry) in index::entries(dir)? {
         // Resume relies on `tmux respawn-pane`, which fails once the original
         // pane is gone. A Paused session whose pane has died is therefore
         // unrecoverable and should be cleaned up immediately rather than waiting
         // for the 7-day retention. Ended sessions stay until the retention check.
-        let stale_pane = session.status != SessionStatus::Ended
-            && session
-                .tmux_info
-                .as_ref()
-                .is_some_and(|info| !is_pane_alive(&info.pane_id));
+        let stale_pane = entry.status != SessionStatus::Ended
+            && entry
+                .pane_id
+                .as_deref()
+                .is_some_and(|pane_id| !is_pane_alive(pane_id));
 
         // Orphaned sessions: the worktree (cwd) is gone but the session file
         // lingered, e.g. `wm delete` raced with `kill_window`, the worktree
@@ -626,17 +911,28 @@ where
         // session is done regardless of status, even if its tmux pane
         // happens to still be attachable. Ended is handled by
         // `expired_ended` below instead, since it has its own retention.
-        let orphaned = session.status != SessionStatus::Ended && !cwd_exists(&session.cwd);
+        let orphaned = entry.status != SessionStatus::Ended && !cwd_exists(&entry.cwd);
 
-        let expired_ended = matches!(session.status, SessionStatus::Ended | SessionStatus::Paused)
-            && now - session.updated_at > retention;
+        let expired_ended = matches!(entry.status, SessionStatus::Ended | SessionStatus::Paused)
+            && now - entry.updated_at > retention;
 
         if stale_pane || orphaned || expired_ended {
+            let Ok(path) = session_file_in(dir, &session_id) else {
+                continue;
+            };
+            let dir_modified_before = index::dir_modified(dir);
             if fs::remove_file(&path).is_ok() {
                 removed_any = true;
             }
-            let lock_path = path.with_extension("json.lock");
-            let _ = fs::remove_file(&lock_path);
+            if let Ok(lock_path) = lock_file_in(dir, &session_id) {
+                let _ = fs::remove_file(&lock_path);
+            }
+            // Lock file of a version that kept locks next to the sessions
+            let _ = fs::remove_file(path.with_extension("json.lock"));
+            index::record(dir, &session_id, None, dir_modified_before);
+            if checkpoints {
+                delete_checkpoints(&entry.cwd, &session_id);
+            }
         }
     }
 
@@ -687,6 +983,13 @@ mod tests {
             pending_permission_agent_ids: std::collections::BTreeSet::new(),
             read_at: None,
             sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
         }
     }
 
@@ -906,6 +1209,7 @@ mod tests {
                 &temp_session_dir.sessions_path,
                 mock_pane_always_dead,
                 mock_cwd_always_exists,
+                false,
             )
             .expect("cleanup should succeed");
 
@@ -932,6 +1236,7 @@ mod tests {
                 &temp_session_dir.sessions_path,
                 mock_pane_always_alive,
                 mock_cwd_always_exists,
+                false,
             )
             .expect("cleanup should succeed");
 
@@ -955,6 +1260,7 @@ mod tests {
                 &temp_session_dir.sessions_path,
                 mock_pane_always_dead,
                 mock_cwd_always_exists,
+                false,
             )
             .expect("cleanup should succeed");
 
@@ -977,13 +1283,16 @@ mod tests {
             save_session_to(&temp_session_dir.sessions_path, &session)
                 .expect("save should succeed");
 
-            let lock_path = path.with_extension("json.lock");
+            let lock_path = lock_file_in(&temp_session_dir.sessions_path, session_id)
+                .expect("lock_file_in should succeed");
             assert!(path.exists(), "session file should exist");
+            assert!(lock_path.exists(), "lock file should exist");
 
             cleanup_stale_sessions_in(
                 &temp_session_dir.sessions_path,
                 mock_pane_always_dead,
                 mock_cwd_always_exists,
+                false,
             )
             .expect("cleanup should succeed");
 
@@ -1017,6 +1326,7 @@ mod tests {
                 &temp_session_dir.sessions_path,
                 mock_pane_always_alive,
                 mock_cwd_always_missing,
+                false,
             )
             .expect("cleanup should succeed");
 
@@ -1048,12 +1358,53 @@ mod tests {
                 &temp_session_dir.sessions_path,
                 mock_pane_always_dead,
                 mock_cwd_always_exists,
+                false,
             )
             .expect("cleanup should succeed");
 
             assert!(path.exists(), "session should be kept while its cwd exists");
         }
 
+        #[rstest]
+        #[case::enabled(true, 0)]
+        #[case::disabled(false, 1)]
+        fn removes_checkpoints_of_removed_session_when_enabled(
+            temp_session_dir: TempSessionDir,
+            #[case] checkpoints: bool,
+            #[case] expected_refs: usize,
+        ) {
+            let repo = crate::infra::git::test_utils::TempRepo::new("owner", "repo", "main");
+            fs::write(repo.path().join("f.txt"), "1\n").expect("write should succeed");
+            let session_id = "checkpointed";
+            checkpoint::take(&repo.path(), session_id, 50).expect("checkpoint should succeed");
+
+            let mut session = create_test_session(session_id);
+            session.cwd = repo.path();
+            session.tmux_info = Some(TmuxInfo {
+                session_name: "test".to_string(),
+                window_name: "test".to_string(),
+                window_index: 0,
+                pane_id: "%99999".to_string(),
+            });
+            save_session_to(&temp_session_dir.sessions_path, &session)
+                .expect("save should succeed");
+
+            cleanup_stale_sessions_in(
+                &temp_session_dir.sessions_path,
+                mock_pane_always_dead,
+                mock_cwd_always_exists,
+                checkpoints,
+            )
+            .expect("cleanup should succeed");
+
+            let refs = crate::infra::git::cmd::run_git(
+                &repo.path(),
+                ["for-each-ref", "refs/armyknife/checkpoints/"],
+            )
+            .expect("for-each-ref should succeed");
+            assert_eq!(refs.lines().count(), expected_refs);
+        }
+
         #[rstest]
         fn keeps_ended_session_when_cwd_is_gone_but_not_expired(temp_session_dir: TempSessionDir) {
             let session_id = "ended-not-expired";
@@ -1069,6 +1420,7 @@ mod tests {
                 &temp_session_dir.sessions_path,
                 mock_pane_always_dead,
                 mock_cwd_always_missing,
+                false,
             )
             .expect("cleanup should succeed");
 
@@ -1264,6 +1616,127 @@ mod tests {
         }
     }
 
+    mod update_session_report_tests {
+        use super::*;
+        use crate::commands::cc::types::ReportStatus;
+        use rstest::rstest;
+
+        fn report(summary: &str) -> SessionReport {
+            SessionReport {
+                status: ReportStatus::Done,
+                summary: summary.to_string(),
+                pr: None,
+                artifacts: Vec::new(),
+                reported_at: Utc.with_ymd_and_hms(2026, 6, 1, 12, 0, 0).unwrap(),
+            }
+        }
+
+        #[rstest]
+        fn overwrites_previous_report(temp_session_dir: TempSessionDir) {
+            let mut session = create_test_session("report-target");
+            session.report = Some(report("first"));
+            save_session_to(&temp_session_dir.sessions_path, &session).expect("save");
+
+            let applied = update_session_report_in(
+                &temp_session_dir.sessions_path,
+                "report-target",
+                report("second"),
+            )
+            .expect("update should succeed");
+
+            let reloaded = load_session_from(&temp_session_dir.sessions_path, "report-target")
+                .expect("load")
+                .expect("session exists");
+            assert_eq!((applied, reloaded.report), (true, Some(report("second"))));
+        }
+
+        #[rstest]
+        fn missing_session_file_returns_false(temp_session_dir: TempSessionDir) {
+            let applied =
+                update_session_report_in(&temp_session_dir.sessions_path, "ghost", report("x"))
+                    .expect("missing session should be ok");
+
+            assert!(!applied);
+        }
+    }
+
+    mod update_session_tags_tests {
+        use super::*;
+        use rstest::rstest;
+
+        #[rstest]
+        fn edits_existing_tags_and_returns_result(temp_session_dir: TempSessionDir) {
+            let mut session = create_test_session("tag-target");
+            session.tags = BTreeSet::from(["needs-review".to_string(), "old".to_string()]);
+            save_session_to(&temp_session_dir.sessions_path, &session).expect("save");
+
+            let tags = update_session_tags_in(&temp_session_dir.sessions_path, "tag-target", |t| {
+                t.remove("old");
+                t.insert("waiting-on-ci".to_string());
+            })
+            .expect("update should succeed");
+
+            let expected =
+                BTreeSet::from(["needs-review".to_string(), "waiting-on-ci".to_string()]);
+            let reloaded = load_session_from(&temp_session_dir.sessions_path, "tag-target")
+                .expect("load")
+                .expect("session exists");
+            assert_eq!((tags, reloaded.tags), (Some(expected.clone()), expected));
+        }
+
+        #[rstest]
+        fn missing_session_file_returns_none(temp_session_dir: TempSessionDir) {
+            let tags = update_session_tags_in(&temp_session_dir.sessions_path, "ghost", |t| {
+                t.insert("x".to_string());
+            })
+            .expect("missing session should be ok");
+
+            assert_eq!(tags, None);
+        }
+    }
+
+    mod update_session_note_tests {
+        use super::*;
+        use rstest::rstest;
+
+        #[rstest]
+        #[case::set(None, Some("line 1\nline 2"))]
+        #[case::clear(Some("old"), None)]
+        fn overwrites_note(
+            temp_session_dir: TempSessionDir,
+            #[case] initial: Option<&str>,
+            #[case] note: Option<&str>,
+        ) {
+            let mut session = create_test_session("note-target");
+            session.note = initial.map(str::to_string);
+            save_session_to(&temp_session_dir.sessions_path, &session).expect("save");
+
+            let applied = update_session_note_in(
+                &temp_session_dir.sessions_path,
+                "note-target",
+                note.map(str::to_string),
+            )
+            .expect("update should succeed");
+
+            let reloaded = load_session_from(&temp_session_dir.sessions_path, "note-target")
+                .expect("load")
+                .expect("session exists");
+            assert_eq!((applied, reloaded.note), (true, note.map(str::to_string)));
+        }
+
+        #[rstest]
+        fn missing_session_file_returns_false(temp_session_dir: TempSessionDir) {
+            let applied = update_session_note_in(
+                &temp_session_dir.sessions_path,
+                "ghost",
+                Some("x".to_string()),
+            )
+            .expect("missing session should be ok");
+
+            assert!(!applied);
+        }
+    }
+
     mod update_session_last_message_if_unchanged_tests {
         use super::*;
         use rstest::rstest;
@@ -1330,6 +1803,82 @@ mod tests {
         }
     }
 
+    mod schema_version_tests {
+        use super::*;
+        use rstest::rstest;
+
+        fn write_raw(dir: &Path, id: &str, schema_version: Option<u32>) {
+            let mut value = serde_json::to_value(create_test_session(id)).expect("to_value");
+            let fields = value.as_object_mut().expect("object");
+            let tasks = fields.remove("pending_agent_task_ids").expect("field");
+            fields.insert("pending_agent_task_outputs".to_string(), tasks);
+            if let Some(version) = schema_version {
+                fields.insert("schema_version".to_string(), version.into());
+            }
+            fs::write(dir.join(format!("{id}.json")), value.to_string()).expect("write");
+        }
+
+        #[rstest]
+        fn save_refuses_to_overwrite_a_newer_file(temp_session_dir: TempSessionDir) {
+            let dir = &temp_session_dir.sessions_path;
+            write_raw(dir, "future", Some(schema::CURRENT_VERSION + 1));
+
+            let err = save_session_to(dir, &create_test_session("future"))
+                .expect_err("save should refuse");
+            assert!(matches!(
+                err.downcast_ref::<CcError>(),
+                Some(CcError::SessionSchemaTooNew { .. })
+            ));
+            let label_err = update_session_label_in(dir, "future", Some("x".to_string()))
+                .expect_err("update should refuse");
+            assert!(matches!(
+                label_err.downcast_ref::<CcError>(),
+                Some(CcError::SessionSchemaTooNew { .. })
+            ));
+        }
+
+        #[rstest]
+        fn migrate_check_reports_without_writing(temp_session_dir: TempSessionDir) {
+            let dir = &temp_session_dir.sessions_path;
+            write_raw(dir, "old", None);
+            write_raw(dir, "future", Some(schema::CURRENT_VERSION + 1));
+            save_session_to(dir, &create_test_session("current")).expect("save");
+            let before = fs::read_to_string(dir.join("old.json")).expect("read");
+
+            let report = migrate_sessions_in(dir, true).expect("check");
+
+            assert_eq!(
+                report,
+                MigrationReport {
+                    outdated: vec![("old".to_string(), 0)],
+                    too_new: vec![("future".to_string(), schema::CURRENT_VERSION + 1)],
+                }
+            );
+            assert_eq!(
+                fs::read_to_string(dir.join("old.json")).expect("read"),
+                before
+            );
+        }
+
+        #[rstest]
+        fn migrate_rewrites_outdated_files_at_the_current_version(
+            temp_session_dir: TempSessionDir,
+        ) {
+            let dir = &temp_session_dir.sessions_path;
+            write_raw(dir, "old", None);
+
+            migrate_sessions_in(dir, false).expect("migrate");
+
+            let content = fs::read_to_string(dir.join("old.json")).expect("read");
+            assert_eq!(schema::version_of(&content), schema::CURRENT_VERSION);
+            assert!(!content.contains("pending_agent_task_outputs"));
+            assert_eq!(
+                migrate_sessions_in(dir, true).expect("check"),
+                MigrationReport::default()
+            );
+        }
+    }
+
     mod corrupted_file_recovery_tests {
         use super::*;
         use rstest::rstest;
@@ -1408,6 +1957,13 @@ mod tests {
                 pending_permission_agent_ids: std::collections::BTreeSet::new(),
                 read_at: None,
                 sweep_signaled: false,
+                changed_files: Default::default(),
+                subagents: Default::default(),
+                compaction_count: 0,
+                last_compacted_at: None,
+                report: None,
+                tags: Default::default(),
+                note: None,
             }
         }
 
@@ -0,0 +1,168 @@
+//! Versioning of the session file format.
+//!
+//! Every session file carries a top-level `schema_version`. Loading parses
+//! the file as plain JSON first, runs the migrations from its version up to
+//! `CURRENT_VERSION`, and only then deserializes it into `Session`, so a
+//! renamed or reshaped field is carried over instead of silently dropped
+//! by `#[serde(default)]`. Files without the field predate versioning and
+//! count as version 0.
+//!
+//! A file written by a newer armyknife (a version above `CURRENT_VERSION`)
+//! still loads best-effort, ignoring the fields this build does not know,
+//! but the store refuses to overwrite it: writing it back would drop those
+//! fields for the newer build.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::commands::cc::types::Session;
+
+/// The session file version this build reads and writes.
+pub(crate) const CURRENT_VERSION: u32 = 1;
+
+const VERSION_KEY: &str = "schema_version";
+
+/// One upgrade step, from `from` to `from + 1`.
+struct Migration {
+    from: u32,
+    apply: fn(&mut Map<String, Value>),
+}
+
+/// Ordered by `from`; each step's `from` is the previous step's `from + 1`.
+const MIGRATIONS: &[Migration] = &[Migration {
+    from: 0,
+    apply: rename_pending_agent_task_outputs,
+}];
+
+/// v0 -> v1: `pending_agent_task_outputs` was renamed to
+/// `pending_agent_task_ids`.
+fn rename_pending_agent_task_outputs(fields: &mut Map<String, Value>) {
+    if let Some(value) = fields.remove("pending_agent_task_outputs") {
+        fields.entry("pending_agent_task_ids").or_insert(value);
+    }
+}
+
+#[derive(Serialize)]
+struct Versioned<'a> {
+    schema_version: u32,
+    #[serde(flatten)]
+    session: &'a Session,
+}
+
+/// The `schema_version` of a session file, without parsing the rest of it.
+#[derive(Deserialize)]
+struct VersionProbe {
+    #[serde(default)]
+    schema_version: u32,
+}
+
+/// Serializes `session` as a current-version session file.
+pub(crate) fn encode(session: &Session) -> Result<String> {
+    Ok(serde_json::to_string_pretty(&Versioned {
+        schema_version: CURRENT_VERSION,
+        session,
+    })?)
+}
+
+/// A session file's content, migrated to the current version.
+pub(crate) struct Decoded {
+    pub session: Session,
+    /// The version the file was written with.
+    pub version: u32,
+}
+
+/// Parses a session file of any version, migrating older ones.
+pub(crate) fn decode(content: &str) -> Result<Decoded> {
+    let mut value: Value = serde_json::from_str(content)?;
+    let fields = value
+        .as_object_mut()
+        .ok_or_else(|| anyhow::anyhow!("session file is not a JSON object"))?;
+    let version = match fields.remove(VERSION_KEY) {
+        Some(v) => serde_json::from_value(v)?,
+        None => 0,
+    };
+    for migration in MIGRATIONS.iter().filter(|m| m.from >= version) {
+        (migration.apply)(fields);
+    }
+    Ok(Decoded {
+        session: serde_json::from_value(value)?,
+        version,
+    })
+}
+
+/// The version of the session file with this content; 0 for content that
+/// does not parse, so a corrupted file never blocks the write that fixes it.
+pub(crate) fn version_of(content: &str) -> u32 {
+    serde_json::from_str::<VersionProbe>(content).map_or(0, |probe| probe.schema_version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::cc::types::SessionStatus;
+    use indoc::indoc;
+    use std::collections::BTreeSet;
+
+    const LEGACY: &str = indoc! {r#"
+        {
+          "session_id": "legacy",
+          "cwd": "/tmp/legacy",
+          "transcript_path": null,
+          "tmux_info": null,
+          "status": "stopped",
+          "created_at": "2026-01-01T00:00:00Z",
+          "updated_at": "2026-01-01T00:00:00Z",
+          "last_message": null,
+          "pending_agent_task_outputs": ["/tmp/claude-1/proj/legacy/tasks/agent-1.output"]
+        }
+    "#};
+
+    #[test]
+    fn migrations_are_contiguous_and_end_at_current_version() {
+        let froms: Vec<u32> = MIGRATIONS.iter().map(|m| m.from).collect();
+        assert_eq!(froms, (0..CURRENT_VERSION).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn unversioned_file_migrates_pending_agent_task_outputs() {
+        // A session written by an older armyknife build (before
+        // `pending_agent_task_outputs` was renamed to `pending_agent_task_ids`)
+        // must still load non-empty, not silently drop the pending task and
+        // revert to an empty set until the next `Stop`.
+        let decoded = decode(LEGACY).expect("legacy session should decode");
+        assert_eq!(decoded.version, 0);
+        assert_eq!(decoded.session.status, SessionStatus::Stopped);
+        assert_eq!(
+            decoded.session.pending_agent_task_ids,
+            BTreeSet::from(["/tmp/claude-1/proj/legacy/tasks/agent-1.output".to_string()])
+        );
+    }
+
+    #[test]
+    fn encode_round_trips_at_the_current_version() {
+        let session = decode(LEGACY).expect("decode").session;
+        let encoded = encode(&session).expect("encode");
+
+        assert_eq!(version_of(&encoded), CURRENT_VERSION);
+        let decoded = decode(&encoded).expect("decode");
+        assert_eq!(decoded.version, CURRENT_VERSION);
+        assert_eq!(
+            decoded.session.pending_agent_task_ids,
+            session.pending_agent_task_ids
+        );
+    }
+
+    #[test]
+    fn newer_file_loads_best_effort() {
+        let content = LEGACY.replacen('{', r#"{"schema_version": 99, "future_field": 1,"#, 1);
+        let decoded = decode(&content).expect("newer session should still decode");
+        assert_eq!(decoded.version, 99);
+        assert_eq!(decoded.session.session_id, "legacy");
+    }
+
+    #[test]
+    fn version_of_unparseable_content_is_zero() {
+        assert_eq!(version_of("{ half-written"), 0);
+    }
+}
@@ -35,18 +35,25 @@ use std::time::Duration;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use clap::{Args, Subcommand};
+use tracing::Instrument;
 
 use super::auto_pause::{self, PauseDecision};
+use super::hook;
 use super::signal::{LibcSignalSender, SignalSender};
 use super::store;
 use super::tmux_sync::{LiveTmuxStatusSyncer, TmuxStatusSyncer};
 use super::types::{Session, SessionStatus};
 use crate::infra::process::ProcessSnapshot;
-use crate::shared::active_session::{ActivityProbe, TmuxActivityProbe, effective_updated_at};
+use crate::shared::active_session::{
+    ActivityProbe, MultiplexerActivityProbe, effective_updated_at,
+};
 use crate::shared::config;
 use crate::shared::log::short_run_id;
+use crate::shared::notify;
 
+mod reminder;
 mod service;
+pub(crate) mod stats;
 
 #[derive(Args, Clone, PartialEq, Eq)]
 pub struct SweepArgs {
@@ -81,22 +88,41 @@ pub enum SweepCommands {
 }
 
 /// Entry point for `a cc sweep`.
-pub fn run(args: &SweepArgs) -> Result<()> {
+pub async fn run(args: &SweepArgs) -> Result<()> {
     match args.command.clone().unwrap_or(SweepCommands::Run) {
-        SweepCommands::Run => run_sweep(args),
+        SweepCommands::Run => {
+            let run_id = short_run_id();
+            let span = tracing::info_span!("cc.sweep", run_id = %run_id);
+            run_sweep(args).instrument(span).await
+        }
         SweepCommands::Install => service::install(),
         SweepCommands::Uninstall => service::uninstall(),
         SweepCommands::Status => service::status(),
     }
 }
 
-fn run_sweep(args: &SweepArgs) -> Result<()> {
-    let run_id = short_run_id();
-    let span = tracing::info_span!("cc.sweep", run_id = %run_id);
-    let _entered = span.enter();
-
+async fn run_sweep(args: &SweepArgs) -> Result<()> {
     let config = config::load_config().unwrap_or_default();
 
+    // Sweep runs periodically, so it is what delivers the digest of
+    // notifications held during quiet hours once they end, even when no
+    // hook fires afterwards, and what sends reminders for sessions left
+    // waiting on the user
+    if !args.dry_run
+        && let Err(e) = notify::flush_held(&config.notification)
+    {
+        tracing::warn!(event = "cc.sweep.flush_held_failed", error = %e);
+    }
+    if !args.dry_run && hook::is_notification_enabled(&config) {
+        let sent = match store::list_sessions() {
+            Ok(sessions) => reminder::send_due(&sessions, &config).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = sent {
+            tracing::warn!(event = "cc.sweep.reminders_failed", error = %e);
+        }
+    }
+
     // Respect the enabled flag unless a manual --timeout override was given.
     // (A manual `--timeout 1s` run is an explicit opt-in; we should honor it
     // even if the user set `enabled: false` in their config.)
@@ -116,7 +142,7 @@ fn run_sweep(args: &SweepArgs) -> Result<()> {
     let snapshot = ProcessSnapshot::capture();
     let probe = TmuxSessionProbe {
         snapshot: snapshot.as_ref(),
-        activity: TmuxActivityProbe,
+        activity: MultiplexerActivityProbe,
     };
     tracing::info!(
         event = "cc.sweep.start",
@@ -142,6 +168,11 @@ fn run_sweep(args: &SweepArgs) -> Result<()> {
         timeout = %timeout_str,
         dry_run = args.dry_run,
     );
+    if !args.dry_run
+        && let Err(e) = stats::stats_path().and_then(|path| stats::record_to(&path, &report))
+    {
+        tracing::warn!(event = "cc.sweep.stats_failed", error = %e);
+    }
 
     // Always print a summary in --dry-run so the user can see the reasoning.
     // Otherwise only speak up when a pause was actually confirmed. A session
@@ -545,6 +576,13 @@ mod tests {
             pending_permission_agent_ids: std::collections::BTreeSet::new(),
             read_at: None,
             sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
         }
     }
 
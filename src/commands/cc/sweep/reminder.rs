@@ -0,0 +1,382 @@
+//! Escalating reminders for sessions left waiting on the user
+//! (`notification.reminders`).
+//!
+//! A session is reminded while it is `WaitingInput` or an unread `Stopped`.
+//! Each sweep run sends the highest step the session has crossed but not yet
+//! been reminded of -- so a sweep that was not running for a while sends
+//! one reminder, not a burst. What was sent is tracked in
+//! `~/.cache/armyknife/cc/reminders.json` together with the status and
+//! `updated_at` it was sent for: a status change, a new hook event, or the
+//! session being read (which takes it out of the reminded set) all start
+//! over from the first step.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::super::auto_pause;
+use super::super::hook;
+use super::super::types::{Session, SessionStatus};
+use crate::infra::notification::{Notification, NotificationAction, webhook};
+use crate::shared::cache;
+use crate::shared::config::{Config, ReminderConfig, ReminderStep};
+use crate::shared::notify::{self, Delivery};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ReminderState {
+    status: SessionStatus,
+    updated_at: DateTime<Utc>,
+    /// Number of steps already sent.
+    sent: usize,
+}
+
+type States = BTreeMap<String, ReminderState>;
+
+/// A reminder due for a session.
+#[derive(Debug)]
+struct Reminder<'a> {
+    session: &'a Session,
+    step: &'a ReminderStep,
+    /// 1-based position of `step`, shown in the title.
+    level: usize,
+}
+
+/// Path: ~/.cache/armyknife/cc/reminders.json
+fn state_file() -> Result<PathBuf> {
+    cache::base_dir()
+        .map(|d| d.join("cc").join("reminders.json"))
+        .context("Failed to get cache directory")
+}
+
+fn load_states(path: &Path) -> States {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_states(path: &Path, states: &States) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let temp_path = path.with_extension("json.tmp");
+    let mut temp_file = File::create(&temp_path)?;
+    temp_file.write_all(serde_json::to_string(states)?.as_bytes())?;
+    fs::rename(&temp_path, path)?;
+    Ok(())
+}
+
+fn steps_for<'a>(session: &Session, config: &'a ReminderConfig) -> &'a [ReminderStep] {
+    match session.status {
+        SessionStatus::WaitingInput => &config.waiting_input,
+        SessionStatus::Stopped if session.is_unread_stopped() => &config.stopped,
+        _ => &[],
+    }
+}
+
+/// `steps` ordered by their threshold. Steps with an unparseable `after` are
+/// dropped.
+fn ordered_steps(steps: &[ReminderStep]) -> Vec<(Duration, &ReminderStep)> {
+    let mut ordered: Vec<_> = steps
+        .iter()
+        .filter_map(|step| match auto_pause::parse_duration(&step.after) {
+            Ok(after) => Some((after, step)),
+            Err(e) => {
+                tracing::warn!(event = "cc.reminder.invalid_after", after = %step.after, error = %e);
+                None
+            }
+        })
+        .collect();
+    ordered.sort_by_key(|(after, _)| *after);
+    ordered
+}
+
+/// Works out which reminders are due at `now` and the state to persist
+/// afterwards. Sessions no longer waiting on the user drop out of the state.
+fn plan<'a>(
+    sessions: &'a [Session],
+    previous: &States,
+    config: &'a ReminderConfig,
+    now: DateTime<Utc>,
+) -> (States, Vec<Reminder<'a>>) {
+    let mut states = States::new();
+    let mut due = Vec::new();
+    for session in sessions {
+        let steps = ordered_steps(steps_for(session, config));
+        if steps.is_empty() {
+            continue;
+        }
+        let sent = previous
+            .get(&session.session_id)
+            .filter(|state| {
+                state.status == session.status && state.updated_at == session.updated_at
+            })
+            .map_or(0, |state| state.sent);
+        let waited = (now - session.updated_at).to_std().unwrap_or_default();
+        let crossed = steps.iter().filter(|(after, _)| *after <= waited).count();
+        if crossed > sent {
+            due.push(Reminder {
+                session,
+                step: steps[crossed - 1].1,
+                level: crossed,
+            });
+        }
+        states.insert(
+            session.session_id.clone(),
+            ReminderState {
+                status: session.status,
+                updated_at: session.updated_at,
+                sent: sent.max(crossed),
+            },
+        );
+    }
+    (states, due)
+}
+
+fn build_notification(reminder: &Reminder<'_>, config: &Config) -> Notification {
+    let session = reminder.session;
+    let after = &reminder.step.after;
+    let (title, message) = match session.status {
+        SessionStatus::WaitingInput => (
+            "\u{1f514} Claude Code - Still waiting",
+            format!("Waiting for input for over {after}"),
+        ),
+        _ => (
+            "\u{1f514} Claude Code - Still unread",
+            format!("Stopped over {after} ago"),
+        ),
+    };
+    let title = match reminder.level {
+        1 => title.to_string(),
+        level => format!("{title} (x{level})"),
+    };
+
+    // Same group as the hook's notification, so the reminder replaces it
+    let mut notification = Notification::new(title, message).with_group(&session.session_id);
+    let sound = reminder
+        .step
+        .sound
+        .as_deref()
+        .unwrap_or(&config.notification.sound);
+    if !sound.is_empty() {
+        notification = notification.with_sound(sound);
+    }
+    if let Some(subtitle) = hook::build_subtitle(session) {
+        notification = notification.with_subtitle(subtitle);
+    }
+    if session.tmux_info.is_some() {
+        let session_id = shlex::try_quote(&session.session_id)
+            .unwrap_or_else(|_| session.session_id.clone().into());
+        let focus_cmd = notify::focus_app_command(config);
+        notification = notification.with_action(NotificationAction::new(format!(
+            "a cc focus {session_id}; {focus_cmd}"
+        )));
+    }
+    notification
+}
+
+async fn send(reminder: &Reminder<'_>, config: &Config) -> Result<()> {
+    let notification = build_notification(reminder, config);
+    let session = reminder.session;
+    let kind = match session.status {
+        SessionStatus::WaitingInput if !session.pending_permission_agent_ids.is_empty() => {
+            notify::Kind::Permission
+        }
+        SessionStatus::WaitingInput => notify::Kind::Waiting,
+        _ => notify::Kind::Stopped,
+    };
+    // The webhook follows the desktop notification: nothing goes out while
+    // quiet hours or focus mode hold or silence it
+    let delivery = notify::deliver(notification.clone(), kind, config)?;
+    if let (Delivery::Send, Some(url)) = (delivery, &reminder.step.webhook) {
+        webhook::post(url, &notification).await?;
+    }
+    Ok(())
+}
+
+/// Sends the reminders due for `sessions`, as configured in `config`.
+pub(super) async fn send_due(sessions: &[Session], config: &Config) -> Result<()> {
+    let path = state_file()?;
+    let previous = load_states(&path);
+    let (states, due) = plan(
+        sessions,
+        &previous,
+        &config.notification.reminders,
+        Utc::now(),
+    );
+    for reminder in &due {
+        tracing::info!(
+            event = "cc.reminder.sent",
+            session_id = %reminder.session.session_id,
+            level = reminder.level,
+        );
+        // A failed reminder still counts as sent so a broken webhook can't
+        // re-fire on every sweep
+        if let Err(e) = send(reminder, config).await {
+            tracing::warn!(
+                event = "cc.reminder.failed",
+                session_id = %reminder.session.session_id,
+                error = %e,
+            );
+        }
+    }
+    if states != previous {
+        save_states(&path, &states)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn step(after: &str) -> ReminderStep {
+        ReminderStep {
+            after: after.to_string(),
+            sound: None,
+            webhook: None,
+        }
+    }
+
+    fn config() -> ReminderConfig {
+        ReminderConfig {
+            waiting_input: vec![step("10m"), step("1h"), step("30m")],
+            stopped: vec![step("2h")],
+        }
+    }
+
+    fn session(status: SessionStatus, read: bool, waited_mins: i64, now: DateTime<Utc>) -> Session {
+        let updated_at = now - chrono::TimeDelta::minutes(waited_mins);
+        Session {
+            session_id: "s1".to_string(),
+            cwd: PathBuf::from("/tmp"),
+            transcript_path: None,
+            tty: None,
+            tmux_info: None,
+            status,
+            created_at: updated_at,
+            updated_at,
+            last_message: None,
+            current_tool: None,
+            label: None,
+            ancestor_session_ids: Vec::new(),
+            pending_bg_task_ids: Default::default(),
+            pending_agent_task_ids: Default::default(),
+            pending_permission_agent_ids: Default::default(),
+            read_at: read.then_some(now),
+            sweep_signaled: false,
+            report: None,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            tags: Default::default(),
+            note: None,
+        }
+    }
+
+    fn sent_state(session: &Session, sent: usize) -> States {
+        States::from([(
+            session.session_id.clone(),
+            ReminderState {
+                status: session.status,
+                updated_at: session.updated_at,
+                sent,
+            },
+        )])
+    }
+
+    /// (thresholds crossed, already sent) -> level due, if any
+    #[rstest]
+    #[case::not_yet(5, 0, None)]
+    #[case::first_step(12, 0, Some(1))]
+    #[case::already_sent(12, 1, None)]
+    #[case::escalates_in_threshold_order(35, 1, Some(2))]
+    #[case::skips_to_highest_crossed(90, 0, Some(3))]
+    #[case::exhausted(300, 3, None)]
+    fn waiting_input_escalation(
+        #[case] waited_mins: i64,
+        #[case] sent: usize,
+        #[case] expected_level: Option<usize>,
+    ) {
+        let now = Utc::now();
+        let config = config();
+        let sessions = [session(
+            SessionStatus::WaitingInput,
+            false,
+            waited_mins,
+            now,
+        )];
+        let previous = sent_state(&sessions[0], sent);
+
+        let (states, due) = plan(&sessions, &previous, &config, now);
+
+        assert_eq!(due.first().map(|r| r.level), expected_level);
+        assert_eq!(
+            states["s1"].sent,
+            sent.max(expected_level.unwrap_or_default())
+        );
+    }
+
+    #[test]
+    fn threshold_is_picked_from_the_sorted_steps() {
+        let now = Utc::now();
+        let config = config();
+        let sessions = [session(SessionStatus::WaitingInput, false, 35, now)];
+        let (_, due) = plan(&sessions, &States::new(), &config, now);
+        assert_eq!(due[0].step.after, "30m");
+    }
+
+    #[test]
+    fn new_event_starts_over() {
+        let now = Utc::now();
+        let config = config();
+        let sessions = [session(SessionStatus::WaitingInput, false, 12, now)];
+        let mut previous = sent_state(&sessions[0], 3);
+        previous.get_mut("s1").unwrap().updated_at -= chrono::TimeDelta::hours(2);
+
+        let (_, due) = plan(&sessions, &previous, &config, now);
+
+        assert_eq!(due.first().map(|r| r.level), Some(1));
+    }
+
+    #[rstest]
+    #[case::unread_stopped(SessionStatus::Stopped, false, true)]
+    #[case::read_stopped(SessionStatus::Stopped, true, false)]
+    #[case::running(SessionStatus::Running, false, false)]
+    #[case::paused(SessionStatus::Paused, false, false)]
+    fn only_unread_stopped_sessions_are_reminded(
+        #[case] status: SessionStatus,
+        #[case] read: bool,
+        #[case] expected: bool,
+    ) {
+        let now = Utc::now();
+        let config = config();
+        let sessions = [session(status, read, 180, now)];
+        let previous = sent_state(&sessions[0], 0);
+
+        let (states, due) = plan(&sessions, &previous, &config, now);
+
+        assert_eq!(!due.is_empty(), expected);
+        assert_eq!(states.contains_key("s1"), expected);
+    }
+
+    #[test]
+    fn invalid_steps_are_ignored() {
+        let now = Utc::now();
+        let config = ReminderConfig {
+            waiting_input: vec![step("soon"), step("10m")],
+            stopped: Vec::new(),
+        };
+        let sessions = [session(SessionStatus::WaitingInput, false, 12, now)];
+        let (_, due) = plan(&sessions, &States::new(), &config, now);
+        assert_eq!(due.first().map(|r| r.step.after.as_str()), Some("10m"));
+    }
+}
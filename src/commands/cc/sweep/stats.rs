@@ -0,0 +1,95 @@
+//! Running totals of what `a cc sweep` has done, exported by `a cc metrics`.
+//!
+//! Sweep itself is stateless between runs (everything it decides on lives in
+//! the session files), so the counters are kept in a small JSON file next
+//! to the sessions directory. Only sweep writes it, and sweep runs one pass
+//! at a time from its scheduler, so a plain atomic replace is enough.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use super::SweepReport;
+use crate::commands::cc::error::CcError;
+use crate::shared::cache;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct SweepStats {
+    /// Completed (non-dry-run) sweep passes.
+    #[serde(default)]
+    pub runs: u64,
+    /// Sessions confirmed as `Paused`.
+    #[serde(default)]
+    pub paused: u64,
+    /// SIGTERMs sent, including re-signals of a session still exiting.
+    #[serde(default)]
+    pub signaled: u64,
+}
+
+/// Returns the stats file path.
+/// Path: ~/.cache/armyknife/cc/sweep-stats.json
+pub(crate) fn stats_path() -> Result<PathBuf> {
+    cache::base_dir()
+        .map(|d| d.join("cc").join("sweep-stats.json"))
+        .ok_or_else(|| CcError::CacheDirNotFound.into())
+}
+
+/// Totals recorded so far; all zero when sweep has never run (or the file
+/// is unreadable, which only costs the counters a reset).
+pub(crate) fn load_from(path: &Path) -> SweepStats {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Adds one sweep pass to the totals at `path`.
+pub(crate) fn record_to(path: &Path, report: &SweepReport) -> Result<()> {
+    let mut stats = load_from(path);
+    stats.runs += 1;
+    stats.paused += report.paused as u64;
+    stats.signaled += report.signaled as u64;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let temp_path = path.with_extension("json.tmp");
+    let mut temp_file = File::create(&temp_path)?;
+    temp_file.write_all(serde_json::to_string_pretty(&stats)?.as_bytes())?;
+    temp_file.sync_all()?;
+    fs::rename(&temp_path, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn record_accumulates_across_runs() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("cc").join("sweep-stats.json");
+        assert_eq!(load_from(&path), SweepStats::default());
+
+        let report = SweepReport {
+            paused: 2,
+            signaled: 1,
+            ..Default::default()
+        };
+        record_to(&path, &report).unwrap();
+        record_to(&path, &report).unwrap();
+
+        assert_eq!(
+            load_from(&path),
+            SweepStats {
+                runs: 2,
+                paused: 4,
+                signaled: 2,
+            }
+        );
+    }
+}
@@ -0,0 +1,162 @@
+//! The small template language of the `cc` outputs users can restyle
+//! (`a cc statusline`, the tmux window status and title, `a cc list --tmux`).
+//!
+//! `{{name}}` expands to a variable. `{{#name}}...{{/name}}` keeps its body
+//! only when the variable is non-empty and `{{^name}}...{{/name}}` only when
+//! it is empty; sections nest. Everything else is copied verbatim, so tmux
+//! style markup like `#[fg=yellow]` passes straight through.
+
+use std::collections::HashMap;
+
+use anyhow::{Context, Result, bail};
+
+/// `{{name}}`, with optional whitespace inside the braces.
+fn placeholder_re() -> &'static lazy_regex::Regex {
+    lazy_regex::regex!(r"\{\{\s*([A-Za-z0-9_]+)\s*\}\}")
+}
+
+/// A section's opening (`{{#name}}`, `{{^name}}`) or closing (`{{/name}}`)
+/// tag.
+fn section_tag_re() -> &'static lazy_regex::Regex {
+    lazy_regex::regex!(r"\{\{[#^/]([A-Za-z0-9_]+)\}\}")
+}
+
+/// Whether `template` references `name`, as a placeholder or a section,
+/// by the same patterns `render` expands. Lets callers skip computing
+/// variables that need I/O.
+pub(crate) fn uses(template: &str, name: &str) -> bool {
+    [placeholder_re(), section_tag_re()].iter().any(|re| {
+        re.captures_iter(template)
+            .any(|caps| caps.get(1).is_some_and(|m| m.as_str() == name))
+    })
+}
+
+/// Renders `template`: first drops or unwraps each section (innermost
+/// first, so sections nest), then expands `{{name}}`. A reference to an
+/// unknown variable or an unbalanced section is an error rather than being
+/// printed verbatim; `what` names the template in the message.
+pub(crate) fn render(
+    template: &str,
+    vars: &HashMap<&'static str, String>,
+    what: &str,
+) -> Result<String> {
+    let lookup = |name: &str| -> Result<&String> {
+        vars.get(name)
+            .with_context(|| format!("unknown {what} variable '{name}'"))
+    };
+
+    let close_re = lazy_regex::regex!(r"\{\{/([A-Za-z0-9_]+)\}\}");
+    let mut text = template.to_string();
+    while let Some(caps) = close_re.captures(&text) {
+        let (Some(close), Some(name)) = (caps.get(0), caps.get(1)) else {
+            break;
+        };
+        let name = name.as_str().to_string();
+        let before = &text[..close.start()];
+        let normal_tag = format!("{{{{#{name}}}}}");
+        let inverted_tag = format!("{{{{^{name}}}}}");
+        let (open_start, open_len, inverted) =
+            match (before.rfind(&normal_tag), before.rfind(&inverted_tag)) {
+                (Some(n), Some(i)) if i > n => (i, inverted_tag.len(), true),
+                (Some(n), _) => (n, normal_tag.len(), false),
+                (None, Some(i)) => (i, inverted_tag.len(), true),
+                (None, None) => {
+                    bail!("{what} template closes section '{name}' that was never opened")
+                }
+            };
+        let body_start = open_start + open_len;
+        let kept = if lookup(&name)?.is_empty() == inverted {
+            text[body_start..close.start()].to_string()
+        } else {
+            String::new()
+        };
+        text.replace_range(open_start..close.end(), &kept);
+    }
+    if let Some(caps) = lazy_regex::regex!(r"\{\{[#^]([A-Za-z0-9_]+)\}\}").captures(&text) {
+        bail!("{what} template section '{}' is never closed", &caps[1]);
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for caps in placeholder_re().captures_iter(&text) {
+        let (Some(whole), Some(name)) = (caps.get(0), caps.get(1)) else {
+            continue;
+        };
+        out.push_str(&text[last..whole.start()]);
+        out.push_str(lookup(name.as_str())?);
+        last = whole.end();
+    }
+    out.push_str(&text[last..]);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn vars(pairs: &[(&'static str, &str)]) -> HashMap<&'static str, String> {
+        pairs.iter().map(|(k, v)| (*k, v.to_string())).collect()
+    }
+
+    #[rstest]
+    #[case::placeholder("{{branch}}", &[("branch", "main")], "main")]
+    #[case::padded_placeholder("{{ branch }}", &[("branch", "main")], "main")]
+    #[case::section_kept("{{#pr}}PR {{pr}}{{/pr}}", &[("pr", "#1")], "PR #1")]
+    #[case::section_dropped("a{{#pr}} PR {{pr}}{{/pr}}b", &[("pr", "")], "ab")]
+    #[case::inverted_kept("{{^pr}}no PR{{/pr}}", &[("pr", "")], "no PR")]
+    #[case::inverted_dropped("{{^pr}}no PR{{/pr}}", &[("pr", "#1")], "")]
+    #[case::section_and_inverted(
+        "{{#pr}}{{pr}}{{/pr}}{{^pr}}-{{/pr}}",
+        &[("pr", "")],
+        "-"
+    )]
+    #[case::nested_inner_dropped(
+        "{{#pr}}{{pr}}{{#ci}} ci:{{ci}}{{/ci}}{{/pr}}",
+        &[("pr", "#1"), ("ci", "")],
+        "#1"
+    )]
+    #[case::nested_outer_dropped(
+        "{{#pr}}{{pr}}{{#ci}} ci:{{ci}}{{/ci}}{{/pr}}",
+        &[("pr", ""), ("ci", "passing")],
+        ""
+    )]
+    #[case::repeated_section(
+        "{{#a}}x{{/a}}-{{#a}}y{{/a}}",
+        &[("a", "1")],
+        "x-y"
+    )]
+    #[case::tmux_markup_passes_through(
+        "#[fg=yellow]{{a}}#[default]",
+        &[("a", "1")],
+        "#[fg=yellow]1#[default]"
+    )]
+    fn render_cases(
+        #[case] template: &str,
+        #[case] pairs: &[(&'static str, &str)],
+        #[case] expected: &str,
+    ) {
+        assert_eq!(render(template, &vars(pairs), "test").unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case::unknown_placeholder("{{nope}}", "unknown test variable 'nope'")]
+    #[case::unknown_section("{{#nope}}x{{/nope}}", "unknown test variable 'nope'")]
+    #[case::unopened("x{{/pr}}", "test template closes section 'pr' that was never opened")]
+    #[case::unclosed("{{#pr}}x", "test template section 'pr' is never closed")]
+    #[case::unclosed_inverted("{{^pr}}x", "test template section 'pr' is never closed")]
+    fn render_errors(#[case] template: &str, #[case] message: &str) {
+        let err = render(template, &vars(&[("pr", "#1")]), "test").unwrap_err();
+        assert_eq!(err.to_string(), message);
+    }
+
+    #[rstest]
+    #[case::placeholder("{{pr}}", true)]
+    #[case::padded_placeholder("{{ pr }}", true)]
+    #[case::section("{{#pr}}x{{/pr}}", true)]
+    #[case::inverted("{{^pr}}x{{/pr}}", true)]
+    #[case::other("{{prs}}", false)]
+    fn uses_cases(#[case] template: &str, #[case] expected: bool) {
+        assert_eq!(uses(template, "pr"), expected);
+    }
+}
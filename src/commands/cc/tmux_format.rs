@@ -0,0 +1,283 @@
+//! Variables of the tmux-facing templates (`cc.tmux.window_status`,
+//! `cc.tmux.window_title`, `cc.tmux.list`), computed over a set of sessions:
+//! one window's for the window options, every session's for
+//! `a cc list --tmux`.
+//!
+//! These render from hooks, so nothing here may block: the branch is read
+//! from the local repository and the PR state only from the PR cache.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+
+use super::pr_cache;
+use super::statusline::pr_state_name;
+use super::template;
+use super::types::{Session, SessionStatus};
+use crate::infra::git::{GitRepo, github_owner_and_repo};
+use crate::infra::github::BranchPrQuery;
+use crate::shared::config::{TmuxStatusStyle, TmuxStatusStyles};
+
+/// Renders `template` over `sessions`; `what` names the template in errors.
+pub(crate) fn render(
+    template: &str,
+    sessions: &[Session],
+    styles: &TmuxStatusStyles,
+    what: &str,
+) -> Result<String> {
+    let uses = |name: &str| template::uses(template, name);
+    let mut vars = variables(sessions, styles);
+
+    let first = sessions.iter().find(|s| s.status != SessionStatus::Ended);
+    let branch = if uses("branch") || uses("pr") || uses("pr_state") {
+        first.and_then(|s| {
+            let repo = GitRepo::open_at(&s.cwd).ok()?;
+            let branch = repo.current_branch().ok().filter(|b| b != "HEAD")?;
+            Some((repo, branch))
+        })
+    } else {
+        None
+    };
+    let pr = branch.as_ref().and_then(|(repo, branch)| {
+        if !uses("pr") && !uses("pr_state") {
+            return None;
+        }
+        let (owner, name) = github_owner_and_repo(repo).ok()?;
+        pr_cache::last_known_pr(&BranchPrQuery {
+            owner,
+            repo: name,
+            branch: branch.clone(),
+        })
+    });
+    vars.insert(
+        "branch",
+        branch.map(|(_, branch)| branch).unwrap_or_default(),
+    );
+    vars.insert(
+        "pr",
+        pr.as_ref()
+            .map(|p| format!("#{}", p.number))
+            .unwrap_or_default(),
+    );
+    vars.insert(
+        "pr_state",
+        pr.map(|p| pr_state_name(&p.state).to_string())
+            .unwrap_or_default(),
+    );
+
+    template::render(template, &vars, what)
+}
+
+/// Status key of `session` in `cc.tmux.styles`, most urgent first, or
+/// `None` for an `Ended` session (Claude Code fully exited), which is not
+/// shown. `Stopped` and `Paused` are: their panes are alive and the
+/// conversation is resumable.
+fn status_key(session: &Session) -> Option<usize> {
+    match session.status {
+        SessionStatus::WaitingInput => Some(0),
+        SessionStatus::Stopped if session.is_unread_stopped() => Some(1),
+        SessionStatus::Running => Some(2),
+        SessionStatus::Stopped => Some(3),
+        SessionStatus::Paused => Some(4),
+        SessionStatus::Ended => None,
+    }
+}
+
+/// `(built-in glyph, override)` for each status key.
+fn status_styles(styles: &TmuxStatusStyles) -> [(&'static str, &TmuxStatusStyle); 5] {
+    [
+        (
+            SessionStatus::WaitingInput.display_symbol(),
+            &styles.waiting,
+        ),
+        (Session::UNREAD_SYMBOL, &styles.unread),
+        (SessionStatus::Running.display_symbol(), &styles.running),
+        (SessionStatus::Stopped.display_symbol(), &styles.stopped),
+        (SessionStatus::Paused.display_symbol(), &styles.paused),
+    ]
+}
+
+/// The glyph wrapped in the status's tmux style, if it has one.
+///
+/// No style is applied by default: a foreground color would visually become
+/// a *background* color whenever the surrounding context has the `reverse`
+/// attribute (a common idiom for `window-status-activity-style`), painting
+/// the icon's cell with a color block that breaks out of the rest of the
+/// tab. Shape alone carries the status well enough.
+fn styled_symbol(glyph: &str, style: &TmuxStatusStyle) -> String {
+    let glyph = style.symbol.as_deref().unwrap_or(glyph);
+    match style.style.as_deref().filter(|s| !s.is_empty()) {
+        Some(style) => format!("#[{style}]{glyph}#[default]"),
+        None => glyph.to_string(),
+    }
+}
+
+/// Every variable that needs no I/O. Counts are empty rather than `0` so
+/// they work as sections.
+fn variables(sessions: &[Session], styles: &TmuxStatusStyles) -> HashMap<&'static str, String> {
+    let styles = status_styles(styles);
+    let keys: Vec<usize> = sessions.iter().filter_map(status_key).collect();
+    let count = |wanted: &[usize]| keys.iter().filter(|k| wanted.contains(k)).count();
+    let count_var = |n: usize| if n > 0 { n.to_string() } else { String::new() };
+
+    let mut vars = HashMap::new();
+    vars.insert(
+        "symbols",
+        keys.iter()
+            .map(|&k| styled_symbol(styles[k].0, styles[k].1))
+            .collect(),
+    );
+    vars.insert(
+        "symbol",
+        keys.iter()
+            .min()
+            .map(|&k| styled_symbol(styles[k].0, styles[k].1))
+            .unwrap_or_default(),
+    );
+    vars.insert("waiting", count_var(count(&[0])));
+    vars.insert("unread", count_var(count(&[1])));
+    vars.insert("running", count_var(count(&[2])));
+    vars.insert("stopped", count_var(count(&[1, 3])));
+    vars.insert("paused", count_var(count(&[4])));
+    vars.insert("pending", count_var(count(&[0, 1, 3])));
+    vars.insert("total", count_var(keys.len()));
+    vars.insert(
+        "label",
+        sessions
+            .iter()
+            .find_map(|s| s.label.clone())
+            .unwrap_or_default(),
+    );
+    vars
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::config::TmuxStatusConfig;
+    use chrono::Utc;
+    use rstest::rstest;
+    use std::path::PathBuf;
+
+    fn session(status: SessionStatus, read: bool) -> Session {
+        Session {
+            session_id: "test-123".to_string(),
+            cwd: PathBuf::from("/nonexistent/armyknife-test"),
+            transcript_path: None,
+            tty: None,
+            tmux_info: None,
+            status,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_message: None,
+            current_tool: None,
+            label: None,
+            ancestor_session_ids: Vec::new(),
+            pending_bg_task_ids: std::collections::BTreeSet::new(),
+            pending_agent_task_ids: std::collections::BTreeSet::new(),
+            pending_permission_agent_ids: std::collections::BTreeSet::new(),
+            read_at: read.then(Utc::now),
+            sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
+        }
+    }
+
+    fn render_default(template: &str, sessions: &[Session]) -> String {
+        render(template, sessions, &TmuxStatusStyles::default(), "test").unwrap()
+    }
+
+    #[rstest]
+    #[case::none(&[], "")]
+    #[case::only_ended(&[(SessionStatus::Ended, false)], "")]
+    #[case::running_waiting_unread(
+        &[
+            (SessionStatus::Running, false),
+            (SessionStatus::WaitingInput, false),
+            (SessionStatus::Stopped, false),
+        ],
+        "\u{25cf}\u{25d0}\u{2731} "
+    )]
+    #[case::read_and_paused(
+        &[(SessionStatus::Stopped, true), (SessionStatus::Paused, false)],
+        "\u{25cb}\u{23f8} "
+    )]
+    fn default_window_status(#[case] sessions: &[(SessionStatus, bool)], #[case] expected: &str) {
+        let sessions: Vec<Session> = sessions.iter().map(|&(s, r)| session(s, r)).collect();
+        assert_eq!(
+            render_default(&TmuxStatusConfig::default().window_status, &sessions),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case::none(&[], "")]
+    #[case::all_running(&[SessionStatus::Running, SessionStatus::Running], "")]
+    #[case::waiting(
+        &[SessionStatus::Running, SessionStatus::WaitingInput, SessionStatus::Stopped],
+        "#[fg=yellow]\u{25d0} 2#[default]"
+    )]
+    #[case::stopped_only(&[SessionStatus::Running, SessionStatus::Stopped], "\u{25cb} 1")]
+    fn default_list(#[case] statuses: &[SessionStatus], #[case] expected: &str) {
+        let sessions: Vec<Session> = statuses.iter().map(|&s| session(s, false)).collect();
+        assert_eq!(
+            render_default(&TmuxStatusConfig::default().list, &sessions),
+            expected
+        );
+    }
+
+    #[test]
+    fn styles_override_glyph_and_wrap_it() {
+        let styles = TmuxStatusStyles {
+            waiting: TmuxStatusStyle {
+                symbol: Some("W".to_string()),
+                style: Some("fg=yellow".to_string()),
+            },
+            running: TmuxStatusStyle {
+                symbol: Some("R".to_string()),
+                style: None,
+            },
+            ..Default::default()
+        };
+        let sessions = vec![
+            session(SessionStatus::Running, false),
+            session(SessionStatus::WaitingInput, false),
+        ];
+        assert_eq!(
+            render("{{symbols}}|{{symbol}}", &sessions, &styles, "test").unwrap(),
+            "R#[fg=yellow]W#[default]|#[fg=yellow]W#[default]"
+        );
+    }
+
+    #[test]
+    fn counts_are_empty_when_zero() {
+        let sessions = vec![
+            session(SessionStatus::Stopped, false),
+            session(SessionStatus::Stopped, true),
+            session(SessionStatus::Ended, false),
+        ];
+        assert_eq!(
+            render_default(
+                "w={{waiting}} u={{unread}} s={{stopped}} p={{pending}} t={{total}}",
+                &sessions
+            ),
+            "w= u=1 s=2 p=2 t=2"
+        );
+    }
+
+    #[test]
+    fn label_is_first_set_label_and_branch_empty_outside_a_repo() {
+        let mut labeled = session(SessionStatus::Running, false);
+        labeled.label = Some("Fix login".to_string());
+        let sessions = vec![session(SessionStatus::Running, false), labeled];
+        assert_eq!(
+            render_default("{{label}}|{{branch}}|{{pr_state}}", &sessions),
+            "Fix login||"
+        );
+    }
+}
@@ -12,7 +12,7 @@ use std::path::Path;
 use super::pane;
 use super::types::SessionStatus;
 use super::window_status;
-use crate::infra::tmux;
+use crate::infra::multiplexer;
 
 /// Pushes the aggregated window status for the window containing `pane_id`
 /// into its window-scoped tmux user option and materializes the pane's
@@ -39,7 +39,7 @@ impl TmuxStatusSyncer for LiveTmuxStatusSyncer {
             return;
         };
         let _ = pane::status::sync_paused_flag(pane_id, status, sessions_dir);
-        let Some(window_id) = tmux::get_window_id_for_pane(pane_id) else {
+        let Some(window_id) = multiplexer::current().window_of_pane(pane_id) else {
             return;
         };
         let _ = window_status::sync_window_option(&window_id, sessions_dir);
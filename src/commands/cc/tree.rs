@@ -0,0 +1,506 @@
+//! `a cc tree` -- render the delegation hierarchy encoded in
+//! `ancestor_session_ids` as text, Graphviz DOT, Mermaid, or JSON.
+//!
+//! `cc watch` only hints at the hierarchy (breadcrumbs and `▸{n}` badges);
+//! this prints the whole tree at once so a large multi-agent run can be
+//! pasted into a PR description or design doc. Each session hangs off its
+//! nearest ancestor still on disk, the same rule the TUI's breadcrumbs use,
+//! so a tree with an intermediate session already garbage-collected stays
+//! connected.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+
+use super::error::CcError;
+use super::store;
+use super::types::{Session, SessionStatus};
+use crate::infra::git::{GitRepo, github_owner_and_repo};
+use crate::infra::github::{BranchPrQuery, GitHubClient};
+
+#[derive(Args, Clone, PartialEq, Eq)]
+pub struct TreeArgs {
+    /// Only render the subtree rooted at this session ID
+    #[arg(long)]
+    pub root: Option<String>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = TreeFormat::Text)]
+    pub format: TreeFormat,
+
+    /// Skip the GitHub lookup for each node's PR; only PRs recorded via
+    /// `a cc peer report` are shown
+    #[arg(long)]
+    pub offline: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TreeFormat {
+    Text,
+    Dot,
+    Mermaid,
+    Json,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct TreeNode {
+    session_id: String,
+    label: Option<String>,
+    status: SessionStatus,
+    cwd: PathBuf,
+    branch: Option<String>,
+    pr: Option<String>,
+    last_message: Option<String>,
+    updated_at: DateTime<Utc>,
+    children: Vec<TreeNode>,
+}
+
+impl TreeNode {
+    /// `label` when set, otherwise the cwd basename -- the same fallback the
+    /// TUI's worktree rows use.
+    fn title(&self) -> String {
+        self.label.clone().unwrap_or_else(|| {
+            self.cwd
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| self.session_id.clone())
+        })
+    }
+}
+
+pub async fn run(args: &TreeArgs) -> Result<()> {
+    let sessions = store::list_all_sessions()?;
+    let branches: HashMap<String, String> = sessions
+        .iter()
+        .filter_map(|s| {
+            let branch = GitRepo::open_at(&s.cwd).ok()?.current_branch().ok()?;
+            Some((s.session_id.clone(), branch))
+        })
+        .collect();
+    let prs = if args.offline {
+        HashMap::new()
+    } else {
+        fetch_pr_urls(&sessions, &branches).await
+    };
+
+    let mut roots = build_forest(&sessions, &branches, &prs);
+    if let Some(root_id) = &args.root {
+        let subtree = find_subtree(roots, root_id)
+            .ok_or_else(|| CcError::SessionNotFound(root_id.clone()))?;
+        roots = vec![subtree];
+    }
+
+    let rendered = match args.format {
+        TreeFormat::Text => render_text(&roots),
+        TreeFormat::Dot => render_dot(&roots),
+        TreeFormat::Mermaid => render_mermaid(&roots),
+        TreeFormat::Json => serde_json::to_string_pretty(&roots)? + "\n",
+    };
+    print!("{rendered}");
+    Ok(())
+}
+
+/// Resolves each session's PR URL from GitHub in one batched query.
+/// Best-effort: a lookup failure (no network, no token) is reported on
+/// stderr and the tree is rendered with report-recorded PRs only.
+pub(crate) async fn fetch_pr_urls(
+    sessions: &[Session],
+    branches: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    let mut keys: Vec<(String, (String, String, String))> = Vec::new();
+    for session in sessions {
+        let Some(branch) = branches.get(&session.session_id) else {
+            continue;
+        };
+        if branch == "HEAD" {
+            continue;
+        }
+        let Some((owner, repo)) = GitRepo::open_at(&session.cwd)
+            .ok()
+            .and_then(|r| github_owner_and_repo(&r).ok())
+        else {
+            continue;
+        };
+        keys.push((session.session_id.clone(), (owner, repo, branch.clone())));
+    }
+    if keys.is_empty() {
+        return HashMap::new();
+    }
+
+    // Sibling sessions often share a worktree, so dedupe before querying.
+    let unique: HashSet<&(String, String, String)> = keys.iter().map(|(_, key)| key).collect();
+    let queries: Vec<BranchPrQuery> = unique
+        .into_iter()
+        .map(|(owner, repo, branch)| BranchPrQuery {
+            owner: owner.clone(),
+            repo: repo.clone(),
+            branch: branch.clone(),
+        })
+        .collect();
+    let result = match GitHubClient::get() {
+        Ok(client) => client.get_prs_for_branches_batch(&queries).await,
+        Err(e) => Err(e),
+    };
+    let pr_map = match result {
+        Ok(map) => map,
+        Err(e) => {
+            eprintln!("[armyknife] warning: failed to fetch PRs: {e}");
+            return HashMap::new();
+        }
+    };
+
+    keys.into_iter()
+        .filter_map(|(session_id, key)| {
+            let info = pr_map.get(&key)?.as_ref()?;
+            Some((session_id, info.url.clone()))
+        })
+        .collect()
+}
+
+/// Builds the forest of delegation trees. Each session is attached under
+/// its nearest ancestor present in `sessions`; sessions without one are
+/// roots. Roots and siblings keep the order of `sessions`. A PR recorded
+/// in the session's own report takes precedence over `prs`.
+pub(crate) fn build_forest(
+    sessions: &[Session],
+    branches: &HashMap<String, String>,
+    prs: &HashMap<String, String>,
+) -> Vec<TreeNode> {
+    let ids: HashSet<&str> = sessions.iter().map(|s| s.session_id.as_str()).collect();
+
+    let mut children_of: HashMap<Option<&str>, Vec<&Session>> = HashMap::new();
+    for session in sessions {
+        children_of
+            .entry(parent_of(session, &ids))
+            .or_default()
+            .push(session);
+    }
+
+    fn build(
+        session: &Session,
+        children_of: &HashMap<Option<&str>, Vec<&Session>>,
+        branches: &HashMap<String, String>,
+        prs: &HashMap<String, String>,
+    ) -> TreeNode {
+        let children = children_of
+            .get(&Some(session.session_id.as_str()))
+            .map(|kids| {
+                kids.iter()
+                    .map(|kid| build(kid, children_of, branches, prs))
+                    .collect()
+            })
+            .unwrap_or_default();
+        TreeNode {
+            session_id: session.session_id.clone(),
+            label: session.label.clone(),
+            status: session.status,
+            cwd: session.cwd.clone(),
+            branch: branches.get(&session.session_id).cloned(),
+            pr: session
+                .report
+                .as_ref()
+                .and_then(|r| r.pr.clone())
+                .or_else(|| prs.get(&session.session_id).cloned()),
+            last_message: session.last_message.clone(),
+            updated_at: session.updated_at,
+            children,
+        }
+    }
+
+    children_of
+        .get(&None)
+        .map(|roots| {
+            roots
+                .iter()
+                .map(|root| build(root, &children_of, branches, prs))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The nearest ancestor of `session` present in `ids`.
+fn parent_of<'a>(session: &'a Session, ids: &HashSet<&str>) -> Option<&'a str> {
+    session
+        .ancestor_session_ids
+        .iter()
+        .rev()
+        .map(String::as_str)
+        .find(|id| ids.contains(id) && *id != session.session_id)
+}
+
+fn find_subtree(nodes: Vec<TreeNode>, session_id: &str) -> Option<TreeNode> {
+    for node in nodes {
+        if node.session_id == session_id {
+            return Some(node);
+        }
+        if let Some(found) = find_subtree(node.children, session_id) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+/// Second line of a node: `status · branch · PR`, skipping missing parts.
+fn node_details(node: &TreeNode) -> String {
+    let mut parts = vec![node.status.display_name().to_string()];
+    parts.extend(node.branch.clone());
+    parts.extend(node.pr.clone());
+    parts.join(" · ")
+}
+
+fn render_text(roots: &[TreeNode]) -> String {
+    fn walk(node: &TreeNode, prefix: &str, connector: &str, child_prefix: &str, out: &mut String) {
+        let _ = writeln!(
+            out,
+            "{prefix}{connector}{} {}  {}",
+            node.status.display_symbol(),
+            node.title(),
+            node_details(node)
+        );
+        let last = node.children.len().saturating_sub(1);
+        for (i, child) in node.children.iter().enumerate() {
+            let (conn, next) = if i == last {
+                ("└─ ", "   ")
+            } else {
+                ("├─ ", "│  ")
+            };
+            walk(child, &format!("{prefix}{child_prefix}"), conn, next, out);
+        }
+    }
+
+    let mut out = String::new();
+    for root in roots {
+        walk(root, "", "", "", &mut out);
+    }
+    out
+}
+
+/// The node's PR as a link target: only http(s) URLs, serialized by the URL
+/// parser so quotes and spaces come out percent-encoded. Anything else
+/// (e.g. a `javascript:` value in a hand-edited session file) gets no link.
+fn pr_link(node: &TreeNode) -> Option<String> {
+    let url = reqwest::Url::parse(node.pr.as_deref()?).ok()?;
+    matches!(url.scheme(), "http" | "https").then(|| url.as_str().to_string())
+}
+
+/// Escapes a value for a double-quoted DOT / Mermaid string.
+fn escape_quoted(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn render_dot(roots: &[TreeNode]) -> String {
+    fn walk(node: &TreeNode, out: &mut String) {
+        let id = escape_quoted(&node.session_id);
+        let label = format!(
+            "{}\\n{}",
+            escape_quoted(&node.title()),
+            escape_quoted(&node_details(node))
+        );
+        let url = pr_link(node)
+            .map(|pr| format!(", URL=\"{}\"", escape_quoted(&pr)))
+            .unwrap_or_default();
+        let _ = writeln!(out, "  \"{id}\" [label=\"{label}\"{url}];");
+        for child in &node.children {
+            let _ = writeln!(
+                out,
+                "  \"{id}\" -> \"{}\";",
+                escape_quoted(&child.session_id)
+            );
+            walk(child, out);
+        }
+    }
+
+    let mut out = String::from("digraph sessions {\n  rankdir=LR;\n  node [shape=box];\n");
+    for root in roots {
+        walk(root, &mut out);
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Mermaid node IDs must be plain identifiers, so nodes are numbered in
+/// render order instead of using session IDs directly.
+fn render_mermaid(roots: &[TreeNode]) -> String {
+    fn walk(node: &TreeNode, next_id: &mut usize, out: &mut String) -> usize {
+        let id = *next_id;
+        *next_id += 1;
+        // Mermaid has no escape for `"` inside a quoted label; `#quot;` is
+        // its entity syntax.
+        let label = format!("{}<br/>{}", node.title(), node_details(node)).replace('"', "#quot;");
+        let _ = writeln!(out, "  n{id}[\"{label}\"]");
+        if let Some(pr) = pr_link(node) {
+            let _ = writeln!(out, "  click n{id} \"{}\"", pr.replace('"', "%22"));
+        }
+        for child in &node.children {
+            let child_id = walk(child, next_id, out);
+            let _ = writeln!(out, "  n{id} --> n{child_id}");
+        }
+        id
+    }
+
+    let mut out = String::from("graph TD\n");
+    let mut next_id = 0;
+    for root in roots {
+        walk(root, &mut next_id, &mut out);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use indoc::indoc;
+
+    fn session(id: &str, ancestors: &[&str], status: SessionStatus) -> Session {
+        Session {
+            session_id: id.to_string(),
+            cwd: PathBuf::from(format!("/repo/.worktrees/{id}")),
+            transcript_path: None,
+            tty: None,
+            tmux_info: None,
+            status,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_message: None,
+            current_tool: None,
+            label: None,
+            ancestor_session_ids: ancestors.iter().map(|a| (*a).to_string()).collect(),
+            pending_bg_task_ids: Default::default(),
+            pending_agent_task_ids: Default::default(),
+            pending_permission_agent_ids: Default::default(),
+            read_at: None,
+            sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
+        }
+    }
+
+    /// root ─┬─ a ── a1
+    ///       └─ b
+    /// plus `orphan`, whose only ancestor is no longer on disk.
+    fn forest() -> Vec<TreeNode> {
+        let sessions = vec![
+            session("root", &[], SessionStatus::Running),
+            session("a", &["root"], SessionStatus::Stopped),
+            session("a1", &["root", "a"], SessionStatus::Running),
+            session("b", &["root"], SessionStatus::Ended),
+            session("orphan", &["gone"], SessionStatus::Paused),
+        ];
+        let branches = HashMap::from([("a".to_string(), "feature-a".to_string())]);
+        let prs = HashMap::from([("a".to_string(), "https://github.com/o/r/pull/1".to_string())]);
+        build_forest(&sessions, &branches, &prs)
+    }
+
+    #[test]
+    fn build_forest_attaches_to_nearest_living_ancestor() {
+        let sessions = vec![
+            session("root", &[], SessionStatus::Running),
+            // "mid" was garbage-collected; "leaf" must hang off "root".
+            session("leaf", &["root", "mid"], SessionStatus::Running),
+        ];
+
+        let roots = build_forest(&sessions, &HashMap::new(), &HashMap::new());
+
+        assert_eq!(
+            roots
+                .iter()
+                .map(|r| (
+                    r.session_id.as_str(),
+                    r.children
+                        .iter()
+                        .map(|c| c.session_id.as_str())
+                        .collect::<Vec<_>>()
+                ))
+                .collect::<Vec<_>>(),
+            vec![("root", vec!["leaf"])]
+        );
+    }
+
+    #[test]
+    fn find_subtree_returns_nested_node() {
+        let subtree = find_subtree(forest(), "a").expect("subtree exists");
+
+        assert_eq!(
+            (subtree.session_id.as_str(), subtree.children.len()),
+            ("a", 1)
+        );
+    }
+
+    #[test]
+    fn render_text_draws_connectors() {
+        assert_eq!(
+            render_text(&forest()),
+            indoc! {"
+                ● root  running
+                ├─ ○ a  stopped · feature-a · https://github.com/o/r/pull/1
+                │  └─ ● a1  running
+                └─ ○ b  ended
+                ⏸ orphan  paused
+            "}
+        );
+    }
+
+    #[test]
+    fn render_dot_emits_nodes_edges_and_urls() {
+        let roots = find_subtree(forest(), "a").into_iter().collect::<Vec<_>>();
+
+        assert_eq!(
+            render_dot(&roots),
+            indoc! {r#"
+                digraph sessions {
+                  rankdir=LR;
+                  node [shape=box];
+                  "a" [label="a\nstopped · feature-a · https://github.com/o/r/pull/1", URL="https://github.com/o/r/pull/1"];
+                  "a" -> "a1";
+                  "a1" [label="a1\nrunning"];
+                }
+            "#}
+        );
+    }
+
+    #[test]
+    fn render_mermaid_numbers_nodes_and_adds_click_links() {
+        let roots = find_subtree(forest(), "a").into_iter().collect::<Vec<_>>();
+
+        assert_eq!(
+            render_mermaid(&roots),
+            indoc! {r#"
+                graph TD
+                  n0["a<br/>stopped · feature-a · https://github.com/o/r/pull/1"]
+                  click n0 "https://github.com/o/r/pull/1"
+                  n1["a1<br/>running"]
+                  n0 --> n1
+            "#}
+        );
+    }
+
+    #[test]
+    fn exports_link_only_http_pr_urls() {
+        let mut roots = find_subtree(forest(), "a").into_iter().collect::<Vec<_>>();
+        roots[0].pr = Some("javascript:alert(\"x\")".to_string());
+        roots[0].children[0].pr = Some("https://example.com/a b\"".to_string());
+
+        let (dot, mermaid) = (render_dot(&roots), render_mermaid(&roots));
+
+        assert_eq!(
+            (
+                dot.contains("javascript:") && !dot.contains("URL=\"javascript"),
+                dot.contains(r#"URL="https://example.com/a%20b%22""#),
+                mermaid.contains("click n0"),
+                mermaid.contains(r#"click n1 "https://example.com/a%20b%22""#),
+            ),
+            (true, true, false, true)
+        );
+    }
+}
@@ -0,0 +1,230 @@
+//! Editing of a session's user metadata besides its title: tags in place
+//! via the `t` key (`AppMode::EditTags`), and the note in `$EDITOR` via the
+//! `n` key (carried out by the run loop, which owns the terminal).
+
+use std::collections::BTreeSet;
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+use crate::commands::cc::annotate::parse_tags;
+use crate::commands::cc::store;
+
+use super::app::{App, AppMode};
+use super::event::KeyEvent;
+
+impl App {
+    /// Enters tags-edit mode for the currently selected session. No-op
+    /// without a selection. Seeds the buffer with the session's tags,
+    /// space-separated, so existing tags can be edited rather than retyped.
+    pub fn enter_edit_tags(&mut self) {
+        let Some(session) = self.selected_session() else {
+            return;
+        };
+        let session_id = session.session_id.clone();
+        self.edit_tags_query = session
+            .tags
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ");
+        self.mode = AppMode::EditTags { session_id };
+    }
+
+    /// Replaces the tags buffer wholesale, mirroring `update_edit_title_query`.
+    pub fn update_edit_tags_query(&mut self, query: String) {
+        self.edit_tags_query = query;
+    }
+
+    /// Leaves tags-edit mode without persisting the buffer.
+    pub fn cancel_edit_tags(&mut self) {
+        self.mode = AppMode::Normal;
+    }
+
+    /// Confirms the tags edit: the buffer (space- or comma-separated)
+    /// replaces the session's tags, an empty buffer clearing them. Only the
+    /// store write's failure is surfaced to the caller.
+    pub fn confirm_edit_tags(&mut self) -> Result<()> {
+        let session_id = match &self.mode {
+            AppMode::EditTags { session_id } => session_id.clone(),
+            _ => return Ok(()),
+        };
+
+        let tags = parse_tags(&self.edit_tags_query);
+        let new_tags = tags.clone();
+        store::update_session_tags(&session_id, |current| *current = new_tags)?;
+
+        self.set_session_tags(&session_id, tags);
+        self.mode = AppMode::Normal;
+
+        Ok(())
+    }
+
+    /// Reflects a tags change in the in-memory session list ahead of the
+    /// watcher-driven reload.
+    pub(crate) fn set_session_tags(&mut self, session_id: &str, tags: BTreeSet<String>) {
+        if let Some(session) = self
+            .sessions
+            .iter_mut()
+            .find(|s| s.session_id == session_id)
+        {
+            session.tags = tags;
+        }
+    }
+
+    /// Reflects a note change in the in-memory session list ahead of the
+    /// watcher-driven reload.
+    pub(crate) fn set_session_note(&mut self, session_id: &str, note: Option<String>) {
+        if let Some(session) = self
+            .sessions
+            .iter_mut()
+            .find(|s| s.session_id == session_id)
+        {
+            session.note = note;
+        }
+    }
+}
+
+/// Handles key events in `AppMode::EditTags`, with the same text
+/// vocabulary as the title editor.
+pub(super) fn handle_key_event(app: &mut App, key: KeyEvent) -> super::KeyEffects {
+    match (key.code, key.modifiers) {
+        (KeyCode::Esc, _) => {
+            app.cancel_edit_tags();
+        }
+        (KeyCode::Enter, _) => {
+            if let Err(e) = app.confirm_edit_tags() {
+                app.set_error(format!("Failed to update tags: {e}"));
+            }
+        }
+        (KeyCode::Backspace, _) => {
+            let mut query = app.edit_tags_query.clone();
+            query.pop();
+            app.update_edit_tags_query(query);
+        }
+        (KeyCode::Char('u'), KeyModifiers::CONTROL) => {
+            app.update_edit_tags_query(String::new());
+        }
+        (KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+            let mut query = app.edit_tags_query.clone();
+            query.push(c);
+            app.update_edit_tags_query(query);
+        }
+        _ => {}
+    }
+    super::KeyEffects::default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::commands::cc::types::{Session, SessionStatus};
+
+    fn tagged_session(id: &str) -> Session {
+        Session {
+            session_id: id.to_string(),
+            cwd: PathBuf::from("/tmp/test"),
+            transcript_path: None,
+            tty: None,
+            tmux_info: None,
+            status: SessionStatus::Running,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            last_message: None,
+            current_tool: None,
+            label: None,
+            ancestor_session_ids: Vec::new(),
+            pending_bg_task_ids: BTreeSet::new(),
+            pending_agent_task_ids: BTreeSet::new(),
+            pending_permission_agent_ids: BTreeSet::new(),
+            read_at: None,
+            sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: BTreeSet::from(["needs-review".to_string(), "wip".to_string()]),
+            note: None,
+        }
+    }
+
+    #[test]
+    fn enter_seeds_buffer_with_current_tags() {
+        let mut app = App::with_sessions(vec![tagged_session("s1")]);
+
+        app.enter_edit_tags();
+
+        assert_eq!(
+            (app.mode.clone(), app.edit_tags_query.as_str()),
+            (
+                AppMode::EditTags {
+                    session_id: "s1".to_string()
+                },
+                "needs-review wip"
+            )
+        );
+    }
+
+    #[test]
+    fn esc_cancels_without_persisting() {
+        let mut app = App::with_sessions(vec![tagged_session("s1")]);
+        app.enter_edit_tags();
+        app.update_edit_tags_query("other".to_string());
+
+        handle_key_event(
+            &mut app,
+            KeyEvent {
+                code: KeyCode::Esc,
+                modifiers: KeyModifiers::NONE,
+            },
+        );
+
+        assert_eq!(
+            (app.mode.clone(), app.sessions[0].tags.clone()),
+            (
+                AppMode::Normal,
+                BTreeSet::from(["needs-review".to_string(), "wip".to_string()])
+            )
+        );
+    }
+
+    #[test]
+    fn confirm_replaces_tags_on_disk_and_in_memory() {
+        // Point `store::sessions_dir()` at a disposable cache root.
+        let temp_dir = TempDir::new().expect("temp dir");
+        let sessions_dir = temp_dir
+            .path()
+            .join("armyknife")
+            .join("cc")
+            .join("sessions");
+        let session = tagged_session("s1");
+        store::save_session_to(&sessions_dir, &session).expect("save should succeed");
+
+        let mut app = App::with_sessions(vec![session]);
+        app.enter_edit_tags();
+        app.update_edit_tags_query("#waiting-on-ci, wip".to_string());
+
+        let cache_home = temp_dir.path().to_str().expect("utf8 path");
+        temp_env::with_vars([("XDG_CACHE_HOME", Some(cache_home))], || {
+            app.confirm_edit_tags().expect("confirm should succeed");
+        });
+
+        let expected = BTreeSet::from(["waiting-on-ci".to_string(), "wip".to_string()]);
+        let reloaded = store::load_session_from(&sessions_dir, "s1")
+            .expect("load should succeed")
+            .expect("session exists");
+        assert_eq!(
+            (
+                app.mode.clone(),
+                app.sessions[0].tags.clone(),
+                reloaded.tags
+            ),
+            (AppMode::Normal, expected.clone(), expected)
+        );
+    }
+}
@@ -1,5 +1,7 @@
+use crate::commands::cc::auto_pause::parse_duration;
 use crate::commands::cc::store;
 use crate::commands::cc::types::{Session, SessionStatus};
+use crate::shared::config::{WmConfig, load_config};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use ratatui::widgets::ListState;
@@ -14,9 +16,11 @@ mod clean;
 mod delete;
 mod filter;
 mod navigation;
+mod pr;
 mod reload;
 mod worktree;
 
+pub use pr::SessionPrState;
 use reload::{build_title_cache, get_title_display_name, load_sessions};
 pub(super) use worktree::resolve_labels_for_cwds;
 
@@ -60,6 +64,10 @@ pub enum AppMode {
     Edit {
         session_id: String,
     },
+    /// Editing the tags of the session with this ID. See `annotation_edit`.
+    EditTags {
+        session_id: String,
+    },
 }
 
 /// Application state for the TUI.
@@ -81,12 +89,18 @@ pub struct App {
     /// Edit buffer for `AppMode::Edit`, seeded from the session's currently
     /// displayed title when entering edit mode.
     pub edit_title_query: String,
+    /// Edit buffer for `AppMode::EditTags`: the session's tags as
+    /// space-separated text.
+    pub edit_tags_query: String,
     /// Indices of sessions that match the current filter.
     pub filtered_indices: Vec<usize>,
     /// Selection index before entering search mode (for restoration on cancel).
     pub pre_search_selection: Option<usize>,
     /// Status filter: when set, only sessions with this status are shown.
     pub status_filter: Option<SessionStatus>,
+    /// Unread filter: when set, only sessions waiting on the user (unread
+    /// `Stopped` or `WaitingInput`) are shown. AND'd with `status_filter`.
+    pub unread_filter: bool,
     /// Drill-down scope: when set, the session list shows only this session
     /// (by id) and its descendants (any depth -- same rule as the `▸{n}`
     /// badge). Entered via `App::enter_drilldown`, exited via
@@ -127,6 +141,11 @@ pub struct App {
     /// user confirms `y` in the clean view; cleared once the bottom-bar
     /// summary has been on screen long enough for the user to read it.
     pub clean_progress: Option<CleanProgress>,
+    /// `wm.worktrees_dir`, used to match the same repo file edited from
+    /// different worktrees when flagging overlapping changes.
+    pub worktrees_dir: String,
+    /// PRs of the sessions' worktree branches (see `app::pr`).
+    pub session_prs: SessionPrState,
 }
 
 impl App {
@@ -139,6 +158,11 @@ impl App {
     pub fn new() -> Result<Self> {
         let sessions = load_sessions()?;
         let mut app = Self::with_sessions(sessions);
+        if let Ok(config) = load_config() {
+            app.worktrees_dir = config.wm.worktrees_dir;
+            app.session_prs.ttl =
+                parse_duration(&config.cc.watch.pr_ttl).unwrap_or(pr::DEFAULT_PR_TTL);
+        }
 
         // Prefer ARMYKNIFE_FOCUS_SESSION over persisted selection
         let initial_session_id = std::env::var("ARMYKNIFE_FOCUS_SESSION")
@@ -174,9 +198,11 @@ impl App {
             search_query: String::new(),
             confirmed_query: String::new(),
             edit_title_query: String::new(),
+            edit_tags_query: String::new(),
             filtered_indices,
             pre_search_selection: None,
             status_filter: None,
+            unread_filter: false,
             drilldown_scope: None,
             // Searchable text cache is lazily built on first search
             searchable_text_cache: None,
@@ -190,6 +216,8 @@ impl App {
             worktree_view: WorktreeView::new(),
             clean_view: CleanView::new(),
             clean_progress: None,
+            worktrees_dir: WmConfig::default().worktrees_dir,
+            session_prs: SessionPrState::default(),
         };
         app.rebuild_row_order();
         app.list_state
@@ -300,6 +328,13 @@ mod tests {
             pending_permission_agent_ids: std::collections::BTreeSet::new(),
             read_at: None,
             sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
         }
     }
 
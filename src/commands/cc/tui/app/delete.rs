@@ -1,6 +1,7 @@
 use std::path::Path;
 
 use crate::commands::cc::store;
+use crate::infra::multiplexer;
 use crate::infra::tmux;
 
 use super::worktree::resolve_worktree_root;
@@ -16,10 +17,9 @@ impl App {
         if let Some(session) = self.selected_session() {
             let session_id = session.session_id.clone();
             let cwd = session.cwd.clone();
-            let is_alive = session
-                .tmux_info
-                .as_ref()
-                .is_some_and(|info| tmux::is_pane_alive(&info.pane_id));
+            let is_alive = session.tmux_info.as_ref().is_some_and(|info| {
+                multiplexer::current().is_pane_alive(&info.pane_id) == Some(true)
+            });
 
             let worktree_cleanup = resolve_worktree_root(&cwd).filter(|wt_root| {
                 !self
@@ -11,6 +11,7 @@ impl App {
     pub fn has_filter(&self) -> bool {
         !self.confirmed_query.is_empty()
             || self.status_filter.is_some()
+            || self.unread_filter
             || self.drilldown_scope.is_some()
     }
 
@@ -55,6 +56,7 @@ impl App {
         self.search_query.clear();
         self.confirmed_query.clear();
         self.status_filter = None;
+        self.unread_filter = false;
         self.drilldown_scope = None;
         self.filtered_indices = (0..self.sessions.len()).collect();
         self.rebuild_row_order();
@@ -72,14 +74,20 @@ impl App {
         self.apply_filter();
     }
 
+    /// Toggles the unread filter.
+    pub fn toggle_unread_filter(&mut self) {
+        self.unread_filter = !self.unread_filter;
+        self.apply_filter();
+    }
+
     /// Updates the search query and re-applies the filter.
     pub fn update_search_query(&mut self, query: String) {
         self.search_query = query;
         self.apply_filter();
     }
 
-    /// Applies the current search query, status filter, and drill-down scope
-    /// (all AND'd together) to filter sessions.
+    /// Applies the current search query, status and unread filters, and
+    /// drill-down scope (all AND'd together) to filter sessions.
     pub(super) fn apply_filter(&mut self) {
         // A scoped-out root (deleted, or dropped by a reload) leaves the
         // scope with nothing to anchor on -- clear it rather than filtering
@@ -98,6 +106,7 @@ impl App {
         };
 
         let status_filter = self.status_filter;
+        let unread_filter = self.unread_filter;
         let scope_root = self.drilldown_scope.clone();
 
         self.filtered_indices = self
@@ -120,6 +129,13 @@ impl App {
                     return false;
                 }
 
+                if unread_filter
+                    && !(session.is_unread_stopped()
+                        || session.status == SessionStatus::WaitingInput)
+                {
+                    return false;
+                }
+
                 // Text search filter
                 if !query.is_empty()
                     && let Some(ref cache) = self.searchable_text_cache
@@ -199,7 +215,7 @@ fn session_matches_cached(
         .get(&session.session_id)
         .map(|(text, _)| text.as_str())
         .unwrap_or("");
-    let searchable_lower = searchable.to_lowercase();
+    let searchable_lower = format!("{searchable} {}", annotation_text(session)).to_lowercase();
 
     // All words must match (AND logic)
     words
@@ -217,13 +233,22 @@ fn session_matches(session: &Session, query: &str) -> bool {
     }
 
     let searchable = build_searchable_text(session);
-    let searchable_lower = searchable.to_lowercase();
+    let searchable_lower = format!("{searchable} {}", annotation_text(session)).to_lowercase();
 
     words
         .iter()
         .all(|word| searchable_lower.contains(&word.to_lowercase()))
 }
 
+/// Tags and note, matched alongside the cached searchable text. Kept out
+/// of the cache: they are cheap to read, and editing them (`a cc tag`,
+/// `a cc note`) does not bump `updated_at`, which the cache keys on.
+fn annotation_text(session: &Session) -> String {
+    let mut parts: Vec<&str> = session.tags.iter().map(String::as_str).collect();
+    parts.extend(session.note.as_deref());
+    parts.join(" ")
+}
+
 /// Builds a searchable text string from session fields.
 fn build_searchable_text(session: &Session) -> String {
     let mut parts = Vec::new();
@@ -290,6 +315,13 @@ mod tests {
             pending_permission_agent_ids: std::collections::BTreeSet::new(),
             read_at: None,
             sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
         }
     }
 
@@ -346,6 +378,18 @@ mod tests {
         assert_eq!(session_matches(&session, query), expected);
     }
 
+    #[rstest]
+    #[case::tag("waiting-on-ci", true)]
+    #[case::tag_prefix("waiting", true)]
+    #[case::note_word("REBASE", true)]
+    #[case::nonexistent("needs-review", false)]
+    fn test_session_matches_tags_and_note(#[case] query: &str, #[case] expected: bool) {
+        let mut session = create_test_session("test");
+        session.tags = ["waiting-on-ci".to_string()].into();
+        session.note = Some("rebase after\nthe fix lands".to_string());
+        assert_eq!(session_matches(&session, query), expected);
+    }
+
     #[rstest]
     #[case::both_match("webapp feature", true)]
     #[case::across_fields("user working", true)]
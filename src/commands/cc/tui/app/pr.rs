@@ -0,0 +1,183 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use super::super::pr_fetch::SessionPr;
+use super::App;
+
+/// Fallback for `cc.watch.pr_ttl` when it is missing or unparseable.
+pub(super) const DEFAULT_PR_TTL: Duration = Duration::from_secs(120);
+
+/// PRs of the sessions' worktree branches, refreshed in the background.
+#[derive(Debug)]
+pub struct SessionPrState {
+    /// Keyed by session cwd; sessions sharing a worktree share the entry.
+    prs: HashMap<PathBuf, SessionPr>,
+    /// Cwds covered by the last dispatched lookup, so a session showing up
+    /// in a new worktree is looked up without waiting out `ttl`.
+    queried: HashSet<PathBuf>,
+    fetched_at: Option<Instant>,
+    in_flight: bool,
+    /// `cc.watch.pr_ttl`: how often every cwd is looked up again.
+    pub ttl: Duration,
+}
+
+impl Default for SessionPrState {
+    fn default() -> Self {
+        Self {
+            prs: HashMap::new(),
+            queried: HashSet::new(),
+            fetched_at: None,
+            in_flight: false,
+            ttl: DEFAULT_PR_TTL,
+        }
+    }
+}
+
+impl App {
+    /// The PR of the branch checked out at `cwd`, once looked up.
+    pub fn session_pr(&self, cwd: &Path) -> Option<&SessionPr> {
+        self.session_prs.prs.get(cwd)
+    }
+
+    /// The PR of the selected session's branch, once looked up.
+    pub fn selected_session_pr(&self) -> Option<&SessionPr> {
+        self.selected_session()
+            .and_then(|session| self.session_pr(&session.cwd))
+    }
+
+    /// Returns every distinct session cwd when a PR lookup is due (never
+    /// run, `ttl` elapsed, or a cwd not covered by the last one) and none
+    /// is in flight, marking one in flight. Callers dispatch the list to
+    /// `EventHandler::start_session_pr_fetch`.
+    pub fn claim_session_pr_fetch(&mut self, now: Instant) -> Option<Vec<PathBuf>> {
+        let state = &self.session_prs;
+        if state.in_flight {
+            return None;
+        }
+        let mut seen: HashSet<&Path> = HashSet::new();
+        let cwds: Vec<PathBuf> = self
+            .sessions
+            .iter()
+            .map(|s| s.cwd.as_path())
+            .filter(|cwd| seen.insert(cwd))
+            .map(Path::to_path_buf)
+            .collect();
+        if cwds.is_empty() {
+            return None;
+        }
+        let expired = state
+            .fetched_at
+            .is_none_or(|at| now.duration_since(at) >= state.ttl);
+        if !expired && cwds.iter().all(|cwd| state.queried.contains(cwd)) {
+            return None;
+        }
+
+        let state = &mut self.session_prs;
+        state.in_flight = true;
+        state.fetched_at = Some(now);
+        state.queried = cwds.iter().cloned().collect();
+        Some(cwds)
+    }
+
+    /// Replaces the looked-up PRs with the result of the in-flight lookup.
+    pub fn apply_session_prs(&mut self, prs: HashMap<PathBuf, SessionPr>) {
+        self.session_prs.in_flight = false;
+        self.session_prs.prs = prs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::cc::types::{Session, SessionStatus};
+    use crate::infra::github::{PrInfo, PrState};
+    use chrono::Utc;
+
+    fn session_in(id: &str, cwd: &str) -> Session {
+        Session {
+            session_id: id.to_string(),
+            cwd: PathBuf::from(cwd),
+            transcript_path: None,
+            tty: None,
+            tmux_info: None,
+            status: SessionStatus::Running,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_message: None,
+            current_tool: None,
+            label: None,
+            ancestor_session_ids: Vec::new(),
+            pending_bg_task_ids: Default::default(),
+            pending_agent_task_ids: Default::default(),
+            pending_permission_agent_ids: Default::default(),
+            read_at: None,
+            sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
+        }
+    }
+
+    fn session_pr(number: u64) -> SessionPr {
+        SessionPr {
+            repo: "o/r".to_string(),
+            pr: PrInfo {
+                number,
+                state: PrState::Open,
+                url: format!("https://github.com/o/r/pull/{number}"),
+                title: String::new(),
+                draft: false,
+                review: None,
+                checks: None,
+                unresolved_threads: None,
+            },
+        }
+    }
+
+    #[test]
+    fn claims_each_cwd_once_and_waits_for_the_result() {
+        let mut app = App::with_sessions(vec![
+            session_in("a", "/w/a"),
+            session_in("b", "/w/a"),
+            session_in("c", "/w/c"),
+        ]);
+        let now = Instant::now();
+
+        let cwds = app
+            .claim_session_pr_fetch(now)
+            .expect("first lookup is due");
+        assert_eq!(cwds, vec![PathBuf::from("/w/a"), PathBuf::from("/w/c")]);
+        assert!(app.claim_session_pr_fetch(now).is_none());
+
+        app.apply_session_prs(HashMap::from([(PathBuf::from("/w/a"), session_pr(7))]));
+        assert!(app.claim_session_pr_fetch(now).is_none());
+        assert_eq!(
+            app.session_pr(Path::new("/w/a")).map(|p| p.pr.number),
+            Some(7)
+        );
+        assert!(app.session_pr(Path::new("/w/c")).is_none());
+    }
+
+    #[test]
+    fn refetches_after_ttl_or_for_a_new_cwd() {
+        let mut app = App::with_sessions(vec![session_in("a", "/w/a")]);
+        let now = Instant::now();
+        app.claim_session_pr_fetch(now).unwrap();
+        app.apply_session_prs(HashMap::new());
+
+        app.sessions.push(session_in("b", "/w/b"));
+        assert_eq!(app.claim_session_pr_fetch(now).map(|c| c.len()), Some(2));
+        app.apply_session_prs(HashMap::new());
+
+        assert!(app.claim_session_pr_fetch(now).is_none());
+        assert!(
+            app.claim_session_pr_fetch(now + app.session_prs.ttl)
+                .is_some()
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use crate::commands::cc::claude_sessions;
 use crate::commands::cc::store;
 use crate::commands::cc::types::{Session, SessionStatus};
-use crate::infra::tmux;
+use crate::infra::multiplexer;
 use anyhow::Result;
 use std::collections::HashMap;
 
@@ -243,7 +243,7 @@ impl App {
 /// Ended and Paused sessions are never considered stale -- they are retained
 /// for `claude -c` resume even after their pane dies.
 fn is_session_stale(session: &Session) -> bool {
-    if !tmux::is_server_available() {
+    if !multiplexer::current().is_available() {
         return false;
     }
     if matches!(session.status, SessionStatus::Ended | SessionStatus::Paused) {
@@ -252,7 +252,7 @@ fn is_session_stale(session: &Session) -> bool {
     session
         .tmux_info
         .as_ref()
-        .is_some_and(|info| !tmux::is_pane_alive(&info.pane_id))
+        .is_some_and(|info| multiplexer::current().is_pane_alive(&info.pane_id) == Some(false))
 }
 
 /// Builds the title cache for all sessions.
@@ -328,6 +328,13 @@ mod tests {
             pending_permission_agent_ids: std::collections::BTreeSet::new(),
             read_at: None,
             sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
         }
     }
 
@@ -1,10 +1,11 @@
-use crate::commands::cc::store;
+use crate::commands::cc::{auto_label, store};
 use anyhow::Result;
 use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
 use notify::{
     EventKind, RecommendedWatcher, RecursiveMode, Watcher,
     event::{CreateKind, ModifyKind, RemoveKind},
 };
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::mpsc::{self, Receiver, Sender};
 use std::thread;
@@ -12,6 +13,7 @@ use std::time::Duration;
 
 use super::clean_progress::{self, CleanLogEvent, TAIL_INTERVAL};
 use super::clean_view::CleanRow;
+use super::pr_fetch::SessionPr;
 use super::worktree_view::WorktreeRow;
 use crate::commands::cc::types::Session;
 
@@ -51,10 +53,14 @@ pub enum AppEvent {
     /// Background session-label (repo name + worktree name) resolution
     /// finished for one batch of cwds.
     SessionLabelsResolved(Vec<(PathBuf, String, String)>),
+    /// PR lookup for the session view's rows completed, keyed by cwd.
+    SessionPrsFetched(HashMap<PathBuf, SessionPr>),
     /// PR status fetch for the clean view completed.
     CleanPrFetched(std::result::Result<Vec<CleanRow>, String>),
     /// One or more JSONL events from the detached clean child.
     CleanLogEvents(Vec<CleanLogEvent>),
+    /// Opening a PR in the browser failed, with the reason.
+    PrOpenFailed(String),
 }
 
 /// Event handler that combines keyboard input and file system events.
@@ -156,6 +162,49 @@ impl EventHandler {
         });
     }
 
+    /// Look up the PRs of the branches checked out at `cwds` in the
+    /// background. The result arrives as [`AppEvent::SessionPrsFetched`];
+    /// without a runtime it arrives empty, so the caller's in-flight guard
+    /// is still released.
+    pub fn start_session_pr_fetch(&self, cwds: Vec<PathBuf>, ttl: Duration) {
+        let tx = self.sender.clone();
+        let Some(rt) = self.rt_handle.as_ref().cloned() else {
+            let _ = tx.send(AppEvent::SessionPrsFetched(HashMap::new()));
+            return;
+        };
+        rt.spawn(async move {
+            let prs = super::pr_fetch::fetch_session_prs(cwds, ttl).await;
+            // Relabel sessions whose PR showed up (`cc.auto_label`); the
+            // session watcher brings the new labels into the list.
+            let titles: HashMap<PathBuf, (String, String)> = prs
+                .iter()
+                .map(|(cwd, pr)| (cwd.clone(), (pr.repo.clone(), pr.pr.title.clone())))
+                .collect();
+            let _ = tokio::task::spawn_blocking(move || {
+                auto_label::relabel_sessions_for_prs(&titles);
+            })
+            .await;
+            let _ = tx.send(AppEvent::SessionPrsFetched(prs));
+        });
+    }
+
+    /// Open `pr` in the browser via `gh` in the background. Only a failure
+    /// is reported, as [`AppEvent::PrOpenFailed`].
+    pub fn start_pr_open(&self, pr: SessionPr) {
+        let tx = self.sender.clone();
+        let Some(rt) = self.rt_handle.as_ref().cloned() else {
+            let _ = tx.send(AppEvent::PrOpenFailed(
+                "tokio runtime is not available".to_string(),
+            ));
+            return;
+        };
+        rt.spawn_blocking(move || {
+            if let Err(message) = super::open_pr_in_browser(&pr) {
+                let _ = tx.send(AppEvent::PrOpenFailed(message));
+            }
+        });
+    }
+
     /// Begin tailing `log_path` for JSONL events from the detached
     /// clean child. Stops on the first `Done` event or when the
     /// receiver is dropped. Polling cadence matches
@@ -1,3 +1,4 @@
+mod annotation_edit;
 mod app;
 mod clean_progress;
 mod clean_view;
@@ -11,8 +12,10 @@ mod worktree_session_children;
 mod worktree_view;
 
 use std::collections::HashMap;
-use std::io;
+use std::io::{self, Write};
 use std::path::{Path, PathBuf};
+use std::process::{ExitStatus, Stdio};
+use std::time::Instant;
 
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyModifiers};
@@ -24,16 +27,20 @@ use ratatui::DefaultTerminal;
 
 use self::app::{App, AppMode, View};
 use self::event::{AppEvent, EventHandler, KeyEvent, SessionChange, SessionChangeType};
+use self::pr_fetch::SessionPr;
 use self::worktree_view::WorktreeMode;
+use crate::commands::cc::annotate::normalize_note;
 use crate::commands::cc::resume;
+use crate::commands::cc::store;
 use crate::commands::cc::types::SessionStatus;
-use crate::infra::tmux;
+use crate::infra::external_tool::ExternalTool;
+use crate::infra::multiplexer;
 use crate::shared::command;
 
-/// Runs the TUI application.
-pub fn run() -> Result<()> {
+/// Runs the TUI application. `unread` starts it with the unread filter on.
+pub fn run(unread: bool) -> Result<()> {
     let mut terminal = ratatui::init();
-    let result = run_app(&mut terminal);
+    let result = run_app(&mut terminal, unread);
     ratatui::restore();
     result
 }
@@ -54,6 +61,14 @@ struct KeyEffects {
     /// User pressed Ctrl+g in title-edit mode: spawn the detached
     /// title-generation process for this request.
     spawn_title_generation: Option<title_generate::SpawnTitleGenerationRequest>,
+    /// User pressed `o`: open this PR in the browser in the background.
+    open_pr: Option<SessionPr>,
+    /// User pressed `v`: show `a gh pr-review check` for this PR after
+    /// suspending the TUI.
+    show_pr_review: Option<SessionPr>,
+    /// User pressed `n`: edit this session's note in `$EDITOR` after
+    /// suspending the TUI.
+    edit_note: Option<String>,
 }
 
 impl KeyEffects {
@@ -70,6 +85,15 @@ impl KeyEffects {
         if other.spawn_title_generation.is_some() {
             self.spawn_title_generation = other.spawn_title_generation;
         }
+        if other.open_pr.is_some() {
+            self.open_pr = other.open_pr;
+        }
+        if other.show_pr_review.is_some() {
+            self.show_pr_review = other.show_pr_review;
+        }
+        if other.edit_note.is_some() {
+            self.edit_note = other.edit_note;
+        }
     }
 }
 
@@ -84,8 +108,11 @@ const MAX_DRAIN_PER_ITERATION: usize = 100;
 /// 3. Key events are processed immediately during drain
 /// 4. SessionsChanged events are merged (deduplicated by session_id)
 /// 5. The merged reload + render happens once per iteration
-fn run_app(terminal: &mut DefaultTerminal) -> Result<()> {
+fn run_app(terminal: &mut DefaultTerminal, unread: bool) -> Result<()> {
     let mut app = App::new()?;
+    if unread {
+        app.toggle_unread_filter();
+    }
     let event_handler = EventHandler::new()?;
 
     loop {
@@ -93,6 +120,9 @@ fn run_app(terminal: &mut DefaultTerminal) -> Result<()> {
         if !unresolved.is_empty() {
             event_handler.start_session_labels_resolve(unresolved);
         }
+        if let Some(cwds) = app.claim_session_pr_fetch(Instant::now()) {
+            event_handler.start_session_pr_fetch(cwds, app.session_prs.ttl);
+        }
 
         terminal.draw(|frame| ui::render(frame, &mut app))?;
 
@@ -129,6 +159,9 @@ fn run_app(terminal: &mut DefaultTerminal) -> Result<()> {
                 AppEvent::SessionLabelsResolved(results) => {
                     app.apply_resolved_labels(results);
                 }
+                AppEvent::SessionPrsFetched(prs) => {
+                    app.apply_session_prs(prs);
+                }
                 AppEvent::CleanPrFetched(Ok(rows)) => {
                     app.apply_clean_pr_results(rows);
                 }
@@ -138,6 +171,9 @@ fn run_app(terminal: &mut DefaultTerminal) -> Result<()> {
                 AppEvent::CleanLogEvents(events) => {
                     app.apply_clean_log_events(&events);
                 }
+                AppEvent::PrOpenFailed(message) => {
+                    app.set_error(format!("Failed to open PR: {message}"));
+                }
             }
         }
 
@@ -158,6 +194,36 @@ fn run_app(terminal: &mut DefaultTerminal) -> Result<()> {
                 Err(PreviewError::Fatal(e)) => return Err(e),
             }
         }
+        if let Some(pr) = effects.open_pr {
+            event_handler.start_pr_open(pr);
+        }
+        if let Some(pr) = effects.show_pr_review {
+            match show_pr_review(terminal, &pr) {
+                Ok(()) => {}
+                Err(PreviewError::Viewer(e)) => {
+                    app.set_error(format!("Failed to show PR review: {e}"));
+                }
+                Err(PreviewError::Fatal(e)) => return Err(e),
+            }
+        }
+        if let Some(session_id) = effects.edit_note {
+            let current = app
+                .sessions
+                .iter()
+                .find(|s| s.session_id == session_id)
+                .and_then(|s| s.note.clone());
+            match edit_note(terminal, current.as_deref()) {
+                Ok(note) if note == current => {}
+                Ok(note) => match store::update_session_note(&session_id, note.clone()) {
+                    Ok(_) => app.set_session_note(&session_id, note),
+                    Err(e) => app.set_error(format!("Failed to update note: {e}")),
+                },
+                Err(PreviewError::Viewer(e)) => {
+                    app.set_error(format!("Failed to edit note: {e}"));
+                }
+                Err(PreviewError::Fatal(e)) => return Err(e),
+            }
+        }
         if let Some(paths) = effects.spawn_detached_clean {
             match clean_progress::spawn_detached_clean(&paths) {
                 Ok(run_id) => {
@@ -224,35 +290,133 @@ fn merge_session_changes(
     )
 }
 
-/// Focuses on the selected session's tmux pane.
+/// Focuses on the selected session's pane.
 fn focus_selected_session(app: &mut App) {
     if let Some(session) = app.selected_session()
         && let Some(ref tmux_info) = session.tmux_info
-        && let Err(e) = tmux::focus_pane(&tmux_info.pane_id)
+        && let Err(e) = multiplexer::current().focus_pane(&tmux_info.pane_id)
     {
-        app.set_error(format!("Failed to focus tmux pane: {e}"));
+        app.set_error(format!("Failed to focus pane: {e}"));
     }
 }
 
+/// Opens `pr` in the browser via `gh`, returning `gh`'s error output on
+/// failure.
+fn open_pr_in_browser(pr: &SessionPr) -> std::result::Result<(), String> {
+    let output = ExternalTool::Gh
+        .command()
+        .args([
+            "pr",
+            "view",
+            &pr.pr.number.to_string(),
+            "-R",
+            &pr.repo,
+            "--web",
+        ])
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(())
+}
+
 /// Preview a session's JSONL with `claude-history`.
 fn preview_session(
     terminal: &mut DefaultTerminal,
     jsonl_path: &Path,
+) -> std::result::Result<(), PreviewError> {
+    run_suspended(terminal, "claude-history", || {
+        command::new("claude-history").arg(jsonl_path).status()
+    })
+}
+
+/// Show `a gh pr-review check` for `pr` in a pager.
+fn show_pr_review(
+    terminal: &mut DefaultTerminal,
+    pr: &SessionPr,
+) -> std::result::Result<(), PreviewError> {
+    run_suspended(terminal, "a gh pr-review check", || {
+        let mut check = command::new(std::env::current_exe()?)
+            .args(["gh", "pr-review", "check", &pr.pr.number.to_string()])
+            .args(["-R", &pr.repo])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let mut pager = command::new("less")
+            .arg("-R")
+            .stdin(Stdio::piped())
+            .spawn()?;
+        // The pager comes up right away with a heading while the threads
+        // are fetched, and shows them as they arrive
+        let streamed = match (pager.stdin.take(), check.stdout.take()) {
+            (Some(mut stdin), Some(mut threads)) => {
+                writeln!(stdin, "Review threads of {}#{}\n", pr.repo, pr.pr.number)
+                    .and_then(|()| io::copy(&mut threads, &mut stdin))
+                    .is_ok()
+            }
+            _ => false,
+        };
+        let status = pager.wait()?;
+        if !streamed {
+            // Quitting the pager before it read everything is not an error
+            let _ = check.kill();
+        }
+        let output = check.wait_with_output()?;
+        if streamed && !output.status.success() {
+            return Err(io::Error::other(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+        Ok(status)
+    })
+}
+
+/// Edit a session's note in `$EDITOR`, returning the note as saved (a
+/// blank file clears it).
+fn edit_note(
+    terminal: &mut DefaultTerminal,
+    current: Option<&str>,
+) -> std::result::Result<Option<String>, PreviewError> {
+    let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
+    let viewer_err =
+        |e: io::Error| PreviewError::Viewer(anyhow::Error::from(e).context("note file"));
+    let mut file = tempfile::Builder::new()
+        .prefix("cc-note-")
+        .suffix(".md")
+        .tempfile()
+        .map_err(viewer_err)?;
+    file.write_all(current.unwrap_or_default().as_bytes())
+        .map_err(viewer_err)?;
+    run_suspended(terminal, &editor, || {
+        command::new(&editor).arg(file.path()).status()
+    })?;
+    let text = std::fs::read_to_string(file.path()).map_err(viewer_err)?;
+    Ok(normalize_note(&text))
+}
+
+/// Runs `run` with the TUI suspended, so the child owns the terminal.
+fn run_suspended(
+    terminal: &mut DefaultTerminal,
+    viewer: &str,
+    run: impl FnOnce() -> io::Result<ExitStatus>,
 ) -> std::result::Result<(), PreviewError> {
     let suspend_err = suspend_terminal().err();
     // Even if suspend partially failed (raw mode disabled but alt screen
     // still active), we must still try to restore — the alternative is
     // leaving the TUI in a broken state.
     let status = if suspend_err.is_none() {
-        Some(command::new("claude-history").arg(jsonl_path).status())
+        Some(run())
     } else {
         None
     };
     let restore_err = resume_terminal(terminal).err();
-    combine_preview_result(suspend_err, status, restore_err)
+    combine_preview_result(viewer, suspend_err, status, restore_err)
 }
 
-/// Categorized failure from `preview_session`. `Fatal` means the terminal
+/// Categorized failure from `run_suspended`. `Fatal` means the terminal
 /// itself is in a bad state (suspend or restore failed) — the caller must
 /// bail so `ratatui::restore()` runs. `Viewer` means only the child failed,
 /// so the TUI can keep going.
@@ -271,6 +435,7 @@ impl std::fmt::Display for PreviewError {
 }
 
 fn combine_preview_result(
+    viewer: &str,
     suspend_err: Option<io::Error>,
     status: Option<io::Result<std::process::ExitStatus>>,
     restore_err: Option<io::Error>,
@@ -278,9 +443,7 @@ fn combine_preview_result(
     // A non-zero exit is a viewer failure the user should see, not just a
     // spawn failure — collapse both into the same "child error" slot.
     let child_err = status.and_then(|r| match r {
-        Ok(st) if !st.success() => {
-            Some(io::Error::other(format!("claude-history exited with {st}")))
-        }
+        Ok(st) if !st.success() => Some(io::Error::other(format!("{viewer} exited with {st}"))),
         Ok(_) => None,
         Err(e) => Some(e),
     });
@@ -298,7 +461,7 @@ fn combine_preview_result(
             anyhow::Error::from(re).context("failed to restore terminal"),
         )),
         (None, Some(e), None) => Err(PreviewError::Viewer(
-            anyhow::Error::from(e).context("failed to run claude-history"),
+            anyhow::Error::from(e).context(format!("failed to run {viewer}")),
         )),
         // Restore failure surfaces first — a broken terminal is more urgent
         // than a missing viewer — with the child error folded in.
@@ -328,7 +491,7 @@ fn resume_selected_session(app: &mut App) {
 
     match resume::respawn_paused_session(session) {
         Ok(pane_id) => {
-            if let Err(e) = tmux::focus_pane(&pane_id) {
+            if let Err(e) = multiplexer::current().focus_pane(&pane_id) {
                 app.set_error(format!("Failed to focus pane: {e}"));
             }
         }
@@ -460,6 +623,11 @@ fn handle_normal_key_event(app: &mut App, key: KeyEvent) {
             app.enter_edit_title();
         }
 
+        // Edit the selected session's tags
+        (KeyCode::Char('t'), KeyModifiers::NONE) => {
+            app.enter_edit_tags();
+        }
+
         // Status filters (toggle). Use Ctrl-prefixed bindings so that plain
         // letters (`r`, `s`, `w`) remain available for other actions such as
         // resuming a paused session.
@@ -475,6 +643,9 @@ fn handle_normal_key_event(app: &mut App, key: KeyEvent) {
         (KeyCode::Char('p'), KeyModifiers::CONTROL) => {
             app.toggle_status_filter(SessionStatus::Paused);
         }
+        (KeyCode::Char('u'), KeyModifiers::CONTROL) => {
+            app.toggle_unread_filter();
+        }
 
         // Quick select (1-9)
         (KeyCode::Char(c), KeyModifiers::NONE) if c.is_ascii_digit() && c != '0' => {
@@ -550,6 +721,50 @@ fn handle_session_view_key_event(app: &mut App, key: KeyEvent) -> KeyEffects {
         };
     }
 
+    // `o` opens the selected session's PR in the browser. `gh` takes a
+    // moment to resolve the URL, so the run loop starts it in the background.
+    if app.mode == AppMode::Normal
+        && let (KeyCode::Char('o'), KeyModifiers::NONE) = (key.code, key.modifiers)
+    {
+        app.clear_error();
+        let pr = app.selected_session_pr().cloned();
+        if pr.is_none() {
+            app.set_error("No PR found for this session's branch".to_string());
+        }
+        return KeyEffects {
+            open_pr: pr,
+            ..Default::default()
+        };
+    }
+
+    // `v` shows the review threads of the selected session's PR. Needs the
+    // terminal, so it is carried out by the run loop.
+    if app.mode == AppMode::Normal
+        && let (KeyCode::Char('v'), KeyModifiers::NONE) = (key.code, key.modifiers)
+    {
+        app.clear_error();
+        let pr = app.selected_session_pr().cloned();
+        if pr.is_none() {
+            app.set_error("No PR found for this session's branch".to_string());
+        }
+        return KeyEffects {
+            show_pr_review: pr,
+            ..Default::default()
+        };
+    }
+
+    // `n` edits the selected session's note in `$EDITOR`, which needs the
+    // terminal, so it is carried out by the run loop.
+    if app.mode == AppMode::Normal
+        && let (KeyCode::Char('n'), KeyModifiers::NONE) = (key.code, key.modifiers)
+    {
+        app.clear_error();
+        return KeyEffects {
+            edit_note: app.selected_session().map(|s| s.session_id.clone()),
+            ..Default::default()
+        };
+    }
+
     match app.mode {
         AppMode::Normal => {
             handle_normal_key_event(app, key);
@@ -564,6 +779,7 @@ fn handle_session_view_key_event(app: &mut App, key: KeyEvent) -> KeyEffects {
             KeyEffects::default()
         }
         AppMode::Edit { .. } => title_edit::handle_key_event(app, key),
+        AppMode::EditTags { .. } => annotation_edit::handle_key_event(app, key),
     }
 }
 
@@ -681,8 +897,8 @@ fn focus_selected_worktree_session(app: &mut App) {
             return;
         }
     };
-    if let Err(e) = tmux::focus_pane(&pane_id) {
-        app.set_error(format!("Failed to focus tmux pane: {e}"));
+    if let Err(e) = multiplexer::current().focus_pane(&pane_id) {
+        app.set_error(format!("Failed to focus pane: {e}"));
     }
 }
 
@@ -691,8 +907,8 @@ fn focus_session_child(app: &mut App, child: &self::worktree_session_children::S
         app.set_error("No tmux pane for this session".to_string());
         return;
     };
-    if let Err(e) = tmux::focus_pane(pane_id) {
-        app.set_error(format!("Failed to focus tmux pane: {e}"));
+    if let Err(e) = multiplexer::current().focus_pane(pane_id) {
+        app.set_error(format!("Failed to focus pane: {e}"));
     }
 }
 
@@ -725,6 +941,13 @@ mod tests {
                 pending_permission_agent_ids: std::collections::BTreeSet::new(),
                 read_at: None,
                 sweep_signaled: false,
+                changed_files: Default::default(),
+                subagents: Default::default(),
+                compaction_count: 0,
+                last_compacted_at: None,
+                report: None,
+                tags: Default::default(),
+                note: None,
             })
             .collect();
 
@@ -1007,6 +1230,54 @@ mod tests {
         assert_eq!(handle_key_event(&mut app, key), expected);
     }
 
+    #[test]
+    fn test_open_pr_key_without_pr_reports_error() {
+        let mut app = create_test_app_with_sessions(1);
+        let effects = handle_key_event(&mut app, key(KeyCode::Char('o')));
+        assert_eq!(
+            (effects, app.error_message),
+            (
+                KeyEffects::default(),
+                Some("No PR found for this session's branch".to_string())
+            )
+        );
+    }
+
+    #[rstest]
+    #[case::with_selection(
+        1,
+        key(KeyCode::Char('n')),
+        KeyEffects {
+            edit_note: Some("session-0".to_string()),
+            ..Default::default()
+        },
+    )]
+    #[case::no_selection(0, key(KeyCode::Char('n')), KeyEffects::default())]
+    // Ctrl+n still moves the selection
+    #[case::ctrl_n_moves(1, key_ctrl('n'), KeyEffects::default())]
+    fn test_note_key_effects(
+        #[case] session_count: usize,
+        #[case] key: KeyEvent,
+        #[case] expected: KeyEffects,
+    ) {
+        let mut app = create_test_app_with_sessions(session_count);
+        assert_eq!(handle_key_event(&mut app, key), expected);
+    }
+
+    #[test]
+    fn test_t_enters_tag_edit_mode() {
+        let mut app = create_test_app_with_sessions(1);
+
+        handle_key_event(&mut app, key(KeyCode::Char('t')));
+
+        assert_eq!(
+            app.mode,
+            AppMode::EditTags {
+                session_id: "session-0".to_string()
+            }
+        );
+    }
+
     #[test]
     fn test_ctrl_p_toggles_paused_filter_even_with_p_binding() {
         // Regression guard: plain `p` intercepts before the Ctrl+p filter
@@ -1116,7 +1387,12 @@ mod tests {
         #[case] expected: ExpectedOutcome,
     ) {
         assert_eq!(
-            categorize(combine_preview_result(suspend_err, status, restore_err)),
+            categorize(combine_preview_result(
+                "claude-history",
+                suspend_err,
+                status,
+                restore_err
+            )),
             expected,
         );
     }
@@ -1146,6 +1422,13 @@ mod tests {
                 pending_permission_agent_ids: std::collections::BTreeSet::new(),
                 read_at: None,
                 sweep_signaled: false,
+                changed_files: Default::default(),
+                subagents: Default::default(),
+                compaction_count: 0,
+                last_compacted_at: None,
+                report: None,
+                tags: Default::default(),
+                note: None,
             },
             Session {
                 session_id: "session-waiting".to_string(),
@@ -1165,6 +1448,13 @@ mod tests {
                 pending_permission_agent_ids: std::collections::BTreeSet::new(),
                 read_at: None,
                 sweep_signaled: false,
+                changed_files: Default::default(),
+                subagents: Default::default(),
+                compaction_count: 0,
+                last_compacted_at: None,
+                report: None,
+                tags: Default::default(),
+                note: None,
             },
             Session {
                 session_id: "session-stopped".to_string(),
@@ -1184,6 +1474,13 @@ mod tests {
                 pending_permission_agent_ids: std::collections::BTreeSet::new(),
                 read_at: None,
                 sweep_signaled: false,
+                changed_files: Default::default(),
+                subagents: Default::default(),
+                compaction_count: 0,
+                last_compacted_at: None,
+                report: None,
+                tags: Default::default(),
+                note: None,
             },
             Session {
                 session_id: "session-paused".to_string(),
@@ -1203,6 +1500,13 @@ mod tests {
                 pending_permission_agent_ids: std::collections::BTreeSet::new(),
                 read_at: None,
                 sweep_signaled: false,
+                changed_files: Default::default(),
+                subagents: Default::default(),
+                compaction_count: 0,
+                last_compacted_at: None,
+                report: None,
+                tags: Default::default(),
+                note: None,
             },
         ];
         App::with_sessions(sessions)
@@ -1240,6 +1544,24 @@ mod tests {
         assert!(app.status_filter.is_none());
     }
 
+    #[rstest]
+    fn test_unread_filter_shows_sessions_waiting_on_the_user(app_with_statuses: App) {
+        let mut app = app_with_statuses;
+
+        handle_key_event(&mut app, key_ctrl('u'));
+        let mut ids: Vec<&str> = app
+            .filtered_sessions()
+            .iter()
+            .map(|s| s.session_id.as_str())
+            .collect();
+        ids.sort_unstable();
+        assert_eq!(ids, vec!["session-stopped", "session-waiting"]);
+
+        handle_key_event(&mut app, key_ctrl('u'));
+        assert!(!app.unread_filter);
+        assert_eq!(app.filtered_sessions().len(), 4);
+    }
+
     #[rstest]
     fn test_status_filter_toggle_off(app_with_statuses: App) {
         let mut app = app_with_statuses;
@@ -1442,6 +1764,13 @@ mod tests {
             pending_permission_agent_ids: std::collections::BTreeSet::new(),
             read_at: None,
             sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
         }
     }
 
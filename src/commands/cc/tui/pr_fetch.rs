@@ -5,17 +5,33 @@
 //! render immediately. Phase 2 ([`fetch_clean_inputs`]) opens each
 //! repository, batches a single GraphQL query for every worktree, and
 //! returns PR-enriched rows that replace the placeholders.
+//!
+//! The session view's PR badges come from [`fetch_session_prs`], which goes
+//! through `pr_cache` so periodic refreshes only hit GitHub for branches
+//! whose cached state has expired.
 
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use chrono::Utc;
 
 use super::clean_view::{CleanRow, CleanRowInput, build_clean_rows};
 use super::worktree_view::WorktreeRow;
+use crate::commands::cc::pr_cache;
 use crate::commands::cc::types::Session;
 use crate::infra::git::{GitRepo, github_owner_and_repo, merge_status_from_pr};
 use crate::infra::github::{BranchPrQuery, GitHubClient, PrInfo};
 
+/// The PR of a session's worktree branch, shown on its row in the session
+/// view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionPr {
+    /// `owner/repo`, for `gh pr view -R` and `a gh pr-review check -R`.
+    pub repo: String,
+    pub pr: PrInfo,
+}
+
 /// Timeout the clean view uses for its own active-session check. Unlike
 /// `wm clean` / `cc sweep`, which wait out `auto_pause.timeout` before
 /// treating a `Stopped` session as gone (giving the user a grace period
@@ -117,3 +133,40 @@ pub async fn fetch_clean_inputs(
         clean_view_active_timeout(),
     ))
 }
+
+/// Look up the PR of the branch checked out at each of `cwds`, reusing
+/// cached entries younger than `ttl`. Cwds outside a GitHub repository, on
+/// a detached HEAD, or whose branch has no PR are absent from the result.
+pub async fn fetch_session_prs(cwds: Vec<PathBuf>, ttl: Duration) -> HashMap<PathBuf, SessionPr> {
+    let targets: Vec<(PathBuf, BranchPrQuery)> = cwds
+        .into_iter()
+        .filter_map(|cwd| {
+            let repo = GitRepo::open_at(&cwd).ok()?;
+            let branch = repo.current_branch().ok().filter(|b| b != "HEAD")?;
+            let (owner, name) = github_owner_and_repo(&repo).ok()?;
+            Some((
+                cwd,
+                BranchPrQuery {
+                    owner,
+                    repo: name,
+                    branch,
+                },
+            ))
+        })
+        .collect();
+    if targets.is_empty() {
+        return HashMap::new();
+    }
+
+    let queries: Vec<BranchPrQuery> = targets.iter().map(|(_, q)| q.clone()).collect();
+    let mut prs = pr_cache::cached_prs(&queries, ttl).await;
+    targets
+        .into_iter()
+        .filter_map(|(cwd, q)| {
+            let repo = format!("{}/{}", q.owner, q.repo);
+            // Several sessions may share a worktree, so clone rather than take
+            let pr = prs.get_mut(&(q.owner, q.repo, q.branch))?.clone()?;
+            Some((cwd, SessionPr { repo, pr }))
+        })
+        .collect()
+}
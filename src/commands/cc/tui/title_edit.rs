@@ -149,6 +149,13 @@ mod tests {
             pending_permission_agent_ids: std::collections::BTreeSet::new(),
             read_at: None,
             sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
         }
     }
 
@@ -326,6 +333,7 @@ mod tests {
                         "Updated auth.rs"
                     ),
                     previous_label: None,
+                    fallback_title: Some("Fix the login bug".to_string()),
                 })
             )
         );
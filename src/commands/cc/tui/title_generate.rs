@@ -26,8 +26,13 @@ pub struct SpawnTitleGenerationRequest {
     /// through to the detached process so it can no-op if the label has
     /// since changed (e.g. a manual rename while generation was running).
     pub previous_label: Option<String>,
+    /// Title applied when no LLM backend answers (see `offline_title`).
+    pub fallback_title: Option<String>,
 }
 
+/// Longest title `offline_title` produces, in characters.
+const OFFLINE_TITLE_MAX_CHARS: usize = 30;
+
 /// Handles `Ctrl+g` from `AppMode::Edit`: reads the session's first user
 /// message and last assistant message from its `.jsonl`, builds the LLM
 /// prompt, and snapshots the session's current `label` (so the eventual
@@ -60,10 +65,33 @@ pub(super) fn request_generate_title(app: &mut App) -> Option<SpawnTitleGenerati
         last_assistant_message.as_deref().unwrap_or(""),
     );
 
+    let fallback_title = offline_title(
+        first_user_message.as_deref(),
+        last_assistant_message.as_deref(),
+    );
+
     Some(SpawnTitleGenerationRequest {
         session_id,
         prompt,
         previous_label,
+        fallback_title,
+    })
+}
+
+/// Title derived from the transcript alone, for when no LLM backend
+/// answers: the first line of the first user message (or, without one, of
+/// the latest assistant message), cut to `OFFLINE_TITLE_MAX_CHARS`.
+pub fn offline_title(
+    first_user_message: Option<&str>,
+    last_assistant_message: Option<&str>,
+) -> Option<String> {
+    let line = [first_user_message, last_assistant_message]
+        .into_iter()
+        .flatten()
+        .find_map(|message| message.lines().map(str::trim).find(|l| !l.is_empty()))?;
+    Some(match line.char_indices().nth(OFFLINE_TITLE_MAX_CHARS) {
+        Some((byte_idx, _)) => format!("{}…", &line[..byte_idx]),
+        None => line.to_string(),
     })
 }
 
@@ -129,6 +157,9 @@ pub(super) fn spawn_detached_title_generation(request: SpawnTitleGenerationReque
     if let Some(label) = &request.previous_label {
         cmd.arg("--previous-label").arg(label);
     }
+    if let Some(title) = &request.fallback_title {
+        cmd.arg("--fallback-title").arg(title);
+    }
 
     // SAFETY: `setsid` only manipulates the calling process's session
     // membership; it is async-signal-safe and documented as one of the
@@ -180,6 +211,13 @@ mod tests {
             pending_permission_agent_ids: std::collections::BTreeSet::new(),
             read_at: None,
             sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
         }
     }
 
@@ -235,6 +273,30 @@ mod tests {
         );
     }
 
+    #[rstest::rstest]
+    #[case::first_user_line(
+        Some("\n  Fix the login bug\nsteps..."),
+        Some("Done"),
+        Some("Fix the login bug")
+    )]
+    #[case::assistant_only(None, Some("Updated auth.rs"), Some("Updated auth.rs"))]
+    #[case::truncated(
+        Some("Investigate why the nightly deploy keeps failing"),
+        None,
+        Some("Investigate why the nightly de…")
+    )]
+    #[case::nothing(Some("  "), None, None)]
+    fn offline_title_cases(
+        #[case] first_user_message: Option<&str>,
+        #[case] last_assistant_message: Option<&str>,
+        #[case] expected: Option<&str>,
+    ) {
+        assert_eq!(
+            offline_title(first_user_message, last_assistant_message).as_deref(),
+            expected
+        );
+    }
+
     /// Creates a `.claude/projects/{encoded}/{session_id}.jsonl` fixture
     /// under `home_dir`, mirroring `claude_sessions.rs`'s own test harness --
     /// `get_first_user_message`/`get_last_assistant_message` resolve the
@@ -290,6 +352,7 @@ mod tests {
                     session_id: "s1".to_string(),
                     prompt: build_prompt("Fix the login bug", "Updated auth.rs"),
                     previous_label: Some("Old Title".to_string()),
+                    fallback_title: Some("Fix the login bug".to_string()),
                 }),
                 AppMode::Edit {
                     session_id: "s1".to_string()
@@ -12,7 +12,7 @@ use crate::commands::cc::tui::app::{App, AppMode, View};
 use crate::commands::cc::tui::worktree_view::WorktreeMode;
 
 use super::clean_list::render_clean_list;
-use super::edit_bar::render_edit_input;
+use super::edit_bar::{render_edit_input, render_edit_tags_input};
 use super::helpers::{count_statuses, truncate};
 use super::session_list::render_session_list;
 use super::worktree_list::render_worktree_list;
@@ -30,7 +30,8 @@ pub(super) fn render_with_time(frame: &mut Frame, app: &mut App, now: DateTime<U
     // The top bar (search / rename) is session-view only.
     let has_error = app.error_message.is_some();
     let is_search_mode = app.view == View::Session && app.mode == AppMode::Search;
-    let is_edit_mode = app.view == View::Session && matches!(app.mode, AppMode::Edit { .. });
+    let is_edit_mode = app.view == View::Session
+        && matches!(app.mode, AppMode::Edit { .. } | AppMode::EditTags { .. });
     let has_text_filter = app.view == View::Session && !app.confirmed_query.is_empty();
     let has_drilldown_scope = app.view == View::Session && app.drilldown_scope.is_some();
     let show_top_bar = is_search_mode || has_text_filter || is_edit_mode || has_drilldown_scope;
@@ -94,14 +95,14 @@ pub(super) fn render_with_time(frame: &mut Frame, app: &mut App, now: DateTime<U
 }
 
 /// Dispatches the bar rendered above the session list: the rename bar
-/// while `AppMode::Edit` is active, the search bar otherwise (live query
-/// while searching, or the confirmed filter query while browsing a
-/// filtered list).
+/// while `AppMode::Edit` is active, the tags bar while `AppMode::EditTags`
+/// is, the search bar otherwise (live query while searching, or the
+/// confirmed filter query while browsing a filtered list).
 fn render_top_bar(frame: &mut Frame, area: Rect, app: &App) {
-    if matches!(app.mode, AppMode::Edit { .. }) {
-        render_edit_input(frame, area, app);
-    } else {
-        render_search_input(frame, area, app);
+    match app.mode {
+        AppMode::Edit { .. } => render_edit_input(frame, area, app),
+        AppMode::EditTags { .. } => render_edit_tags_input(frame, area, app),
+        _ => render_search_input(frame, area, app),
     }
 }
 
@@ -319,6 +320,12 @@ fn build_session_help_lines(app: &App, bold: Style) -> Vec<Line<'static>> {
             Span::styled("Esc", bold),
             Span::raw(": cancel"),
         ])],
+        AppMode::EditTags { .. } => vec![Line::from(vec![
+            Span::styled("  Enter", bold),
+            Span::raw(": save (space-separated)  "),
+            Span::styled("Esc", bold),
+            Span::raw(": cancel"),
+        ])],
         AppMode::Normal if app.show_help && app.has_filter() => vec![
             Line::from(vec![
                 Span::styled("  j/k", bold),
@@ -339,11 +346,12 @@ fn build_session_help_lines(app: &App, bold: Style) -> Vec<Line<'static>> {
                 Span::raw(": parent  "),
                 Span::styled("→/l", bold),
                 Span::raw(": drill down  "),
-                Span::styled("C-r/w/s/p", bold),
+                Span::styled("C-r/w/s/p/u", bold),
                 Span::raw(": filter  "),
                 Span::styled("Esc", bold),
                 Span::raw(": clear"),
             ]),
+            build_session_edit_help_line(bold),
         ],
         AppMode::Normal if app.show_help => vec![
             Line::from(vec![
@@ -367,13 +375,14 @@ fn build_session_help_lines(app: &App, bold: Style) -> Vec<Line<'static>> {
                 Span::raw(": parent  "),
                 Span::styled("→/l", bold),
                 Span::raw(": drill down  "),
-                Span::styled("C-r/w/s/p", bold),
+                Span::styled("C-r/w/s/p/u", bold),
                 Span::raw(": filter  "),
                 Span::styled("Tab", bold),
                 Span::raw(": worktree view  "),
                 Span::styled("q", bold),
                 Span::raw(": quit"),
             ]),
+            build_session_edit_help_line(bold),
         ],
         AppMode::Normal if app.has_filter() => build_compact_help_line(
             bold,
@@ -390,6 +399,23 @@ fn build_session_help_lines(app: &App, bold: Style) -> Vec<Line<'static>> {
     }
 }
 
+/// Key bindings editing the selected session's metadata or acting on its
+/// PR, shown in the expanded session help.
+fn build_session_edit_help_line(bold: Style) -> Line<'static> {
+    Line::from(vec![
+        Span::styled("  e", bold),
+        Span::raw(": rename  "),
+        Span::styled("t", bold),
+        Span::raw(": tags  "),
+        Span::styled("n", bold),
+        Span::raw(": note  "),
+        Span::styled("o", bold),
+        Span::raw(": open PR  "),
+        Span::styled("v", bold),
+        Span::raw(": PR review threads"),
+    ])
+}
+
 /// Extracts the clean-view's help/confirmation content. The bottom line is
 /// the `Clean N worktree (M active excluded)? [y/N]` prompt; the line above
 /// lists the basic key bindings. Always 2 lines — not gated by
@@ -559,7 +585,8 @@ mod tests {
     ])]
     #[case::session_view_expanded(View::Session, true, vec![
         "  j/k: move  f: focus  r: resume  p: preview  d: delete  1-9: quick  /: search".to_string(),
-        "  h/←: parent  →/l: drill down  C-r/w/s/p: filter  Tab: worktree view  q: quit".to_string(),
+        "  h/←: parent  →/l: drill down  C-r/w/s/p/u: filter  Tab: worktree view  q: quit".to_string(),
+        "  e: rename  t: tags  n: note  o: open PR  v: PR review threads".to_string(),
     ])]
     #[case::worktree_view_default(View::Worktree, false, vec![
         " ?: keys   Enter/f: focus   Tab: switch view   q: quit".to_string(),
@@ -1,5 +1,6 @@
-//! Inline rename bar shown while `AppMode::Edit` is active, rendered in the
-//! same slot the search bar occupies (see `chrome::render_top_bar`).
+//! Inline rename bar shown while `AppMode::Edit` is active, and tags bar
+//! shown while `AppMode::EditTags` is, rendered in the same slot the search
+//! bar occupies (see `chrome::render_top_bar`).
 
 use ratatui::{
     Frame,
@@ -25,6 +26,18 @@ pub(super) fn render_edit_input(frame: &mut Frame, area: Rect, app: &App) {
     frame.render_widget(Paragraph::new(Line::from(spans)), area);
 }
 
+/// Renders the live tags buffer, seeded from the selected session's tags
+/// (see `App::enter_edit_tags`), with a blinking cursor.
+pub(super) fn render_edit_tags_input(frame: &mut Frame, area: Rect, app: &App) {
+    let spans = vec![
+        Span::styled("  Tags: ", Style::default().fg(Color::Yellow)),
+        Span::raw(app.edit_tags_query.clone()),
+        Span::styled("_", Style::default().add_modifier(Modifier::SLOW_BLINK)),
+    ];
+
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
 #[cfg(test)]
 mod tests {
     use chrono::Utc;
@@ -46,4 +59,19 @@ mod tests {
         let bar_line = output.lines().nth(1).unwrap();
         assert_eq!(bar_line.trim_end(), "  Rename: New Title_");
     }
+
+    #[test]
+    fn renders_tags_prefix_and_buffer_with_cursor() {
+        let now = Utc::now();
+        let sessions = vec![create_test_session("s1")];
+        let output = render_to_string_with(&sessions, Some(1), now, 80, 9, |app| {
+            app.mode = AppMode::EditTags {
+                session_id: "s1".to_string(),
+            };
+            app.edit_tags_query = "waiting-on-ci needs-review".to_string();
+        });
+
+        let bar_line = output.lines().nth(1).unwrap();
+        assert_eq!(bar_line.trim_end(), "  Tags: waiting-on-ci needs-review_");
+    }
 }
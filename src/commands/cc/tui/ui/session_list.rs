@@ -1,4 +1,7 @@
+use crate::commands::cc::changes::shared_changed_files;
+use crate::commands::cc::statusline::{checks_name, pr_state_name, review_name};
 use crate::commands::cc::types::{Session, SessionStatus};
+use crate::infra::github::{CheckState, PrInfo, PrState};
 use chrono::{DateTime, Utc};
 use ratatui::{
     Frame,
@@ -7,6 +10,7 @@ use ratatui::{
     text::{Line, Span},
     widgets::{List, ListItem, Paragraph},
 };
+use std::path::Path;
 use unicode_width::UnicodeWidthStr;
 
 use crate::commands::cc::tui::app::{App, AppMode};
@@ -64,6 +68,9 @@ pub(super) fn render_session_list(
             if let Some(status) = app.status_filter {
                 parts.push(format!("status:{}", status.display_name()));
             }
+            if app.unread_filter {
+                parts.push("unread".to_string());
+            }
             if !app.confirmed_query.is_empty() {
                 parts.push(format!("\"{}\"", app.confirmed_query));
             }
@@ -235,7 +242,8 @@ fn build_header_item(
 /// Renders one session row: status glyph, fixed-width repo column,
 /// variable-width title column (with breadcrumb prefix when this session
 /// has a displayed ancestor), and a right-aligned fixed-width time column.
-/// `WaitingInput` sessions get a second line holding only the question.
+/// `WaitingInput` sessions get a second line holding only the question, and
+/// the selected session gets lines for its PR and the files it changed.
 fn build_session_item(
     entry: &SessionRowEntry,
     app: &App,
@@ -316,9 +324,174 @@ fn build_session_item(
         ]));
     }
 
+    let is_selected = app
+        .selected_session()
+        .is_some_and(|selected| selected.session_id == session.session_id);
+    if is_selected && let Some(detail) = annotation_detail(session) {
+        let detail_width = term_width.saturating_sub(WAITING_QUESTION_INDENT);
+        lines.push(Line::from(vec![
+            Span::raw(" ".repeat(WAITING_QUESTION_INDENT - MARKER_WIDTH)),
+            Span::styled(truncate(&detail, detail_width), Style::default().fg(DIM_FG)),
+        ]));
+    }
+    if is_selected && let Some(pr) = app.session_pr(&session.cwd) {
+        let detail_width = term_width.saturating_sub(WAITING_QUESTION_INDENT);
+        lines.push(Line::from(vec![
+            Span::raw(" ".repeat(WAITING_QUESTION_INDENT - MARKER_WIDTH)),
+            Span::styled(
+                truncate(&pr_detail(&pr.pr), detail_width),
+                Style::default().fg(DIM_FG),
+            ),
+        ]));
+    }
+    if is_selected && let Some(detail) = changed_files_detail(session, app) {
+        let detail_width = term_width.saturating_sub(WAITING_QUESTION_INDENT);
+        lines.push(Line::from(vec![
+            Span::raw(" ".repeat(WAITING_QUESTION_INDENT - MARKER_WIDTH)),
+            Span::styled(truncate(&detail, detail_width), Style::default().fg(DIM_FG)),
+        ]));
+    }
+    if is_selected && let Some(detail) = activity_detail(session, now) {
+        let detail_width = term_width.saturating_sub(WAITING_QUESTION_INDENT);
+        lines.push(Line::from(vec![
+            Span::raw(" ".repeat(WAITING_QUESTION_INDENT - MARKER_WIDTH)),
+            Span::styled(truncate(&detail, detail_width), Style::default().fg(DIM_FG)),
+        ]));
+    }
+
     ListItem::new(lines)
 }
 
+/// The user's tags and the first line of their note, e.g. `#needs-review
+/// #wip  note: rebase after #12 lands …` (`…` when the note has more
+/// lines), or `None` when there is neither.
+fn annotation_detail(session: &Session) -> Option<String> {
+    let mut parts: Vec<String> = session.tags.iter().map(|tag| format!("#{tag}")).collect();
+    if let Some(note) = &session.note {
+        let mut lines = note.lines();
+        let first = lines.next().unwrap_or_default();
+        let more = if lines.next().is_some() {
+            " \u{2026}"
+        } else {
+            ""
+        };
+        parts.push(format!(" note: {first}{more}"));
+    }
+    (!parts.is_empty()).then(|| parts.join(" ").trim_start().to_string())
+}
+
+/// Summary of the subagents `session` has running and how often its
+/// conversation was compacted, e.g. `⚙ 2 subagents: Explore, Plan  ⟲
+/// compacted 3× (12m ago)`, or `None` when there is neither.
+fn activity_detail(session: &Session, now: DateTime<Utc>) -> Option<String> {
+    let mut parts = Vec::new();
+    let running: Vec<&str> = session
+        .subagents
+        .values()
+        .filter(|agent| agent.is_running())
+        .map(|agent| agent.agent_type.as_deref().unwrap_or("agent"))
+        .collect();
+    if !running.is_empty() {
+        parts.push(format!(
+            "\u{2699} {} subagent{}: {}",
+            running.len(),
+            if running.len() == 1 { "" } else { "s" },
+            running.join(", ")
+        ));
+    }
+    if session.compaction_count > 0 {
+        let mut part = format!("\u{27f2} compacted {}\u{00d7}", session.compaction_count);
+        if let Some(at) = session.last_compacted_at {
+            let ago = match format_compact_time(at, now) {
+                just_now if just_now == "just now" => just_now,
+                elapsed => format!("{elapsed} ago"),
+            };
+            part.push_str(&format!(" ({ago})"));
+        }
+        parts.push(part);
+    }
+    (!parts.is_empty()).then(|| parts.join("  "))
+}
+
+/// Summary of the files `session` changed, e.g. `⚠ overlap: a.rs  ✎ 2
+/// files: a.rs, b.rs`, or `None` when it changed nothing. Files another
+/// session changed too come first so a long file list can't truncate them
+/// away. Only file names are shown; `a cc changes` prints the full paths.
+fn changed_files_detail(session: &Session, app: &App) -> Option<String> {
+    if session.changed_files.is_empty() {
+        return None;
+    }
+    let file_name = |path: &Path| {
+        path.file_name().map_or_else(
+            || path.display().to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        )
+    };
+
+    let mut detail = String::new();
+    let shared = shared_changed_files(session, &app.sessions, &app.worktrees_dir);
+    if !shared.is_empty() {
+        let shared: Vec<String> = shared.into_iter().map(file_name).collect();
+        detail.push_str(&format!("\u{26a0} overlap: {}  ", shared.join(", ")));
+    }
+    let names: Vec<String> = session.changed_files.iter().map(|p| file_name(p)).collect();
+    detail.push_str(&format!(
+        "\u{270e} {} file{}: {}",
+        names.len(),
+        if names.len() == 1 { "" } else { "s" },
+        names.join(", ")
+    ));
+    Some(detail)
+}
+
+/// Summary of a session's PR for its detail line, e.g. `PR #42 draft ·
+/// changes requested · 2 unresolved · CI failing`.
+fn pr_detail(pr: &PrInfo) -> String {
+    let mut parts = vec![format!("PR #{} {}", pr.number, pr_display_state(pr))];
+    if let Some(review) = pr.review {
+        parts.push(review_name(review).to_string());
+    }
+    if let Some(threads) = pr.unresolved_threads.filter(|&n| n > 0) {
+        parts.push(format!("{threads} unresolved"));
+    }
+    if let Some(checks) = pr.checks {
+        parts.push(format!("CI {}", checks_name(checks)));
+    }
+    parts.join(" \u{00b7} ")
+}
+
+/// `pr_state_name`, except that an open draft reads as `draft`.
+fn pr_display_state(pr: &PrInfo) -> &'static str {
+    if pr.draft && pr.state == PrState::Open {
+        "draft"
+    } else {
+        pr_state_name(&pr.state)
+    }
+}
+
+/// Compact PR badge appended to the title, e.g. ` #42✓`: the CI result as a
+/// glyph, and as the badge's color for an open, non-draft PR. A PR that is
+/// no longer open, or still a draft, is dimmed whatever its CI says.
+fn pr_badge(pr: &PrInfo) -> (String, Style) {
+    let glyph = match pr.checks {
+        Some(CheckState::Success) => "\u{2713}",
+        Some(CheckState::Failure | CheckState::Error) => "\u{2717}",
+        _ => "",
+    };
+    let color = match (&pr.state, pr.draft, pr.checks) {
+        (PrState::Merged, _, _) => Color::Magenta,
+        (PrState::Closed, _, _) | (PrState::Open, true, _) => DIM_FG,
+        (_, _, Some(CheckState::Success)) => Color::Green,
+        (_, _, Some(CheckState::Failure | CheckState::Error)) => Color::Red,
+        (_, _, Some(CheckState::Pending | CheckState::Expected)) => Color::Yellow,
+        (_, _, None) => DIM_FG,
+    };
+    (
+        format!(" #{}{glyph}", pr.number),
+        Style::default().fg(color),
+    )
+}
+
 fn descendant_badge_text(descendant_count: usize) -> String {
     if descendant_count == 0 {
         String::new()
@@ -350,7 +523,13 @@ fn build_title_spans(
     } else {
         String::new()
     };
-    let content_width = title_width - badge.width();
+    // The PR badge only shows when it leaves the title some room
+    let pr_badge = app
+        .session_pr(&entry.session.cwd)
+        .map(|pr| pr_badge(&pr.pr))
+        .filter(|(text, _)| text.width() + badge.width() + MIN_TITLE_WIDTH <= title_width);
+    let pr_badge_width = pr_badge.as_ref().map_or(0, |(text, _)| text.width());
+    let content_width = title_width - badge.width() - pr_badge_width;
 
     let (mut spans, content_width_used) = build_breadcrumb_title_spans(
         entry,
@@ -367,6 +546,10 @@ fn build_title_spans(
         used_width += badge.width();
         spans.push(Span::styled(badge, dim_style));
     }
+    if let Some((text, style)) = pr_badge {
+        used_width += pr_badge_width;
+        spans.push(Span::styled(text, style));
+    }
     if used_width < title_width {
         spans.push(Span::raw(" ".repeat(title_width - used_width)));
     }
@@ -420,12 +603,17 @@ fn build_breadcrumb_title_spans(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::commands::cc::tui::pr_fetch::SessionPr;
     use crate::commands::cc::tui::ui::test_support::{
         create_test_session, render_buffer, render_buffer_with, render_to_string,
         render_to_string_with,
     };
+    use crate::commands::cc::types::Subagent;
+    use crate::infra::github::ReviewDecision;
     use indoc::indoc;
     use rstest::{fixture, rstest};
+    use std::collections::HashMap;
+    use std::path::PathBuf;
 
     #[rstest]
     #[case::just_now(0, "just now")]
@@ -611,6 +799,199 @@ mod tests {
         assert_eq!(output, expected);
     }
 
+    #[rstest]
+    #[case::tags_only(&["wip", "needs-review"], None, Some("#needs-review #wip"))]
+    #[case::note_only(&[], Some("rebase first"), Some("note: rebase first"))]
+    #[case::both_multi_line_note(
+        &["wip"],
+        Some("rebase first\nthen squash"),
+        Some("#wip  note: rebase first \u{2026}")
+    )]
+    #[case::neither(&[], None, None)]
+    fn test_annotation_detail(
+        #[case] tags: &[&str],
+        #[case] note: Option<&str>,
+        #[case] expected: Option<&str>,
+    ) {
+        let mut session = create_test_session("s1");
+        session.tags = tags.iter().map(|t| t.to_string()).collect();
+        session.note = note.map(str::to_string);
+        assert_eq!(annotation_detail(&session).as_deref(), expected);
+    }
+
+    #[test]
+    fn test_render_selected_session_shows_changed_files_line() {
+        let now = Utc::now();
+
+        let mut selected = create_test_session("s1");
+        selected.updated_at = now;
+        selected.changed_files = [
+            "/repo/.worktrees/a/src/lib.rs",
+            "/repo/.worktrees/a/README.md",
+        ]
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+        let mut other = create_test_session("s2");
+        other.updated_at = now;
+        other.changed_files = [PathBuf::from("/repo/.worktrees/b/src/lib.rs")].into();
+
+        let sessions = vec![selected, other];
+        let output = render_to_string(&sessions, Some(1), now, 80, 10);
+
+        let expected = indoc! {"
+             cc watch                                       0 needs you · 2 running · 0 idle
+             ── RUNNING (2) ────────────────────────────────────────────────────────────────
+            >● project         project                                              just now
+                               ⚠ overlap: lib.rs  ✎ 2 files: README.md, lib.rs
+             ● project         project                                              just now
+
+
+
+
+             ?: keys   /: search   Tab: worktree   q: quit"};
+
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_render_selected_session_shows_subagents_and_compactions_line() {
+        let now = Utc::now();
+
+        let mut session = create_test_session("s1");
+        session.updated_at = now;
+        for (id, agent_type, stopped) in [
+            ("a1", Some("Explore"), false),
+            ("a2", None, false),
+            ("a3", Some("Plan"), true),
+        ] {
+            session.subagents.insert(
+                id.to_string(),
+                Subagent {
+                    agent_type: agent_type.map(str::to_string),
+                    started_at: now,
+                    stopped_at: stopped.then_some(now),
+                },
+            );
+        }
+        session.compaction_count = 2;
+        session.last_compacted_at = Some(now - chrono::Duration::minutes(12));
+
+        let sessions = vec![session];
+        let output = render_to_string(&sessions, Some(1), now, 80, 9);
+
+        let expected = indoc! {"
+             cc watch                                       0 needs you · 1 running · 0 idle
+             ── RUNNING (1) ────────────────────────────────────────────────────────────────
+            >● project         project                                              just now
+                               ⚙ 2 subagents: Explore, agent  ⟲ compacted 2× (12m ago)
+
+
+
+
+             ?: keys   /: search   Tab: worktree   q: quit"};
+
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_render_session_pr_badge_and_selected_detail_line() {
+        let now = Utc::now();
+
+        let mut selected = create_test_session("s1");
+        selected.updated_at = now;
+        let mut other = create_test_session("s2");
+        other.updated_at = now;
+        other.cwd = PathBuf::from("/home/user/other");
+
+        let pr = |number, checks| SessionPr {
+            repo: "o/r".to_string(),
+            pr: PrInfo {
+                number,
+                state: PrState::Open,
+                url: format!("https://github.com/o/r/pull/{number}"),
+                title: String::new(),
+                draft: false,
+                review: Some(ReviewDecision::ChangesRequested),
+                checks,
+                unresolved_threads: Some(2),
+            },
+        };
+        let sessions = vec![selected, other];
+        let output = render_to_string_with(&sessions, Some(1), now, 80, 10, |app| {
+            app.apply_session_prs(HashMap::from([
+                (
+                    PathBuf::from("/home/user/project"),
+                    pr(42, Some(CheckState::Failure)),
+                ),
+                (PathBuf::from("/home/user/other"), pr(7, None)),
+            ]));
+        });
+
+        let expected = indoc! {"
+             cc watch                                       0 needs you · 2 running · 0 idle
+             ── RUNNING (2) ────────────────────────────────────────────────────────────────
+            >● project         project #42✗                                         just now
+                               PR #42 open · changes requested · 2 unresolved · CI failing
+             ● other           other #7                                             just now
+
+
+
+
+             ?: keys   /: search   Tab: worktree   q: quit"};
+
+        assert_eq!(output, expected);
+    }
+
+    #[rstest]
+    #[case::draft_is_dimmed_despite_passing_ci(
+        PrState::Open,
+        true,
+        Some(CheckState::Success),
+        " #1\u{2713}",
+        DIM_FG
+    )]
+    #[case::failing(
+        PrState::Open,
+        false,
+        Some(CheckState::Error),
+        " #1\u{2717}",
+        Color::Red
+    )]
+    #[case::pending(PrState::Open, false, Some(CheckState::Pending), " #1", Color::Yellow)]
+    #[case::merged(
+        PrState::Merged,
+        false,
+        Some(CheckState::Success),
+        " #1\u{2713}",
+        Color::Magenta
+    )]
+    fn test_pr_badge(
+        #[case] state: PrState,
+        #[case] draft: bool,
+        #[case] checks: Option<CheckState>,
+        #[case] expected_text: &str,
+        #[case] expected_color: Color,
+    ) {
+        let pr = PrInfo {
+            number: 1,
+            state,
+            url: String::new(),
+            title: String::new(),
+            draft,
+            review: None,
+            checks,
+            unresolved_threads: None,
+        };
+        assert_eq!(
+            pr_badge(&pr),
+            (
+                expected_text.to_string(),
+                Style::default().fg(expected_color)
+            )
+        );
+    }
+
     #[rstest]
     #[case::zero_shows_no_badge(0, "")]
     #[case::one(1, " \u{25b8}1")]
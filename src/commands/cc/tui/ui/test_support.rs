@@ -28,6 +28,13 @@ pub(super) fn create_test_session(id: &str) -> Session {
         pending_permission_agent_ids: BTreeSet::new(),
         read_at: None,
         sweep_signaled: false,
+        changed_files: Default::default(),
+        subagents: Default::default(),
+        compaction_count: 0,
+        last_compacted_at: None,
+        report: None,
+        tags: Default::default(),
+        note: None,
     }
 }
 
@@ -29,10 +29,11 @@ pub struct SessionChild {
 }
 
 impl SessionChild {
-    /// Same rule as `Session::display_symbol`: unread Stopped → `✱`.
+    /// Same rule as `Session::display_symbol`: unread Stopped →
+    /// `Session::UNREAD_SYMBOL`.
     pub fn display_symbol(&self) -> &'static str {
         if self.status == SessionStatus::Stopped && self.read_at.is_none() {
-            "✱"
+            Session::UNREAD_SYMBOL
         } else {
             self.status.display_symbol()
         }
@@ -175,6 +176,13 @@ mod tests {
             pending_permission_agent_ids: BTreeSet::new(),
             read_at: None,
             sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
         }
     }
 
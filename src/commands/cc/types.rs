@@ -1,7 +1,7 @@
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::PathBuf;
 
 use super::error::CcError;
@@ -51,6 +51,15 @@ pub struct Session {
     /// Short title for session identification (set via env var or auto-generated)
     #[serde(default)]
     pub label: Option<String>,
+    /// Free-form user tags (e.g. `waiting-on-ci`), set via `a cc tag` or
+    /// `cc watch`'s `t` key and matched by `cc watch`'s search filter.
+    /// Unlike `label`, never written automatically.
+    #[serde(default)]
+    pub tags: BTreeSet<String>,
+    /// Free-form, possibly multi-line user note, set via `a cc note` or
+    /// `cc watch`'s `n` key.
+    #[serde(default)]
+    pub note: Option<String>,
     /// Ancestor session IDs from root to immediate parent.
     /// Used to build tree view: if intermediate sessions are deleted,
     /// child sessions can still find their nearest living ancestor.
@@ -79,12 +88,10 @@ pub struct Session {
     /// `type == "subagent"`; see `HookInput::pending_agent_task_ids`). Same
     /// rationale and refresh model as `pending_bg_task_ids` above, including
     /// `sweep`'s early clear once no `claude` process resolves. Consumed by
-    /// `sweep` exactly like `pending_bg_task_ids`.
-    ///
-    /// `alias` accepts the field's pre-rename name so a session file
-    /// written by an older `armyknife` build still deserializes instead of
-    /// silently reverting to an empty set until the next `Stop`.
-    #[serde(default, alias = "pending_agent_task_outputs")]
+    /// `sweep` exactly like `pending_bg_task_ids`. Session files from before
+    /// the rename from `pending_agent_task_outputs` are carried over by the
+    /// store's schema migration.
+    #[serde(default)]
     pub pending_agent_task_ids: BTreeSet<String>,
     /// Keys of hook events currently blocked on a permission prompt
     /// (`PermissionRequest`), one per concurrently running agent. A key is
@@ -115,6 +122,93 @@ pub struct Session {
     /// longer relevant) and by `sweep::confirm_paused`.
     #[serde(default)]
     pub sweep_signaled: bool,
+    /// Latest structured result this session filed via `a cc peer report`,
+    /// read back by its delegator through `a cc peer results`. Each report
+    /// overwrites the previous one; `None` until the session first reports.
+    #[serde(default)]
+    pub report: Option<SessionReport>,
+
+    /// Absolute paths of files this session modified through Edit /
+    /// MultiEdit / Write / NotebookEdit, recorded on each successful
+    /// `PostToolUse`. Read by `a cc changes` and the `cc watch` detail line
+    /// to show who touched what and flag files edited by several sessions.
+    /// Files changed via Bash are not tracked.
+    #[serde(default)]
+    pub changed_files: BTreeSet<PathBuf>,
+
+    /// Subagents this session launched, keyed by `agent_id`. An entry is
+    /// added by `SubagentStart` (or the first event a subagent fires, for
+    /// builds without that hook) and marked stopped by `SubagentStop`.
+    /// Stopped entries are dropped at the next main-thread prompt, so the
+    /// map covers the current turn plus any still-running background
+    /// subagents. Shown on `cc watch`'s detail line.
+    #[serde(default)]
+    pub subagents: BTreeMap<String, Subagent>,
+
+    /// How many times the conversation has been compacted (`PreCompact`
+    /// events, manual `/compact` and auto-compaction alike).
+    #[serde(default)]
+    pub compaction_count: u32,
+
+    /// When the conversation was last compacted.
+    #[serde(default)]
+    pub last_compacted_at: Option<DateTime<Utc>>,
+}
+
+/// Lifecycle of one subagent, as recorded in `Session::subagents`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Subagent {
+    /// The subagent's type (e.g. `Explore`), when Claude Code reports it.
+    #[serde(default)]
+    pub agent_type: Option<String>,
+    pub started_at: DateTime<Utc>,
+    /// `None` while the subagent is still running.
+    #[serde(default)]
+    pub stopped_at: Option<DateTime<Utc>>,
+}
+
+impl Subagent {
+    pub fn is_running(&self) -> bool {
+        self.stopped_at.is_none()
+    }
+}
+
+/// A delegated session's structured handoff to its delegator, written by
+/// `a cc peer report`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionReport {
+    pub status: ReportStatus,
+    pub summary: String,
+    #[serde(default)]
+    pub pr: Option<String>,
+    /// Absolute paths of files the delegate wants the delegator to look at
+    /// (logs, generated docs, ...). Resolved against the reporter's cwd at
+    /// report time so the delegator can read them from its own worktree.
+    #[serde(default)]
+    pub artifacts: Vec<PathBuf>,
+    pub reported_at: DateTime<Utc>,
+}
+
+/// Outcome a delegated session reports back to its delegator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportStatus {
+    /// The delegated task is finished.
+    Done,
+    /// The delegate cannot proceed without input from the delegator.
+    Blocked,
+    /// The delegate gave up on the task.
+    Failed,
+}
+
+impl ReportStatus {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::Done => "done",
+            Self::Blocked => "blocked",
+            Self::Failed => "failed",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -155,6 +249,10 @@ pub enum StatusColor {
 }
 
 impl Session {
+    /// Glyph of an unread `Stopped` session, shown in place of the status's
+    /// own symbol.
+    pub const UNREAD_SYMBOL: &'static str = "✱";
+
     /// A `Stopped` session is unread when it has never been focused since its
     /// most recent transition into `Stopped`. Drives the `✱` glyph.
     pub fn is_unread_stopped(&self) -> bool {
@@ -182,7 +280,7 @@ impl Session {
     /// Status symbol that also reflects unread state.
     pub fn display_symbol(&self) -> &'static str {
         if self.is_unread_stopped() {
-            "✱"
+            Self::UNREAD_SYMBOL
         } else {
             self.status.display_symbol()
         }
@@ -253,16 +351,31 @@ pub struct HookInput {
     #[serde(default)]
     pub agent_id: Option<String>,
 
+    /// Type of the subagent that fired this event (`SubagentStart` /
+    /// `SubagentStop`), e.g. `Explore`. Recorded in `Session::subagents`.
+    #[serde(default)]
+    pub agent_type: Option<String>,
+
+    /// What triggered a `PreCompact` event: `manual` (`/compact`) or `auto`.
+    #[serde(default)]
+    pub trigger: Option<String>,
+
+    /// The submitted prompt on `UserPromptSubmit`. Only used to derive an
+    /// automatic label (see `auto_label.rs`) when the session is first seen.
+    #[serde(default)]
+    pub prompt: Option<String>,
+
     /// Claude Code's own task registry snapshot. Per
     /// https://code.claude.com/docs/en/hooks.md (Stop input / SubagentStop
     /// input), Claude Code v2.1.145+ populates this on both `Stop` and
-    /// `SubagentStop` input, but armyknife only wires the `Stop` hook (see
-    /// `hook.rs`), so this is only ever read there. Older builds omit the
-    /// field entirely, which deserializes to an empty vec. An entry
-    /// disappears once its task is no longer in flight or scheduled -- the
-    /// docs describe the array itself as empty whenever nothing is
-    /// in-flight/scheduled, so presence in this list is the pending signal,
-    /// not any particular `status` string.
+    /// `SubagentStop` input, but only `Stop` reflects the whole session's
+    /// view once the main loop has gone idle (see `hook.rs`), so this is
+    /// only ever read there. Older builds omit the field entirely, which
+    /// deserializes to an empty vec. An entry disappears once its task is
+    /// no longer in flight or scheduled -- the docs describe the array
+    /// itself as empty whenever nothing is in-flight/scheduled, so presence
+    /// in this list is the pending signal, not any particular `status`
+    /// string.
     #[serde(default)]
     pub background_tasks: Vec<BackgroundTask>,
 
@@ -272,6 +385,22 @@ pub struct HookInput {
 }
 
 impl HookInput {
+    /// The file a file-writing tool call (see `FILE_WRITING_TOOLS`) targets,
+    /// resolved against `cwd` when the tool was given a relative path.
+    /// `None` for any other tool.
+    pub fn written_file_path(&self) -> Option<PathBuf> {
+        let tool_name = self.tool_name.as_deref()?;
+        if !FILE_WRITING_TOOLS.contains(&tool_name) {
+            return None;
+        }
+        let tool_input = self.tool_input.as_ref()?;
+        let path = tool_input
+            .file_path
+            .as_deref()
+            .or(tool_input.notebook_path.as_deref())?;
+        Some(self.cwd.join(path))
+    }
+
     /// IDs of Bash background tasks (`run_in_background: true`) that Claude
     /// Code's task registry reports as still in flight or scheduled, per
     /// `background_tasks` (see its doc comment). Filtered to
@@ -318,10 +447,17 @@ pub struct ToolInput {
     pub command: Option<String>,
     /// File path for Read/Write/Edit tools
     pub file_path: Option<String>,
+    /// Notebook path for the NotebookEdit tool
+    #[serde(default)]
+    pub notebook_path: Option<String>,
     /// Pattern for Grep/Glob tools
     pub pattern: Option<String>,
 }
 
+/// Tools whose `file_path` / `notebook_path` input is a file they modify,
+/// as opposed to merely read.
+pub const FILE_WRITING_TOOLS: &[&str] = &["Edit", "MultiEdit", "Write", "NotebookEdit"];
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HookEvent {
     SessionStart,
@@ -332,9 +468,23 @@ pub enum HookEvent {
     Notification,
     Stop,
     SessionEnd,
+    SubagentStart,
+    SubagentStop,
+    PreCompact,
 }
 
 impl HookEvent {
+    /// Whether the event describes the main loop's own progress. Subagent
+    /// lifecycle and compaction events fire alongside it (a background
+    /// subagent may stop while the session sits idle), so they leave the
+    /// session's status, read state, and notifications alone.
+    pub fn reports_main_loop(self) -> bool {
+        !matches!(
+            self,
+            Self::SubagentStart | Self::SubagentStop | Self::PreCompact
+        )
+    }
+
     pub fn from_str(s: &str) -> Result<Self> {
         match s {
             "session-start" => Ok(Self::SessionStart),
@@ -345,6 +495,9 @@ impl HookEvent {
             "notification" => Ok(Self::Notification),
             "stop" => Ok(Self::Stop),
             "session-end" => Ok(Self::SessionEnd),
+            "subagent-start" => Ok(Self::SubagentStart),
+            "subagent-stop" => Ok(Self::SubagentStop),
+            "pre-compact" => Ok(Self::PreCompact),
             _ => Err(CcError::UnknownHookEvent(s.to_string()).into()),
         }
     }
@@ -375,6 +528,13 @@ mod tests {
             pending_permission_agent_ids: BTreeSet::new(),
             read_at,
             sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
         }
     }
 
@@ -416,31 +576,6 @@ mod tests {
         assert_eq!(session.read_at, None);
     }
 
-    #[test]
-    fn pending_agent_task_ids_accepts_pre_rename_field_name() {
-        // A session written by an older armyknife build (before
-        // `pending_agent_task_outputs` was renamed to `pending_agent_task_ids`)
-        // must still deserialize non-empty, not silently drop the pending
-        // task and revert to an empty set until the next `Stop`.
-        let json = serde_json::json!({
-            "session_id": "legacy",
-            "cwd": "/tmp/legacy",
-            "transcript_path": null,
-            "tmux_info": null,
-            "status": "stopped",
-            "created_at": "2026-01-01T00:00:00Z",
-            "updated_at": "2026-01-01T00:00:00Z",
-            "last_message": null,
-            "pending_agent_task_outputs": ["/tmp/claude-1/proj/legacy/tasks/agent-1.output"],
-        });
-        let session: Session =
-            serde_json::from_value(json).expect("legacy session should deserialize");
-        assert_eq!(
-            session.pending_agent_task_ids,
-            BTreeSet::from(["/tmp/claude-1/proj/legacy/tasks/agent-1.output".to_string()])
-        );
-    }
-
     #[rstest]
     #[case::neither(false, false, false)]
     #[case::bg_only(true, false, true)]
@@ -4,10 +4,15 @@ use clap::Args;
 use super::tui;
 
 #[derive(Args, Clone, PartialEq, Eq)]
-pub struct WatchArgs {}
+pub struct WatchArgs {
+    /// Only show sessions waiting on you: unread stopped sessions and those
+    /// waiting for input (toggle with Ctrl+u)
+    #[arg(long)]
+    pub unread: bool,
+}
 
 /// Runs the watch command.
 /// Launches the TUI for real-time session monitoring.
-pub fn run(_args: &WatchArgs) -> Result<()> {
-    tui::run()
+pub fn run(args: &WatchArgs) -> Result<()> {
+    tui::run(args.unread)
 }
@@ -6,11 +6,12 @@ use anyhow::Result;
 use clap::Args;
 
 use super::store;
+use super::tmux_format;
 use super::types::{
-    Session, SessionStatus, TMUX_SESSION_OPTION, TMUX_WINDOW_STATUS_OPTION,
-    TMUX_WINDOW_TITLE_OPTION,
+    Session, TMUX_SESSION_OPTION, TMUX_WINDOW_STATUS_OPTION, TMUX_WINDOW_TITLE_OPTION,
 };
-use crate::infra::tmux;
+use crate::infra::multiplexer::{self, Multiplexer};
+use crate::shared::config::{self, TmuxStatusConfig};
 
 #[derive(Args, Clone, PartialEq, Eq)]
 pub struct WindowStatusArgs {
@@ -26,10 +27,19 @@ pub struct WindowStatusArgs {
 /// `sync_window_option`); this command exists for manual inspection and for
 /// a polling-based `window-status-format` that calls `#(a cc window-status)`.
 pub fn run(args: &WindowStatusArgs) -> Result<()> {
-    let sessions = load_window_sessions(&args.window_id, &store::sessions_dir()?)?;
+    let sessions = load_window_sessions(
+        multiplexer::current(),
+        &args.window_id,
+        &store::sessions_dir()?,
+    )?;
+    let config = config::load_config().unwrap_or_default();
 
     let mut stdout = io::stdout().lock();
-    write!(stdout, "{}", render_window_status(&sessions))?;
+    write!(
+        stdout,
+        "{}",
+        render_window_status(&sessions, &config.cc.tmux)?
+    )?;
 
     Ok(())
 }
@@ -53,7 +63,7 @@ pub fn sync_window_title_for_session(session: &Session) {
     let Some(pane_id) = session.tmux_info.as_ref().map(|info| info.pane_id.as_str()) else {
         return;
     };
-    let Some(window_id) = tmux::get_window_id_for_pane(pane_id) else {
+    let Some(window_id) = multiplexer::current().window_of_pane(pane_id) else {
         return;
     };
     let Ok(sessions_dir) = store::sessions_dir() else {
@@ -63,14 +73,29 @@ pub fn sync_window_title_for_session(session: &Session) {
 }
 
 pub fn sync_window_option(window_id: &str, sessions_dir: &Path) -> Result<()> {
-    let sessions = load_window_sessions(window_id, sessions_dir)?;
+    let config = config::load_config().unwrap_or_default();
+    sync_window_option_with(
+        multiplexer::current(),
+        window_id,
+        sessions_dir,
+        &config.cc.tmux,
+    )
+}
+
+fn sync_window_option_with(
+    mux: &dyn Multiplexer,
+    window_id: &str,
+    sessions_dir: &Path,
+    config: &TmuxStatusConfig,
+) -> Result<()> {
+    let sessions = load_window_sessions(mux, window_id, sessions_dir)?;
 
-    let rendered_status = render_window_status(&sessions);
-    let current_status = tmux::get_window_option(window_id, TMUX_WINDOW_STATUS_OPTION);
+    let rendered_status = render_window_status(&sessions, config)?;
+    let current_status = mux.window_option(window_id, TMUX_WINDOW_STATUS_OPTION);
     let status_changed = tmux_option_changed(current_status.as_deref(), &rendered_status);
 
-    let rendered_title = render_window_title(&sessions);
-    let current_title = tmux::get_window_option(window_id, TMUX_WINDOW_TITLE_OPTION);
+    let rendered_title = render_window_title(&sessions, config)?;
+    let current_title = mux.window_option(window_id, TMUX_WINDOW_TITLE_OPTION);
     let title_changed = tmux_option_changed(current_title.as_deref(), &rendered_title);
 
     if !status_changed && !title_changed {
@@ -78,12 +103,12 @@ pub fn sync_window_option(window_id: &str, sessions_dir: &Path) -> Result<()> {
     }
 
     if status_changed {
-        tmux::set_window_option(window_id, TMUX_WINDOW_STATUS_OPTION, &rendered_status)?;
+        mux.set_window_option(window_id, TMUX_WINDOW_STATUS_OPTION, &rendered_status)?;
     }
     if title_changed {
-        tmux::set_window_option(window_id, TMUX_WINDOW_TITLE_OPTION, &rendered_title)?;
+        mux.set_window_option(window_id, TMUX_WINDOW_TITLE_OPTION, &rendered_title)?;
     }
-    tmux::refresh_status()?;
+    mux.refresh_status()?;
 
     Ok(())
 }
@@ -94,8 +119,12 @@ pub fn sync_window_option(window_id: &str, sessions_dir: &Path) -> Result<()> {
 /// O(panes in window) rather than O(all sessions on disk). Two panes can carry
 /// the same session id (e.g. a split pane keeps the option), so duplicates are
 /// dropped to avoid rendering a session's symbol twice.
-fn load_window_sessions(window_id: &str, sessions_dir: &Path) -> Result<Vec<Session>> {
-    let session_ids = tmux::list_window_pane_options(window_id, TMUX_SESSION_OPTION);
+fn load_window_sessions(
+    mux: &dyn Multiplexer,
+    window_id: &str,
+    sessions_dir: &Path,
+) -> Result<Vec<Session>> {
+    let session_ids = mux.window_pane_options(window_id, TMUX_SESSION_OPTION);
 
     let mut seen = HashSet::new();
     let mut sessions = Vec::with_capacity(session_ids.len());
@@ -111,40 +140,33 @@ fn load_window_sessions(window_id: &str, sessions_dir: &Path) -> Result<Vec<Sess
     Ok(sessions)
 }
 
-/// Renders the aggregated status symbols for a window's sessions.
-///
-/// Each session contributes one symbol, in the given order, with no
-/// separator. When at least one symbol is emitted, a trailing space is added
-/// so the result reads cleanly when prepended to a window name. Returns an
-/// empty string when no session contributes a symbol.
-fn render_window_status(sessions: &[Session]) -> String {
-    let mut symbols = String::new();
-
-    for session in sessions {
-        if let Some(symbol) = format_window_symbol(session) {
-            symbols.push_str(symbol);
-        }
-    }
-
-    if symbols.is_empty() {
-        String::new()
-    } else {
-        format!("{symbols} ")
-    }
+/// Renders `cc.tmux.window_status` over a window's sessions. The default
+/// template prints one status symbol per session, in the given order, plus
+/// a trailing space so the result reads cleanly when prepended to a window
+/// name, and nothing when no session is shown.
+fn render_window_status(sessions: &[Session], config: &TmuxStatusConfig) -> Result<String> {
+    tmux_format::render(
+        &config.window_status,
+        sessions,
+        &config.styles,
+        "window_status",
+    )
 }
 
-/// Picks the `label` of the first session (in `sessions`' order, i.e. the
-/// existing deterministic pane order from `load_window_sessions`) that has
-/// one set, or an empty string if none do.
+/// Renders `cc.tmux.window_title` over a window's sessions. The default
+/// template is the `label` of the first session (in `load_window_sessions`'
+/// deterministic pane order) that has one set.
 ///
-/// Deliberately does not concatenate across sessions: a tmux window
-/// normally hosts one worktree / one session in practice, and a multi-session
-/// window has no well-defined "combined" title.
-fn render_window_title(sessions: &[Session]) -> String {
-    sessions
-        .iter()
-        .find_map(|s| s.label.clone())
-        .unwrap_or_default()
+/// Labels are deliberately not concatenated across sessions: a tmux window
+/// normally hosts one worktree / one session in practice, and a
+/// multi-session window has no well-defined "combined" title.
+fn render_window_title(sessions: &[Session], config: &TmuxStatusConfig) -> Result<String> {
+    tmux_format::render(
+        &config.window_title,
+        sessions,
+        &config.styles,
+        "window_title",
+    )
 }
 
 /// Whether a tmux window option must be rewritten: true when the freshly
@@ -156,28 +178,10 @@ fn tmux_option_changed(current: Option<&str>, rendered: &str) -> bool {
     current.unwrap_or("") != rendered
 }
 
-/// Formats a single status symbol for embedding in tmux's window-status.
-///
-/// Returns `None` only for `Ended` sessions (Claude Code fully exited).
-/// `Stopped` and `Paused` are still shown: their panes are alive and the
-/// conversation is resumable, which is exactly what the window indicator is
-/// for.
-///
-/// No tmux style markup is emitted. A foreground color here would visually
-/// become a *background* color whenever the surrounding context has the
-/// `reverse` attribute (a common idiom for `window-status-activity-style`),
-/// painting the icon's cell with a color block that breaks out of the rest
-/// of the tab. Shape alone (●/◐/○/⏸) carries the status well enough.
-fn format_window_symbol(session: &Session) -> Option<&'static str> {
-    if session.status == SessionStatus::Ended {
-        return None;
-    }
-    Some(session.display_symbol())
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::commands::cc::types::SessionStatus;
     use chrono::Utc;
     use rstest::rstest;
     use std::path::PathBuf;
@@ -201,27 +205,19 @@ mod tests {
             pending_permission_agent_ids: std::collections::BTreeSet::new(),
             read_at,
             sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
         }
     }
 
     fn render(statuses: &[SessionStatus]) -> String {
         let sessions: Vec<Session> = statuses.iter().copied().map(|s| session(s, None)).collect();
-        render_window_status(&sessions)
-    }
-
-    #[rstest]
-    #[case::running(SessionStatus::Running, None, Some("\u{25cf}"))]
-    #[case::waiting(SessionStatus::WaitingInput, None, Some("\u{25d0}"))]
-    #[case::stopped_unread(SessionStatus::Stopped, None, Some("\u{2731}"))]
-    #[case::stopped_read(SessionStatus::Stopped, Some(Utc::now()), Some("\u{25cb}"))]
-    #[case::paused(SessionStatus::Paused, None, Some("\u{23f8}"))]
-    #[case::ended(SessionStatus::Ended, None, None)]
-    fn test_format_window_symbol(
-        #[case] status: SessionStatus,
-        #[case] read_at: Option<chrono::DateTime<Utc>>,
-        #[case] expected: Option<&str>,
-    ) {
-        assert_eq!(format_window_symbol(&session(status, read_at)), expected);
+        render_window_status(&sessions, &TmuxStatusConfig::default()).unwrap()
     }
 
     #[rstest]
@@ -268,6 +264,47 @@ mod tests {
     #[case::empty_label_string_counts_as_has_label(vec![Some(""), Some("Ignored")], "")]
     fn test_render_window_title(#[case] labels: Vec<Option<&str>>, #[case] expected: &str) {
         let sessions: Vec<Session> = labels.into_iter().map(session_with_label).collect();
-        assert_eq!(render_window_title(&sessions), expected);
+        assert_eq!(
+            render_window_title(&sessions, &TmuxStatusConfig::default()).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_sync_window_option_writes_each_window_option() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut labeled = session(SessionStatus::WaitingInput, None);
+        labeled.session_id = "labeled".to_string();
+        labeled.label = Some("Fix login".to_string());
+        store::save_session_to(dir.path(), &labeled).unwrap();
+        store::save_session_to(dir.path(), &session(SessionStatus::Running, None)).unwrap();
+
+        let mux = multiplexer::FakeMultiplexer::new()
+            .with_pane("%1", "@1")
+            .with_pane("%2", "@1")
+            .with_pane("%3", "@1")
+            .with_pane("%9", "@2");
+        mux.set_pane_option("%1", TMUX_SESSION_OPTION, "test-123")
+            .unwrap();
+        mux.set_pane_option("%2", TMUX_SESSION_OPTION, "labeled")
+            .unwrap();
+        // A split pane keeps its origin's option; its session shows once.
+        mux.set_pane_option("%3", TMUX_SESSION_OPTION, "test-123")
+            .unwrap();
+        mux.set_pane_option("%9", TMUX_SESSION_OPTION, "labeled")
+            .unwrap();
+
+        sync_window_option_with(&mux, "@1", dir.path(), &TmuxStatusConfig::default()).unwrap();
+
+        assert_eq!(
+            mux.window_option("@1", TMUX_WINDOW_STATUS_OPTION)
+                .as_deref(),
+            Some("\u{25cf}\u{25d0} ")
+        );
+        assert_eq!(
+            mux.window_option("@1", TMUX_WINDOW_TITLE_OPTION).as_deref(),
+            Some("Fix login")
+        );
+        assert_eq!(mux.window_option("@2", TMUX_WINDOW_STATUS_OPTION), None);
     }
 }
@@ -5,7 +5,7 @@ use anyhow::Result;
 use clap::Args;
 
 use crate::infra::external_tool::ExternalTool;
-use crate::shared::config::{Config, Terminal};
+use crate::shared::config::{Config, MultiplexerChoice, Terminal};
 
 #[derive(Args, Clone, PartialEq, Eq)]
 pub struct DoctorArgs {}
@@ -23,9 +23,9 @@ pub fn run(_args: &DoctorArgs) -> Result<()> {
 }
 
 /// Filters [`ExternalTool::ALL`] down to the tools the current config actually
-/// uses. Non-selected terminal alternatives, a non-nvim editor, and disabled
-/// notifications are dropped so users aren't flagged for tools they will never
-/// invoke.
+/// uses. Non-selected terminal and multiplexer alternatives, a non-nvim
+/// editor, and disabled notifications are dropped so users aren't flagged
+/// for tools they will never invoke.
 fn selected_tools(config: &Config) -> Vec<ExternalTool> {
     ExternalTool::ALL
         .iter()
@@ -37,6 +37,8 @@ fn selected_tools(config: &Config) -> Vec<ExternalTool> {
             // probe `nvim`, so silently skip when the user picked something else.
             ExternalTool::Nvim => config.editor.editor_command == "nvim",
             ExternalTool::Hammerspoon => config.notification.enabled,
+            ExternalTool::Tmux => config.multiplexer != MultiplexerChoice::Zellij,
+            ExternalTool::Zellij => config.multiplexer == MultiplexerChoice::Zellij,
             _ => true,
         })
         .collect()
@@ -245,6 +247,17 @@ mod tests {
             ExternalTool::Claude, ExternalTool::Opencode,
         ],
     )]
+    #[case::zellij_multiplexer(
+        Config {
+            multiplexer: MultiplexerChoice::Zellij,
+            ..Default::default()
+        },
+        &[
+            ExternalTool::Git, ExternalTool::Gh, ExternalTool::Zellij,
+            ExternalTool::Nvim, ExternalTool::Wezterm, ExternalTool::Delta,
+            ExternalTool::Claude, ExternalTool::Opencode, ExternalTool::Hammerspoon,
+        ],
+    )]
     fn selected_tools_cases(#[case] config: Config, #[case] expected: &[ExternalTool]) {
         assert_eq!(selected_tools(&config), expected);
     }
@@ -4,4 +4,5 @@ pub mod config;
 pub mod doctor;
 pub mod gh;
 pub mod name_branch;
+pub mod notify;
 pub mod wm;
@@ -1,7 +1,5 @@
-mod backend;
 mod error;
 
-pub use backend::{Backend, detect_backend};
 pub use error::{Error, Result};
 
 use std::io::IsTerminal;
@@ -9,6 +7,10 @@ use std::io::IsTerminal;
 use clap::Args;
 use indicatif::{ProgressBar, ProgressStyle};
 use indoc::formatdoc;
+use sha2::{Digest, Sha256};
+
+use crate::infra::llm::{self, Backend, LlmTask};
+use crate::shared::hex;
 
 /// Branch prefix for new branches
 pub const BRANCH_PREFIX: &str = "fohte/";
@@ -21,7 +23,7 @@ pub struct NameBranchArgs {
 
 impl NameBranchArgs {
     pub fn run(&self) -> Result<()> {
-        let backend = detect_backend();
+        let backend = llm::backend_for(LlmTask::BranchName);
 
         let spinner = if std::io::stderr().is_terminal() {
             let s = ProgressBar::new_spinner();
@@ -48,7 +50,8 @@ impl NameBranchArgs {
     }
 }
 
-/// Generate a branch name from a description using the specified backend.
+/// Generate a branch name from a description using the specified backend,
+/// or derive one offline (see `offline_branch_name`) when it fails.
 ///
 /// Returns only the generated name part (without prefix).
 /// The caller is responsible for adding the prefix if needed.
@@ -57,7 +60,8 @@ pub fn generate_branch_name(
     backend: &(impl Backend + ?Sized),
 ) -> Result<String> {
     let prompt = build_prompt(description);
-    let name = backend.generate(&prompt)?;
+    let name =
+        llm::generate_with_fallback(backend, &prompt, || Some(offline_branch_name(description)))?;
     let name = sanitize_branch_name(&name);
     validate_branch_name(&name)?;
     Ok(name)
@@ -80,6 +84,23 @@ fn build_prompt(description: &str) -> String {
     }
 }
 
+/// Branch name derived from the description alone, for when no LLM
+/// backend answers: its first four ASCII words, or `task-<hash>` when it
+/// has none (e.g. a description written in Japanese).
+fn offline_branch_name(description: &str) -> String {
+    let words: Vec<String> = sanitize_branch_name(description)
+        .split('-')
+        .take(4)
+        .map(str::to_string)
+        .collect();
+    let name = words.join("-");
+    if !name.is_empty() {
+        return name;
+    }
+    let digest = Sha256::digest(description.as_bytes());
+    format!("task-{}", &hex::encode(&digest)[..8])
+}
+
 fn sanitize_branch_name(name: &str) -> String {
     let name = name.trim().to_lowercase();
     let mut result = String::with_capacity(name.len());
@@ -146,6 +167,35 @@ mod tests {
         assert_eq!(sanitize_branch_name(input), expected);
     }
 
+    #[rstest]
+    #[case::first_words("Fix the login redirect loop on Safari", "fix-the-login-redirect")]
+    #[case::short("bump deps", "bump-deps")]
+    #[case::non_ascii("ログインのリダイレクトを直す", "task-")]
+    fn test_offline_branch_name(#[case] description: &str, #[case] expected_prefix: &str) {
+        let name = offline_branch_name(description);
+        assert_eq!(
+            (
+                name.starts_with(expected_prefix),
+                validate_branch_name(&name).is_ok(),
+                offline_branch_name(description) == name,
+            ),
+            (true, true, true)
+        );
+    }
+
+    #[test]
+    fn test_generate_branch_name_falls_back_offline() {
+        struct FailingBackend;
+        impl Backend for FailingBackend {
+            fn generate(&self, _prompt: &str) -> anyhow::Result<String> {
+                anyhow::bail!("no LLM backend is available")
+            }
+        }
+
+        let name = generate_branch_name("Fix login redirect", &FailingBackend).unwrap();
+        assert_eq!(name, "fix-login-redirect");
+    }
+
     #[rstest]
     #[case::valid("fix-login", true)]
     #[case::valid_with_numbers("fix123", true)]
@@ -0,0 +1,129 @@
+//! `a notify` -- focus mode and the notifications it held back.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, TimeDelta, Utc};
+use clap::{Args, Subcommand};
+
+use crate::commands::cc::auto_pause;
+use crate::shared::config;
+use crate::shared::notify::{self, held, quiet};
+
+#[derive(Subcommand, Clone, PartialEq, Eq)]
+pub enum NotifyCommands {
+    /// Turn focus mode (do not disturb) on or off, or show its state
+    #[command(subcommand)]
+    Dnd(DndCommands),
+
+    /// Deliver the digest of held notifications now, unless still quiet
+    Flush,
+
+    /// Internal: deliver a coalesced batch once its window closes.
+    #[command(name = "flush-batch", hide = true)]
+    FlushBatch(FlushBatchArgs),
+}
+
+#[derive(Subcommand, Clone, PartialEq, Eq)]
+pub enum DndCommands {
+    /// Quiet notifications until turned off, or for a while
+    On(DndOnArgs),
+
+    /// End focus mode and deliver what it held back
+    Off,
+
+    /// Show whether notifications are quiet and how many are held
+    Status,
+}
+
+#[derive(Args, Clone, PartialEq, Eq)]
+pub struct DndOnArgs {
+    /// How long to stay quiet (e.g. "30m", "1h30m"); until `dnd off` if omitted
+    #[arg(long = "for", value_name = "DURATION")]
+    pub duration: Option<String>,
+}
+
+#[derive(Args, Clone, PartialEq, Eq)]
+pub struct FlushBatchArgs {
+    /// When the batch was opened, identifying it (RFC 3339)
+    #[arg(long)]
+    pub started_at: DateTime<Utc>,
+}
+
+impl NotifyCommands {
+    pub fn run(&self) -> Result<()> {
+        let config = config::load_config().unwrap_or_default();
+        match self {
+            Self::Dnd(DndCommands::On(args)) => run_dnd_on(args),
+            Self::Dnd(DndCommands::Off) => {
+                if !quiet::clear_dnd()? {
+                    println!("Focus mode was not on");
+                }
+                if notify::flush_held(&config.notification)? {
+                    println!("Delivered held notifications");
+                }
+                Ok(())
+            }
+            Self::Dnd(DndCommands::Status) => run_dnd_status(&config.notification),
+            // Spawned detached, so there is nobody to report to but the log
+            Self::FlushBatch(args) => {
+                if let Err(e) = notify::flush_batch(args.started_at, &config) {
+                    tracing::warn!(event = "notify.batch.flush_failed", error = %e);
+                }
+                Ok(())
+            }
+            Self::Flush => {
+                if !notify::flush_held(&config.notification)? {
+                    println!("Nothing delivered (no held notifications, or still quiet)");
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn run_dnd_on(args: &DndOnArgs) -> Result<()> {
+    let now = Utc::now();
+    let until = match &args.duration {
+        Some(duration) => {
+            let duration = auto_pause::parse_duration(duration)
+                .with_context(|| format!("invalid --for `{duration}`"))?;
+            Some(now + TimeDelta::from_std(duration)?)
+        }
+        None => None,
+    };
+    quiet::save_dnd(&quiet::DndState { since: now, until })?;
+    match until {
+        Some(until) => println!(
+            "Focus mode on until {}",
+            until.with_timezone(&Local).format("%H:%M")
+        ),
+        None => println!("Focus mode on until `a notify dnd off`"),
+    }
+    Ok(())
+}
+
+fn run_dnd_status(config: &config::NotificationConfig) -> Result<()> {
+    let now = Utc::now();
+    match quiet::load_dnd().filter(|state| state.is_active(now)) {
+        Some(quiet::DndState {
+            until: Some(until), ..
+        }) => println!(
+            "Focus mode: on until {}",
+            until.with_timezone(&Local).format("%H:%M")
+        ),
+        Some(_) => println!("Focus mode: on until `a notify dnd off`"),
+        None => println!("Focus mode: off"),
+    }
+    if quiet::in_quiet_hours(Local::now().time(), &config.quiet_hours) {
+        println!("Quiet hours: now");
+    }
+
+    let held = held::pending()?;
+    if let Some(oldest) = held.first() {
+        println!(
+            "Held: {} notification(s) since {}",
+            held.len(),
+            oldest.held_at.with_timezone(&Local).format("%H:%M")
+        );
+    }
+    Ok(())
+}
@@ -19,7 +19,7 @@ use crate::infra::git::get_merge_status_for_repo;
 use crate::infra::git::{github_owner_and_repo, merge_status_from_git, merge_status_from_pr};
 use crate::infra::github::{BranchPrQuery, GitHubClient};
 use crate::shared::active_session::{
-    ActivityProbe, NoActivityProbe, TmuxActivityProbe, contains_active_session,
+    ActivityProbe, MultiplexerActivityProbe, NoActivityProbe, contains_active_session,
 };
 use crate::shared::config::load_config;
 use crate::shared::merge_notify::notify_delegator_of_merge;
@@ -308,7 +308,13 @@ fn apply_active_session_protection(
     // wm clean may be invoked from a non-tmux context (cron, plain shell);
     // fall back to the no-op probe so we never block on tmux calls.
     if std::env::var_os("TMUX").is_some() {
-        protect_active_worktrees(to_delete, to_keep, &sessions, timeout, &TmuxActivityProbe);
+        protect_active_worktrees(
+            to_delete,
+            to_keep,
+            &sessions,
+            timeout,
+            &MultiplexerActivityProbe,
+        );
     } else {
         protect_active_worktrees(to_delete, to_keep, &sessions, timeout, &NoActivityProbe);
     }
@@ -649,6 +655,13 @@ mod tests {
             pending_permission_agent_ids: BTreeSet::new(),
             read_at: None,
             sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
         }
     }
 
@@ -15,6 +15,7 @@ pub enum ExternalTool {
     Git,
     Gh,
     Tmux,
+    Zellij,
     Nvim,
     Wezterm,
     Ghostty,
@@ -49,6 +50,7 @@ impl ExternalTool {
         Self::Git,
         Self::Gh,
         Self::Tmux,
+        Self::Zellij,
         Self::Nvim,
         Self::Wezterm,
         Self::Ghostty,
@@ -87,6 +89,15 @@ impl ExternalTool {
                 brew_pkg: None,
                 macos_app_path: None,
             },
+            Self::Zellij => Metadata {
+                name: "zellij",
+                binary: "zellij",
+                purpose: "session monitor, worktree windows (multiplexer: zellij)",
+                version_args: &["--version"],
+                macos_only: false,
+                brew_pkg: None,
+                macos_app_path: None,
+            },
             Self::Nvim => Metadata {
                 name: "nvim",
                 binary: "nvim",
@@ -126,7 +137,7 @@ impl ExternalTool {
             Self::Claude => Metadata {
                 name: "claude",
                 binary: "claude",
-                purpose: "Claude Code backend for `a name-branch`",
+                purpose: "claude_code LLM backend (`llm.backends`) and `a cc` sessions",
                 version_args: &["--version"],
                 macos_only: false,
                 brew_pkg: None,
@@ -135,7 +146,7 @@ impl ExternalTool {
             Self::Opencode => Metadata {
                 name: "opencode",
                 binary: "opencode",
-                purpose: "fallback backend for `a name-branch` when claude is unavailable",
+                purpose: "opencode LLM backend (`llm.backends`)",
                 version_args: &["--version"],
                 macos_only: false,
                 brew_pkg: None,
@@ -226,6 +237,7 @@ mod tests {
             "git",
             "gh",
             "tmux",
+            "zellij",
             "nvim",
             "wezterm",
             "ghostty",
@@ -30,6 +30,23 @@ where
     check_output(output)
 }
 
+/// Like [`run_git`], with extra environment variables set for the command
+/// (e.g. `GIT_INDEX_FILE` to stage into a scratch index instead of the
+/// repository's own).
+pub fn run_git_with_env<I, S>(dir: &Path, envs: &[(&str, &OsStr)], args: I) -> Result<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    let mut cmd = git_at(dir);
+    cmd.envs(envs.iter().copied());
+    for arg in args {
+        cmd.arg(arg);
+    }
+    let output = cmd.output().map_err(GitError::SpawnFailed)?;
+    check_output(output)
+}
+
 /// Run a global `git` command (no `-C`).
 pub fn run_git_global<I, S>(args: I) -> Result<String>
 where
@@ -2,7 +2,7 @@
 
 use std::collections::HashMap;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use super::client::GitHubClient;
 use super::error::{GitHubError, Result};
@@ -20,19 +20,57 @@ pub struct CreatePrParams {
 }
 
 /// PR state from GitHub API.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PrState {
     Open,
     Closed,
     Merged,
 }
 
+/// Review decision of a PR (GraphQL `reviewDecision`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReviewDecision {
+    Approved,
+    ChangesRequested,
+    ReviewRequired,
+}
+
+/// Combined CI state of a PR's head commit (GraphQL `statusCheckRollup`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CheckState {
+    Success,
+    Failure,
+    Error,
+    Pending,
+    Expected,
+}
+
 /// PR information from GitHub API.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PrInfo {
     pub number: u64,
     pub state: PrState,
     pub url: String,
+    /// Empty in PR caches written before the title was recorded.
+    #[serde(default)]
+    pub title: String,
+    /// Only filled in by the batched GraphQL lookup; always `false` from REST.
+    #[serde(default)]
+    pub draft: bool,
+    /// Only filled in by the batched GraphQL lookup; `None` from REST or
+    /// when the repository requires no review.
+    #[serde(default)]
+    pub review: Option<ReviewDecision>,
+    /// Only filled in by the batched GraphQL lookup; `None` from REST or
+    /// when the head commit has no checks.
+    #[serde(default)]
+    pub checks: Option<CheckState>,
+    /// Review threads not yet resolved, among the first
+    /// `REVIEW_THREADS_LIMIT`. Only filled in by the batched GraphQL lookup.
+    #[serde(default)]
+    pub unresolved_threads: Option<u32>,
 }
 
 /// Parameters for updating a pull request.
@@ -76,6 +114,7 @@ struct PrResponse {
     number: u64,
     state: Option<String>,
     html_url: Option<String>,
+    title: Option<String>,
     merged_at: Option<String>,
 }
 
@@ -151,6 +190,11 @@ impl PrClient for GitHubClient {
             number: pr.number,
             state,
             url,
+            title: pr.title.unwrap_or_default(),
+            draft: false,
+            review: None,
+            checks: None,
+            unresolved_threads: None,
         }))
     }
 }
@@ -159,6 +203,10 @@ impl PrClient for GitHubClient {
 /// GitHub GraphQL API has complexity limits; 50 branches keeps us well within bounds.
 const BATCH_SIZE: usize = 50;
 
+/// Review threads fetched per PR to count the unresolved ones. Kept small
+/// since it multiplies the query's node cost by up to `BATCH_SIZE`.
+const REVIEW_THREADS_LIMIT: usize = 50;
+
 impl GitHubClient {
     /// Fetch PR status for multiple repo/branch combinations in a single GraphQL call.
     ///
@@ -233,7 +281,7 @@ impl GitHubClient {
                 );
 
                 branch_parts.push(format!(
-                    "{branch_alias}: pullRequests(headRefName: ${branch_var}, states: [OPEN, CLOSED, MERGED], first: 1, orderBy: {{field: CREATED_AT, direction: DESC}}) {{ nodes {{ number state url mergedAt }} }}"
+                    "{branch_alias}: pullRequests(headRefName: ${branch_var}, states: [OPEN, CLOSED, MERGED], first: 1, orderBy: {{field: CREATED_AT, direction: DESC}}) {{ nodes {{ number state url title mergedAt isDraft reviewDecision reviewThreads(first: {REVIEW_THREADS_LIMIT}) {{ nodes {{ isResolved }} }} commits(last: 1) {{ nodes {{ commit {{ statusCheckRollup {{ state }} }} }} }} }} }}"
                 ));
                 branch_alias_map
                     .insert(branch_alias, (owner.clone(), repo.clone(), branch.clone()));
@@ -313,5 +361,84 @@ fn parse_pr_node(node: &serde_json::Value) -> Option<PrInfo> {
         }
     };
 
-    Some(PrInfo { number, state, url })
+    let review = node
+        .get("reviewDecision")
+        .and_then(|v| serde_json::from_value(v.clone()).ok());
+    let checks = node
+        .pointer("/commits/nodes/0/commit/statusCheckRollup/state")
+        .and_then(|v| serde_json::from_value(v.clone()).ok());
+    let unresolved_threads = node
+        .pointer("/reviewThreads/nodes")
+        .and_then(|nodes| nodes.as_array())
+        .map(|nodes| {
+            nodes
+                .iter()
+                .filter(|thread| thread.get("isResolved").and_then(|v| v.as_bool()) == Some(false))
+                .count() as u32
+        });
+
+    Some(PrInfo {
+        number,
+        state,
+        url,
+        title: node
+            .get("title")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string(),
+        draft: node
+            .get("isDraft")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        review,
+        checks,
+        unresolved_threads,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_pr_node_reads_review_and_checks() {
+        let node = json!({
+            "number": 42,
+            "state": "OPEN",
+            "url": "https://github.com/o/r/pull/42",
+            "title": "Fix login redirect",
+            "mergedAt": null,
+            "isDraft": true,
+            "reviewDecision": "CHANGES_REQUESTED",
+            "reviewThreads": {"nodes": [{"isResolved": false}, {"isResolved": true}, {"isResolved": false}]},
+            "commits": {"nodes": [{"commit": {"statusCheckRollup": {"state": "FAILURE"}}}]}
+        });
+        let info = parse_pr_node(&node).unwrap();
+        assert_eq!(info.number, 42);
+        assert_eq!(info.title, "Fix login redirect");
+        assert_eq!(info.state, PrState::Open);
+        assert!(info.draft);
+        assert_eq!(info.review, Some(ReviewDecision::ChangesRequested));
+        assert_eq!(info.checks, Some(CheckState::Failure));
+        assert_eq!(info.unresolved_threads, Some(2));
+    }
+
+    #[test]
+    fn parse_pr_node_tolerates_missing_review_and_checks() {
+        let node = json!({
+            "number": 7,
+            "state": "MERGED",
+            "url": "https://github.com/o/r/pull/7",
+            "mergedAt": "2026-01-01T00:00:00Z",
+            "reviewDecision": null,
+            "commits": {"nodes": [{"commit": {"statusCheckRollup": null}}]}
+        });
+        let info = parse_pr_node(&node).unwrap();
+        assert_eq!(info.state, PrState::Merged);
+        assert!(!info.draft);
+        assert_eq!(info.review, None);
+        assert_eq!(info.checks, None);
+        assert_eq!(info.unresolved_threads, None);
+    }
 }
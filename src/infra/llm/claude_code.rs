@@ -0,0 +1,24 @@
+use std::time::Duration;
+
+use anyhow::Result;
+
+use super::{Backend, run_cli};
+use crate::infra::external_tool::ExternalTool;
+
+/// Claude Code backend using `claude --model <model> --print`
+pub struct ClaudeCode {
+    pub model: String,
+    pub timeout: Duration,
+}
+
+impl ClaudeCode {
+    pub const DEFAULT_MODEL: &str = "haiku";
+}
+
+impl Backend for ClaudeCode {
+    fn generate(&self, prompt: &str) -> Result<String> {
+        let mut command = ExternalTool::Claude.command();
+        command.args(["--model", &self.model, "--print"]);
+        run_cli(command, Some(prompt), self.timeout, "claude")
+    }
+}
@@ -0,0 +1,428 @@
+//! Registry of LLM backends for short generation tasks (session titles,
+//! branch names).
+//!
+//! [`backend_for`] resolves the backends configured for a task (`llm` in
+//! the config) into one [`Backend`] that tries them in order, skipping
+//! backends that are not installed or not configured, each bounded by the
+//! task's timeout. Callers pass its result through
+//! [`generate_with_fallback`] with a deterministic offline answer, so a
+//! machine with no backend at all still gets a result.
+
+mod claude_code;
+mod openai;
+mod opencode;
+
+pub use claude_code::ClaudeCode;
+pub use openai::OpenAi;
+pub use opencode::OpenCode;
+
+use std::io::{Read, Write};
+use std::process::{Command, ExitStatus, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, bail};
+
+use crate::commands::cc::auto_pause::parse_duration;
+use crate::shared::command::is_command_available;
+use crate::shared::config::{LlmBackendKind, LlmConfig, LlmTaskConfig, load_config};
+
+/// Fallback for `llm.timeout` when it is unparseable.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Upper bound on how long we sleep between exit checks of a CLI backend.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Backend trait for generating text from a prompt.
+///
+/// This abstraction allows swapping LLM backends (Claude Code, OpenCode,
+/// OpenAI-compatible endpoints, ...).
+pub trait Backend {
+    fn generate(&self, prompt: &str) -> Result<String>;
+}
+
+/// A generation task, selecting its overrides in `llm.tasks`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LlmTask {
+    Title,
+    BranchName,
+}
+
+impl LlmTask {
+    fn config(self, config: &LlmConfig) -> &LlmTaskConfig {
+        match self {
+            Self::Title => &config.tasks.title,
+            Self::BranchName => &config.tasks.branch_name,
+        }
+    }
+}
+
+/// The backends configured for `task`, tried in order. Falls back to the
+/// default configuration when the config file cannot be loaded.
+pub fn backend_for(task: LlmTask) -> Box<dyn Backend> {
+    let config = load_config().map(|c| c.llm).unwrap_or_default();
+    let runtime = tokio::runtime::Handle::try_current().ok();
+    Box::new(resolve(&config, task, is_command_available, runtime))
+}
+
+/// Builds the chain for `task` from `config`; `is_available` reports
+/// whether a CLI is installed, and `runtime` is what HTTP backends send
+/// their requests on (they are skipped without one).
+fn resolve(
+    config: &LlmConfig,
+    task: LlmTask,
+    is_available: impl Fn(&str) -> bool,
+    runtime: Option<tokio::runtime::Handle>,
+) -> Chain {
+    let task_config = task.config(config);
+    let timeout = task_config.timeout.as_deref().unwrap_or(&config.timeout);
+    let timeout = parse_duration(timeout).unwrap_or(DEFAULT_TIMEOUT);
+    let model = |task_model: &Option<String>, own: &Option<String>, default: Option<&str>| {
+        task_model
+            .clone()
+            .or_else(|| own.clone())
+            .or_else(|| default.map(str::to_string))
+    };
+
+    let kinds = task_config.backends.as_ref().unwrap_or(&config.backends);
+    let mut backends: Vec<(&'static str, Box<dyn Backend>)> = Vec::new();
+    for kind in kinds {
+        match kind {
+            LlmBackendKind::ClaudeCode if is_available("claude") => backends.push((
+                "claude_code",
+                Box::new(ClaudeCode {
+                    model: model(
+                        &task_config.claude_code.model,
+                        &config.claude_code.model,
+                        Some(ClaudeCode::DEFAULT_MODEL),
+                    )
+                    .unwrap_or_default(),
+                    timeout,
+                }),
+            )),
+            LlmBackendKind::Opencode if is_available("opencode") => backends.push((
+                "opencode",
+                Box::new(OpenCode {
+                    model: model(
+                        &task_config.opencode.model,
+                        &config.opencode.model,
+                        Some(OpenCode::DEFAULT_MODEL),
+                    )
+                    .unwrap_or_default(),
+                    timeout,
+                }),
+            )),
+            LlmBackendKind::Openai => {
+                let openai = &config.openai;
+                if let (Some(base_url), Some(model), Some(runtime)) = (
+                    openai.base_url.clone(),
+                    model(&task_config.openai.model, &openai.model, None),
+                    runtime.clone(),
+                ) {
+                    backends.push((
+                        "openai",
+                        Box::new(OpenAi {
+                            base_url,
+                            model,
+                            api_key: openai
+                                .api_key_env
+                                .as_deref()
+                                .and_then(|name| std::env::var(name).ok()),
+                            timeout,
+                            runtime,
+                        }),
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+    Chain { task, backends }
+}
+
+/// Tries each backend in order, returning the first answer.
+struct Chain {
+    task: LlmTask,
+    backends: Vec<(&'static str, Box<dyn Backend>)>,
+}
+
+impl Backend for Chain {
+    fn generate(&self, prompt: &str) -> Result<String> {
+        let mut errors = Vec::new();
+        for (name, backend) in &self.backends {
+            match backend.generate(prompt) {
+                Ok(text) => return Ok(text),
+                Err(e) => {
+                    tracing::warn!(
+                        event = "llm.backend_failed",
+                        task = ?self.task,
+                        backend = name,
+                        error = format!("{e:#}"),
+                    );
+                    errors.push(format!("{name}: {e:#}"));
+                }
+            }
+        }
+        if errors.is_empty() {
+            bail!("no LLM backend is available (see `llm.backends`)");
+        }
+        bail!("every LLM backend failed: {}", errors.join("; "))
+    }
+}
+
+/// Runs `prompt` through `backend`, falling back to `offline` when it
+/// fails, which is then logged. Fails only when there is no fallback
+/// either.
+pub fn generate_with_fallback(
+    backend: &(impl Backend + ?Sized),
+    prompt: &str,
+    offline: impl FnOnce() -> Option<String>,
+) -> Result<String> {
+    match backend.generate(prompt) {
+        Ok(text) => Ok(text),
+        Err(e) => match offline() {
+            Some(text) => {
+                tracing::info!(event = "llm.offline_fallback", error = format!("{e:#}"));
+                Ok(text)
+            }
+            None => Err(e),
+        },
+    }
+}
+
+/// Runs a CLI backend's `command`, feeding it `stdin` when given, and
+/// returns its stdout. The process is killed once `timeout` elapses.
+fn run_cli(
+    mut command: Command,
+    stdin: Option<&str>,
+    timeout: Duration,
+    command_name: &str,
+) -> Result<String> {
+    let mut child = command
+        .stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to spawn {command_name}"))?;
+
+    if let (Some(input), Some(mut pipe)) = (stdin, child.stdin.take()) {
+        pipe.write_all(input.as_bytes())
+            .context("Failed to write to stdin")?;
+    }
+    // Drain both pipes on their own threads so a chatty backend cannot
+    // block on a full pipe while we wait for it to exit.
+    let stdout = child.stdout.take().map(read_to_end_in_background);
+    let stderr = child.stderr.take().map(read_to_end_in_background);
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            bail!("{command_name} timed out after {timeout:?}");
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    let collect = |handle: Option<thread::JoinHandle<Vec<u8>>>| {
+        handle
+            .and_then(|h| h.join().ok())
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+            .unwrap_or_default()
+    };
+    let stdout = collect(stdout);
+    check_command_status(status, &collect(stderr), command_name)?;
+    extract_first_line(&stdout, command_name)
+}
+
+fn read_to_end_in_background(mut pipe: impl Read + Send + 'static) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = pipe.read_to_end(&mut buf);
+        buf
+    })
+}
+
+/// Check command exit status and return error if failed.
+fn check_command_status(status: ExitStatus, stderr: &str, command_name: &str) -> Result<()> {
+    if !status.success() {
+        bail!("{command_name} exited with status {status}: {stderr}");
+    }
+    Ok(())
+}
+
+/// Extract the first non-empty line from a backend's answer.
+fn extract_first_line(text: &str, command_name: &str) -> Result<String> {
+    let result = text
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("")
+        .to_string();
+
+    if result.is_empty() {
+        bail!("{command_name} returned empty output");
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::config::{LlmOpenAiConfig, LlmTaskBackendConfig};
+    use rstest::rstest;
+
+    /// Backend that returns a fixed result.
+    struct StubBackend(std::result::Result<&'static str, &'static str>);
+
+    impl Backend for StubBackend {
+        fn generate(&self, _prompt: &str) -> Result<String> {
+            self.0.map(str::to_string).map_err(|e| anyhow::anyhow!(e))
+        }
+    }
+
+    fn chain_names(chain: &Chain) -> Vec<&'static str> {
+        chain.backends.iter().map(|(name, _)| *name).collect()
+    }
+
+    fn openai_config() -> LlmConfig {
+        LlmConfig {
+            openai: LlmOpenAiConfig {
+                base_url: Some("http://localhost:11434/v1".to_string()),
+                model: Some("llama3.2".to_string()),
+                api_key_env: None,
+            },
+            ..Default::default()
+        }
+    }
+
+    #[rstest]
+    #[case::everything_installed(true, openai_config(), &["claude_code", "opencode", "openai"])]
+    #[case::no_cli(false, openai_config(), &["openai"])]
+    #[case::openai_unconfigured(true, LlmConfig::default(), &["claude_code", "opencode"])]
+    #[case::nothing(false, LlmConfig::default(), &[])]
+    #[tokio::test]
+    async fn test_resolve_skips_unavailable_backends(
+        #[case] installed: bool,
+        #[case] config: LlmConfig,
+        #[case] expected: &[&str],
+    ) {
+        let runtime = Some(tokio::runtime::Handle::current());
+        let chain = resolve(&config, LlmTask::Title, |_| installed, runtime);
+        assert_eq!(chain_names(&chain), expected);
+    }
+
+    #[test]
+    fn test_resolve_skips_http_backends_without_a_runtime() {
+        let chain = resolve(&openai_config(), LlmTask::Title, |_| true, None);
+        assert_eq!(chain_names(&chain), ["claude_code", "opencode"]);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_applies_task_overrides() {
+        let mut config = openai_config();
+        config.openai.model = None;
+        config.tasks.branch_name = LlmTaskConfig {
+            backends: Some(vec![LlmBackendKind::Openai, LlmBackendKind::ClaudeCode]),
+            openai: LlmTaskBackendConfig {
+                model: Some("qwen2.5".to_string()),
+            },
+            ..Default::default()
+        };
+        // Scoped to claude_code, so it does not give openai a model
+        config.tasks.title = LlmTaskConfig {
+            claude_code: LlmTaskBackendConfig {
+                model: Some("sonnet".to_string()),
+            },
+            ..Default::default()
+        };
+
+        let runtime = Some(tokio::runtime::Handle::current());
+        let branch_chain = resolve(&config, LlmTask::BranchName, |_| true, runtime.clone());
+        let title_chain = resolve(&config, LlmTask::Title, |_| true, runtime);
+
+        assert_eq!(
+            (chain_names(&branch_chain), chain_names(&title_chain)),
+            (
+                vec!["openai", "claude_code"],
+                vec!["claude_code", "opencode"]
+            )
+        );
+    }
+
+    #[test]
+    fn test_chain_returns_first_answer() {
+        let chain = Chain {
+            task: LlmTask::Title,
+            backends: vec![
+                ("failing", Box::new(StubBackend(Err("boom")))),
+                ("working", Box::new(StubBackend(Ok("answer")))),
+                ("unused", Box::new(StubBackend(Ok("other")))),
+            ],
+        };
+        assert_eq!(chain.generate("prompt").unwrap(), "answer");
+    }
+
+    #[test]
+    fn test_empty_chain_fails() {
+        let chain = Chain {
+            task: LlmTask::Title,
+            backends: Vec::new(),
+        };
+        assert!(chain.generate("prompt").is_err());
+    }
+
+    #[rstest]
+    #[case::backend_answers(Ok("answer"), Some("offline"), Some("answer"))]
+    #[case::falls_back(Err("boom"), Some("offline"), Some("offline"))]
+    #[case::no_fallback(Err("boom"), None, None)]
+    fn test_generate_with_fallback(
+        #[case] answer: std::result::Result<&'static str, &'static str>,
+        #[case] offline: Option<&str>,
+        #[case] expected: Option<&str>,
+    ) {
+        let result = generate_with_fallback(&StubBackend(answer), "prompt", || {
+            offline.map(str::to_string)
+        });
+        assert_eq!(result.ok().as_deref(), expected);
+    }
+
+    #[test]
+    fn test_run_cli_returns_first_line() {
+        let mut command = Command::new("sh");
+        command.args(["-c", "cat; echo; echo second"]);
+        let output = run_cli(command, Some("\n  first  "), Duration::from_secs(5), "sh");
+        assert_eq!(output.unwrap(), "first");
+    }
+
+    #[test]
+    fn test_run_cli_kills_on_timeout() {
+        let mut command = Command::new("sleep");
+        command.arg("5");
+        let started = Instant::now();
+        let result = run_cli(command, None, Duration::from_millis(100), "sleep");
+        assert_eq!(
+            (result.is_err(), started.elapsed() < Duration::from_secs(5)),
+            (true, true)
+        );
+    }
+
+    #[test]
+    fn test_run_cli_reports_failure() {
+        let mut command = Command::new("sh");
+        command.args(["-c", "echo oops >&2; exit 3"]);
+        let err = run_cli(command, None, Duration::from_secs(5), "sh").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "sh exited with status exit status: 3: oops\n"
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::{Backend, extract_first_line};
+
+/// Backend for an OpenAI-compatible `POST <base_url>/chat/completions`
+/// endpoint (OpenAI itself, Ollama, llama.cpp's server, ...).
+pub struct OpenAi {
+    /// Base URL up to the API version, e.g. `http://localhost:11434/v1`.
+    pub base_url: String,
+    pub model: String,
+    /// Sent as a bearer token when set.
+    pub api_key: Option<String>,
+    pub timeout: Duration,
+    /// Runtime the request is sent on.
+    pub runtime: tokio::runtime::Handle,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: [ChatMessage<'a>; 1],
+    temperature: f32,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatMessage<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatResponse {
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChoice {
+    message: ChatAnswer,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatAnswer {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+impl OpenAi {
+    fn endpoint(&self) -> String {
+        format!("{}/chat/completions", self.base_url.trim_end_matches('/'))
+    }
+}
+
+impl Backend for OpenAi {
+    fn generate(&self, prompt: &str) -> Result<String> {
+        let url = self.endpoint();
+        let body = serde_json::to_string(&ChatRequest {
+            model: &self.model,
+            messages: [ChatMessage {
+                role: "user",
+                content: prompt,
+            }],
+            temperature: 0.0,
+        })?;
+        let request = async {
+            let mut request = reqwest::Client::builder()
+                .timeout(self.timeout)
+                .build()?
+                .post(&url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body);
+            if let Some(key) = &self.api_key {
+                request = request.bearer_auth(key);
+            }
+            anyhow::Ok(request.send().await?.error_for_status()?.json().await?)
+        };
+        let response: ChatResponse = tokio::task::block_in_place(|| self.runtime.block_on(request))
+            .with_context(|| format!("Failed to POST to {url}"))?;
+
+        let content = response
+            .choices
+            .into_iter()
+            .next()
+            .and_then(|choice| choice.message.content)
+            .unwrap_or_default();
+        extract_first_line(&content, "openai")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{bearer_token, body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn backend(server: &MockServer, api_key: Option<&str>) -> OpenAi {
+        OpenAi {
+            base_url: format!("{}/v1/", server.uri()),
+            model: "llama3.2".to_string(),
+            api_key: api_key.map(str::to_string),
+            timeout: Duration::from_secs(5),
+            runtime: tokio::runtime::Handle::current(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generate_returns_the_first_line_of_the_answer() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(bearer_token("secret"))
+            .and(body_partial_json(serde_json::json!({
+                "model": "llama3.2",
+                "messages": [{"role": "user", "content": "name it"}],
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "choices": [{"message": {"role": "assistant", "content": "\nfix-login\nmore"}}],
+            })))
+            .mount(&server)
+            .await;
+
+        let backend = backend(&server, Some("secret"));
+        let answer = tokio::task::spawn_blocking(move || backend.generate("name it"))
+            .await
+            .unwrap();
+
+        assert_eq!(answer.unwrap(), "fix-login");
+    }
+
+    #[tokio::test]
+    async fn test_generate_fails_on_http_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let backend = backend(&server, None);
+        let answer = tokio::task::spawn_blocking(move || backend.generate("name it"))
+            .await
+            .unwrap();
+
+        assert!(answer.is_err());
+    }
+}
@@ -0,0 +1,24 @@
+use std::time::Duration;
+
+use anyhow::Result;
+
+use super::{Backend, run_cli};
+use crate::infra::external_tool::ExternalTool;
+
+/// OpenCode backend using `opencode run -m <model>`
+pub struct OpenCode {
+    pub model: String,
+    pub timeout: Duration,
+}
+
+impl OpenCode {
+    pub const DEFAULT_MODEL: &str = "opencode/glm-4.7-free";
+}
+
+impl Backend for OpenCode {
+    fn generate(&self, prompt: &str) -> Result<String> {
+        let mut command = ExternalTool::Opencode.command();
+        command.args(["run", "-m", &self.model, prompt]);
+        run_cli(command, None, self.timeout, "opencode")
+    }
+}
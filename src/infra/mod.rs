@@ -2,6 +2,8 @@ pub mod external_tool;
 pub mod git;
 pub mod github;
 pub mod launchd;
+pub mod llm;
+pub mod multiplexer;
 pub mod notification;
 pub mod process;
 pub mod tmux;
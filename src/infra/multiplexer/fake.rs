@@ -0,0 +1,143 @@
+//! In-memory `Multiplexer` for tests: panes and options are whatever the
+//! test set up, and focus changes are recorded.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+use anyhow::{Result, bail};
+
+use super::{Multiplexer, PaneInfo, WindowSpec};
+
+#[derive(Default)]
+pub struct FakeMultiplexer {
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    /// Alive panes and the window each is in; ordered so pane order is
+    /// deterministic.
+    panes: BTreeMap<String, String>,
+    pane_options: HashMap<(String, String), String>,
+    window_options: HashMap<(String, String), String>,
+    focused: Vec<String>,
+}
+
+impl FakeMultiplexer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_pane(self, pane_id: &str, window_id: &str) -> Self {
+        self.state
+            .lock()
+            .unwrap()
+            .panes
+            .insert(pane_id.to_string(), window_id.to_string());
+        self
+    }
+
+    /// Panes passed to `focus_pane`, in call order.
+    pub fn focused(&self) -> Vec<String> {
+        self.state.lock().unwrap().focused.clone()
+    }
+}
+
+impl Multiplexer for FakeMultiplexer {
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn current_pane(&self) -> Option<PaneInfo> {
+        None
+    }
+
+    /// Adds a window identified by `spec.window_name` with one pane,
+    /// `<window_name>.1`.
+    fn create_window(&self, spec: &WindowSpec) -> Result<()> {
+        self.state.lock().unwrap().panes.insert(
+            format!("{}.1", spec.window_name),
+            spec.window_name.to_string(),
+        );
+        Ok(())
+    }
+
+    fn focus_pane(&self, pane_id: &str) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if !state.panes.contains_key(pane_id) {
+            bail!("can't find pane: {pane_id}");
+        }
+        state.focused.push(pane_id.to_string());
+        Ok(())
+    }
+
+    fn is_pane_alive(&self, pane_id: &str) -> Option<bool> {
+        Some(self.state.lock().unwrap().panes.contains_key(pane_id))
+    }
+
+    fn pane_option(&self, pane_id: &str, option: &str) -> Option<String> {
+        self.state
+            .lock()
+            .unwrap()
+            .pane_options
+            .get(&(pane_id.to_string(), option.to_string()))
+            .cloned()
+    }
+
+    fn set_pane_option(&self, pane_id: &str, option: &str, value: &str) -> Result<()> {
+        self.state
+            .lock()
+            .unwrap()
+            .pane_options
+            .insert((pane_id.to_string(), option.to_string()), value.to_string());
+        Ok(())
+    }
+
+    fn window_of_pane(&self, pane_id: &str) -> Option<String> {
+        self.state.lock().unwrap().panes.get(pane_id).cloned()
+    }
+
+    fn window_option(&self, window_id: &str, option: &str) -> Option<String> {
+        self.state
+            .lock()
+            .unwrap()
+            .window_options
+            .get(&(window_id.to_string(), option.to_string()))
+            .cloned()
+    }
+
+    fn set_window_option(&self, window_id: &str, option: &str, value: &str) -> Result<()> {
+        self.state.lock().unwrap().window_options.insert(
+            (window_id.to_string(), option.to_string()),
+            value.to_string(),
+        );
+        Ok(())
+    }
+
+    fn window_pane_options(&self, window_id: &str, option: &str) -> Vec<String> {
+        let state = self.state.lock().unwrap();
+        state
+            .panes
+            .iter()
+            .filter(|(_, window)| *window == window_id)
+            .filter_map(|(pane, _)| {
+                state
+                    .pane_options
+                    .get(&(pane.clone(), option.to_string()))
+                    .cloned()
+            })
+            .collect()
+    }
+
+    fn refresh_status(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn capture_pane(&self, _pane_id: &str) -> Option<String> {
+        None
+    }
+
+    fn open_window_command(&self, _name: &str, _command: &str) -> Option<String> {
+        None
+    }
+}
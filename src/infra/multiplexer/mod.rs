@@ -0,0 +1,187 @@
+//! Terminal multiplexer abstraction.
+//!
+//! The session tooling (`cc new`, `cc focus`, the window status options, the
+//! `cc watch` TUI, the sweep activity probe) drives the multiplexer through
+//! the `Multiplexer` trait rather than `infra::tmux` directly, so it works
+//! under tmux or Zellij. The backend is picked once per process by
+//! `current()`: `$ARMYKNIFE_MULTIPLEXER`, then the `multiplexer` config key,
+//! then auto-detection (Zellij inside a Zellij session, tmux otherwise).
+//!
+//! Pane and window IDs are opaque strings owned by the backend: `%3` / `@2`
+//! for tmux, `<session>:terminal_3` / `<session>` for Zellij.
+//!
+//! tmux-resurrect integration and `cc snapshot` stay on `infra::tmux`: they
+//! exist to work around tmux's own server lifecycle.
+
+#[cfg(test)]
+mod fake;
+mod tmux;
+mod zellij;
+
+use std::sync::OnceLock;
+
+use anyhow::Result;
+
+#[cfg(test)]
+pub use fake::FakeMultiplexer;
+pub use tmux::Tmux;
+pub use zellij::Zellij;
+
+pub use super::tmux::PaneInfo;
+use crate::shared::config::{self, LayoutNode, MultiplexerChoice};
+use crate::shared::env_var::EnvVars;
+
+/// A `Multiplexer` backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultiplexerKind {
+    Tmux,
+    Zellij,
+}
+
+/// Inputs for `Multiplexer::create_window`.
+pub struct WindowSpec<'a> {
+    /// Session to create the window in; created when missing.
+    pub session: &'a str,
+    /// Working directory of a newly created session.
+    pub session_cwd: &'a str,
+    /// Working directory of the window's panes.
+    pub cwd: &'a str,
+    pub window_name: &'a str,
+    pub layout: &'a LayoutNode,
+    /// Inserted right after `claude` in claude pane commands.
+    pub model: Option<&'a str>,
+    /// Passed to claude pane commands as their initial prompt.
+    pub prompt: Option<&'a str>,
+    /// Exported to every pane of the window.
+    pub env_vars: &'a [(&'a str, &'a str)],
+    /// When true, avoids stealing focus from the attached client.
+    pub background: bool,
+    /// tmux only: turns `automatic-rename` back on after creating the window
+    /// (see `infra::tmux::layout::LayoutCommandsSpec`).
+    pub restore_automatic_rename: bool,
+}
+
+/// Operations the session tooling needs from a terminal multiplexer.
+///
+/// Options are the multiplexer's user options (`@armyknife-...`); backends
+/// without them persist the values themselves.
+pub trait Multiplexer: Send + Sync {
+    /// Whether the multiplexer server is running and responding.
+    fn is_available(&self) -> bool;
+
+    /// The pane the calling process runs in.
+    fn current_pane(&self) -> Option<PaneInfo>;
+
+    /// Creates a window laid out per `spec.layout` and starts each pane's
+    /// command, then switches the attached client to it unless
+    /// `spec.background`.
+    fn create_window(&self, spec: &WindowSpec) -> Result<()>;
+
+    /// Switches the attached client to `pane_id`.
+    fn focus_pane(&self, pane_id: &str) -> Result<()>;
+
+    /// Whether `pane_id` still exists; `None` when the backend cannot tell,
+    /// in which case callers must neither act on the pane as alive nor
+    /// treat it as gone.
+    fn is_pane_alive(&self, pane_id: &str) -> Option<bool>;
+
+    fn pane_option(&self, pane_id: &str, option: &str) -> Option<String>;
+
+    fn set_pane_option(&self, pane_id: &str, option: &str, value: &str) -> Result<()>;
+
+    /// The window containing `pane_id`.
+    fn window_of_pane(&self, pane_id: &str) -> Option<String>;
+
+    fn window_option(&self, window_id: &str, option: &str) -> Option<String>;
+
+    fn set_window_option(&self, window_id: &str, option: &str, value: &str) -> Result<()>;
+
+    /// The non-empty values of `option` across the panes of `window_id`, in
+    /// pane order.
+    fn window_pane_options(&self, window_id: &str, option: &str) -> Vec<String>;
+
+    /// Redraws status bars so option changes show immediately.
+    fn refresh_status(&self) -> Result<()>;
+
+    /// The visible text of `pane_id`, for the activity probe and input-box
+    /// parsing. `None` when the backend cannot read that pane.
+    fn capture_pane(&self, pane_id: &str) -> Option<String>;
+
+    /// A shell command that runs `command` in a new window named `name`,
+    /// usable from outside the multiplexer (e.g. a notification click).
+    /// `None` when the backend cannot tell where to open it.
+    fn open_window_command(&self, name: &str, command: &str) -> Option<String>;
+}
+
+/// Picks the backend: a valid `env_override` wins, then a non-`auto`
+/// config, then Zellij if the process runs inside it.
+pub fn resolve_kind(
+    choice: MultiplexerChoice,
+    env_override: Option<&str>,
+    inside_zellij: bool,
+) -> MultiplexerKind {
+    match env_override {
+        Some("tmux") => return MultiplexerKind::Tmux,
+        Some("zellij") => return MultiplexerKind::Zellij,
+        _ => {}
+    }
+    match choice {
+        MultiplexerChoice::Tmux => MultiplexerKind::Tmux,
+        MultiplexerChoice::Zellij => MultiplexerKind::Zellij,
+        MultiplexerChoice::Auto if inside_zellij => MultiplexerKind::Zellij,
+        MultiplexerChoice::Auto => MultiplexerKind::Tmux,
+    }
+}
+
+/// The backend for this process, resolved on first use.
+pub fn current() -> &'static dyn Multiplexer {
+    static CURRENT: OnceLock<Box<dyn Multiplexer>> = OnceLock::new();
+    CURRENT
+        .get_or_init(|| {
+            let choice = config::load_config()
+                .map(|c| c.multiplexer)
+                .unwrap_or_default();
+            let kind = resolve_kind(
+                choice,
+                EnvVars::load().multiplexer.as_deref(),
+                std::env::var_os("ZELLIJ").is_some(),
+            );
+            match kind {
+                MultiplexerKind::Tmux => Box::new(Tmux),
+                MultiplexerKind::Zellij => Box::new(Zellij::new()),
+            }
+        })
+        .as_ref()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::auto_outside_zellij(MultiplexerChoice::Auto, None, false, MultiplexerKind::Tmux)]
+    #[case::auto_inside_zellij(MultiplexerChoice::Auto, None, true, MultiplexerKind::Zellij)]
+    #[case::config_wins_over_detection(MultiplexerChoice::Tmux, None, true, MultiplexerKind::Tmux)]
+    #[case::config_zellij(MultiplexerChoice::Zellij, None, false, MultiplexerKind::Zellij)]
+    #[case::env_wins_over_config(
+        MultiplexerChoice::Tmux,
+        Some("zellij"),
+        false,
+        MultiplexerKind::Zellij
+    )]
+    #[case::unknown_env_is_ignored(
+        MultiplexerChoice::Auto,
+        Some("screen"),
+        true,
+        MultiplexerKind::Zellij
+    )]
+    fn resolve_kind_cases(
+        #[case] choice: MultiplexerChoice,
+        #[case] env_override: Option<&str>,
+        #[case] inside_zellij: bool,
+        #[case] expected: MultiplexerKind,
+    ) {
+        assert_eq!(resolve_kind(choice, env_override, inside_zellij), expected);
+    }
+}
@@ -0,0 +1,91 @@
+//! tmux backend: a thin adapter over the `infra::tmux` free functions.
+
+use anyhow::{Context, Result};
+
+use super::{Multiplexer, PaneInfo, WindowSpec};
+use crate::infra::tmux;
+
+pub struct Tmux;
+
+impl Multiplexer for Tmux {
+    fn is_available(&self) -> bool {
+        tmux::is_server_available()
+    }
+
+    fn current_pane(&self) -> Option<PaneInfo> {
+        tmux::get_pane_info_by_pid(std::process::id())
+    }
+
+    fn create_window(&self, spec: &WindowSpec) -> Result<()> {
+        tmux::ensure_session(spec.session, spec.session_cwd)
+            .context("Failed to ensure tmux session")?;
+
+        tmux::layout::build_layout(tmux::layout::LayoutSpec {
+            common: tmux::layout::TmuxSessionSpec {
+                session: spec.session,
+                cwd: spec.cwd,
+                model: spec.model,
+                prompt: spec.prompt,
+                env_vars: spec.env_vars,
+                background: spec.background,
+            },
+            window_name: spec.window_name,
+            layout: spec.layout,
+            restore_automatic_rename: spec.restore_automatic_rename,
+        })
+        .context("Failed to create tmux layout")?;
+
+        if !spec.background {
+            tmux::switch_to_session(spec.session).context("Failed to switch to tmux session")?;
+        }
+        Ok(())
+    }
+
+    fn focus_pane(&self, pane_id: &str) -> Result<()> {
+        Ok(tmux::focus_pane(pane_id)?)
+    }
+
+    fn is_pane_alive(&self, pane_id: &str) -> Option<bool> {
+        Some(tmux::is_pane_alive(pane_id))
+    }
+
+    fn pane_option(&self, pane_id: &str, option: &str) -> Option<String> {
+        tmux::get_pane_option(pane_id, option)
+    }
+
+    fn set_pane_option(&self, pane_id: &str, option: &str, value: &str) -> Result<()> {
+        Ok(tmux::set_pane_option(pane_id, option, value)?)
+    }
+
+    fn window_of_pane(&self, pane_id: &str) -> Option<String> {
+        tmux::get_window_id_for_pane(pane_id)
+    }
+
+    fn window_option(&self, window_id: &str, option: &str) -> Option<String> {
+        tmux::get_window_option(window_id, option)
+    }
+
+    fn set_window_option(&self, window_id: &str, option: &str, value: &str) -> Result<()> {
+        Ok(tmux::set_window_option(window_id, option, value)?)
+    }
+
+    fn window_pane_options(&self, window_id: &str, option: &str) -> Vec<String> {
+        tmux::list_window_pane_options(window_id, option)
+    }
+
+    fn refresh_status(&self) -> Result<()> {
+        Ok(tmux::refresh_status()?)
+    }
+
+    fn capture_pane(&self, pane_id: &str) -> Option<String> {
+        tmux::capture_pane(pane_id)
+    }
+
+    fn open_window_command(&self, name: &str, command: &str) -> Option<String> {
+        Some(format!(
+            "tmux new-window -n {} {}",
+            shlex::try_quote(name).ok()?,
+            shlex::try_quote(command).ok()?
+        ))
+    }
+}
@@ -0,0 +1,705 @@
+//! Zellij backend, driven through the `zellij` CLI.
+//!
+//! Zellij has no user options, so pane and window options are kept in
+//! `~/.cache/armyknife/zellij/options.json`. A pane is addressed as
+//! `<session>:terminal_<id>` (from `$ZELLIJ_SESSION_NAME` and
+//! `$ZELLIJ_PANE_ID`), and the whole Zellij session is treated as its
+//! window, since a pane cannot learn which tab it is in.
+//!
+//! The CLI only sees panes through the clients' focus (`action
+//! list-clients`), so:
+//!
+//! - `focus_pane` switches to the pane's tab by name (recorded for panes
+//!   opened by `create_window`, which export `$ARMYKNIFE_ZELLIJ_TAB`) and
+//!   cycles the tab's focus until the pane has it. Other panes, and panes
+//!   in another session, cannot be focused and report an error.
+//! - `capture_pane` dumps the pane only while a client focuses it, and
+//!   returns `None` otherwise (the activity probe then reports no
+//!   observation).
+//! - `is_pane_alive` is `Some(false)` once the pane's session is gone,
+//!   `Some(true)` while a client focuses the pane, and `None` otherwise.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+use super::{Multiplexer, PaneInfo, WindowSpec};
+use crate::infra::external_tool::ExternalTool;
+use crate::infra::tmux::layout::{apply_prompt_if_claude, write_prompt_file};
+use crate::shared::cache;
+use crate::shared::config::{LayoutNode, SplitDirection};
+use crate::shared::env_var::EnvVars;
+
+/// Upper bound on the panes `focus_pane` cycles through in one tab.
+const MAX_TAB_PANES: usize = 32;
+
+pub struct Zellij {
+    options_path: Option<PathBuf>,
+}
+
+impl Zellij {
+    pub fn new() -> Self {
+        Self {
+            options_path: cache::base_dir().map(|d| d.join("zellij").join("options.json")),
+        }
+    }
+
+    #[cfg(test)]
+    fn with_options_path(path: PathBuf) -> Self {
+        Self {
+            options_path: Some(path),
+        }
+    }
+
+    fn load_options(&self) -> Options {
+        self.options_path
+            .as_ref()
+            .and_then(|p| fs::read_to_string(p).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Concurrent hooks may race here; the last rename wins, which at worst
+    /// drops another hook's write until that pane's next event.
+    fn update_options(&self, update: impl FnOnce(&mut Options)) -> Result<()> {
+        let path = self
+            .options_path
+            .as_ref()
+            .context("Could not determine cache directory")?;
+        let mut options = self.load_options();
+        update(&mut options);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let temp_path = path.with_extension(format!("json.{}.tmp", std::process::id()));
+        fs::write(&temp_path, serde_json::to_string(&options)?)?;
+        fs::rename(&temp_path, path)?;
+        Ok(())
+    }
+}
+
+impl Default for Zellij {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Emulated user options, keyed by pane or window ID, then option name.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Options {
+    #[serde(default)]
+    panes: BTreeMap<String, BTreeMap<String, String>>,
+    #[serde(default)]
+    windows: BTreeMap<String, BTreeMap<String, String>>,
+    /// Tab name of each pane that reported one, for `focus_pane`.
+    #[serde(default)]
+    tabs: BTreeMap<String, String>,
+}
+
+fn run_zellij(session: Option<&str>, args: &[&str]) -> Result<String> {
+    let mut command = ExternalTool::Zellij.command();
+    if let Some(session) = session {
+        command.args(["--session", session]);
+    }
+    command.args(args);
+    let output = command
+        .output()
+        .with_context(|| format!("Failed to run zellij {}", args.join(" ")))?;
+    if !output.status.success() {
+        bail!(
+            "zellij {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Names of the running (not exited, resurrectable) sessions.
+fn running_sessions() -> Result<Vec<String>> {
+    let output = run_zellij(None, &["list-sessions", "--no-formatting"])?;
+    Ok(parse_running_sessions(&output))
+}
+
+fn parse_running_sessions(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter(|line| !line.contains("EXITED"))
+        .filter_map(|line| line.split_whitespace().next())
+        .map(str::to_string)
+        .collect()
+}
+
+fn env_session() -> Option<String> {
+    std::env::var("ZELLIJ_SESSION_NAME")
+        .ok()
+        .filter(|s| !s.is_empty())
+}
+
+/// Panes (`terminal_<id>`) focused by the clients attached to `session`.
+fn focused_panes(session: &str) -> Result<Vec<String>> {
+    let output = run_zellij(Some(session), &["action", "list-clients"])?;
+    Ok(parse_focused_panes(&output))
+}
+
+/// Parses `list-clients` output: a `CLIENT_ID ZELLIJ_PANE_ID
+/// RUNNING_COMMAND` header, then one row per client.
+fn parse_focused_panes(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .skip(1)
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .map(str::to_string)
+        .collect()
+}
+
+fn pane_ref(session: &str, pane_id: &str) -> String {
+    format!("{session}:terminal_{pane_id}")
+}
+
+/// The session part of a `pane_ref`.
+fn pane_session(pane_id: &str) -> Option<&str> {
+    pane_id
+        .rsplit_once(":terminal_")
+        .map(|(session, _)| session)
+}
+
+/// The `terminal_<id>` part of a `pane_ref`, as `list-clients` prints it.
+fn pane_terminal(pane_id: &str) -> Option<&str> {
+    pane_id
+        .rsplit_once(':')
+        .map(|(_, terminal)| terminal)
+        .filter(|terminal| terminal.starts_with("terminal_"))
+}
+
+/// Zellij session names may not contain `/`, which `tmux::get_session_name`
+/// produces (`org/repo`).
+fn session_name(name: &str) -> String {
+    name.replace('/', "_")
+}
+
+impl Multiplexer for Zellij {
+    fn is_available(&self) -> bool {
+        running_sessions().is_ok()
+    }
+
+    fn current_pane(&self) -> Option<PaneInfo> {
+        let session = env_session()?;
+        let pane_id = std::env::var("ZELLIJ_PANE_ID")
+            .ok()
+            .filter(|s| !s.is_empty())?;
+        Some(PaneInfo {
+            window_name: session.clone(),
+            window_index: 0,
+            pane_id: pane_ref(&session, &pane_id),
+            session_name: session,
+        })
+    }
+
+    /// Inside Zellij the tab opens in the current session (a client cannot
+    /// be moved to another session from the CLI) and always takes focus;
+    /// outside it the session is created in the background if needed.
+    fn create_window(&self, spec: &WindowSpec) -> Result<()> {
+        let session = match env_session() {
+            Some(current) => current,
+            None => {
+                let session = session_name(spec.session);
+                if !running_sessions()?.contains(&session) {
+                    let mut command = ExternalTool::Zellij.command();
+                    command
+                        .args(["attach", "--create-background", &session])
+                        .current_dir(spec.session_cwd);
+                    let status = command.status().context("Failed to run zellij attach")?;
+                    if !status.success() {
+                        bail!("Failed to create zellij session '{session}'");
+                    }
+                }
+                session
+            }
+        };
+
+        let prompt_file = spec.prompt.map(write_prompt_file).transpose()?;
+        let mut env_vars = spec.env_vars.to_vec();
+        env_vars.push((EnvVars::zellij_tab_name(), spec.window_name));
+        let commands = pane_commands(spec.layout, spec.model, prompt_file.as_deref(), &env_vars);
+        let layout_file = tempfile::Builder::new()
+            .prefix("armyknife-layout-")
+            .suffix(".kdl")
+            .tempfile()
+            .context("Failed to create zellij layout file")?;
+        fs::write(
+            layout_file.path(),
+            layout_kdl(spec.layout, spec.cwd, &commands),
+        )?;
+
+        let layout_path = layout_file.path().to_string_lossy().into_owned();
+        run_zellij(
+            Some(&session),
+            &[
+                "action",
+                "new-tab",
+                "--layout",
+                &layout_path,
+                "--name",
+                spec.window_name,
+                "--cwd",
+                spec.cwd,
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn focus_pane(&self, pane_id: &str) -> Result<()> {
+        let (Some(session), Some(terminal)) = (pane_session(pane_id), pane_terminal(pane_id))
+        else {
+            bail!("Not a Zellij pane: {pane_id}");
+        };
+        if env_session().as_deref() != Some(session) {
+            bail!(
+                "Zellij cannot move this client to session '{session}'; run `zellij attach {session}`"
+            );
+        }
+        let Some(tab) = self.load_options().tabs.remove(pane_id) else {
+            bail!(
+                "The Zellij tab of pane {pane_id} is unknown; only panes opened by `a cc new` can be focused"
+            );
+        };
+        run_zellij(Some(session), &["action", "go-to-tab-name", &tab])?;
+
+        // There is no focus-by-ID action, so step through the tab's panes
+        // until the target has focus, giving up after a full cycle.
+        let mut first_seen = None;
+        for _ in 0..MAX_TAB_PANES {
+            let focused = focused_panes(session)?;
+            if focused.iter().any(|p| p == terminal) {
+                return Ok(());
+            }
+            match &first_seen {
+                Some(first) if *first == focused => break,
+                Some(_) => {}
+                None => first_seen = Some(focused),
+            }
+            run_zellij(Some(session), &["action", "focus-next-pane"])?;
+        }
+        bail!("Zellij pane {pane_id} was not found in tab '{tab}'")
+    }
+
+    fn is_pane_alive(&self, pane_id: &str) -> Option<bool> {
+        let session = pane_session(pane_id)?;
+        if !running_sessions().ok()?.iter().any(|s| s == session) {
+            return Some(false);
+        }
+        let terminal = pane_terminal(pane_id)?;
+        focused_panes(session)
+            .ok()?
+            .iter()
+            .any(|p| p == terminal)
+            .then_some(true)
+    }
+
+    fn pane_option(&self, pane_id: &str, option: &str) -> Option<String> {
+        self.load_options()
+            .panes
+            .get(pane_id)?
+            .get(option)
+            .filter(|v| !v.is_empty())
+            .cloned()
+    }
+
+    /// Also records the caller's tab when it sets an option on its own pane,
+    /// which is how `focus_pane` learns where the pane is.
+    fn set_pane_option(&self, pane_id: &str, option: &str, value: &str) -> Result<()> {
+        let tab = EnvVars::load()
+            .zellij_tab
+            .filter(|_| self.current_pane().is_some_and(|p| p.pane_id == pane_id));
+        self.update_options(|options| {
+            options
+                .panes
+                .entry(pane_id.to_string())
+                .or_default()
+                .insert(option.to_string(), value.to_string());
+            if let Some(tab) = tab {
+                options.tabs.insert(pane_id.to_string(), tab);
+            }
+        })
+    }
+
+    fn window_of_pane(&self, pane_id: &str) -> Option<String> {
+        pane_session(pane_id).map(str::to_string)
+    }
+
+    fn window_option(&self, window_id: &str, option: &str) -> Option<String> {
+        self.load_options()
+            .windows
+            .get(window_id)?
+            .get(option)
+            .filter(|v| !v.is_empty())
+            .cloned()
+    }
+
+    fn set_window_option(&self, window_id: &str, option: &str, value: &str) -> Result<()> {
+        self.update_options(|options| {
+            options
+                .windows
+                .entry(window_id.to_string())
+                .or_default()
+                .insert(option.to_string(), value.to_string());
+        })
+    }
+
+    fn window_pane_options(&self, window_id: &str, option: &str) -> Vec<String> {
+        self.load_options()
+            .panes
+            .iter()
+            .filter(|(pane, _)| pane_session(pane) == Some(window_id))
+            .filter_map(|(_, values)| values.get(option).filter(|v| !v.is_empty()).cloned())
+            .collect()
+    }
+
+    /// Zellij draws nothing from these options, so there is nothing to
+    /// refresh.
+    fn refresh_status(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// `dump-screen` only reads the focused pane, so other panes yield
+    /// `None`.
+    fn capture_pane(&self, pane_id: &str) -> Option<String> {
+        let session = pane_session(pane_id)?;
+        let terminal = pane_terminal(pane_id)?;
+        if !focused_panes(session).ok()?.iter().any(|p| p == terminal) {
+            return None;
+        }
+        let dump = tempfile::Builder::new()
+            .prefix("armyknife-zellij-screen-")
+            .tempfile()
+            .ok()?;
+        let dump_path = dump.path().to_string_lossy().into_owned();
+        run_zellij(Some(session), &["action", "dump-screen", &dump_path]).ok()?;
+        fs::read_to_string(dump.path()).ok()
+    }
+
+    /// Opens a pane running `command` in the caller's session, the one a
+    /// notification came from. A pane rather than a tab, since `new-tab`
+    /// only runs commands through a layout file.
+    fn open_window_command(&self, name: &str, command: &str) -> Option<String> {
+        Some(open_pane_command(&env_session()?, name, command))
+    }
+}
+
+fn open_pane_command(session: &str, name: &str, command: &str) -> String {
+    format!(
+        "zellij --session {} action new-pane --name {} -- sh -c {}",
+        quote(session),
+        quote(name),
+        quote(command)
+    )
+}
+
+/// The shell command of each layout leaf, in layout order, with the model
+/// and prompt applied to claude panes (only the last one deletes the prompt
+/// file) and `env_vars` exported first. Empty for a plain shell pane.
+fn pane_commands(
+    layout: &LayoutNode,
+    model: Option<&str>,
+    prompt_file: Option<&std::path::Path>,
+    env_vars: &[(&str, &str)],
+) -> Vec<String> {
+    let mut leaves = Vec::new();
+    collect_leaves(layout, &mut leaves);
+    let last_claude =
+        prompt_file.and_then(|_| leaves.iter().rposition(|c| c.starts_with("claude")));
+    let exports: String = env_vars
+        .iter()
+        .map(|(key, value)| format!("export {key}={}; ", quote(value)))
+        .collect();
+    leaves
+        .iter()
+        .enumerate()
+        .map(|(i, command)| {
+            if command.is_empty() {
+                return String::new();
+            }
+            let command =
+                apply_prompt_if_claude(command, model, prompt_file, last_claude == Some(i));
+            format!("{exports}{command}; exec \"${{SHELL:-sh}}\"")
+        })
+        .collect()
+}
+
+fn collect_leaves<'a>(node: &'a LayoutNode, leaves: &mut Vec<&'a str>) {
+    match node {
+        LayoutNode::Pane(pane) => leaves.push(&pane.command),
+        LayoutNode::Split(split) => {
+            collect_leaves(&split.first, leaves);
+            collect_leaves(&split.second, leaves);
+        }
+    }
+}
+
+fn quote(value: &str) -> String {
+    shlex::try_quote(value)
+        .map(|c| c.into_owned())
+        .unwrap_or_else(|_| value.to_string())
+}
+
+/// A KDL string literal.
+fn kdl_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Zellij layout for `layout`, one pane per leaf running `commands[i]`.
+/// The last pane with `focus: true` gets focus, as with tmux.
+fn layout_kdl(layout: &LayoutNode, cwd: &str, commands: &[String]) -> String {
+    let mut leaves = Vec::new();
+    collect_leaves(layout, &mut leaves);
+    let focus = focus_index(layout);
+
+    let mut out = String::from("layout {\n");
+    let mut index = 0;
+    write_node(&mut out, layout, cwd, commands, focus, &mut index, 1);
+    out.push_str("}\n");
+    out
+}
+
+fn focus_index(layout: &LayoutNode) -> Option<usize> {
+    fn walk(node: &LayoutNode, index: &mut usize, found: &mut Option<usize>) {
+        match node {
+            LayoutNode::Pane(pane) => {
+                if pane.focus {
+                    *found = Some(*index);
+                }
+                *index += 1;
+            }
+            LayoutNode::Split(split) => {
+                walk(&split.first, index, found);
+                walk(&split.second, index, found);
+            }
+        }
+    }
+    let mut found = None;
+    walk(layout, &mut 0, &mut found);
+    found
+}
+
+fn write_node(
+    out: &mut String,
+    node: &LayoutNode,
+    cwd: &str,
+    commands: &[String],
+    focus: Option<usize>,
+    index: &mut usize,
+    depth: usize,
+) {
+    let indent = "    ".repeat(depth);
+    match node {
+        LayoutNode::Pane(_) => {
+            let mut attrs = format!("cwd={}", kdl_string(cwd));
+            if focus == Some(*index) {
+                attrs.push_str(" focus=true");
+            }
+            match commands.get(*index).filter(|c| !c.is_empty()) {
+                Some(command) => {
+                    let _ = writeln!(out, "{indent}pane {attrs} command=\"sh\" {{");
+                    let _ = writeln!(out, "{indent}    args \"-c\" {}", kdl_string(command));
+                    let _ = writeln!(out, "{indent}}}");
+                }
+                None => {
+                    let _ = writeln!(out, "{indent}pane {attrs}");
+                }
+            }
+            *index += 1;
+        }
+        LayoutNode::Split(split) => {
+            // Zellij names a split after its divider: "vertical" places the
+            // panes side by side, which is tmux's `split-window -h`.
+            let direction = match split.direction {
+                SplitDirection::Horizontal => "vertical",
+                SplitDirection::Vertical => "horizontal",
+            };
+            let _ = writeln!(out, "{indent}pane split_direction=\"{direction}\" {{");
+            write_node(out, &split.first, cwd, commands, focus, index, depth + 1);
+            write_node(out, &split.second, cwd, commands, focus, index, depth + 1);
+            let _ = writeln!(out, "{indent}}}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::config::{PaneConfig, SplitConfig};
+    use indoc::indoc;
+    use rstest::rstest;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn pane(command: &str, focus: bool) -> LayoutNode {
+        LayoutNode::Pane(PaneConfig {
+            command: command.to_string(),
+            focus,
+        })
+    }
+
+    fn split(direction: SplitDirection, first: LayoutNode, second: LayoutNode) -> LayoutNode {
+        LayoutNode::Split(SplitConfig {
+            direction,
+            first: Box::new(first),
+            second: Box::new(second),
+        })
+    }
+
+    #[test]
+    fn layout_kdl_nests_splits_and_focuses_the_last_focus_pane() {
+        let layout = split(
+            SplitDirection::Horizontal,
+            pane("nvim", true),
+            split(
+                SplitDirection::Vertical,
+                pane("claude", true),
+                pane("", false),
+            ),
+        );
+        let commands = pane_commands(&layout, None, None, &[("K", "a b")]);
+        assert_eq!(
+            layout_kdl(&layout, "/w/t", &commands),
+            indoc! {r#"
+                layout {
+                    pane split_direction="vertical" {
+                        pane cwd="/w/t" command="sh" {
+                            args "-c" "export K='a b'; nvim; exec \"${SHELL:-sh}\""
+                        }
+                        pane split_direction="horizontal" {
+                            pane cwd="/w/t" focus=true command="sh" {
+                                args "-c" "export K='a b'; claude; exec \"${SHELL:-sh}\""
+                            }
+                            pane cwd="/w/t"
+                        }
+                    }
+                }
+            "#}
+        );
+    }
+
+    #[test]
+    fn pane_commands_apply_model_and_let_the_last_claude_pane_clean_up() {
+        let layout = split(
+            SplitDirection::Horizontal,
+            pane("claude", false),
+            pane("claude -c", false),
+        );
+        let commands = pane_commands(&layout, Some("opus"), Some(Path::new("/tmp/p.txt")), &[]);
+        assert_eq!(
+            commands,
+            vec![
+                "claude --model opus \"$(cat /tmp/p.txt)\"; exec \"${SHELL:-sh}\"",
+                "claude --model opus -c \"$(cat /tmp/p.txt)\" ; rm /tmp/p.txt; exec \"${SHELL:-sh}\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_running_sessions_skips_exited() {
+        let output = indoc! {"
+            org_repo [Created 2h ago]
+            old [Created 3d ago] (EXITED - attach to resurrect)
+            work [Created 5m ago] (current)
+        "};
+        assert_eq!(parse_running_sessions(output), vec!["org_repo", "work"]);
+    }
+
+    #[test]
+    fn open_pane_command_quotes_its_arguments() {
+        assert_eq!(
+            open_pane_command("org_repo", "cc-unread", "a cc watch --unread"),
+            "zellij --session org_repo action new-pane --name cc-unread -- sh -c 'a cc watch --unread'"
+        );
+    }
+
+    #[test]
+    fn parse_focused_panes_reads_the_pane_column() {
+        let output = indoc! {"
+            CLIENT_ID ZELLIJ_PANE_ID RUNNING_COMMAND
+            1         terminal_3     claude
+            2         plugin_1       zellij:session-manager
+        "};
+        assert_eq!(parse_focused_panes(output), vec!["terminal_3", "plugin_1"]);
+    }
+
+    #[rstest]
+    #[case::pane("org:repo:terminal_3", Some("terminal_3"))]
+    #[case::plugin("org_repo:plugin_1", None)]
+    #[case::tmux_pane("%3", None)]
+    fn pane_terminal_cases(#[case] pane_id: &str, #[case] expected: Option<&str>) {
+        assert_eq!(pane_terminal(pane_id), expected);
+    }
+
+    #[rstest]
+    #[case::own_pane("s:terminal_2", Some("fix-login"))]
+    #[case::other_pane("s:terminal_5", None)]
+    fn set_pane_option_records_the_callers_tab(
+        #[case] pane_id: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        let dir = TempDir::new().unwrap();
+        let zellij = Zellij::with_options_path(dir.path().join("options.json"));
+
+        temp_env::with_vars(
+            [
+                ("ZELLIJ_SESSION_NAME", Some("s")),
+                ("ZELLIJ_PANE_ID", Some("2")),
+                (EnvVars::zellij_tab_name(), Some("fix-login")),
+            ],
+            || zellij.set_pane_option(pane_id, "@id", "a").unwrap(),
+        );
+
+        assert_eq!(
+            zellij.load_options().tabs.get(pane_id).map(String::as_str),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case::pane("org_repo:terminal_3", Some("org_repo"))]
+    #[case::tmux_pane("%3", None)]
+    fn pane_session_cases(#[case] pane_id: &str, #[case] expected: Option<&str>) {
+        assert_eq!(pane_session(pane_id), expected);
+    }
+
+    #[test]
+    fn options_persist_per_pane_and_window() {
+        let dir = TempDir::new().unwrap();
+        let zellij = Zellij::with_options_path(dir.path().join("options.json"));
+
+        zellij.set_pane_option("s:terminal_2", "@id", "b").unwrap();
+        zellij.set_pane_option("s:terminal_1", "@id", "a").unwrap();
+        zellij
+            .set_pane_option("other:terminal_1", "@id", "c")
+            .unwrap();
+        zellij.set_window_option("s", "@status", "●").unwrap();
+
+        assert_eq!(
+            zellij.pane_option("s:terminal_2", "@id").as_deref(),
+            Some("b")
+        );
+        assert_eq!(zellij.pane_option("s:terminal_9", "@id"), None);
+        assert_eq!(zellij.window_pane_options("s", "@id"), vec!["a", "b"]);
+        assert_eq!(zellij.window_option("s", "@status").as_deref(), Some("●"));
+        assert_eq!(zellij.window_of_pane("s:terminal_2").as_deref(), Some("s"));
+    }
+}
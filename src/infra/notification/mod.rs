@@ -1,6 +1,7 @@
 mod hammerspoon;
 pub mod icon;
 mod types;
+pub mod webhook;
 
 pub use types::{Notification, NotificationAction};
 
@@ -36,6 +36,12 @@ impl Notification {
         self
     }
 
+    /// Drops the sound, so the notification is displayed silently.
+    pub fn without_sound(mut self) -> Self {
+        self.sound = None;
+        self
+    }
+
     /// Sets the action to execute when the notification is clicked.
     pub fn with_action(mut self, action: NotificationAction) -> Self {
         self.action = Some(action);
@@ -0,0 +1,69 @@
+//! Webhook sink: POSTs a notification as JSON to a user-configured URL, for
+//! channels beyond the desktop (chat, push services, ...).
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use super::Notification;
+
+/// Bounds how long a slow endpoint can hold up the caller (e.g. a sweep run).
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+struct Payload<'a> {
+    title: &'a str,
+    subtitle: Option<&'a str>,
+    message: &'a str,
+    group: Option<&'a str>,
+}
+
+impl<'a> Payload<'a> {
+    fn of(notification: &'a Notification) -> Self {
+        Self {
+            title: notification.title(),
+            subtitle: notification.subtitle(),
+            message: notification.message(),
+            group: notification.group(),
+        }
+    }
+}
+
+/// Sends `notification` to `url`.
+pub async fn post(url: &str, notification: &Notification) -> Result<()> {
+    let body = serde_json::to_string(&Payload::of(notification))?;
+    reqwest::Client::builder()
+        .timeout(TIMEOUT)
+        .build()?
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(body)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .with_context(|| format!("Failed to POST notification to {url}"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn payload_carries_the_notification_text_and_group() {
+        let notification = Notification::new("title", "message")
+            .with_subtitle("main:api")
+            .with_group("session-1")
+            .with_sound("Sosumi");
+        assert_eq!(
+            serde_json::to_value(Payload::of(&notification)).unwrap(),
+            serde_json::json!({
+                "title": "title",
+                "subtitle": "main:api",
+                "message": "message",
+                "group": "session-1",
+            })
+        );
+    }
+}
@@ -270,7 +270,7 @@ fn count_panes(node: &LayoutNode) -> usize {
 /// If `cleanup` is true, also deletes the temp file after reading.
 /// Only the last claude pane should set `cleanup = true` to avoid
 /// deleting the file before other panes have read it.
-fn apply_prompt_if_claude(
+pub(crate) fn apply_prompt_if_claude(
     command: &str,
     model: Option<&str>,
     prompt_file: Option<&Path>,
@@ -407,6 +407,122 @@ pub fn build_layout(spec: LayoutSpec) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// One pane of a window rebuilt by `restore_window`, in pane order.
+pub struct RestorePane<'a> {
+    pub cwd: &'a str,
+    /// Typed into the pane's shell once the layout is in place.
+    pub command: Option<&'a str>,
+    /// A pane-scoped user option to set, as `(name, value)`.
+    pub option: Option<(&'a str, &'a str)>,
+    pub active: bool,
+}
+
+/// Inputs for `restore_window`.
+pub struct RestoreWindowSpec<'a> {
+    pub session: &'a str,
+    pub window_name: &'a str,
+    /// A `#{window_layout}` string captured earlier; `select-layout`
+    /// applies it once the window has as many panes as it describes.
+    pub layout: &'a str,
+    pub panes: &'a [RestorePane<'a>],
+}
+
+/// Builds the commands that recreate a window with `spec.panes` and
+/// `spec.layout`. Like a background `build_layout_commands`, the window is
+/// created detached and addressed as `{session}:={name}.N`.
+///
+/// Every split is followed by `select-layout tiled` so a many-pane window
+/// never runs out of room before the saved layout is applied.
+pub fn build_restore_window_commands(spec: &RestoreWindowSpec) -> Vec<TmuxCommand> {
+    let RestoreWindowSpec {
+        session,
+        window_name,
+        layout,
+        panes,
+    } = spec;
+    let Some(first) = panes.first() else {
+        return Vec::new();
+    };
+    let pane_prefix = background_pane_prefix(session, window_name);
+    let first_target = format!("{pane_prefix}1");
+
+    let mut commands = vec![TmuxCommand::new(&[
+        "new-window",
+        "-d",
+        "-t",
+        session,
+        "-c",
+        first.cwd,
+        "-n",
+        window_name,
+    ])];
+    for (i, pane) in panes.iter().enumerate().skip(1) {
+        let target = format!("{pane_prefix}{i}");
+        commands.push(TmuxCommand::new(&[
+            "split-window",
+            "-d",
+            "-t",
+            &target,
+            "-c",
+            pane.cwd,
+        ]));
+        commands.push(TmuxCommand::new(&[
+            "select-layout",
+            "-t",
+            &first_target,
+            "tiled",
+        ]));
+    }
+    commands.push(TmuxCommand::new(&[
+        "select-layout",
+        "-t",
+        &first_target,
+        layout,
+    ]));
+
+    for (i, pane) in panes.iter().enumerate() {
+        let target = format!("{pane_prefix}{}", i + 1);
+        if let Some((name, value)) = pane.option {
+            commands.push(TmuxCommand::new(&[
+                "set-option",
+                "-p",
+                "-t",
+                &target,
+                name,
+                value,
+            ]));
+        }
+        if let Some(command) = pane.command {
+            commands.push(TmuxCommand::new(&[
+                "send-keys",
+                "-t",
+                &target,
+                "-l",
+                "--",
+                command,
+            ]));
+            commands.push(TmuxCommand::new(&["send-keys", "-t", &target, "C-m"]));
+        }
+    }
+    if let Some(active) = panes.iter().position(|p| p.active) {
+        let target = format!("{pane_prefix}{}", active + 1);
+        commands.push(TmuxCommand::new(&["select-pane", "-t", &target]));
+    }
+    commands
+}
+
+/// Recreates a window from a saved layout without touching the attached
+/// client's active window. The session is created first if needed.
+pub fn restore_window(spec: &RestoreWindowSpec) -> anyhow::Result<()> {
+    let Some(first) = spec.panes.first() else {
+        return Ok(());
+    };
+    super::ensure_session(spec.session, first.cwd)?;
+    let commands = build_restore_window_commands(spec);
+    execute_background_layout(&commands, spec.session, spec.window_name)?;
+    Ok(())
+}
+
 /// Inputs for `split_pane`.
 pub struct SplitSpec<'a> {
     pub common: TmuxSessionSpec<'a>,
@@ -551,7 +667,7 @@ fn rewrite_pane_targets(
 }
 
 /// Write prompt to a temp file that persists until the shell command reads it.
-fn write_prompt_file(prompt: &str) -> anyhow::Result<std::path::PathBuf> {
+pub(crate) fn write_prompt_file(prompt: &str) -> anyhow::Result<std::path::PathBuf> {
     use anyhow::Context;
 
     let prompt_file = tempfile::Builder::new()
@@ -1466,6 +1582,90 @@ mod tests {
         assert_eq!(find_new_window_index(&commands), expected);
     }
 
+    #[test]
+    fn build_restore_window_commands_recreates_panes_then_layout() {
+        let panes = [
+            RestorePane {
+                cwd: "/repo/.worktrees/a",
+                command: Some("a cc resume s1"),
+                option: Some(("@armyknife-session-id", "s1")),
+                active: false,
+            },
+            RestorePane {
+                cwd: "/repo/.worktrees/a/src",
+                command: None,
+                option: None,
+                active: true,
+            },
+        ];
+        let commands = build_restore_window_commands(&RestoreWindowSpec {
+            session: "main",
+            window_name: "fix",
+            layout: "b25f,80x24,0,0{40x24,0,0,1,39x24,41,0,2}",
+            panes: &panes,
+        });
+
+        assert_eq!(
+            commands,
+            vec![
+                cmd(&[
+                    "new-window",
+                    "-d",
+                    "-t",
+                    "main",
+                    "-c",
+                    "/repo/.worktrees/a",
+                    "-n",
+                    "fix"
+                ]),
+                cmd(&[
+                    "split-window",
+                    "-d",
+                    "-t",
+                    "main:=fix.1",
+                    "-c",
+                    "/repo/.worktrees/a/src"
+                ]),
+                cmd(&["select-layout", "-t", "main:=fix.1", "tiled"]),
+                cmd(&[
+                    "select-layout",
+                    "-t",
+                    "main:=fix.1",
+                    "b25f,80x24,0,0{40x24,0,0,1,39x24,41,0,2}"
+                ]),
+                cmd(&[
+                    "set-option",
+                    "-p",
+                    "-t",
+                    "main:=fix.1",
+                    "@armyknife-session-id",
+                    "s1"
+                ]),
+                cmd(&[
+                    "send-keys",
+                    "-t",
+                    "main:=fix.1",
+                    "-l",
+                    "--",
+                    "a cc resume s1"
+                ]),
+                cmd(&["send-keys", "-t", "main:=fix.1", "C-m"]),
+                cmd(&["select-pane", "-t", "main:=fix.2"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn build_restore_window_commands_without_panes_is_empty() {
+        let commands = build_restore_window_commands(&RestoreWindowSpec {
+            session: "main",
+            window_name: "fix",
+            layout: "l",
+            panes: &[],
+        });
+        assert!(commands.is_empty());
+    }
+
     #[test]
     fn execute_background_layout_errors_when_new_window_missing() {
         let commands = vec![cmd(&["select-pane", "-t", "1"])];
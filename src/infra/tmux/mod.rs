@@ -410,6 +410,29 @@ pub fn respawn_pane(pane_id: &str, command: &str) -> Result<()> {
     run_tmux(&["respawn-pane", "-k", "-t", pane_id, command])
 }
 
+/// Opens a window in `session` running `command` in `cwd`, switches the
+/// session to it, and returns its pane id.
+pub fn new_window_with_command(
+    session: &str,
+    cwd: &str,
+    window_name: &str,
+    command: &str,
+) -> Result<String> {
+    run_tmux_output(&[
+        "new-window",
+        "-t",
+        &format!("{session}:"),
+        "-c",
+        cwd,
+        "-n",
+        window_name,
+        "-P",
+        "-F",
+        "#{pane_id}",
+        command,
+    ])
+}
+
 /// Set a user option on a specific tmux pane.
 /// User options are prefixed with '@' (e.g., "@armyknife-session-id").
 /// This does not require being inside tmux, as it targets a specific pane ID.
@@ -596,6 +619,68 @@ fn parse_pane_with_option_line(line: &str) -> Option<PaneInfoWithOption> {
     }
 }
 
+/// Everything `a cc snapshot save` records about one pane and its window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaneLayoutInfo {
+    pub session_name: String,
+    pub window_index: u32,
+    pub window_name: String,
+    /// tmux's `#{window_layout}`, which `select-layout` accepts back.
+    pub window_layout: String,
+    pub pane_index: u32,
+    pub pane_active: bool,
+    pub current_path: String,
+    pub current_command: String,
+    /// Value of the requested user option, `None` when unset.
+    pub option_value: Option<String>,
+}
+
+/// Lists every pane of every session with its window's layout and the value
+/// of `option`, ordered by session, window, and pane index as tmux lists
+/// them.
+pub fn list_all_pane_layouts(option: &str) -> Result<Vec<PaneLayoutInfo>> {
+    let format = format!(
+        "#{{session_name}}\t#{{window_index}}\t#{{window_layout}}\t#{{pane_index}}\t\
+         #{{pane_active}}\t#{{pane_current_path}}\t#{{pane_current_command}}\t#{{{option}}}\t\
+         #{{window_name}}"
+    );
+    let output = run_tmux_output(&["list-panes", "-a", "-F", &format])?;
+    Ok(output.lines().filter_map(parse_pane_layout_line).collect())
+}
+
+/// Parses a single line of `list_all_pane_layouts`' list-panes output. The
+/// window name comes last so a tab in it can't shift the other fields.
+fn parse_pane_layout_line(line: &str) -> Option<PaneLayoutInfo> {
+    let mut parts = line.splitn(9, '\t');
+    let session_name = parts.next()?.to_string();
+    let window_index = parts.next()?.parse::<u32>().ok()?;
+    let window_layout = parts.next()?.to_string();
+    let pane_index = parts.next()?.parse::<u32>().ok()?;
+    let pane_active = parts.next()? == "1";
+    let current_path = parts.next()?.to_string();
+    let current_command = parts.next()?.to_string();
+    let option_value = Some(parts.next()?.to_string()).filter(|v| !v.is_empty());
+    let window_name = parts.next()?.to_string();
+    Some(PaneLayoutInfo {
+        session_name,
+        window_index,
+        window_name,
+        window_layout,
+        pane_index,
+        pane_active,
+        current_path,
+        current_command,
+        option_value,
+    })
+}
+
+/// Names of the windows in `session`; empty when the session doesn't exist.
+pub fn list_window_names(session: &str) -> Vec<String> {
+    run_tmux_output(&["list-windows", "-t", session, "-F", "#{window_name}"])
+        .map(|output| output.lines().map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
 /// Finds a pane by session:window_index.pane_index and returns its pane_id.
 /// Returns None if the pane is not found.
 pub fn find_pane_id_by_position(
@@ -942,4 +1027,28 @@ mod tests {
             None => assert!(result.is_none()),
         }
     }
+
+    #[test]
+    fn test_parse_pane_layout_line() {
+        let line = "main\t2\tb25f,80x24,0,0{40x24,0,0,1,39x24,41,0,2}\t1\t1\t/repo/.worktrees/a\tzsh\tsess-1\tfix\tlogin";
+        assert_eq!(
+            parse_pane_layout_line(line),
+            Some(PaneLayoutInfo {
+                session_name: "main".to_string(),
+                window_index: 2,
+                window_name: "fix\tlogin".to_string(),
+                window_layout: "b25f,80x24,0,0{40x24,0,0,1,39x24,41,0,2}".to_string(),
+                pane_index: 1,
+                pane_active: true,
+                current_path: "/repo/.worktrees/a".to_string(),
+                current_command: "zsh".to_string(),
+                option_value: Some("sess-1".to_string()),
+            })
+        );
+
+        let unset =
+            parse_pane_layout_line("main\t2\tl\t2\t0\t/tmp\tnvim\t\tw").expect("should parse");
+        assert_eq!((unset.pane_active, unset.option_value), (false, None));
+        assert_eq!(parse_pane_layout_line("main\tx\tl"), None);
+    }
 }
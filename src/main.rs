@@ -1,5 +1,6 @@
 use anyhow::Result;
 use armyknife::cli::{Cli, Commands};
+use armyknife::commands::cc::CcCommands;
 use armyknife::shared;
 use armyknife::shared::update;
 use clap::{CommandFactory, Parser};
@@ -19,12 +20,17 @@ async fn run() -> Result<()> {
 
     if !matches!(
         command,
-        Commands::Update | Commands::Completions { .. } | Commands::Doctor(_)
+        Commands::Update
+            | Commands::Completions { .. }
+            | Commands::Doctor(_)
+            | Commands::Cc(CcCommands::Statusline(_))
     ) {
         // Avoid running the updater twice when `a update` was requested,
         // and skip for completions to keep output clean.
         // Skip for `doctor` so the diagnostic snapshot reflects the current
         // binary rather than the post-update one.
+        // Skip for `cc statusline`: Claude Code shows its stdout verbatim
+        // and re-runs it on every refresh.
         update::auto_update().await;
     }
 
@@ -35,6 +41,7 @@ async fn run() -> Result<()> {
         Commands::Doctor(args) => armyknife::commands::doctor::run(&args)?,
         Commands::Gh(gh_cmd) => gh_cmd.run().await?,
         Commands::NameBranch(args) => args.run()?,
+        Commands::Notify(notify_cmd) => notify_cmd.run()?,
         Commands::Wm(wm_cmd) => wm_cmd.run().await?,
         Commands::Update => update::do_update()?,
         Commands::Completions { shell } => {
@@ -18,7 +18,7 @@ use chrono::{DateTime, TimeZone, Utc};
 use crate::commands::cc::auto_pause::{PauseDecision, decide_pause_with_effective};
 use crate::commands::cc::pane;
 use crate::commands::cc::types::{Session, SessionStatus};
-use crate::infra::tmux;
+use crate::infra::multiplexer;
 
 /// Pane option used to persist the prior `<input-hash>,<unix_seconds>`
 /// snapshot. Both sweep and wm-clean probes write to this option so they
@@ -34,7 +34,7 @@ pub trait ActivityProbe {
     fn last_activity_at(&self, session: &Session, now: DateTime<Utc>) -> Option<DateTime<Utc>>;
 }
 
-/// Probe that always reports "no observation". Useful when tmux is not
+/// Probe that always reports "no observation". Useful when no multiplexer is
 /// available or when the caller does not want activity to influence the
 /// decision (e.g., a non-interactive sweep over disk only).
 pub struct NoActivityProbe;
@@ -45,18 +45,20 @@ impl ActivityProbe for NoActivityProbe {
     }
 }
 
-/// Production probe that reads the live input-box text via tmux and
-/// compares against the prior persisted hash, updating the pane option
+/// Production probe that reads the live input-box text via the multiplexer
+/// and compares against the prior persisted hash, updating the pane option
 /// in place. Side-effectful but safe to run from any process that can
-/// talk to tmux.
-pub struct TmuxActivityProbe;
+/// talk to the multiplexer.
+pub struct MultiplexerActivityProbe;
 
-impl ActivityProbe for TmuxActivityProbe {
+impl ActivityProbe for MultiplexerActivityProbe {
     fn last_activity_at(&self, session: &Session, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
         let pane_id = &session.tmux_info.as_ref()?.pane_id;
         let live = pane::input::get_pane_input_text(pane_id)?;
         let live_hash = hash_input_text(&live);
-        let prior = tmux::get_pane_option(pane_id, PANE_ACTIVITY_OPTION)
+        let mux = multiplexer::current();
+        let prior = mux
+            .pane_option(pane_id, PANE_ACTIVITY_OPTION)
             .as_deref()
             .and_then(parse_pane_activity);
 
@@ -68,7 +70,7 @@ impl ActivityProbe for TmuxActivityProbe {
         // Persist the live hash so the next pass can detect a change
         // against it. Errors are non-fatal: a missed write just means
         // the next pass treats it as "first observation".
-        let _ = tmux::set_pane_option(
+        let _ = mux.set_pane_option(
             pane_id,
             PANE_ACTIVITY_OPTION,
             &format_pane_activity(live_hash, observed_at),
@@ -223,6 +225,13 @@ mod tests {
             pending_permission_agent_ids: BTreeSet::new(),
             read_at: None,
             sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
         }
     }
 
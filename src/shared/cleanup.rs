@@ -145,14 +145,14 @@ fn should_sigterm_session(status: SessionStatus) -> bool {
 ///
 /// Returns the number of sessions cleaned up.
 pub fn cleanup_sessions_in_path(worktree_path: &Path) -> anyhow::Result<usize> {
-    let sessions = store::list_sessions()?;
+    let sessions = store::find_sessions(&store::Lookup::Cwd(worktree_path))?;
     let mut cleaned = 0;
 
     // Batch-fetch alive pane IDs to avoid per-session tmux process spawning
     let alive_panes = tmux::list_all_pane_ids().unwrap_or_default();
 
     for session in &sessions {
-        if session.cwd.starts_with(worktree_path) {
+        if session.status != SessionStatus::Ended {
             if should_sigterm_session(session.status)
                 && let Some(ref tmux_info) = session.tmux_info
                 && alive_panes.contains(&tmux_info.pane_id)
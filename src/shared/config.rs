@@ -26,6 +26,14 @@ pub struct Config {
     #[serde(default)]
     pub cc: CcConfig,
 
+    /// Terminal multiplexer the session tooling drives (default: "auto").
+    #[serde(default)]
+    pub multiplexer: MultiplexerChoice,
+
+    /// LLM backends for generated text (session titles, branch names).
+    #[serde(default)]
+    pub llm: LlmConfig,
+
     /// Per-repository configuration, keyed by "owner/repo".
     #[serde(default)]
     pub repos: HashMap<String, RepoConfig>,
@@ -66,6 +74,27 @@ impl Config {
         }
     }
 
+    /// Resolve the auto-labeling rules for a repo identifier (`owner/repo`),
+    /// or `None` when auto-labeling is disabled there. Each of `enabled` and
+    /// `rules` resolves repo (`repos.<owner>/<repo>.auto_label`) -> global
+    /// (`cc.auto_label`).
+    pub fn resolve_auto_label_rules(&self, repo_id: Option<&str>) -> Option<&[AutoLabelRule]> {
+        let repo_cfg = repo_id
+            .and_then(|id| self.repos.get(id))
+            .map(|cfg| &cfg.auto_label);
+        let enabled = repo_cfg
+            .and_then(|cfg| cfg.enabled)
+            .unwrap_or(self.cc.auto_label.enabled);
+        if !enabled {
+            return None;
+        }
+        Some(
+            repo_cfg
+                .and_then(|cfg| cfg.rules.as_deref())
+                .unwrap_or(&self.cc.auto_label.rules),
+        )
+    }
+
     /// Resolve the list of reviewers to wait for, given a repo identifier (`owner/repo`).
     /// Resolution order: repo (`repos.<owner>/<repo>.ai.review.reviewers`) -> org
     /// (`orgs.<owner>.ai.review.reviewers`) -> None (caller falls back to its own default).
@@ -200,6 +229,177 @@ pub struct PaneConfig {
     pub focus: bool,
 }
 
+/// Terminal multiplexer backend for `cc` and `wm` windows and panes.
+#[cfg_attr(feature = "schema-gen", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MultiplexerChoice {
+    /// Zellij when running inside it (`$ZELLIJ` is set), tmux otherwise.
+    #[default]
+    Auto,
+    /// tmux.
+    Tmux,
+    /// Zellij.
+    Zellij,
+}
+
+/// LLM backend settings shared by every generation task.
+#[cfg_attr(feature = "schema-gen", derive(schemars::JsonSchema))]
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct LlmConfig {
+    /// Backends tried in order until one answers (default: claude_code,
+    /// opencode, openai). A CLI backend whose command is not installed, and
+    /// `openai` without `openai.base_url` and a model, are skipped. When
+    /// none answers, each task falls back to a deterministic offline
+    /// result.
+    #[serde(default = "default_llm_backends")]
+    #[cfg_attr(feature = "schema-gen", schemars(default = "default_llm_backends"))]
+    pub backends: Vec<LlmBackendKind>,
+
+    /// How long one backend may take before the next is tried
+    /// (default: "60s").
+    #[serde(default = "default_llm_timeout")]
+    #[cfg_attr(feature = "schema-gen", schemars(default = "default_llm_timeout"))]
+    pub timeout: String,
+
+    /// `claude --print` settings.
+    #[serde(default)]
+    pub claude_code: LlmCliBackendConfig,
+
+    /// `opencode run` settings.
+    #[serde(default)]
+    pub opencode: LlmCliBackendConfig,
+
+    /// OpenAI-compatible chat completions endpoint (OpenAI, Ollama,
+    /// llama.cpp, ...).
+    #[serde(default)]
+    pub openai: LlmOpenAiConfig,
+
+    /// Per-task overrides.
+    #[serde(default)]
+    pub tasks: LlmTasksConfig,
+}
+
+impl Default for LlmConfig {
+    fn default() -> Self {
+        Self {
+            backends: default_llm_backends(),
+            timeout: default_llm_timeout(),
+            claude_code: LlmCliBackendConfig::default(),
+            opencode: LlmCliBackendConfig::default(),
+            openai: LlmOpenAiConfig::default(),
+            tasks: LlmTasksConfig::default(),
+        }
+    }
+}
+
+fn default_llm_backends() -> Vec<LlmBackendKind> {
+    vec![
+        LlmBackendKind::ClaudeCode,
+        LlmBackendKind::Opencode,
+        LlmBackendKind::Openai,
+    ]
+}
+
+fn default_llm_timeout() -> String {
+    "60s".to_string()
+}
+
+/// An LLM backend.
+#[cfg_attr(feature = "schema-gen", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LlmBackendKind {
+    /// Claude Code (`claude --print`).
+    ClaudeCode,
+    /// OpenCode (`opencode run`).
+    Opencode,
+    /// An OpenAI-compatible HTTP endpoint (`llm.openai`).
+    Openai,
+}
+
+/// Settings of a CLI backend.
+#[cfg_attr(feature = "schema-gen", derive(schemars::JsonSchema))]
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct LlmCliBackendConfig {
+    /// Model passed to the CLI (default: "haiku" for claude_code,
+    /// "opencode/glm-4.7-free" for opencode).
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+/// Settings of the OpenAI-compatible backend.
+#[cfg_attr(feature = "schema-gen", derive(schemars::JsonSchema))]
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct LlmOpenAiConfig {
+    /// Base URL up to the API version, e.g. "http://localhost:11434/v1"
+    /// for Ollama. `/chat/completions` is appended.
+    #[serde(default)]
+    pub base_url: Option<String>,
+
+    /// Model name, e.g. "llama3.2".
+    #[serde(default)]
+    pub model: Option<String>,
+
+    /// Environment variable holding the API key, sent as a bearer token.
+    /// Unset for endpoints without authentication.
+    #[serde(default)]
+    pub api_key_env: Option<String>,
+}
+
+/// Overrides per generation task.
+#[cfg_attr(feature = "schema-gen", derive(schemars::JsonSchema))]
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct LlmTasksConfig {
+    /// Session titles (`cc watch`'s Ctrl+g).
+    #[serde(default)]
+    pub title: LlmTaskConfig,
+
+    /// Branch names (`a name-branch`, `a cc new`, `a cc fork`).
+    #[serde(default)]
+    pub branch_name: LlmTaskConfig,
+}
+
+/// Overrides for one generation task; unset fields fall back to `llm`.
+#[cfg_attr(feature = "schema-gen", derive(schemars::JsonSchema))]
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct LlmTaskConfig {
+    /// Backends tried in order for this task, replacing `llm.backends`.
+    #[serde(default)]
+    pub backends: Option<Vec<LlmBackendKind>>,
+
+    /// Timeout for this task, replacing `llm.timeout`.
+    #[serde(default)]
+    pub timeout: Option<String>,
+
+    /// `claude_code` settings for this task, replacing `llm.claude_code`'s.
+    #[serde(default)]
+    pub claude_code: LlmTaskBackendConfig,
+
+    /// `opencode` settings for this task, replacing `llm.opencode`'s.
+    #[serde(default)]
+    pub opencode: LlmTaskBackendConfig,
+
+    /// `openai` settings for this task, replacing `llm.openai`'s.
+    #[serde(default)]
+    pub openai: LlmTaskBackendConfig,
+}
+
+/// Overrides of one backend for one generation task.
+#[cfg_attr(feature = "schema-gen", derive(schemars::JsonSchema))]
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct LlmTaskBackendConfig {
+    /// Model for this task, replacing the backend's own `model`.
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
 /// Terminal emulator to use for human-in-the-loop reviews.
 #[cfg_attr(feature = "schema-gen", derive(schemars::JsonSchema))]
 #[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
@@ -279,6 +479,39 @@ pub struct NotificationConfig {
         schemars(default = "default_notification_sound")
     )]
     pub sound: String,
+
+    /// Daily windows during which notifications are quiet (see
+    /// `quiet_action`), in local time. A window whose `end` is earlier than
+    /// its `start` wraps past midnight.
+    #[serde(default)]
+    #[cfg_attr(feature = "schema-gen", schemars(default))]
+    pub quiet_hours: Vec<QuietHoursWindow>,
+
+    /// What happens to a notification during quiet hours or `a notify dnd`
+    /// (default: "suppress").
+    #[serde(default)]
+    #[cfg_attr(feature = "schema-gen", schemars(default))]
+    pub quiet_action: QuietAction,
+
+    /// Whether permission-request notifications are delivered normally even
+    /// while quiet (default: true).
+    #[serde(default = "default_true")]
+    #[cfg_attr(feature = "schema-gen", schemars(default = "default_true"))]
+    pub permission_breaks_through: bool,
+
+    /// Window within which notifications are coalesced (default: "30s").
+    /// The first notification is sent right away; any that follow within
+    /// the window are delivered together as one digest when it closes, so a
+    /// burst shows up as two notifications. Empty string or "0s" disables coalescing.
+    #[serde(default = "default_digest_window")]
+    #[cfg_attr(feature = "schema-gen", schemars(default = "default_digest_window"))]
+    pub digest_window: String,
+
+    /// Reminders re-sent, with escalating urgency, while a session stays
+    /// waiting on the user (default: none).
+    #[serde(default)]
+    #[cfg_attr(feature = "schema-gen", schemars(default))]
+    pub reminders: ReminderConfig,
 }
 
 impl Default for NotificationConfig {
@@ -286,10 +519,79 @@ impl Default for NotificationConfig {
         Self {
             enabled: default_true(),
             sound: default_notification_sound(),
+            quiet_hours: Vec::new(),
+            quiet_action: QuietAction::default(),
+            permission_breaks_through: default_true(),
+            digest_window: default_digest_window(),
+            reminders: ReminderConfig::default(),
         }
     }
 }
 
+/// A daily quiet-hours window, as `HH:MM` local times.
+#[cfg_attr(feature = "schema-gen", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct QuietHoursWindow {
+    /// Start of the window, inclusive (e.g. "22:00").
+    pub start: String,
+
+    /// End of the window, exclusive (e.g. "08:00").
+    pub end: String,
+}
+
+/// How notifications are handled while quiet.
+#[cfg_attr(feature = "schema-gen", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum QuietAction {
+    /// Hold notifications back and deliver them as one digest once the
+    /// quiet period ends.
+    #[default]
+    Suppress,
+    /// Deliver notifications without sound.
+    Silent,
+}
+
+/// Escalating reminders, sent by `a cc sweep`. Each list is ordered by
+/// `after`; once a session has been waiting longer than a step's `after`,
+/// that step's reminder is sent once. Reminders stop as soon as the session
+/// changes status or is read.
+#[cfg_attr(feature = "schema-gen", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ReminderConfig {
+    /// Steps for sessions waiting on a permission prompt or other input.
+    #[serde(default)]
+    #[cfg_attr(feature = "schema-gen", schemars(default))]
+    pub waiting_input: Vec<ReminderStep>,
+
+    /// Steps for stopped sessions nobody has looked at yet.
+    #[serde(default)]
+    #[cfg_attr(feature = "schema-gen", schemars(default))]
+    pub stopped: Vec<ReminderStep>,
+}
+
+/// One escalation step of a reminder.
+#[cfg_attr(feature = "schema-gen", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ReminderStep {
+    /// How long the session must have been waiting (e.g. "15m", "1h").
+    pub after: String,
+
+    /// Sound for this reminder (default: `notification.sound`). Empty
+    /// string for silent.
+    #[serde(default)]
+    pub sound: Option<String>,
+
+    /// URL to also POST the reminder to as JSON (`title`, `subtitle`,
+    /// `message`, and `group`, the session ID), e.g. a chat or
+    /// push-notification webhook.
+    #[serde(default)]
+    pub webhook: Option<String>,
+}
+
 /// Per-repository configuration.
 #[cfg_attr(feature = "schema-gen", derive(schemars::JsonSchema))]
 #[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
@@ -308,6 +610,24 @@ pub struct RepoConfig {
     /// AI-related per-repo overrides (e.g., reviewer set for `a ai review wait`).
     #[serde(default)]
     pub ai: AiConfig,
+
+    /// Per-repo overrides of `cc.auto_label`.
+    #[serde(default)]
+    pub auto_label: RepoAutoLabelConfig,
+}
+
+/// Per-repo overrides of `cc.auto_label`; unset fields fall back to it.
+#[cfg_attr(feature = "schema-gen", derive(schemars::JsonSchema))]
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RepoAutoLabelConfig {
+    /// Overrides `cc.auto_label.enabled` for this repo.
+    #[serde(default)]
+    pub enabled: Option<bool>,
+
+    /// Replaces `cc.auto_label.rules` for this repo.
+    #[serde(default)]
+    pub rules: Option<Vec<AutoLabelRule>>,
 }
 
 /// Per-organization (GitHub owner) configuration.
@@ -354,6 +674,89 @@ pub struct CcConfig {
     /// is still warm.
     #[serde(default)]
     pub auto_compact: AutoCompactConfig,
+
+    /// Git checkpoints of a session's worktree, taken on every Stop when
+    /// enabled.
+    #[serde(default)]
+    pub checkpoints: CheckpointsConfig,
+
+    /// Output of `a cc statusline`, Claude Code's statusLine command.
+    #[serde(default)]
+    pub statusline: StatuslineConfig,
+
+    /// The `a cc watch` TUI.
+    #[serde(default)]
+    pub watch: WatchConfig,
+
+    /// Rule-based labels for sessions that have none.
+    #[serde(default)]
+    pub auto_label: AutoLabelConfig,
+
+    /// Templates and per-status styles of the tmux window status, window
+    /// title, and `a cc list --tmux`.
+    #[serde(default)]
+    pub tmux: TmuxStatusConfig,
+
+    /// Named session templates for `a cc new --template <name>`, keyed by
+    /// template name. A `~/.config/armyknife/templates/<name>.md` file with
+    /// the same name takes precedence over the entry here.
+    #[serde(default)]
+    pub templates: HashMap<String, SessionTemplate>,
+}
+
+/// A reusable starting point for `a cc new --template`: the preamble prompt,
+/// model, and layout a recurring kind of session (PR review, dependency
+/// bump, ...) always starts with.
+///
+/// `prompt`, `label`, and `base` may reference variables as `{{name}}`;
+/// values come from `a cc new --var name=value`, falling back to each
+/// variable's `default`.
+#[cfg_attr(feature = "schema-gen", derive(schemars::JsonSchema))]
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SessionTemplate {
+    /// Initial prompt. In a template file, the Markdown body after the
+    /// frontmatter is used instead.
+    #[serde(default)]
+    pub prompt: String,
+
+    /// Model passed to `claude --model` unless `--model` is given.
+    #[serde(default)]
+    pub model: Option<String>,
+
+    /// tmux layout of the worktree window, replacing `wm.layout`
+    /// (ignored without `--worktree`).
+    #[serde(default)]
+    pub layout: Option<LayoutNode>,
+
+    /// Session label pattern (e.g. "review #{{pr}}") used unless `--label`
+    /// is given.
+    #[serde(default)]
+    pub label: Option<String>,
+
+    /// Base ref for a newly created branch unless `--from` is given
+    /// (ignored without `--worktree`).
+    #[serde(default)]
+    pub base: Option<String>,
+
+    /// Variables the template accepts, keyed by name.
+    #[serde(default)]
+    pub variables: HashMap<String, TemplateVariable>,
+}
+
+/// A variable accepted by a `SessionTemplate`.
+#[cfg_attr(feature = "schema-gen", derive(schemars::JsonSchema))]
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TemplateVariable {
+    /// What the variable is for; shown when a required value is missing.
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Value used when `--var` does not set the variable. Variables without
+    /// a default are required.
+    #[serde(default)]
+    pub default: Option<String>,
 }
 
 /// Configuration for automatically pausing sessions that stay in the Stopped
@@ -452,6 +855,288 @@ impl Default for AutoCompactConfig {
     }
 }
 
+/// Per-turn git checkpoints for `a cc checkpoints` / `a cc rollback`.
+///
+/// When enabled, on every Stop the Stop hook snapshots the session's worktree (tracked
+/// and untracked, non-ignored files) into
+/// `refs/armyknife/checkpoints/<session_id>/<n>`. The index, HEAD, and
+/// branches are left untouched. A session's checkpoints are deleted along
+/// with the session.
+#[cfg_attr(feature = "schema-gen", derive(schemars::JsonSchema))]
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CheckpointsConfig {
+    /// Whether checkpoints are taken (default: false). Off by default since
+    /// each Stop then hashes the whole worktree, untracked files included,
+    /// into the object store.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// How many checkpoints to keep per session; older ones are deleted as
+    /// new ones are taken. Default: 50.
+    #[serde(default = "default_checkpoints_keep")]
+    #[cfg_attr(feature = "schema-gen", schemars(default = "default_checkpoints_keep"))]
+    pub keep: usize,
+}
+
+impl Default for CheckpointsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            keep: default_checkpoints_keep(),
+        }
+    }
+}
+
+fn default_checkpoints_keep() -> usize {
+    50
+}
+
+/// Settings for `a cc statusline`, the command Claude Code runs to render its
+/// status line.
+#[cfg_attr(feature = "schema-gen", derive(schemars::JsonSchema))]
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct StatuslineConfig {
+    /// Template of the status line. `{{name}}` expands to a variable and
+    /// `{{#name}}...{{/name}}` keeps its body only when the variable is
+    /// non-empty. See the README for the variables.
+    #[serde(default = "default_statusline_template")]
+    #[cfg_attr(
+        feature = "schema-gen",
+        schemars(default = "default_statusline_template")
+    )]
+    pub template: String,
+
+    /// How long a branch's PR, review, and CI state is reused before it is
+    /// looked up on GitHub again. Default: "60s".
+    #[serde(default = "default_statusline_pr_ttl")]
+    #[cfg_attr(
+        feature = "schema-gen",
+        schemars(default = "default_statusline_pr_ttl")
+    )]
+    pub pr_ttl: String,
+}
+
+impl Default for StatuslineConfig {
+    fn default() -> Self {
+        Self {
+            template: default_statusline_template(),
+            pr_ttl: default_statusline_pr_ttl(),
+        }
+    }
+}
+
+fn default_statusline_template() -> String {
+    "{{#label}}{{label}} │ {{/label}}{{branch}}\
+     {{#pr}} {{pr}}{{#review}} {{review}}{{/review}}{{#ci}} ci:{{ci}}{{/ci}}{{/pr}}\
+     {{#context}} │ ctx {{context}}{{/context}}\
+     {{#bg_tasks}} │ {{bg_tasks}} bg{{/bg_tasks}}"
+        .to_string()
+}
+
+fn default_statusline_pr_ttl() -> String {
+    "60s".to_string()
+}
+
+/// Settings for the `a cc watch` TUI.
+#[cfg_attr(feature = "schema-gen", derive(schemars::JsonSchema))]
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct WatchConfig {
+    /// How often the PR, review, and CI state shown on session rows is
+    /// refreshed from GitHub. Lookups go through the same cache as
+    /// `a cc statusline`, so a branch fetched by either is not fetched again
+    /// by the other within this window. Default: "2m".
+    #[serde(default = "default_watch_pr_ttl")]
+    #[cfg_attr(feature = "schema-gen", schemars(default = "default_watch_pr_ttl"))]
+    pub pr_ttl: String,
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        Self {
+            pr_ttl: default_watch_pr_ttl(),
+        }
+    }
+}
+
+fn default_watch_pr_ttl() -> String {
+    "2m".to_string()
+}
+
+/// Rule-based session labels. A session without a label is labeled by the
+/// first rule that matches when it is first recorded, and relabeled when
+/// its branch's PR shows up, as long as its label is still the automatic
+/// one. A label set by hand (`cc watch`'s `e`, `a cc new --label`) is never
+/// overwritten.
+#[cfg_attr(feature = "schema-gen", derive(schemars::JsonSchema))]
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AutoLabelConfig {
+    /// Whether to label sessions automatically. Default: false.
+    #[serde(default)]
+    #[cfg_attr(feature = "schema-gen", schemars(default))]
+    pub enabled: bool,
+
+    /// Rules tried in order; the first whose `source` is known and whose
+    /// `pattern` matches sets the label. Default: the PR title, then an
+    /// issue number and name from the branch (`fix/123-login` ->
+    /// `#123 login`), then an issue number in the first prompt, then the
+    /// branch name without its `feat/`-style prefix.
+    #[serde(default = "default_auto_label_rules")]
+    #[cfg_attr(feature = "schema-gen", schemars(default = "default_auto_label_rules"))]
+    pub rules: Vec<AutoLabelRule>,
+}
+
+impl Default for AutoLabelConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            rules: default_auto_label_rules(),
+        }
+    }
+}
+
+fn default_auto_label_rules() -> Vec<AutoLabelRule> {
+    [
+        (AutoLabelSource::PrTitle, "^.+$", "$0"),
+        (
+            AutoLabelSource::Branch,
+            r"^(?:[^/]+/)?(?:issue-|gh-)?(\d+)[-_](.+)$",
+            "#$1 $2",
+        ),
+        (AutoLabelSource::Prompt, r"(?:^|\s)#(\d+)\b", "#$1"),
+        (AutoLabelSource::Branch, "^[^/]+/(.+)$", "$1"),
+    ]
+    .into_iter()
+    .map(|(source, pattern, label)| AutoLabelRule {
+        source,
+        pattern: pattern.to_string(),
+        label: label.to_string(),
+    })
+    .collect()
+}
+
+/// One `cc.auto_label` rule.
+#[cfg_attr(feature = "schema-gen", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AutoLabelRule {
+    /// What the pattern is matched against.
+    pub source: AutoLabelSource,
+
+    /// Regular expression matched against the source.
+    pub pattern: String,
+
+    /// The label, with `$1` / `${name}` replaced by the pattern's capture
+    /// groups (`$0` is the whole match).
+    pub label: String,
+}
+
+/// Input an `AutoLabelRule` is matched against.
+#[cfg_attr(feature = "schema-gen", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoLabelSource {
+    /// The branch checked out in the session's cwd.
+    Branch,
+    /// The session's first prompt.
+    Prompt,
+    /// The title of the branch's PR, once it has one.
+    PrTitle,
+}
+
+/// Templates of the tmux-facing outputs, in the `a cc statusline` template
+/// language. See the README for the variables.
+#[cfg_attr(feature = "schema-gen", derive(schemars::JsonSchema))]
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TmuxStatusConfig {
+    /// Written to a window's `@armyknife-cc-window-status` option (and
+    /// printed by `a cc window-status`).
+    #[serde(default = "default_tmux_window_status")]
+    #[cfg_attr(
+        feature = "schema-gen",
+        schemars(default = "default_tmux_window_status")
+    )]
+    pub window_status: String,
+
+    /// Written to a window's `@armyknife-cc-window-title` option.
+    #[serde(default = "default_tmux_window_title")]
+    #[cfg_attr(
+        feature = "schema-gen",
+        schemars(default = "default_tmux_window_title")
+    )]
+    pub window_title: String,
+
+    /// Printed by `a cc list --tmux`, over every session.
+    #[serde(default = "default_tmux_list")]
+    #[cfg_attr(feature = "schema-gen", schemars(default = "default_tmux_list"))]
+    pub list: String,
+
+    /// Glyph and tmux style of each status in `{{symbol}}` and
+    /// `{{symbols}}`.
+    #[serde(default)]
+    pub styles: TmuxStatusStyles,
+}
+
+impl Default for TmuxStatusConfig {
+    fn default() -> Self {
+        Self {
+            window_status: default_tmux_window_status(),
+            window_title: default_tmux_window_title(),
+            list: default_tmux_list(),
+            styles: TmuxStatusStyles::default(),
+        }
+    }
+}
+
+fn default_tmux_window_status() -> String {
+    "{{#symbols}}{{symbols}} {{/symbols}}".to_string()
+}
+
+fn default_tmux_window_title() -> String {
+    "{{label}}".to_string()
+}
+
+fn default_tmux_list() -> String {
+    "{{#waiting}}#[fg=yellow]\u{25d0} {{pending}}#[default]{{/waiting}}\
+     {{^waiting}}{{#pending}}\u{25cb} {{pending}}{{/pending}}{{/waiting}}"
+        .to_string()
+}
+
+/// Per-status overrides; unset fields keep the built-in glyph and no style.
+#[cfg_attr(feature = "schema-gen", derive(schemars::JsonSchema))]
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TmuxStatusStyles {
+    #[serde(default)]
+    pub running: TmuxStatusStyle,
+    #[serde(default)]
+    pub waiting: TmuxStatusStyle,
+    /// Stopped sessions whose result has not been looked at yet.
+    #[serde(default)]
+    pub unread: TmuxStatusStyle,
+    #[serde(default)]
+    pub stopped: TmuxStatusStyle,
+    #[serde(default)]
+    pub paused: TmuxStatusStyle,
+}
+
+#[cfg_attr(feature = "schema-gen", derive(schemars::JsonSchema))]
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TmuxStatusStyle {
+    /// Replaces the status glyph (e.g. "R" for a narrow status bar).
+    #[serde(default)]
+    pub symbol: Option<String>,
+
+    /// tmux style the glyph is wrapped in, e.g. "fg=yellow,bold".
+    #[serde(default)]
+    pub style: Option<String>,
+}
+
 fn default_auto_compact_idle_timeout() -> String {
     "4m30s".to_string()
 }
@@ -476,6 +1161,10 @@ fn default_notification_sound() -> String {
     "Glass".to_string()
 }
 
+fn default_digest_window() -> String {
+    "30s".to_string()
+}
+
 fn default_editor_command() -> String {
     "nvim".to_string()
 }
@@ -683,6 +1372,61 @@ mod tests {
         assert_eq!(config.cc.auto_compact.min_context_tokens, 200_000);
     }
 
+    #[test]
+    fn parse_tmux_status_yaml() {
+        let yaml = indoc! {r#"
+            cc:
+              tmux:
+                window_status: "{{symbol}}{{#total}}{{total}}{{/total}}"
+                styles:
+                  waiting:
+                    symbol: "W"
+                    style: "fg=yellow,bold"
+        "#};
+        let config: Config = serde_yaml::from_str(yaml).unwrap();
+        let tmux = &config.cc.tmux;
+        assert_eq!(
+            tmux.window_status,
+            "{{symbol}}{{#total}}{{total}}{{/total}}"
+        );
+        assert_eq!(tmux.window_title, "{{label}}");
+        assert_eq!(tmux.styles.waiting.symbol.as_deref(), Some("W"));
+        assert_eq!(tmux.styles.waiting.style.as_deref(), Some("fg=yellow,bold"));
+        assert_eq!(tmux.styles.running, TmuxStatusStyle::default());
+    }
+
+    #[test]
+    fn parse_cc_templates_yaml() {
+        let yaml = indoc! {r#"
+            cc:
+              templates:
+                review-pr:
+                  prompt: "Review PR #{{pr}}"
+                  model: opus
+                  label: "review #{{pr}}"
+                  variables:
+                    pr:
+                      description: PR number
+        "#};
+        let config: Config = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(
+            config.cc.templates.get("review-pr"),
+            Some(&SessionTemplate {
+                prompt: "Review PR #{{pr}}".to_string(),
+                model: Some("opus".to_string()),
+                label: Some("review #{{pr}}".to_string()),
+                variables: HashMap::from([(
+                    "pr".to_string(),
+                    TemplateVariable {
+                        description: Some("PR number".to_string()),
+                        default: None,
+                    },
+                )]),
+                ..Default::default()
+            })
+        );
+    }
+
     #[test]
     fn parse_full_yaml_config() {
         let yaml = indoc! {"
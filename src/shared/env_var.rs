@@ -9,6 +9,8 @@ const SESSION_LABEL: &str = "ARMYKNIFE_SESSION_LABEL";
 const ANCESTOR_SESSION_IDS: &str = "ARMYKNIFE_ANCESTOR_SESSION_IDS";
 const CC_HOOK_LOG: &str = "ARMYKNIFE_CC_HOOK_LOG";
 const CC_NOTIFY: &str = "ARMYKNIFE_CC_NOTIFY";
+const MULTIPLEXER: &str = "ARMYKNIFE_MULTIPLEXER";
+const ZELLIJ_TAB: &str = "ARMYKNIFE_ZELLIJ_TAB";
 const WORKTREE_PATH: &str = "ARMYKNIFE_WORKTREE_PATH";
 const BRANCH_NAME: &str = "ARMYKNIFE_BRANCH_NAME";
 const REPO_ROOT: &str = "ARMYKNIFE_REPO_ROOT";
@@ -41,6 +43,12 @@ pub struct EnvVars {
 
     /// Override notification behavior: "on" or "off".
     pub cc_notify: Option<String>,
+
+    /// Overrides the `multiplexer` config: "tmux" or "zellij".
+    pub multiplexer: Option<String>,
+
+    /// Name of the Zellij tab a pane opened by `a cc new` lives in.
+    pub zellij_tab: Option<String>,
 }
 
 fn non_empty_var(name: &str) -> Option<String> {
@@ -57,6 +65,8 @@ impl EnvVars {
             ancestor_session_ids: non_empty_var(ANCESTOR_SESSION_IDS),
             cc_hook_log: non_empty_var(CC_HOOK_LOG),
             cc_notify: non_empty_var(CC_NOTIFY),
+            multiplexer: non_empty_var(MULTIPLEXER),
+            zellij_tab: non_empty_var(ZELLIJ_TAB),
         }
     }
 
@@ -75,6 +85,11 @@ impl EnvVars {
         ANCESTOR_SESSION_IDS
     }
 
+    /// Returns the env var name for ZELLIJ_TAB (exported to Zellij panes).
+    pub fn zellij_tab_name() -> &'static str {
+        ZELLIJ_TAB
+    }
+
     /// Returns the env var name for WORKTREE_PATH (used as key in env var pairs).
     pub fn worktree_path_name() -> &'static str {
         WORKTREE_PATH
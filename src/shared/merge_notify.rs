@@ -159,8 +159,9 @@ async fn fetch_merged_pr_url(main_repo: &GitRepo, branch: &str) -> anyhow::Resul
 /// which rejects these characters), so without this a crafted value could
 /// close the envelope early and inject text the delegator would read as
 /// free-standing, unwrapped content instead of part of this automated
-/// notice.
-fn strip_angle_brackets(value: &str) -> String {
+/// notice. Shared with `a cc peer report`'s `<delegation-report>`
+/// envelope, which embeds delegate-chosen values the same way.
+pub(crate) fn strip_angle_brackets(value: &str) -> String {
     value.chars().filter(|c| *c != '<' && *c != '>').collect()
 }
 
@@ -217,6 +218,13 @@ mod tests {
             pending_permission_agent_ids: Default::default(),
             read_at: None,
             sweep_signaled: false,
+            changed_files: Default::default(),
+            subagents: Default::default(),
+            compaction_count: 0,
+            last_compacted_at: None,
+            report: None,
+            tags: Default::default(),
+            note: None,
         }
     }
 
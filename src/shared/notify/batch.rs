@@ -0,0 +1,374 @@
+//! Coalescing of notifications that arrive in a burst.
+//!
+//! The first notification of a burst is sent right away and opens a batch
+//! in `~/.cache/armyknife/notify/batch.json`; the ones that follow within
+//! `notification.digest_window` are appended to it instead of being sent.
+//! A detached `a notify flush-batch` spawned for the first one waits for the
+//! window to close, then sends what accumulated as one digest. A batch whose
+//! flusher never ran (killed, failed to spawn) is picked up by the first
+//! notification after the window instead.
+//!
+//! The lead is not held back, so that a lone notification (the common case)
+//! arrives without waiting out the window. A burst therefore shows up as two
+//! notifications: the lead, then the digest of the rest.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::Kind;
+use crate::infra::notification::{Notification, NotificationAction};
+use crate::shared::cache;
+
+/// A notification waiting in a batch, with what it takes to send it as-is
+/// if it turns out to be the only one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchedNotification {
+    pub kind: Kind,
+    pub title: String,
+    #[serde(default)]
+    pub subtitle: Option<String>,
+    pub message: String,
+    #[serde(default)]
+    pub sound: Option<String>,
+    #[serde(default)]
+    pub action: Option<String>,
+    #[serde(default)]
+    pub group: Option<String>,
+}
+
+impl BatchedNotification {
+    pub fn of(notification: &Notification, kind: Kind) -> Self {
+        Self {
+            kind,
+            title: notification.title().to_string(),
+            subtitle: notification.subtitle().map(str::to_string),
+            message: notification.message().to_string(),
+            sound: notification.sound().map(str::to_string),
+            action: notification.action().map(|a| a.command().to_string()),
+            group: notification.group().map(str::to_string),
+        }
+    }
+
+    fn to_notification(&self) -> Notification {
+        let mut notification = Notification::new(&self.title, &self.message);
+        if let Some(subtitle) = &self.subtitle {
+            notification = notification.with_subtitle(subtitle);
+        }
+        if let Some(sound) = &self.sound {
+            notification = notification.with_sound(sound);
+        }
+        if let Some(action) = &self.action {
+            notification = notification.with_action(NotificationAction::new(action));
+        }
+        if let Some(group) = &self.group {
+            notification = notification.with_group(group);
+        }
+        notification
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Batch {
+    started_at: DateTime<Utc>,
+    notifications: Vec<BatchedNotification>,
+}
+
+/// What `enqueue` did with a notification.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Enqueued {
+    /// Added to the open batch; its flusher delivers it.
+    Batched,
+    /// Opened a new batch: the notification is the caller's to send, along
+    /// with `overdue`, the leftovers of a batch whose flusher never ran.
+    Lead {
+        started_at: DateTime<Utc>,
+        overdue: Vec<BatchedNotification>,
+    },
+}
+
+/// Path: ~/.cache/armyknife/notify/batch.json
+fn batch_file() -> Result<PathBuf> {
+    cache::base_dir()
+        .map(|d| d.join("notify").join("batch.json"))
+        .context("Failed to get cache directory")
+}
+
+/// Runs `f` on the batch under an exclusive lock and persists what it
+/// returns (`None` removes the batch).
+fn with_locked_batch<T>(f: impl FnOnce(Option<Batch>) -> (Option<Batch>, T)) -> Result<T> {
+    let path = batch_file()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let lock_file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path.with_extension("json.lock"))?;
+    lock_file.lock()?;
+
+    let batch = fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok());
+    let (batch, result) = f(batch);
+    match batch {
+        Some(batch) => {
+            let temp_path = path.with_extension("json.tmp");
+            let mut temp_file = File::create(&temp_path)?;
+            temp_file.write_all(serde_json::to_string(&batch)?.as_bytes())?;
+            fs::rename(&temp_path, &path)?;
+        }
+        None => match fs::remove_file(&path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        },
+    }
+    Ok(result)
+}
+
+/// Adds `notification` to the batch open at `now`, or opens one.
+pub fn enqueue(
+    notification: &Notification,
+    kind: Kind,
+    window: Duration,
+    now: DateTime<Utc>,
+) -> Result<Enqueued> {
+    with_locked_batch(|batch| match batch {
+        Some(mut batch) if now < batch.started_at + window => {
+            batch
+                .notifications
+                .push(BatchedNotification::of(notification, kind));
+            (Some(batch), Enqueued::Batched)
+        }
+        overdue => {
+            let batch = Batch {
+                started_at: now,
+                notifications: Vec::new(),
+            };
+            let overdue = overdue.map(|b| b.notifications).unwrap_or_default();
+            (
+                Some(batch),
+                Enqueued::Lead {
+                    started_at: now,
+                    overdue,
+                },
+            )
+        }
+    })
+}
+
+/// Closes the batch opened at `started_at` and returns what it collected.
+/// Returns nothing when that batch was already taken over by a newer one.
+pub fn take(started_at: DateTime<Utc>) -> Result<Vec<BatchedNotification>> {
+    with_locked_batch(|batch| match batch {
+        Some(batch) if batch.started_at == started_at => (None, batch.notifications),
+        other => (other, Vec::new()),
+    })
+}
+
+/// One notification for `batched`: the notification itself when only one
+/// session is involved, otherwise a count per kind ("7 sessions stopped, 2
+/// waiting for permission") that runs `open_unread` when clicked.
+pub fn build_digest(batched: &[BatchedNotification], open_unread: &str) -> Option<Notification> {
+    // A session notifying twice in a burst counts once, as its latest state
+    let mut latest: Vec<&BatchedNotification> = Vec::new();
+    for notification in batched {
+        latest.retain(|n| n.group.is_none() || n.group != notification.group);
+        latest.push(notification);
+    }
+    match latest.as_slice() {
+        [] => return None,
+        [only] => return Some(only.to_notification()),
+        _ => {}
+    }
+
+    let mut counts: BTreeMap<Kind, usize> = BTreeMap::new();
+    for notification in &latest {
+        *counts.entry(notification.kind).or_default() += 1;
+    }
+    let parts: Vec<String> = counts
+        .iter()
+        .enumerate()
+        .map(|(i, (kind, count))| {
+            let noun = match (i, count) {
+                (0, 1) => "session ",
+                (0, _) => "sessions ",
+                _ => "",
+            };
+            format!("{count} {noun}{}", kind.digest_label())
+        })
+        .collect();
+
+    let title = format!("\u{1f514} Claude Code - {} sessions", latest.len());
+    let mut notification = Notification::new(title, parts.join(", "))
+        .with_action(NotificationAction::new(open_unread));
+    if let Some(sound) = latest.iter().find_map(|n| n.sound.as_deref()) {
+        notification = notification.with_sound(sound);
+    }
+    Some(notification)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn batched(kind: Kind, group: &str) -> BatchedNotification {
+        BatchedNotification {
+            kind,
+            title: format!("{kind:?}"),
+            subtitle: None,
+            message: "m".to_string(),
+            sound: Some("Glass".to_string()),
+            action: Some(format!("a cc focus {group}")),
+            group: Some(group.to_string()),
+        }
+    }
+
+    #[test]
+    fn digest_counts_sessions_per_kind() {
+        let mut notifications: Vec<_> = (0..7)
+            .map(|i| batched(Kind::Stopped, &format!("s{i}")))
+            .collect();
+        notifications.push(batched(Kind::Permission, "p1"));
+        notifications.push(batched(Kind::Permission, "p2"));
+
+        let digest = build_digest(&notifications, "open-unread").expect("digest");
+
+        assert_eq!(digest.title(), "\u{1f514} Claude Code - 9 sessions");
+        assert_eq!(
+            digest.message(),
+            "7 sessions stopped, 2 waiting for permission"
+        );
+        assert_eq!(digest.action().map(|a| a.command()), Some("open-unread"));
+        assert_eq!(digest.sound(), Some("Glass"));
+    }
+
+    #[rstest]
+    #[case::single_session(vec![batched(Kind::Stopped, "s1")], "Stopped", Some("a cc focus s1"))]
+    #[case::same_session_twice_keeps_latest(
+        vec![batched(Kind::Permission, "s1"), batched(Kind::Stopped, "s1")],
+        "Stopped",
+        Some("a cc focus s1")
+    )]
+    fn one_session_is_delivered_as_is(
+        #[case] notifications: Vec<BatchedNotification>,
+        #[case] expected_title: &str,
+        #[case] expected_action: Option<&str>,
+    ) {
+        let digest = build_digest(&notifications, "open-unread").expect("digest");
+        assert_eq!(digest.title(), expected_title);
+        assert_eq!(digest.action().map(|a| a.command()), expected_action);
+    }
+
+    #[test]
+    fn single_kind_of_one_session_each() {
+        let digest = build_digest(
+            &[batched(Kind::Waiting, "w1"), batched(Kind::Other, "o1")],
+            "open-unread",
+        )
+        .expect("digest");
+        assert_eq!(digest.message(), "1 session waiting for input, 1 other");
+    }
+
+    #[test]
+    fn nothing_batched_means_no_digest() {
+        assert!(build_digest(&[], "open-unread").is_none());
+    }
+
+    #[test]
+    fn burst_is_batched_behind_the_first_and_taken_once() {
+        let cache = tempfile::TempDir::new().unwrap();
+        let cache_home = cache.path().to_str().unwrap().to_string();
+        temp_env::with_vars([("XDG_CACHE_HOME", Some(cache_home.as_str()))], || {
+            let window = Duration::from_secs(30);
+            let start = Utc::now();
+            let notification = Notification::new("t", "m").with_group("s1");
+
+            let Enqueued::Lead {
+                started_at,
+                overdue,
+            } = enqueue(&notification, Kind::Stopped, window, start).unwrap()
+            else {
+                panic!("first notification should lead");
+            };
+            assert!(overdue.is_empty());
+            let later = start + chrono::TimeDelta::seconds(5);
+            assert_eq!(
+                enqueue(&notification, Kind::Permission, window, later).unwrap(),
+                Enqueued::Batched
+            );
+
+            let taken = take(started_at).unwrap();
+            assert_eq!(
+                taken.iter().map(|n| n.kind).collect::<Vec<_>>(),
+                vec![Kind::Permission]
+            );
+            assert!(take(started_at).unwrap().is_empty());
+        });
+    }
+
+    #[test]
+    fn burst_is_delivered_as_the_lead_then_a_digest_of_the_rest() {
+        let cache = tempfile::TempDir::new().unwrap();
+        let cache_home = cache.path().to_str().unwrap().to_string();
+        temp_env::with_vars([("XDG_CACHE_HOME", Some(cache_home.as_str()))], || {
+            let window = Duration::from_secs(30);
+            let start = Utc::now();
+            let enqueued: Vec<Enqueued> = ["s1", "s2", "s3"]
+                .into_iter()
+                .map(|session| {
+                    let notification = Notification::new("t", "m").with_group(session);
+                    enqueue(&notification, Kind::Stopped, window, start).unwrap()
+                })
+                .collect();
+            let Some(Enqueued::Lead { started_at, .. }) = enqueued.first() else {
+                panic!("first notification should lead");
+            };
+
+            let digest = build_digest(&take(*started_at).unwrap(), "open-unread").unwrap();
+
+            assert_eq!(
+                (&enqueued[1..], digest.message()),
+                (
+                    &[Enqueued::Batched, Enqueued::Batched][..],
+                    "2 sessions stopped"
+                )
+            );
+        });
+    }
+
+    #[test]
+    fn batch_left_behind_is_handed_to_the_next_lead() {
+        let cache = tempfile::TempDir::new().unwrap();
+        let cache_home = cache.path().to_str().unwrap().to_string();
+        temp_env::with_vars([("XDG_CACHE_HOME", Some(cache_home.as_str()))], || {
+            let window = Duration::from_secs(30);
+            let start = Utc::now();
+            let notification = Notification::new("t", "m");
+            enqueue(&notification, Kind::Stopped, window, start).unwrap();
+            enqueue(&notification, Kind::Waiting, window, start).unwrap();
+
+            let after_window = start + chrono::TimeDelta::seconds(60);
+            let Enqueued::Lead { overdue, .. } =
+                enqueue(&notification, Kind::Stopped, window, after_window).unwrap()
+            else {
+                panic!("notification after the window should lead");
+            };
+            assert_eq!(
+                overdue.iter().map(|n| n.kind).collect::<Vec<_>>(),
+                vec![Kind::Waiting]
+            );
+            // The stale flusher finds its batch replaced and sends nothing
+            assert!(take(start).unwrap().is_empty());
+        });
+    }
+}
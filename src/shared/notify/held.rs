@@ -0,0 +1,201 @@
+//! Notifications held back while quiet, delivered later as one digest.
+//!
+//! Held notifications are appended, one JSON object per line, to
+//! `~/.cache/armyknife/notify/held.jsonl`. Taking them renames the file
+//! away first, so a hook holding a new notification concurrently either
+//! lands in the batch being taken or starts the next one.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::infra::notification::Notification;
+use crate::shared::cache;
+
+/// How many held notifications the digest lists by title before
+/// summarizing the rest as a count.
+const DIGEST_MAX_LINES: usize = 5;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeldNotification {
+    pub held_at: DateTime<Utc>,
+    pub title: String,
+    #[serde(default)]
+    pub subtitle: Option<String>,
+    pub message: String,
+}
+
+impl HeldNotification {
+    fn of(notification: &Notification, now: DateTime<Utc>) -> Self {
+        Self {
+            held_at: now,
+            title: notification.title().to_string(),
+            subtitle: notification.subtitle().map(str::to_string),
+            message: notification.message().to_string(),
+        }
+    }
+}
+
+/// Path: ~/.cache/armyknife/notify/held.jsonl
+fn held_file() -> Result<PathBuf> {
+    cache::base_dir()
+        .map(|d| d.join("notify").join("held.jsonl"))
+        .context("Failed to get cache directory")
+}
+
+pub fn hold(notification: &Notification) -> Result<()> {
+    let path = held_file()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut line = serde_json::to_string(&HeldNotification::of(notification, Utc::now()))?;
+    line.push('\n');
+    // A single O_APPEND write per line keeps concurrent hooks from
+    // interleaving their entries
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)?
+        .write_all(line.as_bytes())?;
+    Ok(())
+}
+
+/// Every held notification, oldest first, leaving them held.
+pub fn pending() -> Result<Vec<HeldNotification>> {
+    match fs::read_to_string(held_file()?) {
+        Ok(content) => Ok(parse_held(&content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Removes and returns every held notification, oldest first.
+pub fn take() -> Result<Vec<HeldNotification>> {
+    let path = held_file()?;
+    let taken = path.with_extension("jsonl.taking");
+    match fs::rename(&path, &taken) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    }
+    let content = fs::read_to_string(&taken)?;
+    fs::remove_file(&taken)?;
+    Ok(parse_held(&content))
+}
+
+fn parse_held(content: &str) -> Vec<HeldNotification> {
+    content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// One notification summarizing `held`, or `None` when nothing was held.
+pub fn build_digest(held: &[HeldNotification]) -> Option<Notification> {
+    match held {
+        [] => return None,
+        // A lone notification is shown as it was, just late
+        [only] => {
+            let notification = Notification::new(&only.title, &only.message);
+            return Some(match &only.subtitle {
+                Some(subtitle) => notification.with_subtitle(subtitle),
+                None => notification,
+            });
+        }
+        _ => {}
+    }
+    let title = format!("\u{1f515} {} notifications while quiet", held.len());
+    let mut lines: Vec<String> = held
+        .iter()
+        .take(DIGEST_MAX_LINES)
+        .map(|n| match &n.subtitle {
+            Some(subtitle) => format!("{} ({subtitle})", n.title),
+            None => n.title.clone(),
+        })
+        .collect();
+    if held.len() > DIGEST_MAX_LINES {
+        lines.push(format!("and {} more", held.len() - DIGEST_MAX_LINES));
+    }
+    Some(Notification::new(title, lines.join("\n")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    fn held(title: &str, subtitle: Option<&str>) -> HeldNotification {
+        HeldNotification {
+            held_at: Utc::now(),
+            title: title.to_string(),
+            subtitle: subtitle.map(str::to_string),
+            message: "m".to_string(),
+        }
+    }
+
+    #[test]
+    fn digest_lists_titles_and_counts_the_rest() {
+        let mut notifications = vec![held("⏹ Claude Code - Stopped", Some("main:api"))];
+        notifications.extend((0..6).map(|_| held("⏳ Claude Code - Waiting", None)));
+
+        let digest = build_digest(&notifications).expect("digest");
+
+        assert_eq!(digest.title(), "\u{1f515} 7 notifications while quiet");
+        assert_eq!(
+            digest.message(),
+            indoc! {"
+                ⏹ Claude Code - Stopped (main:api)
+                ⏳ Claude Code - Waiting
+                ⏳ Claude Code - Waiting
+                ⏳ Claude Code - Waiting
+                ⏳ Claude Code - Waiting
+                and 2 more"}
+        );
+    }
+
+    #[test]
+    fn single_held_notification_is_delivered_as_is() {
+        let digest =
+            build_digest(&[held("⏹ Claude Code - Stopped", Some("main:api"))]).expect("digest");
+        assert_eq!(
+            (digest.title(), digest.subtitle(), digest.message()),
+            ("⏹ Claude Code - Stopped", Some("main:api"), "m")
+        );
+    }
+
+    #[test]
+    fn nothing_held_means_no_digest() {
+        assert!(build_digest(&[]).is_none());
+    }
+
+    #[test]
+    fn hold_then_take_round_trips_and_empties() {
+        let cache = tempfile::TempDir::new().unwrap();
+        let cache_home = cache.path().to_str().unwrap().to_string();
+        temp_env::with_vars([("XDG_CACHE_HOME", Some(cache_home.as_str()))], || {
+            hold(&Notification::new("first", "a")).unwrap();
+            hold(&Notification::new("second", "b").with_subtitle("s")).unwrap();
+
+            assert_eq!(pending().unwrap().len(), 2);
+            let taken = take().unwrap();
+            assert_eq!(
+                taken
+                    .iter()
+                    .map(|n| (n.title.as_str(), n.subtitle.as_deref()))
+                    .collect::<Vec<_>>(),
+                vec![("first", None), ("second", Some("s"))]
+            );
+            assert!(take().unwrap().is_empty());
+        });
+    }
+
+    #[test]
+    fn unparseable_lines_are_skipped() {
+        let line = serde_json::to_string(&held("t", None)).unwrap();
+        assert_eq!(parse_held(&format!("garbage\n{line}\n")).len(), 1);
+    }
+}
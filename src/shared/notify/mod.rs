@@ -0,0 +1,192 @@
+//! Notification delivery policy in front of `infra::notification::send`.
+//!
+//! While notifications are quiet -- inside `notification.quiet_hours` or
+//! with focus mode on (`a notify dnd on`) -- a notification is held back for
+//! a digest or sent silently, per `notification.quiet_action`; permission
+//! requests still go through when `permission_breaks_through` is set. Held
+//! notifications are delivered as one digest by the first delivery (or
+//! `flush_held` call, e.g. from `a cc sweep`) after the quiet period ends.
+//!
+//! Notifications that do go out are coalesced per
+//! `notification.digest_window` (see `batch`), so a burst of sessions
+//! stopping at once becomes one notification rather than one each.
+
+pub mod batch;
+pub mod held;
+pub mod quiet;
+
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::commands::cc::auto_pause;
+use crate::infra::multiplexer;
+use crate::infra::notification::{self, Notification};
+use crate::infra::process;
+use crate::shared::config::{Config, NotificationConfig, Terminal};
+use batch::{BatchedNotification, Enqueued};
+pub use quiet::Delivery;
+
+/// What a notification is about, for quiet-hours breakthrough and for the
+/// counts in a coalesced digest. Declared in digest order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Kind {
+    Stopped,
+    Permission,
+    Waiting,
+    Other,
+}
+
+impl Kind {
+    fn digest_label(self) -> &'static str {
+        match self {
+            Self::Stopped => "stopped",
+            Self::Permission => "waiting for permission",
+            Self::Waiting => "waiting for input",
+            Self::Other => "other",
+        }
+    }
+}
+
+/// Ghostty's default window title. Used to identify the main terminal window
+/// when focusing via AppleScript, since Ghostty's AppleScript API does not
+/// expose tty information per window (https://github.com/ghostty-org/ghostty/issues/10756).
+const GHOSTTY_DEFAULT_TITLE: &str = "👻";
+
+/// Builds a shell command to focus the terminal application.
+/// For Ghostty on macOS, uses AppleScript to focus the main window by its default title.
+/// For other terminals, uses `open -a` which activates the most recent window.
+pub fn focus_app_command(config: &Config) -> String {
+    if cfg!(target_os = "macos")
+        && config.editor.terminal == Terminal::Ghostty
+        && config.editor.focus_app.is_none()
+    {
+        format!(
+            "osascript -e 'tell application \"Ghostty\"' -e 'activate (first window whose name is \"{GHOSTTY_DEFAULT_TITLE}\")' -e 'activate' -e 'end tell'"
+        )
+    } else {
+        let focus_app_str = config.editor.focus_app();
+        let focus_app =
+            shlex::try_quote(focus_app_str).unwrap_or_else(|_| focus_app_str.to_string().into());
+        format!("open -a {focus_app}")
+    }
+}
+
+/// Shell command run when a coalesced digest is clicked: opens `cc watch`
+/// showing only the sessions waiting on the user in a new multiplexer
+/// window, then focuses the terminal. Only focuses the terminal when the
+/// multiplexer cannot open one.
+fn open_unread_command(config: &Config) -> String {
+    match multiplexer::current().open_window_command("cc-unread", "a cc watch --unread") {
+        Some(open) => format!("{open}; {}", focus_app_command(config)),
+        None => focus_app_command(config),
+    }
+}
+
+/// Whether notifications are quiet right now.
+pub fn is_quiet_now(config: &NotificationConfig) -> bool {
+    quiet::is_quiet(Local::now(), quiet::load_dnd().as_ref(), config)
+}
+
+/// Sends `notification` subject to quiet hours, focus mode, and coalescing,
+/// returning how it was delivered.
+pub fn deliver(notification: Notification, kind: Kind, config: &Config) -> Result<Delivery> {
+    let quiet = is_quiet_now(&config.notification);
+    if !quiet {
+        flush_held_unchecked()?;
+    }
+    let delivery = quiet::decide(quiet, kind == Kind::Permission, &config.notification);
+    match delivery {
+        Delivery::Send => send_coalesced(notification, kind, config)?,
+        Delivery::Silent => notification::send(&notification.without_sound())?,
+        Delivery::Hold => held::hold(&notification)?,
+    }
+    Ok(delivery)
+}
+
+/// `notification.digest_window`, or `None` when coalescing is off.
+fn digest_window(config: &NotificationConfig) -> Option<Duration> {
+    auto_pause::parse_duration(&config.digest_window)
+        .ok()
+        .filter(|window| !window.is_zero())
+}
+
+fn send_coalesced(notification: Notification, kind: Kind, config: &Config) -> Result<()> {
+    let Some(window) = digest_window(&config.notification) else {
+        return notification::send(&notification);
+    };
+    match batch::enqueue(&notification, kind, window, Utc::now())? {
+        Enqueued::Batched => Ok(()),
+        Enqueued::Lead {
+            started_at,
+            mut overdue,
+        } => {
+            spawn_batch_flusher(started_at);
+            if overdue.is_empty() {
+                return notification::send(&notification);
+            }
+            overdue.push(BatchedNotification::of(&notification, kind));
+            match batch::build_digest(&overdue, &open_unread_command(config)) {
+                Some(digest) => notification::send(&digest),
+                None => Ok(()),
+            }
+        }
+    }
+}
+
+/// Spawns a detached `a notify flush-batch` for the batch opened at
+/// `started_at`. Failures are only logged: the batch is then delivered by
+/// the first notification after the window.
+fn spawn_batch_flusher(started_at: DateTime<Utc>) {
+    let exe = match std::env::current_exe() {
+        Ok(exe) => exe,
+        Err(e) => {
+            tracing::warn!(event = "notify.batch.spawn_failed", reason = "current_exe", error = %e);
+            return;
+        }
+    };
+    let started_at = started_at.to_rfc3339();
+    if let Err(e) = process::spawn_detached(
+        exe,
+        ["notify", "flush-batch", "--started-at", started_at.as_str()],
+        None,
+        &[],
+    ) {
+        tracing::warn!(event = "notify.batch.spawn_failed", reason = "spawn_detached", error = %e);
+    }
+}
+
+/// Waits for the batch opened at `started_at` to close, then sends what it
+/// collected as one digest. Returns whether anything was sent.
+pub fn flush_batch(started_at: DateTime<Utc>, config: &Config) -> Result<bool> {
+    let window = digest_window(&config.notification).unwrap_or_default();
+    if let Ok(remaining) = (started_at + window - Utc::now()).to_std() {
+        std::thread::sleep(remaining);
+    }
+    let batched = batch::take(started_at)?;
+    let Some(digest) = batch::build_digest(&batched, &open_unread_command(config)) else {
+        return Ok(false);
+    };
+    notification::send(&digest)?;
+    Ok(true)
+}
+
+/// Sends the digest of held notifications unless still quiet. Returns
+/// whether a digest was sent.
+pub fn flush_held(config: &NotificationConfig) -> Result<bool> {
+    if is_quiet_now(config) {
+        return Ok(false);
+    }
+    flush_held_unchecked()
+}
+
+fn flush_held_unchecked() -> Result<bool> {
+    let Some(digest) = held::build_digest(&held::take()?) else {
+        return Ok(false);
+    };
+    notification::send(&digest)?;
+    Ok(true)
+}
@@ -0,0 +1,175 @@
+//! Quiet hours (`notification.quiet_hours`) and the manually toggled focus
+//! mode (`a notify dnd`), and what they mean for a notification.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::shared::cache;
+use crate::shared::config::{NotificationConfig, QuietAction, QuietHoursWindow};
+
+/// Focus mode as persisted by `a notify dnd on`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DndState {
+    pub since: DateTime<Utc>,
+    /// `None` keeps focus mode on until `a notify dnd off`.
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl DndState {
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.until.is_none_or(|until| now < until)
+    }
+}
+
+/// How a notification is delivered given the current quiet state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delivery {
+    Send,
+    /// Send without sound.
+    Silent,
+    /// Keep for the digest sent once the quiet period ends.
+    Hold,
+}
+
+/// Path: ~/.cache/armyknife/notify/dnd.json
+fn dnd_file() -> Result<PathBuf> {
+    cache::base_dir()
+        .map(|d| d.join("notify").join("dnd.json"))
+        .context("Failed to get cache directory")
+}
+
+/// The persisted focus mode, if any -- including an expired one.
+pub fn load_dnd() -> Option<DndState> {
+    let content = fs::read_to_string(dnd_file().ok()?).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+pub fn save_dnd(state: &DndState) -> Result<()> {
+    let path = dnd_file()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let temp_path = path.with_extension("json.tmp");
+    let mut temp_file = File::create(&temp_path)?;
+    temp_file.write_all(serde_json::to_string(state)?.as_bytes())?;
+    fs::rename(&temp_path, &path)?;
+    Ok(())
+}
+
+/// Turns focus mode off. Returns whether it was on.
+pub fn clear_dnd() -> Result<bool> {
+    let was_active = load_dnd().is_some_and(|state| state.is_active(Utc::now()));
+    match fs::remove_file(dnd_file()?) {
+        Ok(()) => Ok(was_active),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn parse_time(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value, "%H:%M").ok()
+}
+
+/// Whether `time` falls in one of `windows`. Windows with an unparseable
+/// bound are ignored.
+pub fn in_quiet_hours(time: NaiveTime, windows: &[QuietHoursWindow]) -> bool {
+    windows.iter().any(|window| {
+        let (Some(start), Some(end)) = (parse_time(&window.start), parse_time(&window.end)) else {
+            return false;
+        };
+        if start <= end {
+            start <= time && time < end
+        } else {
+            time >= start || time < end
+        }
+    })
+}
+
+/// Whether notifications are quiet right now: focus mode is on or the local
+/// time is inside quiet hours.
+pub fn is_quiet(now: DateTime<Local>, dnd: Option<&DndState>, config: &NotificationConfig) -> bool {
+    dnd.is_some_and(|state| state.is_active(now.with_timezone(&Utc)))
+        || in_quiet_hours(now.time(), &config.quiet_hours)
+}
+
+/// `permission` marks a permission-request notification, which breaks
+/// through when `permission_breaks_through` is set.
+pub fn decide(quiet: bool, permission: bool, config: &NotificationConfig) -> Delivery {
+    if !quiet || (permission && config.permission_breaks_through) {
+        return Delivery::Send;
+    }
+    match config.quiet_action {
+        QuietAction::Suppress => Delivery::Hold,
+        QuietAction::Silent => Delivery::Silent,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use rstest::rstest;
+
+    fn window(start: &str, end: &str) -> QuietHoursWindow {
+        QuietHoursWindow {
+            start: start.to_string(),
+            end: end.to_string(),
+        }
+    }
+
+    #[rstest]
+    #[case::inside_same_day("12:30", "12:00", "13:00", true)]
+    #[case::end_is_exclusive("13:00", "12:00", "13:00", false)]
+    #[case::before_midnight_in_wrapping_window("23:10", "22:00", "08:00", true)]
+    #[case::after_midnight_in_wrapping_window("07:59", "22:00", "08:00", true)]
+    #[case::outside_wrapping_window("12:00", "22:00", "08:00", false)]
+    #[case::invalid_window_is_ignored("12:00", "noon", "13:00", false)]
+    fn in_quiet_hours_cases(
+        #[case] time: &str,
+        #[case] start: &str,
+        #[case] end: &str,
+        #[case] expected: bool,
+    ) {
+        let time = parse_time(time).unwrap();
+        assert_eq!(in_quiet_hours(time, &[window(start, end)]), expected);
+    }
+
+    #[rstest]
+    #[case::indefinite(None, true)]
+    #[case::not_yet_expired(Some(3600), true)]
+    #[case::expired(Some(-1), false)]
+    fn dnd_is_active(#[case] until_offset_secs: Option<i64>, #[case] expected: bool) {
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap();
+        let state = DndState {
+            since: now,
+            until: until_offset_secs.map(|secs| now + chrono::TimeDelta::seconds(secs)),
+        };
+        assert_eq!(state.is_active(now), expected);
+    }
+
+    #[rstest]
+    #[case::not_quiet(false, false, QuietAction::Suppress, true, Delivery::Send)]
+    #[case::quiet_suppresses(true, false, QuietAction::Suppress, true, Delivery::Hold)]
+    #[case::quiet_silences(true, false, QuietAction::Silent, true, Delivery::Silent)]
+    #[case::permission_breaks_through(true, true, QuietAction::Suppress, true, Delivery::Send)]
+    #[case::permission_held_when_disabled(true, true, QuietAction::Suppress, false, Delivery::Hold)]
+    fn decide_cases(
+        #[case] quiet: bool,
+        #[case] permission: bool,
+        #[case] quiet_action: QuietAction,
+        #[case] permission_breaks_through: bool,
+        #[case] expected: Delivery,
+    ) {
+        let config = NotificationConfig {
+            quiet_action,
+            permission_breaks_through,
+            ..Default::default()
+        };
+        assert_eq!(decide(quiet, permission, &config), expected);
+    }
+}